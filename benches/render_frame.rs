@@ -0,0 +1,66 @@
+use arkham::components::{Block, Histogram, Layout, LayoutPreset, Pane, Plot, Series};
+use arkham::prelude::*;
+use arkham::testing::TestApp;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn root(ctx: &mut ViewContext, layout: Res<Layout>, tick: State<usize>) {
+    *tick.get_mut() += 1;
+    let size = ctx.size();
+    layout.draw(ctx, size);
+}
+
+fn list(ctx: &mut ViewContext, tick: State<usize>) {
+    let tick = *tick.get();
+    let mut stack = ctx.vertical_stack(ctx.size());
+    for i in 0..ctx.size().height {
+        let line = format!("{:>4} Item number {}", i, i + tick);
+        if i == tick % 20 {
+            stack.insert(line.to_runes().fg(Color::Black).bg(Color::Cyan));
+        } else {
+            stack.insert(line.as_str());
+        }
+    }
+    ctx.component(ctx.size(), stack);
+}
+
+fn chart(ctx: &mut ViewContext, tick: State<usize>) {
+    let tick = *tick.get() as f64;
+    let points = (0..100)
+        .map(|x| (x as f64, ((x as f64 + tick) / 10.0).sin()))
+        .collect();
+    let plot = Plot::new().series(Series::new("sin", points));
+    ctx.component(ctx.size(), plot);
+}
+
+fn histogram(ctx: &mut ViewContext, tick: State<usize>) {
+    let tick = *tick.get();
+    let values: Vec<f64> = (0..500).map(|i| ((i * 7 + tick) % 97) as f64).collect();
+    ctx.component(ctx.size(), Histogram::new(&values, 10));
+}
+
+fn status(ctx: &mut ViewContext) {
+    let size = ctx.size();
+    Block::new().title("Status").draw(ctx, size);
+    ctx.insert((2, 1), "All systems normal");
+}
+
+fn bench_render_frame(c: &mut Criterion) {
+    let layout = Layout::new(LayoutPreset::MainStack(0.5))
+        .pane(Pane::new("list", list).title("List"))
+        .pane(Pane::new("chart", chart).title("Chart"))
+        .pane(Pane::new("histogram", histogram).title("Histogram"))
+        .pane(Pane::new("status", status));
+    let mut app = TestApp::new(root, (200, 60))
+        .insert_resource(layout)
+        .insert_state(0usize);
+    let mut out = Vec::with_capacity(64 * 1024);
+    c.bench_function("Render frame", |b| {
+        b.iter(|| {
+            out.clear();
+            app.draw(&mut out).unwrap();
+        })
+    });
+}
+
+criterion_group!(benches, bench_render_frame);
+criterion_main!(benches);
@@ -1,4 +1,7 @@
-use arkham::prelude::*;
+use arkham::{
+    components::{Block, BorderStyle},
+    prelude::*,
+};
 
 #[derive(Default, Clone, Copy)]
 enum AppRoute {
@@ -39,14 +42,20 @@ fn root_view(ctx: &mut ViewContext, kb: Res<Keyboard>, state: State<AppState>) {
     let size = ctx.size();
 
     ctx.fill_all(Color::Black);
-    ctx.component(size, todo_container);
 
+    // The modal is opened before the list so that it receives keys first.
     if matches!(state.get().route, AppRoute::NewTodo(_)) {
-        let mut rect = Rect::with_size(size);
-        rect.pad(-5, -5);
-        ctx.component(rect, add_todo_modal);
+        ctx.modal(
+            (
+                size.width.saturating_sub(10),
+                size.height.saturating_sub(10),
+            ),
+            add_todo_modal,
+        );
     }
 
+    ctx.component(size, todo_container);
+
     if kb.char() == Some('n') {
         state.get_mut().route = AppRoute::NewTodo(NewTodoRoute::default());
     }
@@ -123,8 +132,12 @@ fn add_todo_modal(
     };
 
     ctx.fill_all(theme.bg_secondary);
-    ctx.fill((0, (size.width, 1)), theme.bg_tertiary);
-    ctx.insert((2, 0), "New Todo Item".to_runes().bold());
+    ctx.component(
+        size,
+        Block::new()
+            .border(BorderStyle::Rounded)
+            .title("New Todo Item".to_runes().bold()),
+    );
 
     if kb.code() == Some(KeyCode::Esc) {
         state.get_mut().route = AppRoute::TodoPage;
@@ -132,7 +145,7 @@ fn add_todo_modal(
         return;
     }
 
-    ctx.component((2, (size.width - 2, 1)), |ctx: &mut ViewContext| {
+    ctx.component(((2, 2), (size.width - 4, 1)), |ctx: &mut ViewContext| {
         let size = ctx.size();
         ctx.insert(0, "Title");
         ctx.fill(((10, 0), (size.width - 12, 1)), theme.bg_tertiary);
@@ -0,0 +1,90 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Animation can be used as an injectable resource to schedule renders for
+/// components that change over time, such as spinners. Renders are otherwise
+/// only performed in response to input or a Renderer.
+///
+/// A requested render is performed once, so an animated component requests
+/// the next render each time it is drawn, and stops animating as soon as it
+/// is no longer drawn. ViewContext::animate is a shortcut for requesting a
+/// render from within a component.
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root).insert_resource(Instant::now()).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, started: Res<Instant>) {
+///     ctx.insert((0, 0), format!("{}s", started.elapsed().as_secs()));
+///     ctx.animate(Duration::from_secs(1));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Animation {
+    next: Rc<Cell<Option<Instant>>>,
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request a render after a delay. When several renders are requested,
+    /// the earliest is performed.
+    pub fn request(&self, delay: Duration) {
+        let at = Instant::now() + delay;
+        self.next
+            .set(Some(self.next.get().map_or(at, |next| next.min(at))));
+    }
+
+    /// Returns true if a render has been requested.
+    pub fn is_pending(&self) -> bool {
+        self.next.get().is_some()
+    }
+
+    /// The time remaining until the requested render, or None if no render
+    /// has been requested.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        self.next
+            .get()
+            .map(|next| next.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns true if the requested render is due, clearing the request.
+    pub(crate) fn take_due(&self) -> bool {
+        match self.next.get() {
+            Some(next) if next <= Instant::now() => {
+                self.next.set(None);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Animation;
+
+    #[test]
+    fn test_request() {
+        let animation = Animation::new();
+        assert!(!animation.take_due());
+        assert_eq!(animation.remaining(), None);
+
+        animation.request(Duration::from_secs(60));
+        animation.request(Duration::ZERO);
+        assert!(animation.remaining().unwrap() < Duration::from_secs(1));
+        assert!(animation.take_due());
+        assert!(!animation.is_pending());
+    }
+}
@@ -0,0 +1,274 @@
+//! Conversion of text containing ANSI escape sequences, such as the output of
+//! `git diff --color` or `ls --color`, into styled runes. See
+//! Runes::from_ansi.
+//!
+//! SGR sequences set the colors and attributes of the text that follows them
+//! and OSC 8 sequences set its hyperlink. Other escape sequences, such as
+//! those moving the cursor, have no meaning within runes and are dropped.
+
+use crossterm::style::Color;
+
+use crate::runes::{styled_runes, Rune};
+
+/// Convert text containing escape sequences into runes styled by them.
+pub(crate) fn parse(value: &str) -> Vec<Rune> {
+    let mut text = String::with_capacity(value.len());
+    // The style of the text from each byte offset of the text onwards.
+    let mut styles: Vec<(usize, Rune)> = vec![(0, Rune::new())];
+    let mut style = Rune::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        if c == 'm' {
+                            apply_sgr(&mut style, &params);
+                        }
+                        break;
+                    }
+                    params.push(c);
+                }
+            }
+            Some(']') => {
+                // The command ends at BEL or ST. Any other escape ends it
+                // unterminated, and starts the next sequence, so that an
+                // escape cannot become part of the command's body.
+                let mut body = String::new();
+                let mut terminated = false;
+                while let Some(&c) = chars.peek() {
+                    match c {
+                        '\x07' | '\u{9c}' => {
+                            chars.next();
+                            terminated = true;
+                            break;
+                        }
+                        '\x1b' => {
+                            let mut ahead = chars.clone();
+                            ahead.next();
+                            if ahead.next() == Some('\\') {
+                                chars = ahead;
+                                terminated = true;
+                            }
+                            break;
+                        }
+                        _ => {
+                            chars.next();
+                            body.push(c);
+                        }
+                    }
+                }
+                if terminated {
+                    apply_osc(&mut style, &body);
+                }
+            }
+            // Character set designations name the set in the next character.
+            Some('(' | ')' | '*' | '+') => {
+                chars.next();
+            }
+            // Other sequences are a single character after the escape.
+            _ => {}
+        }
+        match styles.last_mut() {
+            Some((offset, last)) if *offset == text.len() => *last = style,
+            Some((_, last)) if *last == style => {}
+            _ => styles.push((text.len(), style)),
+        }
+    }
+    styled_runes(&text, |idx| {
+        let pos = styles.partition_point(|(offset, _)| *offset <= idx);
+        styles[pos.saturating_sub(1)].1
+    })
+}
+
+/// Apply the parameters of a Select Graphic Rendition sequence. Parameters
+/// are separated by semicolons, and may have sub parameters separated by
+/// colons.
+fn apply_sgr(style: &mut Rune, params: &str) {
+    let mut groups = params.split(';').map(|group| {
+        group
+            .split(':')
+            .map(|p| p.parse::<u8>().ok())
+            .collect::<Vec<_>>()
+    });
+    while let Some(group) = groups.next() {
+        let code = group[0].unwrap_or(0);
+        match code {
+            0 => {
+                *style = Rune {
+                    link: style.link,
+                    ..Rune::new()
+                }
+            }
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => match group.get(1).copied().flatten() {
+                Some(0) => {
+                    style.underline = false;
+                    style.undercurl = false;
+                }
+                Some(3) => style.undercurl = true,
+                _ => style.underline = true,
+            },
+            7 => style.reverse = true,
+            9 => style.crossed_out = true,
+            21 => style.underline = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => {
+                style.underline = false;
+                style.undercurl = false;
+            }
+            27 => style.reverse = false,
+            29 => style.crossed_out = false,
+            30..=37 => style.fg = Some(named(code - 30)),
+            90..=97 => style.fg = Some(named(code - 90 + 8)),
+            40..=47 => style.bg = Some(named(code - 40)),
+            100..=107 => style.bg = Some(named(code - 100 + 8)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            38 | 48 => {
+                let color = if group.len() > 1 {
+                    extended_color(group[1..].iter().copied(), true)
+                } else {
+                    extended_color(groups.by_ref().map(|group| group[0]), false)
+                };
+                if code == 38 {
+                    style.fg = color.or(style.fg);
+                } else {
+                    style.bg = color.or(style.bg);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Read an extended color following a 38 or 48 code: `5;n` for a color of
+/// the 256 color palette or `2;r;g;b` for an RGB color. In the colon
+/// separated form the RGB values may be preceded by a color space.
+fn extended_color<I>(mut params: I, colons: bool) -> Option<Color>
+where
+    I: Iterator<Item = Option<u8>>,
+{
+    match params.next()?? {
+        5 => Some(Color::AnsiValue(params.next()??)),
+        2 => {
+            let mut values: Vec<Option<u8>> = params.by_ref().take(4).collect();
+            if !colons || values.len() < 4 {
+                values.truncate(3);
+            } else {
+                values.remove(0);
+            }
+            match values[..] {
+                [Some(r), Some(g), Some(b)] => Some(Color::Rgb { r, g, b }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Apply an Operating System Command. Only OSC 8, which starts and ends
+/// hyperlinks, affects the style of text. URLs containing control
+/// characters are not linked, see Rune::link.
+fn apply_osc(style: &mut Rune, body: &str) {
+    let Some(link) = body.strip_prefix("8;") else {
+        return;
+    };
+    let url = link.split_once(';').map_or("", |(_, url)| url);
+    *style = if url.is_empty() {
+        Rune {
+            link: None,
+            ..*style
+        }
+    } else {
+        style.link(url)
+    };
+}
+
+/// The color of one of the 16 standard terminal colors.
+fn named(idx: u8) -> Color {
+    match idx {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        7 => Color::Grey,
+        8 => Color::DarkGrey,
+        9 => Color::Red,
+        10 => Color::Green,
+        11 => Color::Yellow,
+        12 => Color::Blue,
+        13 => Color::Magenta,
+        14 => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use super::parse;
+
+    #[test]
+    fn test_sgr() {
+        let runes = parse("\x1b[1;31ma\x1b[0mb\x1b[38;5;208;4:3mc\x1b[38:2::1:2:3;48;2;4;5;6md");
+        let text: String = runes.iter().filter_map(|r| r.content).collect();
+        assert_eq!(text, "abcd");
+        assert!(runes[0].bold);
+        assert_eq!(runes[0].fg, Some(Color::DarkRed));
+        assert_eq!(runes[1].fg, None);
+        assert!(!runes[1].bold);
+        assert_eq!(runes[2].fg, Some(Color::AnsiValue(208)));
+        assert!(runes[2].undercurl);
+        assert_eq!(runes[3].fg, Some(Color::Rgb { r: 1, g: 2, b: 3 }));
+        assert_eq!(runes[3].bg, Some(Color::Rgb { r: 4, g: 5, b: 6 }));
+    }
+
+    #[test]
+    fn test_other_sequences() {
+        let runes =
+            parse("\x1b]8;;https://example.com\x1b\\ab\x1b]8;;\x07c\x1b[2K\x1b[1Ad\x1b(B\x1b[");
+        let text: String = runes.iter().filter_map(|r| r.content).collect();
+        assert_eq!(text, "abcd");
+        assert_eq!(runes[1].url().as_deref(), Some("https://example.com"));
+        assert_eq!(runes[2].link, None);
+    }
+
+    #[test]
+    fn test_osc_cannot_contain_escapes() {
+        // An escape ends the command, so the link is dropped and the
+        // sequence that follows is not part of a URL. The stray BEL is then
+        // displayed as text.
+        let runes = parse("\x1b]8;;https://a.io/\x1b[2J\x07ab");
+        let text: String = runes.iter().filter_map(|r| r.content).collect();
+        assert_eq!(text, "^Gab");
+        assert!(runes.iter().all(|r| r.link.is_none()));
+
+        // An unterminated command is dropped.
+        let runes = parse("a\x1b]8;;https://a.io/b");
+        assert_eq!(runes.len(), 1);
+        assert_eq!(runes[0].link, None);
+
+        // Other control characters are kept in the body, and the URL is
+        // rejected.
+        let runes = parse("\x1b]8;;https://a.io/\x08\x1b\\a");
+        assert_eq!(runes[0].link, None);
+        let runes = parse("\x1b]8;;https://a.io/\u{9c}a");
+        assert_eq!(runes[0].url().as_deref(), Some("https://a.io/"));
+    }
+}
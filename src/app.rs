@@ -1,47 +1,98 @@
 use std::{
     any::Any,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     io::Write,
     marker::PhantomData,
     rc::Rc,
-    sync::mpsc::{channel, Receiver, Sender},
-    time::Duration,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
 };
 
 use crossterm::{
     cursor,
-    event::{Event, KeyCode, KeyEventKind},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEventKind,
+    },
     execute, queue, terminal,
 };
 
 use crate::{
+    animation::Animation,
+    color::{ColorBlindness, ColorFilter},
     container::{Callable, Container, ContainerRef, FromContainer, Res, State},
     context::ViewContext,
+    dock::{Dock, Docks},
+    features::{Features, FEATURES_VAR},
+    frame::{Frame, FrameStats},
+    idle::Idle,
+    keymap::{KeyBinding, Keymap},
+    layout_checks,
     plugins::Plugin,
-    runes::Rune,
+    pointer::{CursorHints, CursorShape},
+    profiler, provenance,
+    runes::{RenderState, Rune, Runes},
+    terminal::TerminalBackground,
     view::View,
+    zoom::Zoom,
 };
 
-use super::input::Keyboard;
+use super::input::{Keyboard, Macros, Mouse};
+
+/// A handler applied to key presses before components are rendered. See
+/// App::global_keys.
+pub(crate) type KeyHandler = Box<dyn Fn(&Keyboard) -> bool>;
+
+/// Events processed by the application's run loop.
+#[derive(Debug)]
+enum AppEvent {
+    Input(Event),
+    Render,
+}
 
 /// A renderer that can signal a render needs to take place.
+#[derive(Clone, Debug)]
 pub struct Renderer {
-    tx: Sender<()>,
+    tx: Sender<AppEvent>,
+    pending: Arc<AtomicBool>,
 }
 
 impl Renderer {
+    /// Request a render. Requests made before the application has rendered
+    /// are combined into a single render.
     pub fn render(&self) {
-        let _ = self.tx.send(());
+        if !self.pending.swap(true, Ordering::SeqCst) {
+            let _ = self.tx.send(AppEvent::Render);
+        }
     }
 }
 
 struct AppOptions {
     q_to_quit: bool,
+    zoom_key: Option<KeyCode>,
+    idle_timeout: Option<Duration>,
+    mouse: bool,
+    frame_budget: Option<Duration>,
+    color_blindness: Option<ColorBlindness>,
+    hyperlinks: bool,
 }
 
 impl Default for AppOptions {
     fn default() -> Self {
-        Self { q_to_quit: true }
+        Self {
+            q_to_quit: true,
+            zoom_key: None,
+            idle_timeout: None,
+            mouse: false,
+            frame_budget: None,
+            color_blindness: None,
+            hyperlinks: crate::terminal::supports_hyperlinks(std::env::var("TERM").ok().as_deref()),
+        }
     }
 }
 
@@ -70,11 +121,15 @@ where
     container: ContainerRef,
     main_view: View,
     current_view_state: Vec<Vec<Rune>>,
-    render_signal: Receiver<()>,
-    render_tx: Sender<()>,
+    /// The color blindness filter the displayed state was written with.
+    displayed_filter: Option<ColorBlindness>,
+    events: Receiver<AppEvent>,
+    event_tx: Sender<AppEvent>,
+    render_pending: Arc<AtomicBool>,
     root: F,
     args: PhantomData<Args>,
     plugins: Rc<RefCell<Vec<Box<dyn crate::plugins::Plugin>>>>,
+    key_handlers: Vec<KeyHandler>,
 }
 
 impl<F, Args> App<F, Args>
@@ -87,24 +142,38 @@ where
     /// loop and perform the initial screen setup and render.
     pub fn new(root: F) -> App<F, Args> {
         let container = Rc::new(RefCell::new(Container::default()));
+        container
+            .borrow_mut()
+            .bind_default(|| Res::new(Docks::new()));
+        container
+            .borrow_mut()
+            .bind_default(|| Res::new(Features::new()));
+        container
+            .borrow_mut()
+            .bind_default(|| Res::new(Macros::new()));
         let size = terminal::size().unwrap();
         let main_view = View::new(size);
-        let (render_tx, render_signal) = channel();
+        let (event_tx, events) = channel();
 
         App {
             container,
             root,
             main_view,
             current_view_state: vec![vec![Rune::default(); size.0 as usize]; size.1 as usize],
-            render_tx,
-            render_signal,
+            displayed_filter: None,
+            event_tx,
+            events,
+            render_pending: Arc::new(AtomicBool::new(false)),
             options: AppOptions::default(),
             args: PhantomData,
             plugins: Rc::new(RefCell::new(vec![])),
+            key_handlers: vec![],
         }
     }
 
-    /// Disables the default handling of the 'q' key to quit the application
+    /// Disables the default handling of the 'q' key to quit the application.
+    /// The key is then handled like any other, so it can be typed into
+    /// inputs or bound in a Keymap.
     ///
     /// NOTE: You will need to manually handle quitting via the ViewContext::exit function.
     pub fn disbale_q_to_quit(mut self) -> Self {
@@ -112,14 +181,136 @@ where
         self
     }
 
+    /// Set a key which toggles displaying the focused zoomable component
+    /// fullscreen. See ViewContext::zoomable and the Zoom resource.
+    pub fn zoom_key(mut self, key: KeyCode) -> Self {
+        self.options.zoom_key = Some(key);
+        self
+    }
+
+    /// Set the duration without input after which the application becomes
+    /// idle. See the Idle resource and Plugin::on_idle.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.options.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the time a frame is expected to render within. A warning is logged
+    /// for every frame that exceeds it, naming the slowest component when
+    /// the `profiling` feature is enabled. See the FrameStats resource.
+    pub fn frame_budget(mut self, budget: Duration) -> Self {
+        self.options.frame_budget = Some(budget);
+        self
+    }
+
+    /// Simulate a form of color blindness by transforming every color as it
+    /// is written to the terminal. See the ColorFilter resource.
+    pub fn color_blindness(mut self, mode: ColorBlindness) -> Self {
+        self.options.color_blindness = Some(mode);
+        self
+    }
+
+    /// Set whether hyperlinks are written to the terminal with OSC 8 so
+    /// that they can be clicked. This is enabled unless the terminal is
+    /// known not to support it, in which case links are displayed as plain
+    /// text. See Rune::link.
+    pub fn hyperlinks(mut self, enabled: bool) -> Self {
+        self.options.hyperlinks = enabled;
+        self
+    }
+
+    /// Add a handler for application wide shortcuts, such as opening help
+    /// or a command palette. Handlers receive each key press before any
+    /// component is rendered, in the order they were added, after the quit
+    /// and zoom keys. A handler returns true to swallow the key, in which
+    /// case no later handler or component receives it.
+    ///
+    /// ```no_run
+    /// use arkham::prelude::*;
+    ///
+    /// fn main() {
+    ///     let help = State::new(false);
+    ///     App::new(root)
+    ///         .bind_state(help.clone())
+    ///         .global_keys(move |kb| {
+    ///             if kb.code() == Some(KeyCode::F(1)) {
+    ///                 let mut help = help.get_mut();
+    ///                 *help = !*help;
+    ///                 return true;
+    ///             }
+    ///             false
+    ///         })
+    ///         .run()
+    ///         .unwrap();
+    /// }
+    ///
+    /// fn root(ctx: &mut ViewContext, help: State<bool>) {
+    ///     if *help.get() {
+    ///         ctx.insert((0, 0), "Press F1 to close help");
+    ///     }
+    /// }
+    /// ```
+    pub fn global_keys<H>(mut self, handler: H) -> Self
+    where
+        H: Fn(&Keyboard) -> bool + 'static,
+    {
+        self.key_handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Capture mouse input from the terminal. Mouse events are made
+    /// available to components through the Mouse resource.
+    pub fn enable_mouse(mut self) -> Self {
+        self.options.mouse = true;
+        self
+    }
+
+    /// Set the number of columns between tab stops used when text is
+    /// converted into runes. Tabs are expanded to spaces so that indented
+    /// text lines up. Defaults to 4, a width of zero disables expansion.
+    ///
+    /// The width applies to the thread the App is built on, so the App
+    /// should be run on the same thread.
+    pub fn tab_width(self, width: usize) -> Self {
+        Runes::set_tab_width(width);
+        self
+    }
+
     /// Returns a renderer that can signal the application to rerender. This
-    /// renderer can be cloned and passed between threads.
+    /// renderer can be cloned and passed between threads. While the
+    /// application is running the renderer is also available to components
+    /// and plugins as the `Res<Renderer>` resource.
     pub fn get_renderer(&self) -> Renderer {
         Renderer {
-            tx: self.render_tx.clone(),
+            tx: self.event_tx.clone(),
+            pending: self.render_pending.clone(),
         }
     }
 
+    /// Dock a component to a corner of the screen. Docked components are
+    /// drawn above the root component and can be shown or hidden through the
+    /// Docks resource.
+    pub fn dock(self, dock: Dock) -> Self {
+        self.container
+            .borrow()
+            .get::<Res<Docks>>()
+            .unwrap()
+            .add(dock);
+        self
+    }
+
+    /// Enable a feature flag. The `ARKHAM_FEATURES` environment variable is
+    /// applied over the flags enabled here when the application starts. See
+    /// the Features resource.
+    pub fn enable_feature(self, name: &str) -> Self {
+        self.container
+            .borrow()
+            .get::<Res<Features>>()
+            .unwrap()
+            .set(name, true);
+        self
+    }
+
     pub fn insert_plugin(self, plugin: impl Plugin + 'static) -> Self {
         self.plugins.borrow_mut().push(Box::new(plugin));
         self
@@ -131,6 +322,10 @@ where
     /// Interior mutability must be used for anything that requires an internal
     /// state.
     ///
+    /// The resources the framework provides, such as Features or Idle, can
+    /// be supplied this way instead, and the application's value is used in
+    /// place of the default. Inserting a type twice panics.
+    ///
     /// Example:
     /// ```no_run
     /// use arkham::prelude::*;
@@ -155,11 +350,55 @@ where
     /// Bind an existing resource to the application
     ///
     /// Similar to `App::insert_resource` except it accepts an existing resource.
-    pub fn bind_resource<T: Any>(self, v: Res<T>) -> Self {
+    pub fn bind_resource<T: ?Sized + 'static>(self, v: Res<T>) -> Self {
         self.container.borrow_mut().bind(v);
         self
     }
 
+    /// Insert a trait object as a resource. Components inject the resource
+    /// as Res<dyn Trait>, which allows implementations of a service to be
+    /// swapped, for instance replacing a storage backend in tests.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use arkham::prelude::*;
+    ///
+    /// trait Storage {
+    ///     fn load(&self) -> String;
+    /// }
+    ///
+    /// struct FileStorage;
+    ///
+    /// impl Storage for FileStorage {
+    ///     fn load(&self) -> String {
+    ///         std::fs::read_to_string("notes.txt").unwrap_or_default()
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     App::new(root)
+    ///         .bind_trait::<dyn Storage>(Box::new(FileStorage))
+    ///         .run()
+    ///         .unwrap();
+    /// }
+    ///
+    /// fn root(ctx: &mut ViewContext, storage: Res<dyn Storage>) {
+    ///     ctx.insert(0, storage.load());
+    /// }
+    /// ```
+    pub fn bind_trait<T: ?Sized + 'static>(self, v: Box<T>) -> Self {
+        self.bind_resource(Res::from_box(v))
+    }
+
+    /// Insert a resource, replacing any resource of the same type that was
+    /// previously inserted. App::insert_resource panics when a resource type
+    /// is inserted twice, this function should be used when overriding a
+    /// resource is intended.
+    pub fn replace_resource<T: Any>(self, v: T) -> Self {
+        self.container.borrow_mut().bind_replace(Res::new(v));
+        self
+    }
+
     /// Insert a stateful object that can be injected into component functions
     /// unlike App::insert_resource, this value can be borrowed mutably and
     /// is meant to store application state.
@@ -192,18 +431,58 @@ where
         self
     }
 
+    /// Insert a state object, replacing any state of the same type that was
+    /// previously inserted. See App::replace_resource.
+    pub fn replace_state<T: Any>(self, v: T) -> Self {
+        self.container.borrow_mut().bind_replace(State::new(v));
+        self
+    }
+
     /// Executes the main run loop. This should be called to start the
     /// application logic.
     ///
     /// This function will block while it reads events and performs render
     /// cycles.
     pub fn run(&mut self) -> anyhow::Result<()> {
-        self.container.borrow_mut().bind(Res::new(Terminal));
-        self.container.borrow_mut().bind(Res::new(Keyboard::new()));
+        // Resources the application inserted are kept, the framework's own
+        // are bound for any that were not.
+        let options = &self.options;
+        let renderer = self.get_renderer();
+        {
+            let mut container = self.container.borrow_mut();
+            container.bind_default(|| Res::new(Terminal::new()));
+            container.bind_default(|| Res::new(Keyboard::new()));
+            container.bind_default(|| Res::new(Mouse::new()));
+            container.bind_default(|| Res::new(CursorHints::new()));
+            container.bind_default(|| Res::new(Frame::new()));
+            container.bind_default(|| Res::new(Zoom::new()));
+            container.bind_default(|| Res::new(FrameStats::new(options.frame_budget)));
+            container.bind_default(|| Res::new(ColorFilter::new(options.color_blindness)));
+            container.bind_default(|| Res::new(Animation::new()));
+            container.bind_default(|| Res::new(Idle::new(options.idle_timeout)));
+            container.bind_default(|| Res::new(TerminalBackground::detect()));
+            container.bind_default(|| Res::new(renderer));
+        }
+        let animation = self
+            .container
+            .borrow()
+            .get::<Res<Animation>>()
+            .cloned()
+            .unwrap();
+        let idle = self.container.borrow().get::<Res<Idle>>().cloned().unwrap();
+        self.container
+            .borrow()
+            .get::<Res<Features>>()
+            .unwrap()
+            .apply_env(FEATURES_VAR);
 
         let _result = std::panic::catch_unwind(teardown);
         let default_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |info| {
+            // Panics within an error boundary are displayed by the boundary.
+            if crate::context::in_error_boundary() {
+                return;
+            }
             teardown();
             default_hook(info);
         }));
@@ -215,41 +494,121 @@ where
         let _ = ctrlc::set_handler(|| {
             let mut out = std::io::stdout();
             let _ = terminal::disable_raw_mode();
-            let _ = execute!(out, terminal::LeaveAlternateScreen, cursor::Show);
+            let _ = execute!(
+                out,
+                DisableMouseCapture,
+                DisableBracketedPaste,
+                terminal::LeaveAlternateScreen,
+                cursor::Show
+            );
             std::process::exit(0);
         });
 
         let mut out = std::io::stdout();
-        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        execute!(
+            out,
+            terminal::EnterAlternateScreen,
+            EnableBracketedPaste,
+            cursor::Hide
+        )?;
+        if self.options.mouse {
+            execute!(out, EnableMouseCapture)?;
+        }
         terminal::enable_raw_mode()?;
         self.render()?;
 
+        // Keys typed while the terminal background was detected are handled
+        // first. Input is then read on a dedicated thread so that the run
+        // loop can block until either input or a render request arrives.
+        for event in crate::terminal::take_typed_ahead() {
+            let _ = self.event_tx.send(AppEvent::Input(event));
+        }
+        let input = InputThread::spawn(self.event_tx.clone());
+
         loop {
-            if crossterm::event::poll(Duration::from_millis(1000)).unwrap_or(false) {
-                if let Ok(event) = crossterm::event::read() {
+            let timeout = match (idle.remaining(), animation.remaining()) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            let event = match timeout {
+                Some(timeout) => match self.events.recv_timeout(timeout) {
+                    Ok(event) => Some(event),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                },
+                None => match self.events.recv() {
+                    Ok(event) => Some(event),
+                    Err(_) => break,
+                },
+            };
+
+            match event {
+                Some(AppEvent::Render) => {
+                    self.render_pending.store(false, Ordering::SeqCst);
+                    self.render()?;
+                    self.render()?;
+                }
+                Some(AppEvent::Input(event)) => {
+                    if matches!(event, Event::Key(_) | Event::Mouse(_) | Event::Paste(_))
+                        && idle.touch()
+                    {
+                        for plugin in self.plugins.borrow().iter() {
+                            plugin.on_active(self.container.clone());
+                        }
+                    }
                     match event {
                         Event::FocusGained => self.render()?,
                         Event::FocusLost => {}
-                        Event::Key(key_event) if key_event.code == KeyCode::Char('q') => {
-                            if self.options.q_to_quit {
-                                break;
-                            }
+                        Event::Key(key_event)
+                            if key_event.code == KeyCode::Char('q') && self.options.q_to_quit =>
+                        {
+                            break;
+                        }
+                        Event::Key(key_event)
+                            if key_event.kind == KeyEventKind::Press
+                                && Some(key_event.code) == self.options.zoom_key =>
+                        {
+                            self.container.borrow().get::<Res<Zoom>>().unwrap().toggle();
+                            self.render()?;
                         }
                         Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                            {
-                                let container = self.container.borrow();
-                                let kb = container.get::<Res<Keyboard>>().unwrap();
-                                kb.set_key(key_event.code);
-                                kb.set_modifiers(key_event.modifiers);
+                            let key = KeyBinding::new(key_event.code, key_event.modifiers);
+                            let keys = macro_keys(&self.container, key);
+                            if keys.is_empty() {
+                                self.render()?;
+                            }
+                            for key in keys {
+                                {
+                                    let container = self.container.borrow();
+                                    let kb = container.get::<Res<Keyboard>>().unwrap();
+                                    kb.set_key(key.code);
+                                    kb.set_modifiers(key.modifiers);
+                                    handle_global_keys(&self.key_handlers, kb);
+                                }
+                                self.render()?;
+                                self.render()?;
                             }
+                        }
+                        Event::Mouse(mouse_event) => {
+                            self.container
+                                .borrow()
+                                .get::<Res<Mouse>>()
+                                .unwrap()
+                                .set_event(mouse_event);
+                            self.render()?;
+                            self.render()?;
+                        }
+                        Event::Paste(text) => {
+                            self.container
+                                .borrow()
+                                .get::<Res<Keyboard>>()
+                                .unwrap()
+                                .set_paste(text);
                             self.render()?;
                             self.render()?;
                         }
-                        Event::Mouse(_) => todo!(),
-                        Event::Paste(_) => todo!(),
                         Event::Resize(col, row) => {
-                            self.main_view.0 =
-                                vec![vec![Rune::default(); col as usize]; row as usize];
+                            self.main_view = View::new((col as usize, row as usize));
                             self.current_view_state =
                                 vec![vec![Rune::default(); col as usize]; row as usize];
                             self.clear()?;
@@ -258,62 +617,72 @@ where
                         _ => {}
                     }
                 }
+                None => {}
             }
-            if self.render_signal.try_recv().is_ok() {
+
+            if idle.check() {
+                for plugin in self.plugins.borrow().iter() {
+                    plugin.on_idle(self.container.clone());
+                }
                 self.render()?;
+            }
+
+            if animation.take_due() {
                 self.render()?;
             }
         }
+        drop(input);
+        if self.options.mouse {
+            CursorShape::Default.write(&mut std::io::stdout())?;
+        }
         teardown();
 
         Ok(())
     }
 
     fn render(&mut self) -> anyhow::Result<()> {
-        loop {
-            let mut context = ViewContext::new(self.container.clone(), self.main_view.size());
-
-            for plugin in self.plugins.borrow().iter() {
-                plugin.before_render(&mut context, self.container.clone());
-            }
-
-            self.root
-                .call(&mut context, Args::from_container(&self.container.borrow()));
-
-            if context.should_exit {
-                teardown();
-                std::process::exit(0);
-            }
-
-            self.main_view.apply((0, 0), &context.view);
+        let plugins = self.plugins.borrow();
+        if render_frame(&self.container, &self.root, &plugins, &mut self.main_view) {
+            teardown();
+            std::process::exit(0);
+        }
+        drop(plugins);
 
-            for plugin in self.plugins.borrow().iter() {
-                plugin.after_render(&mut context, self.container.clone());
-                self.main_view.apply((0, 0), &context.view);
-            }
+        self.container
+            .borrow()
+            .get::<Res<Frame>>()
+            .unwrap()
+            .set(&self.main_view);
 
-            self.container
-                .borrow()
-                .get::<Res<Keyboard>>()
-                .unwrap()
-                .reset();
-
-            if !context.rerender {
-                break;
-            }
+        let filter = self
+            .container
+            .borrow()
+            .get::<Res<ColorFilter>>()
+            .unwrap()
+            .mode();
+        if filter != self.displayed_filter {
+            self.main_view.damage_all();
+            self.displayed_filter = filter;
         }
+        flush(
+            &mut self.main_view,
+            &mut self.current_view_state,
+            &mut std::io::stdout(),
+            filter,
+            self.options.hyperlinks,
+        )?;
 
-        let mut out = std::io::stdout();
-        for (row, line) in self.main_view.iter().enumerate() {
-            for (col, rune) in line.iter().enumerate() {
-                if &self.current_view_state[row][col] != rune {
-                    queue!(out, cursor::MoveTo(col as u16, row as u16))?;
-                    rune.render(&mut out)?;
-                    self.current_view_state[row][col] = *rune;
-                }
+        if self.options.mouse {
+            let container = self.container.borrow();
+            let position = container.get::<Res<Mouse>>().unwrap().position();
+            if let Some(shape) = container
+                .get::<Res<CursorHints>>()
+                .unwrap()
+                .update(position)
+            {
+                shape.write(&mut std::io::stdout())?;
             }
         }
-        out.flush()?;
         Ok(())
     }
 
@@ -328,21 +697,323 @@ where
     }
 }
 
+/// Apply global key handlers to the pressed key in order, stopping at the
+/// first handler which swallows it. The keyboard is reset if the key was
+/// swallowed.
+pub(crate) fn handle_global_keys(handlers: &[KeyHandler], kb: &Keyboard) {
+    if handlers.iter().any(|handler| handler(kb)) {
+        kb.reset();
+    }
+}
+
+/// The keys the application receives for a key pressed by the user, once
+/// it has been processed by the Macros resource. Macros are controlled by
+/// the actions of the Keymap, so every key is received unchanged if no
+/// Keymap is bound.
+pub(crate) fn macro_keys(container: &ContainerRef, key: KeyBinding) -> Vec<KeyBinding> {
+    let container = container.borrow();
+    let (Some(macros), Some(keymap)) = (
+        container.get::<Res<Macros>>(),
+        container.get::<Res<Keymap>>(),
+    ) else {
+        return vec![key];
+    };
+    let kb = Keyboard::with_key(key.code).with_modifiers(key.modifiers);
+    macros.process(key, keymap.action(&kb).as_deref())
+}
+
+/// Render the root component, and everything drawn above it, into a view.
+/// Rendering is repeated until no component requests a rerender. Returns
+/// true if a component requested that the application exit, in which case
+/// the view is left unchanged.
+pub(crate) fn render_frame<F, Args>(
+    container: &ContainerRef,
+    root: &F,
+    plugins: &[Box<dyn Plugin>],
+    view: &mut View,
+) -> bool
+where
+    F: Callable<Args>,
+    Args: FromContainer,
+{
+    loop {
+        let start = Instant::now();
+        let mut context = ViewContext::new(container.clone(), view.size());
+        let zoom = container.borrow().get::<Res<Zoom>>().cloned();
+        if let Some(zoom) = &zoom {
+            zoom.begin_frame(view.size());
+        }
+        if let Some(hints) = container.borrow().get::<Res<CursorHints>>() {
+            hints.begin_frame();
+        }
+
+        for plugin in plugins.iter() {
+            plugin.before_render(&mut context, container.clone());
+        }
+
+        layout_checks::enter::<F>();
+        profiler::enter::<F>();
+        match context.inject::<Args>() {
+            Ok(args) => root.call(&mut context, args),
+            Err(error) => context.diagnostic(&error),
+        }
+        layout_checks::exit();
+        profiler::exit();
+        provenance::stamp::<F>(&mut context.view);
+
+        if let Some(zoomed) = zoom.and_then(|zoom| zoom.take_view()) {
+            context.view.apply((0, 0), &zoomed);
+        }
+
+        let docks = container.borrow().get::<Res<Docks>>().cloned();
+        if let Some(docks) = docks {
+            docks.draw(&mut context);
+        }
+        context.draw_overlays();
+        context.resolve_tokens();
+
+        if context.should_exit {
+            return true;
+        }
+
+        view.apply((0, 0), &context.view);
+
+        for plugin in plugins.iter() {
+            plugin.after_render(&mut context, container.clone());
+            view.apply((0, 0), &context.view);
+        }
+
+        let stats = container.borrow().get::<Res<FrameStats>>().cloned();
+        if let Some(stats) = stats {
+            let _slow_frame = stats.record(start.elapsed(), profiler::take_slowest());
+            #[cfg(feature = "log")]
+            if let Some(slow_frame) = _slow_frame {
+                log::warn!(target: "arkham", "{}", slow_frame);
+            }
+            #[cfg(feature = "profiling")]
+            if let Some(slow_frame) = stats.warning() {
+                draw_frame_warning(&mut context, &slow_frame);
+                view.apply((0, 0), &context.view);
+            }
+        }
+
+        container.borrow_mut().clear_frame();
+        layout_checks::finish_frame();
+
+        container.borrow().get::<Res<Keyboard>>().unwrap().reset();
+        if let Some(mouse) = container.borrow().get::<Res<Mouse>>() {
+            mouse.reset();
+        }
+
+        if !context.rerender {
+            for plugin in plugins.iter() {
+                plugin.post_process(view, container.clone());
+            }
+            return false;
+        }
+    }
+}
+
+/// Draw a banner across the top of the screen describing a slow frame.
+#[cfg(feature = "profiling")]
+fn draw_frame_warning(ctx: &mut ViewContext, slow_frame: &crate::frame::SlowFrame) {
+    use crossterm::style::Color;
+
+    let theme = ctx.theme();
+    let width = ctx.size().width;
+    ctx.fill(((0, 0), (width, 1)), Rune::new().bg(theme.warning));
+    let text: String = format!(" ⚠ {}", slow_frame).chars().take(width).collect();
+    ctx.insert((0, 0), Runes::from(text).fg(Color::Black).bg(theme.warning));
+}
+
+/// Write the runes of a view that differ from what is currently displayed,
+/// updating the displayed state to match the view. Colors are transformed by
+/// the color blindness filter, if one is set, before they are compared with
+/// the displayed state. Hyperlinks are written with OSC 8 if enabled.
+///
+/// Only the rows of the view damaged since it was last flushed are compared,
+/// and its damage is cleared once they have been written. The view must be
+/// damaged entirely when the filter changes, so that every colored rune is
+/// redrawn.
+///
+/// Continuation cells, which the wide character before them extends into,
+/// are not written themselves.
+pub(crate) fn flush<W: Write>(
+    view: &mut View,
+    current: &mut [Vec<Rune>],
+    out: &mut W,
+    filter: Option<ColorBlindness>,
+    hyperlinks: bool,
+) -> anyhow::Result<()> {
+    let mut render_state = RenderState::new(hyperlinks);
+    // Where the terminal's cursor is left after the last rune written, so
+    // that runs of changed cells are written without moving it.
+    let mut cursor = None;
+    for row in view.damaged_rows() {
+        let line = &view[row];
+        for (col, rune) in line.iter().enumerate() {
+            let mut rune = *rune;
+            if let Some(filter) = filter {
+                rune.fg = rune.fg.map(|c| filter.simulate(c));
+                rune.bg = rune.bg.map(|c| filter.simulate(c));
+            }
+            // A wide character is redrawn when the cell it extends into
+            // changes, as terminals clear the character when it is written
+            // over.
+            let neighbor_changed = line
+                .get(col + 1)
+                .is_some_and(|next| next.continuation != current[row][col + 1].continuation);
+            if current[row][col] != rune || neighbor_changed {
+                if !rune.continuation {
+                    if cursor != Some((col, row)) {
+                        queue!(out, cursor::MoveTo(col as u16, row as u16))?;
+                    }
+                    rune.render(out, &mut render_state)?;
+                    cursor = Some((col + rune.width(), row));
+                }
+                current[row][col] = rune;
+            }
+        }
+    }
+    view.clear_damage();
+    render_state.finish(out)?;
+    out.flush()?;
+    Ok(())
+}
+
 /// Repairs the terminal state so it operates properly.
+/// How often the input thread checks whether it should stop.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The thread reading terminal input while an application runs. The thread
+/// is stopped and joined when dropped, so that it does not go on consuming
+/// key presses once App::run returns, such as those meant for a prompt or
+/// another App.
+struct InputThread {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl InputThread {
+    fn spawn(tx: Sender<AppEvent>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                while !stop.load(Ordering::SeqCst) {
+                    match crossterm::event::poll(INPUT_POLL_INTERVAL) {
+                        Ok(true) => {}
+                        Ok(false) => continue,
+                        Err(_) => break,
+                    }
+                    let Ok(event) = crossterm::event::read() else {
+                        break;
+                    };
+                    if tx.send(AppEvent::Input(event)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for InputThread {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
 fn teardown() {
     let mut out = std::io::stdout();
     let _ = terminal::disable_raw_mode();
-    let _ = execute!(out, terminal::LeaveAlternateScreen, cursor::Show);
+    let _ = execute!(
+        out,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        terminal::LeaveAlternateScreen,
+        cursor::Show
+    );
+}
+
+#[derive(Debug)]
+struct HeadlessTerminal {
+    size: Cell<(u16, u16)>,
+    title: RefCell<Option<String>>,
 }
 
-pub struct Terminal;
+/// Terminal can be used as an injectable resource to interact with the
+/// terminal the application is running in.
+///
+/// A headless terminal can be bound in place of the real terminal to test
+/// components that inject it. A headless terminal reports a fixed size and
+/// records the title instead of writing to stdout.
+///
+/// ```
+/// use arkham::prelude::*;
+///
+/// let terminal = Terminal::headless(80, 24);
+/// terminal.set_title("My App");
+/// assert_eq!(terminal.size(), (80, 24));
+/// assert_eq!(terminal.title(), Some("My App".to_string()));
+/// ```
+#[derive(Debug, Default)]
+pub struct Terminal {
+    headless: Option<HeadlessTerminal>,
+}
 
 impl Terminal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a terminal that is not attached to stdout.
+    pub fn headless(width: u16, height: u16) -> Self {
+        Self {
+            headless: Some(HeadlessTerminal {
+                size: Cell::new((width, height)),
+                title: RefCell::new(None),
+            }),
+        }
+    }
+
     pub fn set_title(&self, name: &str) {
-        let _ = execute!(std::io::stdout(), terminal::SetTitle(name));
+        match &self.headless {
+            Some(headless) => *headless.title.borrow_mut() = Some(name.to_string()),
+            None => {
+                let _ = execute!(std::io::stdout(), terminal::SetTitle(name));
+            }
+        }
     }
+
     pub fn size(&self) -> (u16, u16) {
-        crossterm::terminal::size().unwrap_or_default()
+        match &self.headless {
+            Some(headless) => headless.size.get(),
+            None => crossterm::terminal::size().unwrap_or_default(),
+        }
+    }
+
+    /// The last title set on a headless terminal. The title of a real
+    /// terminal cannot be read back and is always None.
+    pub fn title(&self) -> Option<String> {
+        self.headless
+            .as_ref()
+            .and_then(|headless| headless.title.borrow().clone())
+    }
+
+    /// Change the size reported by a headless terminal. This has no effect
+    /// on a real terminal.
+    pub fn resize(&self, width: u16, height: u16) {
+        if let Some(headless) = &self.headless {
+            headless.size.set((width, height));
+        }
     }
 }
 
@@ -367,4 +1038,101 @@ mod tests {
 
     #[allow(dead_code)]
     fn is_send(_: impl Send) {}
+
+    #[test]
+    fn test_headless_terminal() {
+        use crate::prelude::{Res, Terminal, ViewContext};
+
+        fn status(ctx: &mut ViewContext, terminal: Res<Terminal>) {
+            let (width, height) = terminal.size();
+            terminal.set_title(&format!("{}x{}", width, height));
+            ctx.insert(0, "ok");
+        }
+
+        let mut ctx = crate::context::tests::context_fixture();
+        let terminal = Res::new(Terminal::headless(80, 24));
+        ctx.container.borrow_mut().bind(terminal.clone());
+        ctx.component(((0, 0), (5, 1)), status);
+        assert_eq!(terminal.title(), Some("80x24".to_string()));
+
+        terminal.resize(100, 40);
+        ctx.component(((0, 0), (5, 1)), status);
+        assert_eq!(terminal.title(), Some("100x40".to_string()));
+        assert_eq!(Terminal::new().title(), None);
+    }
+
+    #[test]
+    fn test_flush_wide_characters() {
+        use crate::{runes::Rune, view::View};
+
+        let mut view = View::new((3, 1));
+        let mut current = vec![vec![Rune::default(); 3]; 1];
+        let mut out = vec![];
+        view.insert((0, 0), "中x");
+        super::flush(&mut view, &mut current, &mut out, None, false).unwrap();
+        let written = String::from_utf8_lossy(&out).into_owned();
+        assert!(written.contains('中'));
+        assert!(!written.contains("\x1b[1;2H"));
+
+        // Writing over the continuation directly redraws the wide character.
+        out.clear();
+        view[0][1] = Rune::new().content('y');
+        super::flush(&mut view, &mut current, &mut out, None, false).unwrap();
+        let written = String::from_utf8_lossy(&out);
+        assert!(written.contains("\x1b[1;1H"));
+        assert!(written.contains("\x1b[1;2H"));
+    }
+    #[test]
+    fn test_flush_batches_runs() {
+        use crate::{
+            prelude::Color,
+            runes::{Rune, ToRuneExt},
+            view::View,
+        };
+
+        let mut view = View::new((8, 2));
+        let mut current = vec![vec![Rune::default(); 8]; 2];
+        let mut out = vec![];
+        view.insert((0, 0), "ab中c".to_runes().fg(Color::Red));
+        view.insert((6, 0), "d".to_runes().fg(Color::Red));
+        super::flush(&mut view, &mut current, &mut out, None, false).unwrap();
+        let written = String::from_utf8_lossy(&out);
+        // The cursor is moved once for each run of changed cells, and the
+        // style is written once for all of them.
+        assert_eq!(written.matches('H').count(), 2);
+        assert!(written.contains("\x1b[1;7Hd"));
+        assert_eq!(written.matches("38;5;9").count(), 1);
+    }
+
+    #[test]
+    fn test_color_tokens_follow_theme() {
+        use crate::{
+            container::Res,
+            prelude::{Color, Theme, ToRuneExt, ViewContext},
+            runes::Rune,
+            testing::TestApp,
+        };
+
+        let label = "ok".to_runes().fg_token("brand").bg_token("accent");
+        let mut app = TestApp::new(
+            move |ctx: &mut ViewContext| {
+                ctx.insert((0, 0), label.clone());
+                ctx.fill(((0, 1), (2, 1)), Rune::new().content('-').fg_token("error"));
+            },
+            (4, 2),
+        )
+        .insert_resource(Theme::dark().with_color("brand", Color::Magenta));
+        let view = app.render();
+        assert_eq!(view[0][0].fg, Some(Color::Magenta));
+        assert_eq!(view[0][1].bg, Some(Theme::dark().accent));
+        assert_eq!(view[1][0].fg, Some(Theme::dark().error));
+
+        app.container()
+            .borrow_mut()
+            .bind_replace(Res::new(Theme::light().with_color("brand", Color::Cyan)));
+        let view = app.render();
+        assert_eq!(view[0][0].fg, Some(Color::Cyan));
+        assert_eq!(view[0][1].bg, Some(Theme::light().accent));
+        assert_eq!(view[1][0].fg, Some(Theme::light().error));
+    }
 }
@@ -0,0 +1,389 @@
+use std::{cell::Cell, rc::Rc};
+
+use crossterm::style::Color;
+
+/// Returns the RGB components for a color. Named and ANSI colors are mapped
+/// to the standard xterm palette. Returns None for Color::Reset, which has
+/// no fixed value.
+pub(crate) fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let rgb = match color {
+        Color::Reset => return None,
+        Color::Black => (0, 0, 0),
+        Color::DarkRed => (128, 0, 0),
+        Color::DarkGreen => (0, 128, 0),
+        Color::DarkYellow => (128, 128, 0),
+        Color::DarkBlue => (0, 0, 128),
+        Color::DarkMagenta => (128, 0, 128),
+        Color::DarkCyan => (0, 128, 128),
+        Color::Grey => (192, 192, 192),
+        Color::DarkGrey => (128, 128, 128),
+        Color::Red => (255, 0, 0),
+        Color::Green => (0, 255, 0),
+        Color::Yellow => (255, 255, 0),
+        Color::Blue => (0, 0, 255),
+        Color::Magenta => (255, 0, 255),
+        Color::Cyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::AnsiValue(v) => return Some(ansi_to_rgb(v)),
+    };
+    Some(rgb)
+}
+
+fn ansi_to_rgb(value: u8) -> (u8, u8, u8) {
+    const BASIC: [Color; 16] = [
+        Color::Black,
+        Color::DarkRed,
+        Color::DarkGreen,
+        Color::DarkYellow,
+        Color::DarkBlue,
+        Color::DarkMagenta,
+        Color::DarkCyan,
+        Color::Grey,
+        Color::DarkGrey,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match value {
+        0..=15 => to_rgb(BASIC[value as usize]).unwrap_or_default(),
+        16..=231 => {
+            let v = value - 16;
+            (
+                LEVELS[(v / 36) as usize],
+                LEVELS[((v / 6) % 6) as usize],
+                LEVELS[(v % 6) as usize],
+            )
+        }
+        _ => {
+            let g = 8 + (value - 232) * 10;
+            (g, g, g)
+        }
+    }
+}
+
+/// Linearly interpolate between two colors. A `t` of 0.0 returns `from` and
+/// 1.0 returns `to`. Colors without a fixed RGB value cannot be blended and
+/// switch over at the halfway point.
+pub(crate) fn lerp(from: Color, to: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    match (to_rgb(from), to_rgb(to)) {
+        (Some(a), Some(b)) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color::Rgb {
+                r: mix(a.0, b.0),
+                g: mix(a.1, b.1),
+                b: mix(a.2, b.2),
+            }
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// The fully saturated color of a hue, given in degrees around the color
+/// wheel, where 0.0 is red, 120.0 green and 240.0 blue.
+pub(crate) fn hue(degrees: f32) -> Color {
+    let h = degrees.rem_euclid(360.0) / 60.0;
+    let x = ((1.0 - (h % 2.0 - 1.0).abs()) * 255.0).round() as u8;
+    let (r, g, b) = match h as u8 {
+        0 => (255, x, 0),
+        1 => (x, 255, 0),
+        2 => (0, 255, x),
+        3 => (0, x, 255),
+        4 => (x, 0, 255),
+        _ => (255, 0, x),
+    };
+    Color::Rgb { r, g, b }
+}
+
+/// How strongly disabled content and content behind a modal is dimmed.
+pub(crate) const DIM: f32 = 0.6;
+
+/// Dim a color for content that is disabled or behind a modal, draining it
+/// toward the grey of the same brightness by the given amount between 0.0
+/// and 1.0 and darkening it by half as much. Colors without a fixed RGB
+/// value are returned unchanged.
+pub(crate) fn dim(color: Color, amount: f32) -> Color {
+    let Some((r, g, b)) = to_rgb(color) else {
+        return color;
+    };
+    let luminance = (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32).round() as u8;
+    let grey = Color::Rgb {
+        r: luminance,
+        g: luminance,
+        b: luminance,
+    };
+    lerp(lerp(color, grey, amount), Color::Black, amount / 2.0)
+}
+
+/// Returns black or white, whichever is more legible on the given
+/// background color.
+pub(crate) fn contrast(background: Color) -> Option<Color> {
+    let (r, g, b) = to_rgb(background)?;
+    let luminance = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+    if luminance > 127.5 {
+        Some(Color::Black)
+    } else {
+        Some(Color::White)
+    }
+}
+
+/// A form of color blindness which can be simulated to check that an
+/// interface remains usable. See ColorFilter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorBlindness {
+    /// Reduced sensitivity to red light.
+    Protanopia,
+    /// Reduced sensitivity to green light.
+    Deuteranopia,
+    /// Reduced sensitivity to blue light.
+    Tritanopia,
+}
+
+impl ColorBlindness {
+    /// The simulation matrices from Machado, Oliveira and Fernandes (2009)
+    /// at full severity, applied to linear RGB.
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            ColorBlindness::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorBlindness::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorBlindness::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// Returns the color as it appears to a person with this form of color
+    /// blindness. Colors without a fixed RGB value are returned unchanged.
+    pub fn simulate(self, color: Color) -> Color {
+        let Some((r, g, b)) = to_rgb(color) else {
+            return color;
+        };
+        let linear = [r, g, b].map(|c| {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        });
+        let [r, g, b] = self.matrix().map(|row| {
+            let c = (row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]).clamp(0.0, 1.0);
+            let c = if c <= 0.0031308 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c * 255.0).round() as u8
+        });
+        Color::Rgb { r, g, b }
+    }
+}
+
+/// ColorFilter is an injectable resource which transforms every color
+/// written to the terminal to simulate a form of color blindness. It is
+/// intended as a debugging aid for checking that a theme remains
+/// distinguishable, and only affects output, so components and plugins
+/// continue to see the original colors.
+///
+/// A filter can be set at startup with App::color_blindness, and changed at
+/// runtime:
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root)
+///         .color_blindness(ColorBlindness::Deuteranopia)
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, filter: Res<ColorFilter>, kb: Res<Keyboard>) {
+///     if kb.code() == Some(KeyCode::Char('c')) {
+///         filter.cycle();
+///     }
+///     let name = match filter.mode() {
+///         Some(mode) => format!("{:?}", mode),
+///         None => "None".to_string(),
+///     };
+///     ctx.insert((0, 0), format!("Simulating: {}", name));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ColorFilter {
+    mode: Rc<Cell<Option<ColorBlindness>>>,
+}
+
+impl ColorFilter {
+    pub fn new(mode: Option<ColorBlindness>) -> Self {
+        Self {
+            mode: Rc::new(Cell::new(mode)),
+        }
+    }
+
+    /// The form of color blindness being simulated.
+    pub fn mode(&self) -> Option<ColorBlindness> {
+        self.mode.get()
+    }
+
+    /// Set the form of color blindness to simulate, or None to display
+    /// colors unchanged.
+    pub fn set_mode(&self, mode: Option<ColorBlindness>) {
+        self.mode.set(mode);
+    }
+
+    /// Move to the next form of color blindness, returning to unfiltered
+    /// colors after tritanopia.
+    pub fn cycle(&self) {
+        self.mode.set(match self.mode.get() {
+            None => Some(ColorBlindness::Protanopia),
+            Some(ColorBlindness::Protanopia) => Some(ColorBlindness::Deuteranopia),
+            Some(ColorBlindness::Deuteranopia) => Some(ColorBlindness::Tritanopia),
+            Some(ColorBlindness::Tritanopia) => None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use super::{contrast, dim, hue, lerp, to_rgb, ColorBlindness, ColorFilter};
+
+    #[test]
+    fn test_ansi_to_rgb() {
+        assert_eq!(to_rgb(Color::AnsiValue(9)), Some((255, 0, 0)));
+        assert_eq!(to_rgb(Color::AnsiValue(196)), Some((255, 0, 0)));
+        assert_eq!(to_rgb(Color::AnsiValue(232)), Some((8, 8, 8)));
+        assert_eq!(to_rgb(Color::Reset), None);
+    }
+
+    #[test]
+    fn test_hue() {
+        assert_eq!(hue(0.0), Color::Rgb { r: 255, g: 0, b: 0 });
+        assert_eq!(
+            hue(60.0),
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 0
+            }
+        );
+        assert_eq!(hue(240.0), Color::Rgb { r: 0, g: 0, b: 255 });
+        assert_eq!(hue(-120.0), hue(240.0));
+    }
+
+    #[test]
+    fn test_lerp() {
+        let mid = lerp(Color::Black, Color::White, 0.5);
+        assert_eq!(
+            mid,
+            Color::Rgb {
+                r: 128,
+                g: 128,
+                b: 128
+            }
+        );
+        assert_eq!(lerp(Color::Reset, Color::White, 0.2), Color::Reset);
+        assert_eq!(lerp(Color::Reset, Color::White, 0.7), Color::White);
+    }
+
+    #[test]
+    fn test_dim() {
+        assert_eq!(
+            dim(Color::Red, 0.6),
+            Color::Rgb {
+                r: 94,
+                g: 22,
+                b: 22
+            }
+        );
+        assert_eq!(
+            dim(Color::White, 0.0),
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(dim(Color::Reset, 0.6), Color::Reset);
+    }
+
+    #[test]
+    fn test_contrast() {
+        assert_eq!(contrast(Color::White), Some(Color::Black));
+        assert_eq!(contrast(Color::DarkBlue), Some(Color::White));
+        assert_eq!(contrast(Color::Reset), None);
+    }
+
+    #[test]
+    fn test_simulate_color_blindness() {
+        for mode in [
+            ColorBlindness::Protanopia,
+            ColorBlindness::Deuteranopia,
+            ColorBlindness::Tritanopia,
+        ] {
+            let white = to_rgb(mode.simulate(Color::White)).unwrap();
+            assert!(white.0 >= 254 && white.1 >= 254 && white.2 >= 254);
+            assert_eq!(mode.simulate(Color::Black), Color::Rgb { r: 0, g: 0, b: 0 });
+            assert_eq!(mode.simulate(Color::Reset), Color::Reset);
+        }
+
+        // Red and green are hard to tell apart with deuteranopia.
+        let (r1, g1, _) = to_rgb(ColorBlindness::Deuteranopia.simulate(Color::Red)).unwrap();
+        let (r2, g2, _) = to_rgb(ColorBlindness::Deuteranopia.simulate(Color::Green)).unwrap();
+        assert!(r1.abs_diff(r2) < 100 && g1.abs_diff(g2) < 100);
+
+        let filter = ColorFilter::default();
+        filter.cycle();
+        assert_eq!(filter.mode(), Some(ColorBlindness::Protanopia));
+        filter.set_mode(Some(ColorBlindness::Tritanopia));
+        filter.cycle();
+        assert_eq!(filter.mode(), None);
+    }
+
+    #[test]
+    fn test_filter_output() {
+        use crate::{container::Res, context::ViewContext, runes::ToRuneExt, testing::TestApp};
+
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| ctx.insert((0, 0), "a".to_runes().fg(Color::Red)),
+            (2, 1),
+        );
+        let mut out = vec![];
+        app.draw(&mut out).unwrap();
+        out.clear();
+
+        let container = app.container();
+        let filter = container
+            .borrow()
+            .get::<Res<ColorFilter>>()
+            .unwrap()
+            .clone();
+        filter.set_mode(Some(ColorBlindness::Protanopia));
+        app.draw(&mut out).unwrap();
+        let Color::Rgb { r, g, b } = ColorBlindness::Protanopia.simulate(Color::Red) else {
+            unreachable!()
+        };
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(&format!("38;2;{};{};{}m", r, g, b)));
+    }
+}
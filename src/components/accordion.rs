@@ -0,0 +1,383 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::{boxed_component, BoxedComponent, Callable, FromContainer},
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{Rune, Runes},
+};
+
+/// A collapsible section of an Accordion, made up of a header and a body
+/// component.
+#[derive(Clone)]
+pub struct AccordionSection {
+    title: Runes,
+    height: usize,
+    content: BoxedComponent,
+}
+
+impl std::fmt::Debug for AccordionSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccordionSection")
+            .field("title", &self.title)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl AccordionSection {
+    /// Create a section with a title, the number of rows its body takes
+    /// when open, and the component rendered in the body. The component can
+    /// be injected with resources like any other component.
+    pub fn new<R, F, Args>(title: R, height: usize, component: F) -> Self
+    where
+        R: Into<Runes>,
+        F: Callable<Args> + 'static,
+        Args: FromContainer + 'static,
+    {
+        Self {
+            title: title.into(),
+            height,
+            content: boxed_component(component),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AccordionState {
+    sections: Vec<AccordionSection>,
+    open: Vec<bool>,
+    /// The number of body rows currently displayed for each section, which
+    /// trails the open state while a section is animating.
+    shown: Vec<f32>,
+    focused: usize,
+    multiple: bool,
+    duration: Duration,
+    last_draw: Option<Instant>,
+}
+
+impl Default for AccordionState {
+    fn default() -> Self {
+        Self {
+            sections: vec![],
+            open: vec![],
+            shown: vec![],
+            focused: 0,
+            multiple: false,
+            duration: Duration::from_millis(150),
+            last_draw: None,
+        }
+    }
+}
+
+impl AccordionState {
+    fn set_open(&mut self, idx: usize, open: bool) {
+        if idx >= self.sections.len() {
+            return;
+        }
+        if open && !self.multiple {
+            self.open.iter_mut().for_each(|o| *o = false);
+        }
+        self.open[idx] = open;
+    }
+
+    /// Move the displayed height of each section toward its target, returning
+    /// true if any section is still animating.
+    fn animate(&mut self, elapsed: Duration) -> bool {
+        let mut animating = false;
+        for (idx, section) in self.sections.iter().enumerate() {
+            let target = if self.open[idx] {
+                section.height as f32
+            } else {
+                0.0
+            };
+            let shown = &mut self.shown[idx];
+            if self.duration.is_zero() {
+                *shown = target;
+                continue;
+            }
+            let step = section.height as f32 * elapsed.as_secs_f32() / self.duration.as_secs_f32();
+            *shown = if *shown < target {
+                (*shown + step).min(target)
+            } else {
+                (*shown - step).max(target)
+            };
+            animating |= *shown != target;
+        }
+        animating
+    }
+}
+
+/// Accordion displays a list of sections that can be expanded to reveal
+/// their content. By default opening a section closes the others; use
+/// Accordion::multiple to allow several sections to be open at once.
+///
+/// Accordion::handle_keys provides the key bindings: Up and Down move focus
+/// between section headers, and Enter or Space toggles the focused section.
+///
+/// Sections slide open and closed over a short animation, which can be
+/// changed or disabled with Accordion::animation.
+///
+/// ```no_run
+/// use arkham::{components::{Accordion, AccordionSection}, prelude::*};
+///
+/// fn main() {
+///     let accordion = Accordion::new()
+///         .section(AccordionSection::new("General", 3, general))
+///         .section(AccordionSection::new("Advanced", 5, advanced));
+///     App::new(root).insert_resource(accordion).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, accordion: Res<Accordion>, kb: Res<Keyboard>) {
+///     accordion.handle_keys(&kb);
+///     let size = ctx.size();
+///     accordion.draw(ctx, size);
+/// }
+///
+/// fn general(ctx: &mut ViewContext) {
+///     ctx.insert((2, 0), "Name: arkham");
+/// }
+///
+/// fn advanced(ctx: &mut ViewContext) {
+///     ctx.insert((2, 0), "Threads: 4");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accordion {
+    state: Rc<RefCell<AccordionState>>,
+}
+
+impl Accordion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a section.
+    pub fn section(self, section: AccordionSection) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.sections.push(section);
+            state.open.push(false);
+            state.shown.push(0.0);
+        }
+        self
+    }
+
+    /// Allow several sections to be open at once.
+    pub fn multiple(self) -> Self {
+        self.state.borrow_mut().multiple = true;
+        self
+    }
+
+    /// Set the time a section takes to open or close. A duration of zero
+    /// opens and closes sections immediately. Defaults to 150ms.
+    pub fn animation(self, duration: Duration) -> Self {
+        self.state.borrow_mut().duration = duration;
+        self
+    }
+
+    /// Open a section. Unless multiple sections are allowed, any other open
+    /// section is closed.
+    pub fn open(&self, idx: usize) {
+        self.state.borrow_mut().set_open(idx, true);
+    }
+
+    /// Close a section.
+    pub fn close(&self, idx: usize) {
+        self.state.borrow_mut().set_open(idx, false);
+    }
+
+    /// Open a section if it is closed, or close it if it is open.
+    pub fn toggle(&self, idx: usize) {
+        let open = self.is_open(idx);
+        self.state.borrow_mut().set_open(idx, !open);
+    }
+
+    /// Returns true if a section is open.
+    pub fn is_open(&self, idx: usize) -> bool {
+        self.state.borrow().open.get(idx).copied().unwrap_or(false)
+    }
+
+    /// The index of the focused section.
+    pub fn focused(&self) -> usize {
+        self.state.borrow().focused
+    }
+
+    /// Focus a section. Indexes past the last section focus the last one.
+    pub fn focus(&self, idx: usize) {
+        let mut state = self.state.borrow_mut();
+        state.focused = idx.min(state.sections.len().saturating_sub(1));
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let focused = self.focused();
+        match kb.code() {
+            Some(KeyCode::Up) => self.focus(focused.saturating_sub(1)),
+            Some(KeyCode::Down) => self.focus(focused + 1),
+            Some(KeyCode::Enter) | Some(KeyCode::Char(' ')) => self.toggle(focused),
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the sections within a region of a context. Sections that do not
+    /// fit are clipped.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let (sections, shown, open, focused) = {
+            let mut state = self.state.borrow_mut();
+            let now = Instant::now();
+            let elapsed = state
+                .last_draw
+                .map_or(Duration::ZERO, |last| now.duration_since(last));
+            state.last_draw = Some(now);
+            if state.animate(elapsed) {
+                ctx.animate(Duration::from_millis(16));
+            }
+            (
+                state.sections.clone(),
+                state.shown.clone(),
+                state.open.clone(),
+                state.focused,
+            )
+        };
+
+        let bottom = rect.pos.y + rect.size.height;
+        let mut y = rect.pos.y;
+        for (idx, section) in sections.iter().enumerate() {
+            if y >= bottom {
+                break;
+            }
+            let arrow = if open[idx] {
+                symbols.arrow_down
+            } else {
+                symbols.arrow_right
+            };
+            let width = rect.size.width;
+            let mut header = Runes::new(
+                std::iter::once(Rune::new().content(arrow))
+                    .chain(std::iter::once(Rune::new().content(' ')))
+                    .chain(section.title.iter().copied())
+                    .take(width)
+                    .collect(),
+            );
+            if idx == focused {
+                ctx.fill(
+                    ((rect.pos.x, y), (width, 1)),
+                    Rune::new().bg(theme.bg_selection),
+                );
+                header = header.fg(theme.fg_selection).bg(theme.bg_selection);
+            }
+            ctx.insert((rect.pos.x, y), header);
+            y += 1;
+
+            let rows = (shown[idx].round() as usize).min(bottom - y);
+            if rows > 0 {
+                (section.content)(ctx, Rect::new((rect.pos.x, y), (rect.size.width, rows)));
+                y += rows;
+            }
+        }
+    }
+}
+
+impl Callable<()> for Accordion {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crossterm::event::KeyCode;
+
+    use crate::{context::ViewContext, input::Keyboard};
+
+    use super::{Accordion, AccordionSection};
+
+    fn body(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "body");
+        ctx.insert((0, 1), "more");
+    }
+
+    fn accordion() -> Accordion {
+        Accordion::new()
+            .animation(Duration::ZERO)
+            .section(AccordionSection::new("One", 2, body))
+            .section(AccordionSection::new("Two", 2, body))
+    }
+
+    fn draw(accordion: &Accordion) -> Vec<String> {
+        let mut ctx = crate::context::tests::context_fixture();
+        accordion.draw(&mut ctx, ((0, 0), (10, 5)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        text.lines()
+            .take(5)
+            .map(|l| l.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_toggle_sections() {
+        let accordion = accordion();
+        assert_eq!(draw(&accordion), vec!["→ One", "→ Two", "", "", ""]);
+
+        accordion.handle_keys(&Keyboard::with_key(KeyCode::Enter));
+        assert_eq!(draw(&accordion), vec!["↓ One", "body", "more", "→ Two", ""]);
+
+        // Opening a section closes the other, and bodies are clipped to the
+        // area.
+        accordion.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        accordion.handle_keys(&Keyboard::with_key(KeyCode::Char(' ')));
+        assert!(!accordion.is_open(0));
+        assert_eq!(draw(&accordion), vec!["→ One", "↓ Two", "body", "more", ""]);
+
+        let accordion = accordion.multiple();
+        accordion.open(0);
+        assert!(accordion.is_open(1));
+        assert_eq!(
+            draw(&accordion),
+            vec!["↓ One", "body", "more", "↓ Two", "body"]
+        );
+    }
+
+    #[test]
+    fn test_animation() {
+        let accordion = Accordion::new()
+            .animation(Duration::from_secs(60))
+            .section(AccordionSection::new("One", 2, body));
+        draw(&accordion);
+        accordion.open(0);
+        let mut ctx = crate::context::tests::context_fixture();
+        let animation = crate::animation::Animation::new();
+        ctx.container
+            .borrow_mut()
+            .bind(crate::container::Res::new(animation.clone()));
+        accordion.draw(&mut ctx, ((0, 0), (10, 5)));
+        assert!(animation.is_pending());
+        assert_eq!(
+            ctx.view
+                .render_text()
+                .replace('\0', " ")
+                .lines()
+                .nth(1)
+                .map(str::trim),
+            Some("")
+        );
+    }
+}
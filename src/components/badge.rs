@@ -0,0 +1,123 @@
+use crossterm::style::Color;
+
+use crate::{container::Callable, context::ViewContext, geometry::Pos, runes::Runes, theme::Theme};
+
+/// The status a Badge represents, which determines its color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Success,
+    Warning,
+    Error,
+    Info,
+    #[default]
+    Neutral,
+}
+
+impl Severity {
+    /// The theme color for the severity.
+    pub fn color(self, theme: &Theme) -> Color {
+        match self {
+            Severity::Success => theme.success,
+            Severity::Warning => theme.warning,
+            Severity::Error => theme.error,
+            Severity::Info => theme.info,
+            Severity::Neutral => theme.muted,
+        }
+    }
+}
+
+/// Badge is a short label drawn as a colored pill, with a cell of padding
+/// on either side, for displaying a status such as "OK" or "FAILED".
+///
+/// Badges are created with the badge function. They can be drawn directly,
+/// or converted to runes with Badge::to_runes to be used as the item of a
+/// List or combined with other text.
+///
+/// ```no_run
+/// use arkham::{
+///     components::{badge, List, Severity},
+///     prelude::*,
+/// };
+///
+/// fn root(ctx: &mut ViewContext, theme: Res<Theme>) {
+///     badge("OK", Severity::Success).draw(ctx, (0, 0));
+///
+///     let mut item = badge("WARN", Severity::Warning).to_runes(&theme);
+///     item.add(" disk almost full");
+///     ctx.component(((0, 2), (30, 5)), List::new([item]));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    text: String,
+    severity: Severity,
+}
+
+/// Create a badge displaying text in the color of a severity.
+pub fn badge<S: ToString>(text: S, severity: Severity) -> Badge {
+    Badge {
+        text: text.to_string(),
+        severity,
+    }
+}
+
+impl Badge {
+    /// The number of cells the badge takes, including its padding.
+    pub fn width(&self) -> usize {
+        self.text.chars().count() + 2
+    }
+
+    /// The severity of the badge.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// The runes for the badge, colored using a theme. The text is drawn in
+    /// black or white, whichever is more legible on the severity color.
+    pub fn to_runes(&self, theme: &Theme) -> Runes {
+        let bg = self.severity.color(theme);
+        let fg = crate::color::contrast(bg).unwrap_or(theme.fg);
+        Runes::from(format!(" {} ", self.text)).fg(fg).bg(bg).bold()
+    }
+
+    /// Draw the badge at a position in a context.
+    pub fn draw<P: Into<Pos>>(&self, ctx: &mut ViewContext, pos: P) {
+        let theme = ctx.theme();
+        ctx.insert(pos, self.to_runes(&theme));
+    }
+}
+
+impl Callable<()> for Badge {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        self.draw(ctx, (0, 0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::theme::Theme;
+
+    use super::{badge, Severity};
+
+    #[test]
+    fn test_badge() {
+        let theme = Theme::default();
+        let ok = badge("OK", Severity::Success);
+        assert_eq!(ok.width(), 4);
+        let runes = ok.to_runes(&theme);
+        let text: String = runes.iter().filter_map(|r| r.content).collect();
+        assert_eq!(text, " OK ");
+        assert!(runes.iter().all(|r| r.bg == Some(theme.success) && r.bold));
+
+        let mut ctx = crate::context::tests::context_fixture();
+        badge("E", Severity::Error).draw(&mut ctx, (1, 0));
+        assert_eq!(ctx.view[0][2].content, Some('E'));
+        assert_eq!(ctx.view[0][1].bg, Some(theme.error));
+        assert!(matches!(
+            ctx.view[0][2].fg,
+            Some(Color::Black) | Some(Color::White)
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    prelude::Color,
+    runes::{Rune, Runes},
+    symbols::{boxes, BorderSet, SymbolLevel},
+};
+
+/// The line style of a border.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// The border of the SymbolSet resource, a single line by default.
+    #[default]
+    Single,
+    Double,
+    Rounded,
+    Thick,
+}
+
+/// The horizontal placement of a title segment along the top border.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TitleAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct Title {
+    runes: Runes,
+    alignment: TitleAlignment,
+}
+
+/// Block draws a border around the area of a component, with optional titles
+/// placed along the top edge.
+///
+/// Titles can be aligned to the left, center, or right of the border and
+/// several titles can be added, for instance a name on the left and a
+/// shortcut hint on the right. Titles are runes and can carry their own
+/// styling.
+///
+/// When no border color is given the border color of the current Style is
+/// used. The border characters are taken from the SymbolSet resource when one
+/// is bound. A double, rounded, or thick border can be chosen with
+/// Block::border, and falls back to the SymbolSet's border when it is the
+/// legacy set.
+///
+/// ```
+/// use arkham::{components::{Block, TitleAlignment}, prelude::*};
+///
+/// fn panel(ctx: &mut ViewContext) {
+///     let size = ctx.size();
+///     ctx.component(
+///         size,
+///         Block::new()
+///             .title("Files".to_runes().bold())
+///             .title_aligned("q: quit", TitleAlignment::Right),
+///     );
+///     ctx.insert(Block::inner(size).pos, "Contents");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Block {
+    titles: Vec<Title>,
+    border: BorderStyle,
+    border_color: Option<Color>,
+    bg: Option<Color>,
+    shadow: bool,
+}
+
+impl Block {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a left aligned title segment.
+    pub fn title<R: Into<Runes>>(self, title: R) -> Self {
+        self.title_aligned(title, TitleAlignment::Left)
+    }
+
+    /// Add a title segment with a specific alignment. Segments sharing an
+    /// alignment are placed next to each other in the order they were added.
+    pub fn title_aligned<R: Into<Runes>>(mut self, title: R, alignment: TitleAlignment) -> Self {
+        self.titles.push(Title {
+            runes: title.into(),
+            alignment,
+        });
+        self
+    }
+
+    /// Set the line style of the border.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{components::{Block, BorderStyle}, prelude::*};
+    ///
+    /// fn dialog(ctx: &mut ViewContext) {
+    ///     Block::new()
+    ///         .border(BorderStyle::Double)
+    ///         .title("Save changes?")
+    ///         .draw(ctx, ((4, 2), (30, 6)));
+    /// }
+    /// ```
+    pub fn border(mut self, style: BorderStyle) -> Self {
+        self.border = style;
+        self
+    }
+
+    /// Set the color of the border.
+    pub fn border_color(mut self, color: Color) -> Self {
+        self.border_color = Some(color);
+        self
+    }
+
+    /// Fill the area of the block with a background color.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Draw a drop shadow beneath the block. Because the shadow falls
+    /// outside of the block's own area it is only drawn when the block is
+    /// rendered with Block::draw.
+    pub fn shadow(mut self) -> Self {
+        self.shadow = true;
+        self
+    }
+
+    /// Render the block into a region of a context. Unlike passing the block
+    /// to ViewContext::component, this also draws the block's shadow.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{components::Block, prelude::*};
+    ///
+    /// fn modal(ctx: &mut ViewContext) {
+    ///     Block::new().title("Confirm").shadow().draw(ctx, ((4, 2), (30, 8)));
+    /// }
+    /// ```
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if self.shadow {
+            ctx.shadow(rect);
+        }
+        ctx.component(rect, self.clone());
+    }
+
+    /// The region inside of the border for a block drawn in the given rect.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{components::Block, prelude::*};
+    /// let inner = Block::inner(((2, 2), (10, 5)));
+    /// assert_eq!(inner, Rect::new((3, 3), (8, 3)));
+    /// ```
+    pub fn inner<R: Into<Rect>>(rect: R) -> Rect {
+        let mut rect = rect.into();
+        rect.pos.offset(1, 1);
+        rect.size.grow(-2, -2);
+        rect
+    }
+
+    /// The characters used to draw the border.
+    fn border_set(&self, ctx: &ViewContext) -> BorderSet {
+        let symbols = ctx.symbols();
+        if symbols.level == SymbolLevel::Legacy {
+            return symbols.border;
+        }
+        match self.border {
+            BorderStyle::Single => symbols.border,
+            BorderStyle::Double => boxes::DOUBLE,
+            BorderStyle::Rounded => boxes::ROUNDED,
+            BorderStyle::Thick => boxes::THICK,
+        }
+    }
+
+    /// Combine all title segments sharing an alignment into a single padded
+    /// run of runes.
+    fn title_runes(&self, alignment: TitleAlignment) -> Option<Runes> {
+        let mut segments = self
+            .titles
+            .iter()
+            .filter(|title| title.alignment == alignment)
+            .peekable();
+        segments.peek()?;
+        let mut runes = Runes::from(" ");
+        for segment in segments {
+            runes.add(segment.runes.clone());
+            runes.add(" ");
+        }
+        Some(runes)
+    }
+}
+
+impl Callable<()> for Block {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        if size.width < 2 || size.height < 2 {
+            return;
+        }
+
+        if let Some(bg) = self.bg {
+            ctx.fill_all(bg);
+        }
+
+        let symbols = self.border_set(ctx);
+        let mut border = Rune::new();
+        border.fg = self.border_color.or(ctx.style().border);
+        let (right, bottom) = (size.width - 1, size.height - 1);
+        let horizontal = border.content(symbols.horizontal);
+        let vertical = border.content(symbols.vertical);
+        ctx.fill(((1, 0), (size.width - 2, 1)), horizontal);
+        ctx.fill(((1, bottom), (size.width - 2, 1)), horizontal);
+        ctx.fill(((0, 1), (1, size.height - 2)), vertical);
+        ctx.fill(((right, 1), (1, size.height - 2)), vertical);
+        ctx.set_rune((0, 0), border.content(symbols.top_left));
+        ctx.set_rune((right, 0), border.content(symbols.top_right));
+        ctx.set_rune((0, bottom), border.content(symbols.bottom_left));
+        ctx.set_rune((right, bottom), border.content(symbols.bottom_right));
+
+        let available = size.width - 2;
+        for alignment in [
+            TitleAlignment::Left,
+            TitleAlignment::Right,
+            TitleAlignment::Center,
+        ] {
+            let Some(runes) = self.title_runes(alignment) else {
+                continue;
+            };
+            // Truncate by display width, leaving out a wide character that
+            // would be cut in half.
+            let mut width = 0;
+            let mut len = 0;
+            for rune in runes.iter() {
+                if width + rune.width() > available {
+                    break;
+                }
+                width += rune.width();
+                len += 1;
+            }
+            let x = match alignment {
+                TitleAlignment::Left => 1,
+                TitleAlignment::Right => 1 + available - width,
+                TitleAlignment::Center => 1 + (available - width) / 2,
+            };
+            ctx.insert((x, 0), Runes::new(runes[..len].to_vec()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{components::TitleAlignment, prelude::ToRuneExt};
+
+    use super::{Block, BorderStyle};
+
+    #[test]
+    fn test_border() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (4, 3)), Block::new());
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(3).collect();
+        assert!(lines[0].starts_with("┌──┐"));
+        assert!(lines[1].starts_with("│\0\0│"));
+        assert!(lines[2].starts_with("└──┘"));
+    }
+
+    #[test]
+    fn test_border_styles() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (3, 2)), Block::new().border(BorderStyle::Double));
+        ctx.component(((0, 2), (3, 2)), Block::new().border(BorderStyle::Rounded));
+        ctx.component(((0, 4), (3, 2)), Block::new().border(BorderStyle::Thick));
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(6).collect();
+        assert!(lines[0].starts_with("╔═╗"));
+        assert!(lines[3].starts_with("╰─╯"));
+        assert!(lines[4].starts_with("┏━┓"));
+    }
+
+    #[test]
+    fn test_legacy_border() {
+        use crate::{container::Res, symbols::SymbolSet};
+
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.container.borrow_mut().bind(Res::new(SymbolSet::LEGACY));
+        ctx.component((0, (3, 2)), Block::new());
+        ctx.component(((3, 0), (3, 2)), Block::new().border(BorderStyle::Double));
+        let text = ctx.view.render_text();
+        assert!(text.starts_with("+-++-+"));
+    }
+
+    #[test]
+    fn test_title_alignment() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component(
+            (0, (20, 3)),
+            Block::new()
+                .title("ab")
+                .title("cd".to_runes().bold())
+                .title_aligned("ef", TitleAlignment::Center)
+                .title_aligned("gh", TitleAlignment::Right),
+        );
+        let text = ctx.view.render_text();
+        let top = text.lines().next().unwrap();
+        assert_eq!(top, "┌ ab cd  ef ─── gh ┐");
+        assert!(ctx.view[0][5].bold);
+    }
+
+    #[test]
+    fn test_wide_title_truncated() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (7, 3)), Block::new().title("中文字"));
+        let text = ctx.view.render_text();
+        assert!(text.starts_with("┌ 中文┐"));
+
+        let mut ctx = crate::context::tests::context_fixture();
+        let title = crate::runes::Runes::new(vec![crate::runes::Rune::new().content('中'); 3]);
+        ctx.component(
+            (0, (7, 3)),
+            Block::new().title_aligned(title, TitleAlignment::Right),
+        );
+        let text = ctx.view.render_text();
+        assert!(text.starts_with("┌ 中中┐"));
+
+        // A wide character that would be cut in half is left out.
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (6, 3)), Block::new().title("中文字"));
+        assert!(ctx.view.render_text().starts_with("┌ 中─┐"));
+    }
+
+    #[test]
+    fn test_shadow() {
+        use crate::prelude::Color;
+
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.fill_all(Color::White);
+        Block::new().shadow().draw(&mut ctx, ((1, 1), (4, 3)));
+        assert_eq!(ctx.view[1][5].bg, Some(Color::White));
+        assert_ne!(ctx.view[2][5].bg, Some(Color::White));
+        assert_ne!(ctx.view[4][2].bg, Some(Color::White));
+        assert_eq!(ctx.view[4][1].bg, Some(Color::White));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{prelude::Color, runes::Rune, symbols::braille};
+
+/// Dot bit offsets within a braille character, indexed by [y][x] within the
+/// 2x4 cell.
+const DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// A grid of braille characters addressed by individual dots. Each cell is
+/// two dots wide and four dots tall.
+#[derive(Debug, Clone)]
+pub(crate) struct BrailleGrid {
+    width: usize,
+    height: usize,
+    dots: Vec<u8>,
+    colors: Vec<Option<Color>>,
+}
+
+impl BrailleGrid {
+    /// Create a grid with a size measured in cells.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            dots: vec![0; width * height],
+            colors: vec![None; width * height],
+        }
+    }
+
+    /// The width of the grid in dots.
+    pub fn dot_width(&self) -> usize {
+        self.width * 2
+    }
+
+    /// The height of the grid in dots.
+    pub fn dot_height(&self) -> usize {
+        self.height * 4
+    }
+
+    /// Set a single dot. Dots outside of the grid are ignored.
+    pub fn set(&mut self, x: i64, y: i64, color: Option<Color>) {
+        if x < 0 || y < 0 || x as usize >= self.dot_width() || y as usize >= self.dot_height() {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let idx = (y / 4) * self.width + x / 2;
+        self.dots[idx] |= DOTS[y % 4][x % 2];
+        if color.is_some() {
+            self.colors[idx] = color;
+        }
+    }
+
+    /// Draw a straight line of dots between two points. The line is clipped
+    /// to the grid, so only the dots within it are visited however far away
+    /// the points are.
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64), color: Option<Color>) {
+        let Some((from, to)) = self.clip(from, to) else {
+            return;
+        };
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.set(x, y, color);
+            if (x, y) == to {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Clip a line to the grid with the Cohen-Sutherland algorithm,
+    /// returning the endpoints of the part of the line within the grid, or
+    /// None if the line lies entirely outside of it. Intersections are
+    /// computed with integers, so lines between extreme points are exact.
+    fn clip(&self, from: (i64, i64), to: (i64, i64)) -> Option<((i64, i64), (i64, i64))> {
+        if self.dot_width() == 0 || self.dot_height() == 0 {
+            return None;
+        }
+        let (right, bottom) = (self.dot_width() as i64 - 1, self.dot_height() as i64 - 1);
+        let outcode = |(x, y): (i64, i64)| {
+            u8::from(x < 0)
+                | u8::from(x > right) << 1
+                | u8::from(y < 0) << 2
+                | u8::from(y > bottom) << 3
+        };
+        let (mut a, mut b) = (from, to);
+        loop {
+            let (code_a, code_b) = (outcode(a), outcode(b));
+            if code_a | code_b == 0 {
+                return Some((a, b));
+            }
+            if code_a & code_b != 0 {
+                return None;
+            }
+            let (outside, other, code) = if code_a != 0 {
+                (&mut a, b, code_a)
+            } else {
+                (&mut b, a, code_b)
+            };
+            let (p, q) = (*outside, other);
+            *outside = if code & 0b0011 != 0 {
+                let edge = if code & 0b0001 != 0 { 0 } else { right };
+                (edge, intersect(p, q, edge))
+            } else {
+                let edge = if code & 0b0100 != 0 { 0 } else { bottom };
+                (intersect((p.1, p.0), (q.1, q.0), edge), edge)
+            };
+        }
+    }
+
+    /// Returns the rune for a cell, or None if no dots are set within it.
+    pub fn rune(&self, x: usize, y: usize) -> Option<Rune> {
+        let idx = y * self.width + x;
+        match self.dots.get(idx) {
+            Some(0) | None => None,
+            Some(dots) => {
+                let mut rune = Rune::new().content(braille::from_dots(*dots));
+                rune.fg = self.colors[idx];
+                Some(rune)
+            }
+        }
+    }
+}
+
+/// The second coordinate of the point on the line between two points
+/// where the first coordinate is `edge`, which lies between the first
+/// coordinates of the points, rounded to the nearest dot. This is computed with 128 bit integers, so
+/// the points can be as far apart as the limits of i64.
+fn intersect(from: (i64, i64), to: (i64, i64), edge: i64) -> i64 {
+    let span = i128::from(to.1) - i128::from(from.1);
+    let num = (i128::from(edge) - i128::from(from.0)).unsigned_abs();
+    let den = (i128::from(to.0) - i128::from(from.0)).unsigned_abs();
+    let offset = (span.unsigned_abs() * num + den / 2) / den;
+    (i128::from(from.1) + offset as i128 * span.signum()) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BrailleGrid;
+
+    #[test]
+    fn test_dots() {
+        let mut grid = BrailleGrid::new(2, 1);
+        grid.set(0, 0, None);
+        grid.set(1, 3, None);
+        grid.set(9, 9, None);
+        assert_eq!(grid.rune(0, 0).unwrap().content, Some('⢁'));
+        assert!(grid.rune(1, 0).is_none());
+        grid.line((0, 0), (3, 0), None);
+        assert_eq!(grid.rune(1, 0).unwrap().content, Some('⠉'));
+    }
+
+    #[test]
+    fn test_clipped_lines() {
+        let mut grid = BrailleGrid::new(2, 1);
+        grid.line((-10, 0), (3_000_000_000, 0), None);
+        assert_eq!(grid.rune(0, 0).unwrap().content, Some('⠉'));
+        assert_eq!(grid.rune(1, 0).unwrap().content, Some('⠉'));
+
+        let mut grid = BrailleGrid::new(2, 1);
+        grid.line((i64::MIN, i64::MIN), (i64::MAX, i64::MAX), None);
+        grid.line((i64::MAX, 0), (i64::MAX, 3), None);
+        grid.line((-5, -1), (10, -1), None);
+        assert_eq!(grid.rune(0, 0).unwrap().content, Some('⠑'));
+        assert_eq!(grid.rune(1, 0).unwrap().content, Some('⢄'));
+    }
+}
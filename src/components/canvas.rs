@@ -0,0 +1,173 @@
+use crossterm::style::Color;
+
+use crate::{container::Callable, context::ViewContext, geometry::Rect};
+
+use super::braille::BrailleGrid;
+
+/// A shape recorded on a Canvas, in dot coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Pixel(i64, i64),
+    Line((i64, i64), (i64, i64)),
+    Rect((i64, i64), (i64, i64)),
+}
+
+/// Canvas is a surface for freeform drawing at a finer resolution than
+/// cells. Each cell is split into a grid of two by four dots, drawn using
+/// braille characters.
+///
+/// Coordinates are measured in dots from the top left of the area the
+/// canvas is drawn in, and shapes outside of it are clipped. The shapes are
+/// recorded as they are drawn and rasterized into runes when the canvas is
+/// rendered, so the same canvas can be rendered at different sizes.
+///
+/// Dots within a cell share a single color, so where shapes of different
+/// colors cross, the color of the last shape drawn is used for the cell.
+///
+/// ```
+/// use arkham::{components::Canvas, prelude::*};
+///
+/// fn root(ctx: &mut ViewContext) {
+///     let mut canvas = Canvas::new();
+///     canvas.rect(0, 0, 40, 20);
+///     canvas.color(Color::Red).line((0, 0), (39, 19));
+///     for x in 0..40 {
+///         canvas.set_pixel(x, 10);
+///     }
+///     ctx.component(((0, 0), (20, 5)), canvas);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Canvas {
+    shapes: Vec<(Shape, Option<Color>)>,
+    color: Option<Color>,
+}
+
+impl Canvas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color of shapes drawn after this call. Shapes are drawn in
+    /// the theme's foreground color by default.
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set a single dot.
+    pub fn set_pixel(&mut self, x: i64, y: i64) -> &mut Self {
+        self.shapes.push((Shape::Pixel(x, y), self.color));
+        self
+    }
+
+    /// Draw a straight line between two dots, including both ends.
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64)) -> &mut Self {
+        self.shapes.push((Shape::Line(from, to), self.color));
+        self
+    }
+
+    /// Draw the outline of a rectangle with its top left dot at x and y,
+    /// measured in dots.
+    pub fn rect(&mut self, x: i64, y: i64, width: i64, height: i64) -> &mut Self {
+        if width > 0 && height > 0 {
+            let right = x.saturating_add(width - 1);
+            let bottom = y.saturating_add(height - 1);
+            let shape = Shape::Rect((x, y), (right, bottom));
+            self.shapes.push((shape, self.color));
+        }
+        self
+    }
+
+    /// Remove every shape drawn on the canvas.
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    /// Rasterize the shapes into dots on a grid. Lines are clipped to the
+    /// grid, so shapes far outside of it cost no more than those within it.
+    fn rasterize(&self, grid: &mut BrailleGrid, default: Color) {
+        for (shape, color) in self.shapes.iter() {
+            let color = Some(color.unwrap_or(default));
+            match *shape {
+                Shape::Pixel(x, y) => grid.set(x, y, color),
+                Shape::Line(from, to) => grid.line(from, to, color),
+                Shape::Rect((left, top), (right, bottom)) => {
+                    grid.line((left, top), (right, top), color);
+                    grid.line((left, bottom), (right, bottom), color);
+                    grid.line((left, top), (left, bottom), color);
+                    grid.line((right, top), (right, bottom), color);
+                }
+            }
+        }
+    }
+
+    /// Draw the canvas within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let mut grid = BrailleGrid::new(rect.size.width, rect.size.height);
+        self.rasterize(&mut grid, theme.fg);
+        for y in 0..rect.size.height {
+            for x in 0..rect.size.width {
+                if let Some(rune) = grid.rune(x, y) {
+                    ctx.insert((rect.pos.x + x, rect.pos.y + y), rune);
+                }
+            }
+        }
+    }
+}
+
+impl Callable<()> for Canvas {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Color;
+
+    use super::Canvas;
+
+    #[test]
+    fn test_shapes() {
+        let mut canvas = Canvas::new();
+        canvas.rect(0, 0, 4, 8);
+        canvas
+            .color(Color::Red)
+            .set_pixel(6, 0)
+            .line((4, 7), (7, 7));
+        canvas.set_pixel(100, 100);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        canvas.draw(&mut ctx, ((1, 1), (4, 2)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<String> = text
+            .lines()
+            .skip(1)
+            .take(2)
+            .map(|l| l.trim_end().to_string())
+            .collect();
+        assert_eq!(lines, vec![" ⡏⢹ ⠁", " ⣇⣸⣀⣀"]);
+        assert_eq!(ctx.view[1][4].fg, Some(Color::Red));
+        assert_ne!(ctx.view[1][1].fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_clipping() {
+        let mut canvas = Canvas::new();
+        canvas.line((0, 0), (3_000_000_000, 1));
+        canvas.line((i64::MIN, i64::MIN), (i64::MAX, i64::MAX));
+        canvas.rect(i64::MAX - 1, 0, 10, 10);
+        canvas.rect(-1, -1, i64::MAX, i64::MAX);
+        canvas.set_pixel(i64::MIN, i64::MAX);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        canvas.draw(&mut ctx, ((0, 0), (4, 2)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(2).map(str::trim_end).collect();
+        assert_eq!(lines, vec!["⠙⢍⠉⠉", "  ⠑⢄"]);
+    }
+}
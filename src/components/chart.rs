@@ -0,0 +1,348 @@
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    prelude::Color,
+    runes::{Rune, Runes},
+};
+
+use super::braille::BrailleGrid;
+
+/// How a ChartSeries is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeriesKind {
+    Line,
+    Bar,
+}
+
+/// A named set of values drawn by a Chart, one value per category.
+#[derive(Debug, Clone)]
+pub struct ChartSeries {
+    name: String,
+    values: Vec<f64>,
+    kind: SeriesKind,
+    color: Option<Color>,
+}
+
+impl ChartSeries {
+    /// A series drawn as a line connecting its values.
+    pub fn line<S: ToString>(name: S, values: Vec<f64>) -> Self {
+        Self {
+            name: name.to_string(),
+            values,
+            kind: SeriesKind::Line,
+            color: None,
+        }
+    }
+
+    /// A series drawn as a bar for each value. Bars of several series are
+    /// drawn side by side within each category.
+    pub fn bar<S: ToString>(name: S, values: Vec<f64>) -> Self {
+        Self {
+            kind: SeriesKind::Bar,
+            ..Self::line(name, values)
+        }
+    }
+
+    /// Set the color of the series. By default series are colored using the
+    /// theme's accent and status colors in turn.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Chart renders line and bar series over a set of categories, such as
+/// time periods or hosts, with the values on a vertical axis. The axis
+/// scales to the values unless bounds are given, and includes zero so that
+/// bars are drawn from zero.
+///
+/// Each category takes an equal share of the width, labeled along the
+/// bottom. Labels that would overlap the previous label are left out.
+/// Lines are drawn on a braille canvas, and bars with partial blocks, so
+/// both are finer than a cell.
+///
+/// ```
+/// use arkham::{components::{Chart, ChartSeries}, prelude::*};
+///
+/// fn requests(ctx: &mut ViewContext) {
+///     let size = ctx.size();
+///     ctx.component(
+///         size,
+///         Chart::new()
+///             .labels(["Mon", "Tue", "Wed", "Thu", "Fri"])
+///             .series(ChartSeries::bar("requests", vec![120.0, 180.0, 90.0, 210.0, 160.0]))
+///             .series(ChartSeries::line("errors", vec![4.0, 9.0, 2.0, 30.0, 6.0]).color(Color::Red))
+///             .legend(),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Chart {
+    series: Vec<ChartSeries>,
+    labels: Vec<String>,
+    y_bounds: Option<(f64, f64)>,
+    legend: bool,
+    precision: usize,
+}
+
+impl Chart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a series to the chart.
+    pub fn series(mut self, series: ChartSeries) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Set the labels of the categories, drawn below the x axis.
+    pub fn labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.labels = labels.into_iter().map(|l| l.to_string()).collect();
+        self
+    }
+
+    /// Fix the range of the y axis instead of scaling it to the values.
+    pub fn y_bounds(mut self, min: f64, max: f64) -> Self {
+        self.y_bounds = Some((min, max));
+        self
+    }
+
+    /// Display a legend listing each series along the top of the chart.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Set the number of decimal places shown in the axis labels.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// The range of the y axis, covering every value and zero.
+    fn bounds(&self) -> (f64, f64) {
+        if let Some(bounds) = self.y_bounds {
+            return bounds;
+        }
+        let (min, max) = self
+            .series
+            .iter()
+            .flat_map(|s| s.values.iter().copied())
+            .filter(|v| v.is_finite())
+            .fold((0.0_f64, 0.0_f64), |(min, max), v| (min.min(v), max.max(v)));
+        if min == max {
+            (min, min + 1.0)
+        } else {
+            (min, max)
+        }
+    }
+
+    /// The number of categories, which is the length of the longest series
+    /// or the number of labels.
+    fn categories(&self) -> usize {
+        self.series
+            .iter()
+            .map(|s| s.values.len())
+            .fold(self.labels.len(), usize::max)
+    }
+}
+
+impl Callable<()> for Chart {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let size = ctx.size();
+        let palette = [
+            theme.accent,
+            theme.success,
+            theme.warning,
+            theme.error,
+            theme.info,
+        ];
+        let color = |idx: usize| self.series[idx].color.unwrap_or(palette[idx % 5]);
+
+        let top = usize::from(self.legend);
+        if size.height < top + 3 {
+            return;
+        }
+        if self.legend {
+            let mut legend = Runes::default();
+            for (idx, series) in self.series.iter().enumerate() {
+                let marker = match series.kind {
+                    SeriesKind::Line => symbols.bullet,
+                    SeriesKind::Bar => symbols.block_full,
+                };
+                legend.add(Runes::from(marker).fg(color(idx)));
+                legend.add(format!(" {}  ", series.name));
+            }
+            let x = size.width.saturating_sub(legend.len().saturating_sub(2));
+            ctx.insert((x, 0), legend);
+        }
+
+        let p = self.precision;
+        let (y_min, y_max) = self.bounds();
+        let (y_top, y_bottom) = (format!("{:.p$}", y_max), format!("{:.p$}", y_min));
+        let label_width = y_top.chars().count().max(y_bottom.chars().count());
+        if size.width < label_width + 2 {
+            return;
+        }
+        let axis_row = size.height - 2;
+        let muted = Rune::new().fg(theme.muted);
+
+        ctx.insert((0, top), format!("{:>label_width$}", y_top));
+        ctx.insert((0, axis_row - 1), format!("{:>label_width$}", y_bottom));
+        ctx.fill(
+            ((label_width, top), (1, axis_row - top)),
+            muted.content('│'),
+        );
+        ctx.fill(
+            ((label_width, axis_row), (size.width - label_width, 1)),
+            muted.content('─'),
+        );
+        ctx.set_rune((label_width, axis_row), muted.content('└'));
+
+        let categories = self.categories();
+        if categories == 0 {
+            return;
+        }
+        let (left, width, height) = (
+            label_width + 1,
+            size.width - label_width - 1,
+            axis_row - top,
+        );
+        // The first column of each category, and the column after the last.
+        let columns: Vec<usize> = (0..=categories)
+            .map(|idx| idx * width / categories)
+            .collect();
+        let ratio = |value: f64| ((value - y_min) / (y_max - y_min)).clamp(0.0, 1.0);
+
+        let mut next_label = 0;
+        for (idx, label) in self.labels.iter().enumerate() {
+            let slot = columns[idx + 1] - columns[idx];
+            let len = label.chars().count();
+            let x = columns[idx] + slot.saturating_sub(len) / 2;
+            if x < next_label || x + len > width {
+                continue;
+            }
+            ctx.insert((left + x, axis_row + 1), label.to_string());
+            next_label = x + len + 1;
+        }
+
+        let bars: Vec<usize> = (0..self.series.len())
+            .filter(|&idx| self.series[idx].kind == SeriesKind::Bar)
+            .collect();
+        let steps = symbols.bars_vertical.len() - 1;
+        for category in 0..categories {
+            let slot = columns[category + 1] - columns[category];
+            // Leave a gap between categories when there is room.
+            let usable = if slot > 1 { slot - 1 } else { slot };
+            let bar_width = (usable / bars.len().max(1)).max(1);
+            for (n, &idx) in bars.iter().enumerate() {
+                let Some(&value) = self.series[idx].values.get(category) else {
+                    continue;
+                };
+                let x = columns[category] + n * bar_width;
+                if x + bar_width > columns[category + 1] || !value.is_finite() {
+                    continue;
+                }
+                let levels = (ratio(value) * (height * steps) as f64).round() as usize;
+                for row in 0..height {
+                    let level = levels.saturating_sub(row * steps).min(steps);
+                    if level == 0 {
+                        break;
+                    }
+                    let rune = Rune::new()
+                        .content(symbols.bars_vertical[level])
+                        .fg(color(idx));
+                    ctx.fill(((left + x, top + height - 1 - row), (bar_width, 1)), rune);
+                }
+            }
+        }
+
+        let mut grid = BrailleGrid::new(width, height);
+        let dot_height = grid.dot_height();
+        for (idx, series) in self.series.iter().enumerate() {
+            if series.kind != SeriesKind::Line {
+                continue;
+            }
+            let points: Vec<(i64, i64)> = series
+                .values
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| v.is_finite())
+                .map(|(category, &value)| {
+                    let x = columns[category] + columns[category + 1];
+                    let y = (1.0 - ratio(value)) * (dot_height - 1) as f64;
+                    (x as i64, y.round() as i64)
+                })
+                .collect();
+            match points.as_slice() {
+                [point] => grid.set(point.0, point.1, Some(color(idx))),
+                points => {
+                    for pair in points.windows(2) {
+                        grid.line(pair[0], pair[1], Some(color(idx)));
+                    }
+                }
+            }
+        }
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(rune) = grid.rune(x, y) {
+                    ctx.insert((left + x, top + y), rune);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Color;
+
+    use super::{Chart, ChartSeries};
+
+    fn render(
+        chart: Chart,
+        width: usize,
+        height: usize,
+    ) -> (Vec<String>, crate::context::ViewContext) {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (width, height)), chart);
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines = text
+            .lines()
+            .take(height)
+            .map(|l| l.trim_end().to_string())
+            .collect();
+        (lines, ctx)
+    }
+
+    #[test]
+    fn test_bars() {
+        let chart = Chart::new()
+            .labels(["a", "b", "c"])
+            .series(ChartSeries::bar("n", vec![1.0, 4.0, 2.0]).color(Color::Blue));
+        let (lines, ctx) = render(chart, 8, 4);
+        assert_eq!(lines, vec!["4│  █", "0│▄ █ █", " └──────", "  a b c"]);
+        assert_eq!(ctx.view[0][4].fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_line_and_legend() {
+        let chart = Chart::new()
+            .series(ChartSeries::line("up", vec![0.0, 1.0, 2.0, 3.0]).color(Color::Green))
+            .legend();
+        let (lines, ctx) = render(chart, 10, 5);
+        assert!(lines[0].ends_with("● up"));
+        assert!(lines[1].starts_with("3│"));
+        assert!(lines[2].starts_with("0│"));
+        // The line rises from the bottom left to the top right.
+        assert_eq!(ctx.view[2][3].fg, Some(Color::Green));
+        assert_eq!(ctx.view[1][9].fg, Some(Color::Green));
+    }
+}
@@ -0,0 +1,141 @@
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::{Callable, State},
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{Rune, Runes},
+};
+
+/// Checkbox displays a label with a box that is checked or unchecked,
+/// reflecting a boolean held in application state. Checkbox::handle_keys
+/// toggles it when Space is pressed.
+///
+/// Checkboxes are cheap to create, so they can be built each time the view
+/// is rendered from the state they are bound to. When several checkboxes
+/// are displayed together, only the focused one should respond to keys.
+///
+/// ```no_run
+/// use arkham::{components::Checkbox, prelude::*};
+///
+/// fn main() {
+///     App::new(root).insert_state(false).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, subscribed: State<bool>, kb: Res<Keyboard>) {
+///     let checkbox = Checkbox::new("Subscribe to updates", subscribed.clone());
+///     checkbox.handle_keys(&kb);
+///     ctx.component(((0, 0), (30, 1)), checkbox);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Checkbox {
+    label: Runes,
+    checked: State<bool>,
+    focused: bool,
+}
+
+impl Checkbox {
+    /// Create a checkbox bound to a boolean in application state.
+    pub fn new<L: Into<Runes>>(label: L, checked: State<bool>) -> Self {
+        Self {
+            label: label.into(),
+            checked,
+            focused: true,
+        }
+    }
+
+    /// Set whether the checkbox has focus. Checkboxes without focus do not
+    /// respond to keys and are drawn without the focus color. Checkboxes
+    /// have focus by default.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Returns true if the checkbox is checked.
+    pub fn is_checked(&self) -> bool {
+        *self.checked.get()
+    }
+
+    /// Check or uncheck the checkbox.
+    pub fn toggle(&self) {
+        let mut checked = self.checked.get_mut();
+        *checked = !*checked;
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        if !self.focused || kb.code() != Some(KeyCode::Char(' ')) {
+            return false;
+        }
+        self.toggle();
+        kb.reset();
+        true
+    }
+
+    /// Draw the checkbox on the first row of a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.width == 0 || rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let (symbol, color) = if self.is_checked() {
+            (symbols.checkbox_on, theme.accent)
+        } else {
+            (symbols.checkbox_off, theme.muted)
+        };
+        let mut runes = Runes::from(Rune::new().content(symbol).fg(color));
+        runes.add(" ");
+        let label = if self.focused {
+            self.label.clone().fg(theme.focus)
+        } else {
+            self.label.clone()
+        };
+        runes.add(label);
+        ctx.insert(
+            rect.pos,
+            Runes::new(runes.iter().take(rect.size.width).copied().collect()),
+        );
+    }
+}
+
+impl Callable<()> for Checkbox {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{container::State, input::Keyboard};
+
+    use super::Checkbox;
+
+    #[test]
+    fn test_toggle() {
+        let state = State::new(false);
+        let checkbox = Checkbox::new("Agree", state.clone());
+        assert!(!checkbox.handle_keys(&Keyboard::with_key(KeyCode::Enter)));
+        assert!(checkbox.handle_keys(&Keyboard::with_key(KeyCode::Char(' '))));
+        assert!(*state.get());
+
+        let mut ctx = crate::context::tests::context_fixture();
+        checkbox.draw(&mut ctx, ((0, 0), (8, 1)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        assert_eq!(text.lines().next().unwrap().trim_end(), "☒ Agree");
+        assert_eq!(ctx.view[0][2].fg, Some(ctx.theme().focus));
+
+        let unfocused = checkbox.clone().focused(false);
+        assert!(!unfocused.handle_keys(&Keyboard::with_key(KeyCode::Char(' '))));
+        assert!(unfocused.is_checked());
+    }
+}
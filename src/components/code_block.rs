@@ -0,0 +1,317 @@
+use std::{cell::RefCell, rc::Rc, sync::OnceLock};
+
+use crossterm::{event::KeyCode, style::Color};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{FontStyle, ThemeSet},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::{Pos, Rect},
+    input::Keyboard,
+    runes::{Rune, Runes},
+};
+
+/// The syntax definitions bundled with syntect, loaded on first use.
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// The color themes bundled with syntect, loaded on first use.
+fn themes() -> &'static ThemeSet {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+/// Highlight source code, returning the runes for each line. Tabs are
+/// expanded to four spaces. If the language or theme is not known the code
+/// is returned without colors.
+fn highlight(code: &str, language: &str, theme: &str) -> Vec<Runes> {
+    let syntaxes = syntaxes();
+    let syntax = syntaxes
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let Some(theme) = themes().themes.get(theme) else {
+        return code.lines().map(plain).collect();
+    };
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    LinesWithEndings::from(code)
+        .map(|line| match highlighter.highlight_line(line, syntaxes) {
+            Ok(regions) => {
+                let mut runes = vec![];
+                for (style, text) in regions {
+                    let fg = style.foreground;
+                    let mut rune = Rune::new().fg(Color::Rgb {
+                        r: fg.r,
+                        g: fg.g,
+                        b: fg.b,
+                    });
+                    rune.bold = style.font_style.contains(FontStyle::BOLD);
+                    rune.italic = style.font_style.contains(FontStyle::ITALIC);
+                    rune.underline = style.font_style.contains(FontStyle::UNDERLINE);
+                    runes.extend(expand(text).map(|c| Rune {
+                        content: Some(c),
+                        ..rune
+                    }));
+                }
+                Runes::new(runes)
+            }
+            Err(_) => plain(line),
+        })
+        .collect()
+}
+
+/// The characters of a line with tabs expanded and the line ending removed.
+fn expand(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.trim_end_matches(['\n', '\r'])
+        .chars()
+        .flat_map(|c| match c {
+            '\t' => vec![' '; 4],
+            c => vec![c],
+        })
+}
+
+fn plain(line: &str) -> Runes {
+    Runes::from(expand(line).collect::<String>())
+}
+
+#[derive(Debug)]
+struct CodeBlockState {
+    code: String,
+    language: String,
+    theme: String,
+    line_numbers: bool,
+    /// The highlighted lines, cleared when the code or its highlighting
+    /// changes.
+    lines: Option<Rc<Vec<Runes>>>,
+    /// The first column and line displayed.
+    offset: Pos,
+    /// The size of the code area the block was last drawn with, used to
+    /// limit scrolling and move by a page.
+    viewport: (usize, usize),
+}
+
+impl CodeBlockState {
+    fn lines(&mut self) -> Rc<Vec<Runes>> {
+        self.lines
+            .get_or_insert_with(|| Rc::new(highlight(&self.code, &self.language, &self.theme)))
+            .clone()
+    }
+
+    /// Scroll to an offset, keeping the last line and the end of the longest
+    /// line within the viewport.
+    fn scroll_to(&mut self, x: usize, y: usize) {
+        let lines = self.lines();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let (cols, rows) = self.viewport;
+        self.offset = Pos::new(
+            x.min(width.saturating_sub(cols)),
+            y.min(lines.len().saturating_sub(rows)),
+        );
+    }
+}
+
+/// CodeBlock displays source code highlighted for its language, with line
+/// numbers in a gutter on the left. Code that does not fit can be scrolled
+/// both vertically and horizontally.
+///
+/// The language is a name or file extension, such as `rust` or `rs`.
+/// Highlighting uses the syntax definitions and themes bundled with
+/// syntect. This component requires the `syntax-highlighting` feature.
+///
+/// CodeBlock::handle_keys provides the key bindings: the arrow keys scroll
+/// by a line or column, Page Up and Page Down scroll by a page, and Home and
+/// End scroll to the first and last lines.
+///
+/// ```no_run
+/// use arkham::{components::CodeBlock, prelude::*};
+///
+/// fn main() {
+///     let code = CodeBlock::new("fn main() {\n    println!(\"hi\");\n}", "rs");
+///     App::new(root).insert_resource(code).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, code: Res<CodeBlock>, kb: Res<Keyboard>) {
+///     code.handle_keys(&kb);
+///     ctx.component(((0, 0), (40, 10)), code.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    state: Rc<RefCell<CodeBlockState>>,
+}
+
+impl CodeBlock {
+    pub fn new<C: ToString, L: ToString>(code: C, language: L) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(CodeBlockState {
+                code: code.to_string(),
+                language: language.to_string(),
+                theme: "base16-ocean.dark".to_string(),
+                line_numbers: true,
+                lines: None,
+                offset: Pos::new(0, 0),
+                viewport: (0, 0),
+            })),
+        }
+    }
+
+    /// Set the syntect theme used for highlighting, such as
+    /// `InspiredGitHub` or `Solarized (light)`. Defaults to
+    /// `base16-ocean.dark`.
+    pub fn theme<S: ToString>(self, theme: S) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.theme = theme.to_string();
+            state.lines = None;
+        }
+        self
+    }
+
+    /// Show or hide the line numbers. They are shown by default.
+    pub fn line_numbers(self, show: bool) -> Self {
+        self.state.borrow_mut().line_numbers = show;
+        self
+    }
+
+    /// Replace the code displayed, scrolling back to the top.
+    pub fn set_code<C: ToString>(&self, code: C) {
+        let mut state = self.state.borrow_mut();
+        state.code = code.to_string();
+        state.lines = None;
+        state.offset = Pos::new(0, 0);
+    }
+
+    /// The column and line at the top left of the view.
+    pub fn offset(&self) -> Pos {
+        self.state.borrow().offset
+    }
+
+    /// Scroll so that a column and line are at the top left of the view.
+    pub fn scroll_to(&self, column: usize, line: usize) {
+        self.state.borrow_mut().scroll_to(column, line);
+    }
+
+    /// Scroll by a number of columns and lines.
+    pub fn scroll_by(&self, columns: isize, lines: isize) {
+        let mut state = self.state.borrow_mut();
+        let offset = state.offset;
+        state.scroll_to(
+            offset.x.saturating_add_signed(columns),
+            offset.y.saturating_add_signed(lines),
+        );
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let page = self.state.borrow().viewport.1.max(1) as isize;
+        match kb.code() {
+            Some(KeyCode::Up) => self.scroll_by(0, -1),
+            Some(KeyCode::Down) => self.scroll_by(0, 1),
+            Some(KeyCode::Left) => self.scroll_by(-1, 0),
+            Some(KeyCode::Right) => self.scroll_by(1, 0),
+            Some(KeyCode::PageUp) => self.scroll_by(0, -page),
+            Some(KeyCode::PageDown) => self.scroll_by(0, page),
+            Some(KeyCode::Home) => self.scroll_to(0, 0),
+            Some(KeyCode::End) => self.scroll_to(self.offset().x, usize::MAX),
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the code within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let mut state = self.state.borrow_mut();
+        let lines = state.lines();
+        let gutter = if state.line_numbers {
+            lines.len().max(1).to_string().len() + 1
+        } else {
+            0
+        };
+        let cols = rect.size.width.saturating_sub(gutter);
+        state.viewport = (cols, rect.size.height);
+        let offset = state.offset;
+        state.scroll_to(offset.x, offset.y);
+        let offset = state.offset;
+        drop(state);
+
+        for (row, (idx, line)) in lines
+            .iter()
+            .enumerate()
+            .skip(offset.y)
+            .take(rect.size.height)
+            .enumerate()
+        {
+            let y = rect.pos.y + row;
+            if gutter > 0 {
+                let number = format!("{:>width$} ", idx + 1, width = gutter - 1);
+                ctx.insert((rect.pos.x, y), Runes::from(number).fg(theme.muted));
+            }
+            let visible = line.iter().skip(offset.x).take(cols).copied().collect();
+            ctx.insert((rect.pos.x + gutter, y), Runes::new(visible));
+        }
+    }
+}
+
+impl Callable<()> for CodeBlock {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::input::Keyboard;
+
+    use super::CodeBlock;
+
+    fn draw(code: &CodeBlock) -> Vec<String> {
+        let mut ctx = crate::context::tests::context_fixture();
+        code.draw(&mut ctx, ((0, 0), (9, 2)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        text.lines()
+            .take(2)
+            .map(|l| l.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight() {
+        let code = CodeBlock::new("fn main() {}\n\tlet x = 1;", "rs");
+        assert_eq!(draw(&code), vec!["1 fn main", "2     let"]);
+        let lines = code.state.borrow_mut().lines();
+        assert!(lines[0][0].fg.is_some());
+        assert_ne!(lines[0][0].fg, lines[0][3].fg);
+    }
+
+    #[test]
+    fn test_scroll() {
+        let source: Vec<String> = (0..12).map(|i| format!("line {}", i)).collect();
+        let code = CodeBlock::new(source.join("\n"), "txt");
+        draw(&code);
+        code.handle_keys(&Keyboard::with_key(KeyCode::End));
+        assert_eq!(draw(&code), vec!["11 line 1", "12 line 1"]);
+        code.handle_keys(&Keyboard::with_key(KeyCode::Right));
+        code.handle_keys(&Keyboard::with_key(KeyCode::PageUp));
+        assert_eq!(draw(&code), vec![" 9 ine 8", "10 ine 9"]);
+        code.handle_keys(&Keyboard::with_key(KeyCode::Right));
+        assert_eq!(code.offset().x, 1);
+
+        let code = code.line_numbers(false);
+        code.scroll_to(0, 0);
+        assert_eq!(draw(&code), vec!["line 0", "line 1"]);
+    }
+}
@@ -0,0 +1,406 @@
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{Rune, Runes},
+    search::SearchState,
+};
+
+/// A file or directory listed by a FileBrowser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+#[derive(Debug, Default)]
+struct FileBrowserState {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    /// The error reading the directory, displayed in place of the entries.
+    error: Option<String>,
+    show_hidden: bool,
+    /// The index of the selected entry among the entries matching the
+    /// filter.
+    selected: usize,
+    offset: usize,
+    /// The number of rows of entries the browser was last drawn with, used
+    /// to move by a page.
+    rows: usize,
+    chosen: Option<PathBuf>,
+}
+
+impl FileBrowserState {
+    /// Read the entries of the current directory. Directories are listed
+    /// before files, and both are sorted by name ignoring case.
+    fn read(&mut self) {
+        self.entries.clear();
+        self.error = None;
+        match fs::read_dir(&self.dir) {
+            Ok(read) => {
+                for entry in read.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if name.starts_with('.') && !self.show_hidden {
+                        continue;
+                    }
+                    // Symlinks are followed so links to directories can be
+                    // entered.
+                    let is_dir = entry.path().is_dir();
+                    self.entries.push(Entry { name, is_dir });
+                }
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self.entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        self.selected = 0;
+        self.offset = 0;
+    }
+}
+
+/// FileBrowser displays the contents of a directory and lets the user move
+/// through the file system to choose a file, as in an editor's open dialog.
+/// The current directory is shown on the first row.
+///
+/// FileBrowser::handle_keys provides the key bindings: Up and Down move the
+/// selection, Page Up and Page Down move it by a page, Enter or Right enters
+/// the selected directory or chooses the selected file, and Backspace or
+/// Left goes up to the parent directory. Pressing `/` filters the entries
+/// by name using a SearchState, see SearchState for its key bindings.
+///
+/// Hidden files, whose names start with a dot, are not listed unless
+/// FileBrowser::show_hidden is used.
+///
+/// ```no_run
+/// use std::path::PathBuf;
+/// use arkham::{components::FileBrowser, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_resource(FileBrowser::new("."))
+///         .insert_state(None::<PathBuf>)
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(
+///     ctx: &mut ViewContext,
+///     browser: Res<FileBrowser>,
+///     opened: State<Option<PathBuf>>,
+///     kb: Res<Keyboard>,
+/// ) {
+///     browser.handle_keys(&kb);
+///     if let Some(path) = browser.take_chosen() {
+///         *opened.get_mut() = Some(path);
+///     }
+///     let size = ctx.size();
+///     match opened.get().as_ref() {
+///         Some(path) => ctx.insert((0, 0), format!("Opened {}", path.display())),
+///         None => ctx.component(size, browser.get().clone()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileBrowser {
+    state: Rc<RefCell<FileBrowserState>>,
+    filter: SearchState,
+}
+
+impl FileBrowser {
+    /// Create a browser listing a directory.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        let browser = Self {
+            state: Rc::default(),
+            filter: SearchState::new(),
+        };
+        browser.set_dir(dir);
+        browser
+    }
+
+    /// List hidden files, whose names start with a dot.
+    pub fn show_hidden(self) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.show_hidden = true;
+            state.read();
+        }
+        self
+    }
+
+    /// The directory being listed.
+    pub fn dir(&self) -> PathBuf {
+        self.state.borrow().dir.clone()
+    }
+
+    /// List another directory, clearing the filter.
+    pub fn set_dir<P: AsRef<Path>>(&self, dir: P) {
+        let dir = dir.as_ref();
+        let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+        self.filter.clear();
+        let mut state = self.state.borrow_mut();
+        state.dir = dir;
+        state.read();
+    }
+
+    /// Read the directory again, such as after files have been created.
+    pub fn refresh(&self) {
+        self.state.borrow_mut().read();
+    }
+
+    /// The indexes of the entries matching the filter.
+    fn visible(&self) -> Vec<usize> {
+        let state = self.state.borrow();
+        (0..state.entries.len())
+            .filter(|&idx| self.filter.is_match(&state.entries[idx].name))
+            .collect()
+    }
+
+    /// The path of the selected entry, or None if no entries are listed.
+    pub fn selected(&self) -> Option<PathBuf> {
+        let visible = self.visible();
+        let state = self.state.borrow();
+        let idx = *visible.get(state.selected)?;
+        Some(state.dir.join(&state.entries[idx].name))
+    }
+
+    /// The file chosen with Enter, if one has been chosen since the last
+    /// call to FileBrowser::take_chosen.
+    pub fn chosen(&self) -> Option<PathBuf> {
+        self.state.borrow().chosen.clone()
+    }
+
+    /// Take the chosen file, so that it is only acted on once.
+    pub fn take_chosen(&self) -> Option<PathBuf> {
+        self.state.borrow_mut().chosen.take()
+    }
+
+    /// Go up to the parent directory, selecting the directory that was left.
+    pub fn go_up(&self) {
+        let dir = self.dir();
+        let Some(parent) = dir.parent() else {
+            return;
+        };
+        self.set_dir(parent);
+        let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return;
+        };
+        let mut state = self.state.borrow_mut();
+        if let Some(idx) = state.entries.iter().position(|e| e.name == name) {
+            state.selected = idx;
+        }
+    }
+
+    /// Enter the selected directory, or choose the selected file.
+    fn activate(&self) {
+        let Some(path) = self.selected() else {
+            return;
+        };
+        if path.is_dir() {
+            self.set_dir(path);
+        } else {
+            self.state.borrow_mut().chosen = Some(path);
+        }
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let revision = self.filter.revision();
+        if self.filter.handle_keys(kb) {
+            if self.filter.revision() != revision {
+                self.state.borrow_mut().selected = 0;
+            }
+            return true;
+        }
+        let last = self.visible().len().saturating_sub(1);
+        let page = self.state.borrow().rows.max(1);
+        let selected = self.state.borrow().selected;
+        let selected = match kb.code() {
+            Some(KeyCode::Up) => selected.saturating_sub(1),
+            Some(KeyCode::Down) => selected + 1,
+            Some(KeyCode::PageUp) => selected.saturating_sub(page),
+            Some(KeyCode::PageDown) => selected + page,
+            Some(KeyCode::Home) => 0,
+            Some(KeyCode::End) => last,
+            Some(KeyCode::Enter | KeyCode::Right) => {
+                self.activate();
+                kb.reset();
+                return true;
+            }
+            Some(KeyCode::Backspace | KeyCode::Left) => {
+                self.go_up();
+                kb.reset();
+                return true;
+            }
+            _ => return false,
+        };
+        self.state.borrow_mut().selected = selected.min(last);
+        kb.reset();
+        true
+    }
+
+    /// Draw the directory and its entries within a region of a context. The
+    /// filter is drawn on the last row while it is in use.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let visible = self.visible();
+        let mut rows = rect.size.height - 1;
+        if self.filter.is_editing() || self.filter.is_active() {
+            rows = rows.saturating_sub(1);
+            let names: Vec<String> = {
+                let state = self.state.borrow();
+                state.entries.iter().map(|e| e.name.clone()).collect()
+            };
+            self.filter.update(names);
+            let y = rect.pos.y + rect.size.height - 1;
+            self.filter
+                .draw(ctx, ((rect.pos.x, y), (rect.size.width, 1)));
+        }
+
+        let mut state = self.state.borrow_mut();
+        let dir = state.dir.display().to_string();
+        let skip = dir.chars().count().saturating_sub(rect.size.width);
+        let dir: String = dir.chars().skip(skip).collect();
+        ctx.insert(rect.pos, Runes::from(dir).fg(theme.accent).bold());
+        if let Some(error) = &state.error {
+            let pos = (rect.pos.x, rect.pos.y + 1);
+            ctx.insert(pos, Runes::from(error.clone()).fg(theme.error));
+            return;
+        }
+
+        state.rows = rows;
+        state.selected = state.selected.min(visible.len().saturating_sub(1));
+        if state.selected < state.offset {
+            state.offset = state.selected;
+        } else if rows > 0 && state.selected >= state.offset + rows {
+            state.offset = state.selected + 1 - rows;
+        }
+
+        for (row, &idx) in visible.iter().enumerate().skip(state.offset).take(rows) {
+            let entry = &state.entries[idx];
+            let pos = (rect.pos.x, rect.pos.y + row - state.offset + 1);
+            let mut runes = Runes::from(entry.name.clone());
+            if entry.is_dir {
+                runes.add("/");
+                runes = runes.fg(theme.accent);
+            }
+            if row == state.selected {
+                ctx.fill(
+                    (pos, (rect.size.width, 1)),
+                    Rune::new().bg(theme.bg_selection),
+                );
+                runes = runes.fg(theme.fg_selection).bg(theme.bg_selection);
+            }
+            let runes = self.filter.highlight(runes, &theme);
+            ctx.insert(
+                pos,
+                Runes::new(runes.iter().take(rect.size.width).copied().collect()),
+            );
+        }
+    }
+}
+
+impl Callable<()> for FileBrowser {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crossterm::event::KeyCode;
+
+    use crate::input::Keyboard;
+
+    use super::FileBrowser;
+
+    fn press(browser: &FileBrowser, code: KeyCode) {
+        browser.handle_keys(&Keyboard::with_key(code));
+    }
+
+    fn names(browser: &FileBrowser) -> Vec<String> {
+        let mut ctx = crate::context::tests::context_fixture();
+        browser.draw(&mut ctx, ((0, 0), (20, 8)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        text.lines()
+            .skip(1)
+            .take(6)
+            .map(|l| l.trim_end().to_string())
+            .filter(|l| !l.is_empty())
+            .collect()
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("arkham-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+        fs::write(root.join("build.rs"), "").unwrap();
+        fs::write(root.join(".env"), "").unwrap();
+        fs::canonicalize(root).unwrap()
+    }
+
+    #[test]
+    fn test_navigate_and_choose() {
+        let root = fixture("browse");
+        let browser = FileBrowser::new(&root);
+        assert_eq!(names(&browser), vec!["src/", "build.rs", "README.md"]);
+
+        press(&browser, KeyCode::Enter);
+        assert_eq!(browser.dir(), root.join("src"));
+        assert_eq!(names(&browser), vec!["lib.rs"]);
+        press(&browser, KeyCode::Enter);
+        assert_eq!(browser.take_chosen(), Some(root.join("src/lib.rs")));
+        assert_eq!(browser.chosen(), None);
+
+        press(&browser, KeyCode::Backspace);
+        assert_eq!(browser.dir(), root);
+        assert_eq!(browser.selected(), Some(root.join("src")));
+
+        let hidden = FileBrowser::new(&root).show_hidden();
+        assert_eq!(names(&hidden)[1], ".env");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_filter() {
+        let root = fixture("filter");
+        let browser = FileBrowser::new(&root);
+        for c in "/s".chars() {
+            press(&browser, KeyCode::Char(c));
+        }
+        press(&browser, KeyCode::Enter);
+        assert_eq!(names(&browser), vec!["src/", "build.rs"]);
+        press(&browser, KeyCode::Down);
+        assert_eq!(browser.selected(), Some(root.join("build.rs")));
+
+        // Entering a directory clears the filter.
+        press(&browser, KeyCode::Up);
+        press(&browser, KeyCode::Enter);
+        assert_eq!(names(&browser), vec!["lib.rs"]);
+        assert!(!browser.filter.is_active());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
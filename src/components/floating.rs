@@ -0,0 +1,328 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::{boxed_component, BoxedComponent, Callable, FromContainer},
+    context::ViewContext,
+    geometry::{Rect, Size},
+    input::Keyboard,
+    runes::Runes,
+};
+
+use super::{Block, TitleAlignment};
+
+/// How keyboard input is applied to the focused floating window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowMode {
+    /// Keys are passed through to the application.
+    #[default]
+    Normal,
+    /// The arrow keys move the focused window.
+    Move,
+    /// The arrow keys resize the focused window.
+    Resize,
+}
+
+/// A panel displayed above the main layout by FloatingWindows. The window
+/// draws a bordered frame and renders its component inside of it.
+#[derive(Clone)]
+pub struct FloatingWindow {
+    id: String,
+    title: Option<Runes>,
+    rect: Rect,
+    content: BoxedComponent,
+}
+
+impl std::fmt::Debug for FloatingWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FloatingWindow")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("rect", &self.rect)
+            .finish()
+    }
+}
+
+impl FloatingWindow {
+    /// Create a window with an identifier, an initial position and size, and
+    /// a component rendered inside of it. The component can be injected with
+    /// resources like any other component.
+    pub fn new<S, R, F, Args>(id: S, rect: R, component: F) -> Self
+    where
+        S: ToString,
+        R: Into<Rect>,
+        F: Callable<Args> + 'static,
+        Args: FromContainer + 'static,
+    {
+        Self {
+            id: id.to_string(),
+            title: None,
+            rect: rect.into(),
+            content: boxed_component(component),
+        }
+    }
+
+    /// Set a title displayed along the top of the window.
+    pub fn title<R: Into<Runes>>(mut self, title: R) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct FloatingState {
+    windows: Vec<FloatingWindow>,
+    mode: WindowMode,
+}
+
+/// FloatingWindows manages a stack of movable and resizable panels drawn
+/// above the main layout. The last window in the stack is drawn on top and
+/// has focus.
+///
+/// Windows are moved and resized from the keyboard by switching the
+/// manager into WindowMode::Move or WindowMode::Resize and passing key
+/// presses to FloatingWindows::handle_keys. Tab cycles focus between windows
+/// and Enter or Esc returns to normal mode.
+///
+/// FloatingWindows is meant to be inserted as a resource and drawn at the
+/// end of the root component.
+///
+/// ```no_run
+/// use arkham::{components::{FloatingWindow, FloatingWindows, WindowMode}, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_resource(FloatingWindows::new())
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, windows: Res<FloatingWindows>, kb: Res<Keyboard>) {
+///     if !windows.handle_keys(&kb) {
+///         match kb.code() {
+///             Some(KeyCode::Char('h')) => windows.open(
+///                 FloatingWindow::new("help", ((4, 2), (30, 10)), help).title("Help"),
+///             ),
+///             Some(KeyCode::F(2)) => windows.set_mode(WindowMode::Move),
+///             Some(KeyCode::F(3)) => windows.set_mode(WindowMode::Resize),
+///             _ => {}
+///         }
+///     }
+///     ctx.insert((0, 0), "Main layout");
+///     windows.draw(ctx);
+/// }
+///
+/// fn help(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Press F2 to move this window");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FloatingWindows {
+    state: Rc<RefCell<FloatingState>>,
+}
+
+impl FloatingWindows {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a window on top of all other windows. If a window with the same
+    /// identifier is already open it is replaced, keeping its current
+    /// position and size.
+    pub fn open(&self, mut window: FloatingWindow) {
+        let mut state = self.state.borrow_mut();
+        if let Some(idx) = state.windows.iter().position(|w| w.id == window.id) {
+            window.rect = state.windows.remove(idx).rect;
+        }
+        state.windows.push(window);
+    }
+
+    /// Close the window with an identifier.
+    pub fn close(&self, id: &str) {
+        self.state.borrow_mut().windows.retain(|w| w.id != id);
+    }
+
+    /// Returns true if a window with an identifier is open.
+    pub fn is_open(&self, id: &str) -> bool {
+        self.state.borrow().windows.iter().any(|w| w.id == id)
+    }
+
+    /// Bring a window to the top of the stack, giving it focus.
+    pub fn raise(&self, id: &str) {
+        let mut state = self.state.borrow_mut();
+        if let Some(idx) = state.windows.iter().position(|w| w.id == id) {
+            let window = state.windows.remove(idx);
+            state.windows.push(window);
+        }
+    }
+
+    /// The identifier of the focused window.
+    pub fn focused(&self) -> Option<String> {
+        self.state.borrow().windows.last().map(|w| w.id.clone())
+    }
+
+    /// The current position and size of a window.
+    pub fn rect(&self, id: &str) -> Option<Rect> {
+        let state = self.state.borrow();
+        state.windows.iter().find(|w| w.id == id).map(|w| w.rect)
+    }
+
+    /// Set how keyboard input is applied to the focused window.
+    pub fn set_mode(&self, mode: WindowMode) {
+        self.state.borrow_mut().mode = mode;
+    }
+
+    /// The current keyboard mode.
+    pub fn mode(&self) -> WindowMode {
+        self.state.borrow().mode
+    }
+
+    /// Apply the pressed key to the focused window according to the current
+    /// mode. Holding shift moves or resizes in larger steps.
+    ///
+    /// Returns true if the key was handled, in which case the keyboard is
+    /// reset so that other components do not respond to it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let mut state = self.state.borrow_mut();
+        if state.mode == WindowMode::Normal {
+            return false;
+        }
+        let Some(code) = kb.code() else {
+            return false;
+        };
+        let step = if kb.shift() { 5 } else { 1 };
+        let (dx, dy) = match code {
+            KeyCode::Left => (-step, 0),
+            KeyCode::Right => (step, 0),
+            KeyCode::Up => (0, -step),
+            KeyCode::Down => (0, step),
+            _ => (0, 0),
+        };
+        let mode = state.mode;
+        match code {
+            KeyCode::Enter | KeyCode::Esc => state.mode = WindowMode::Normal,
+            KeyCode::Tab if !state.windows.is_empty() => state.windows.rotate_right(1),
+            KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {
+                if let Some(window) = state.windows.last_mut() {
+                    if mode == WindowMode::Move {
+                        window.rect.pos.offset(dx, dy);
+                    } else {
+                        window.rect.size.grow(dx, dy);
+                        window.rect.size.width = window.rect.size.width.max(3);
+                        window.rect.size.height = window.rect.size.height.max(3);
+                    }
+                }
+            }
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw all open windows in order, keeping each window within the bounds
+    /// of the context.
+    pub fn draw(&self, ctx: &mut ViewContext) {
+        let theme = ctx.theme();
+        let bounds = ctx.size();
+        let windows = {
+            let mut state = self.state.borrow_mut();
+            for window in state.windows.iter_mut() {
+                clamp(&mut window.rect, bounds);
+            }
+            state.windows.clone()
+        };
+        let mode = self.mode();
+
+        for (idx, window) in windows.iter().enumerate() {
+            let focused = idx == windows.len() - 1;
+            let mut block = Block::new()
+                .bg(theme.bg_secondary)
+                .border_color(if focused { theme.focus } else { theme.border })
+                .shadow();
+            if let Some(title) = &window.title {
+                block = block.title(title.clone());
+            }
+            match mode {
+                WindowMode::Move if focused => {
+                    block = block.title_aligned("move", TitleAlignment::Right)
+                }
+                WindowMode::Resize if focused => {
+                    block = block.title_aligned("resize", TitleAlignment::Right)
+                }
+                _ => {}
+            }
+            block.draw(ctx, window.rect);
+            (window.content)(ctx, Block::inner(window.rect));
+        }
+    }
+}
+
+/// Shrink and move a rect so that it fits within a region.
+fn clamp(rect: &mut Rect, bounds: Size) {
+    rect.size.width = rect.size.width.min(bounds.width);
+    rect.size.height = rect.size.height.min(bounds.height);
+    rect.pos.x = rect.pos.x.min(bounds.width - rect.size.width);
+    rect.pos.y = rect.pos.y.min(bounds.height - rect.size.height);
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{context::ViewContext, geometry::Rect, input::Keyboard};
+
+    use super::{FloatingWindow, FloatingWindows, WindowMode};
+
+    fn content(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "hi");
+    }
+
+    #[test]
+    fn test_move_and_resize() {
+        let windows = FloatingWindows::new();
+        windows.open(FloatingWindow::new("a", ((2, 2), (6, 4)), content));
+        windows.open(FloatingWindow::new("b", ((0, 0), (6, 4)), content));
+        let kb = Keyboard::new();
+
+        kb.set_key(KeyCode::Right);
+        assert!(!windows.handle_keys(&kb));
+
+        windows.set_mode(WindowMode::Move);
+        assert!(windows.handle_keys(&kb));
+        assert_eq!(kb.code(), None);
+        assert_eq!(windows.rect("b"), Some(Rect::new((1, 0), (6, 4))));
+
+        kb.set_key(KeyCode::Tab);
+        windows.handle_keys(&kb);
+        assert_eq!(windows.focused(), Some("a".to_string()));
+
+        windows.set_mode(WindowMode::Resize);
+        kb.set_key(KeyCode::Up);
+        windows.handle_keys(&kb);
+        assert_eq!(windows.rect("a"), Some(Rect::new((2, 2), (6, 3))));
+
+        kb.set_key(KeyCode::Esc);
+        windows.handle_keys(&kb);
+        assert_eq!(windows.mode(), WindowMode::Normal);
+    }
+
+    #[test]
+    fn test_draw_z_order() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let windows = FloatingWindows::new();
+        windows.open(FloatingWindow::new("a", ((0, 0), (6, 4)), content));
+        windows.open(FloatingWindow::new("b", ((2, 1), (6, 4)), content));
+        windows.draw(&mut ctx);
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(3).collect();
+        assert!(lines[0].starts_with("┌────┐"));
+        assert!(lines[1].starts_with("│h┌────┐"));
+        assert!(lines[2].starts_with("│\0│hi"));
+
+        windows.raise("a");
+        windows.open(FloatingWindow::new("b", ((30, 30), (6, 4)), content));
+        assert_eq!(windows.focused(), Some("b".to_string()));
+        assert_eq!(windows.rect("b"), Some(Rect::new((2, 1), (6, 4))));
+    }
+}
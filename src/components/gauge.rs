@@ -0,0 +1,125 @@
+use crossterm::style::Color;
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    runes::{Rune, Runes},
+    symbols::partial_fill,
+};
+
+/// Gauge displays a ratio as a horizontal bar with a label centered over
+/// it, showing the percentage by default. The end of the bar is drawn with
+/// partial block characters, so it is finer than a cell.
+///
+/// ```no_run
+/// use arkham::{components::Gauge, prelude::*};
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.component(((0, 0), (30, 1)), Gauge::new(0.42));
+///     ctx.component(
+///         ((0, 1), (30, 1)),
+///         Gauge::new(0.9).label("Disk 90%").color(Color::Red),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Gauge {
+    ratio: f64,
+    label: Option<String>,
+    color: Option<Color>,
+}
+
+impl Gauge {
+    /// Create a gauge filled to a ratio between 0.0 and 1.0.
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            ..Self::default()
+        }
+    }
+
+    /// Set the label drawn over the bar, in place of the percentage.
+    pub fn label<S: ToString>(mut self, label: S) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Set the color of the bar. Defaults to the theme's accent color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw the gauge on the first row of a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let width = rect.size.width;
+        if width == 0 || rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let color = self.color.unwrap_or(theme.accent);
+        let bars = symbols.bars_horizontal;
+
+        let fill = partial_fill(self.ratio, width, bars);
+        let full = fill
+            .iter()
+            .take_while(|&&c| Some(c) == bars.last().copied())
+            .count();
+        let mut runes: Vec<Rune> = fill
+            .into_iter()
+            .map(|c| Rune::new().content(c).fg(color).bg(theme.bg_tertiary))
+            .collect();
+
+        let label = self
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("{:.0}%", self.ratio * 100.0));
+        let label = Runes::from(label);
+        let start = width.saturating_sub(label.len()) / 2;
+        let text_fg = crate::color::contrast(color).unwrap_or(theme.fg);
+        for (x, rune) in label.iter().take(width).enumerate() {
+            let cell = &mut runes[start + x];
+            cell.content = rune.content;
+            cell.cluster = rune.cluster;
+            // Text over the filled part of the bar is drawn on the bar color.
+            if start + x < full {
+                cell.fg = Some(text_fg);
+                cell.bg = Some(color);
+            } else {
+                cell.fg = Some(theme.fg);
+            }
+        }
+        ctx.insert(rect.pos, Runes::new(runes));
+    }
+}
+
+impl Callable<()> for Gauge {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::theme::Theme;
+
+    use super::Gauge;
+
+    #[test]
+    fn test_gauge() {
+        let theme = Theme::default();
+        let mut ctx = crate::context::tests::context_fixture();
+        Gauge::new(0.5).draw(&mut ctx, ((0, 0), (10, 1)));
+        Gauge::new(0.25).label("").draw(&mut ctx, ((0, 1), (5, 1)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(&lines[0][..lines[0].find('%').unwrap() + 1], "███50%");
+        assert_eq!(ctx.view[0][4].bg, Some(theme.accent));
+        assert_eq!(ctx.view[0][5].bg, Some(theme.bg_tertiary));
+        assert_eq!(lines[1].trim_end(), "█▎");
+    }
+}
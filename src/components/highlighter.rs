@@ -0,0 +1,145 @@
+use regex::Regex;
+
+use crate::runes::{styled_runes, Rune, Runes};
+
+/// Highlighter styles text as it is converted to runes, using rules that
+/// match regular expressions, such as coloring URLs or error keywords in
+/// log messages and command output.
+///
+/// Each rule applies the styling set on a rune to the text its pattern
+/// matches. Colors and attributes that are not set on the rune are left as
+/// they are, and where the matches of several rules overlap, rules added
+/// later take precedence.
+///
+/// This requires the `regex` feature.
+///
+/// ```
+/// use arkham::{components::Highlighter, prelude::*};
+///
+/// let highlighter = Highlighter::new()
+///     .rule(Highlighter::URL, Rune::new().fg(Color::Blue).underline())
+///     .rule(Highlighter::IPV4, Rune::new().fg(Color::Cyan))
+///     .rule(r"\b(ERROR|FAIL(ED)?)\b", Rune::new().fg(Color::Red).bold());
+///
+/// let runes = highlighter.highlight("FAILED to reach 10.0.0.1");
+/// assert_eq!(runes[0].fg, Some(Color::Red));
+/// assert_eq!(runes[16].fg, Some(Color::Cyan));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Highlighter {
+    rules: Vec<(Regex, Rune)>,
+}
+
+impl Highlighter {
+    /// Matches http and https URLs.
+    pub const URL: &'static str = r#"https?://[^\s<>"']+[^\s<>"'.,;:!?)\]]"#;
+
+    /// Matches IPv4 addresses, with an optional port.
+    pub const IPV4: &'static str = r"\b(?:\d{1,3}\.){3}\d{1,3}(?::\d{1,5})?\b";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule styling the text matched by a pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is not a valid regular expression. Use
+    /// Highlighter::try_rule for patterns that are not known in advance.
+    pub fn rule(self, pattern: &str, style: Rune) -> Self {
+        match self.try_rule(pattern, style) {
+            Ok(highlighter) => highlighter,
+            Err(err) => panic!("invalid highlighter pattern: {}", err),
+        }
+    }
+
+    /// Add a rule styling the text matched by a pattern, returning an error
+    /// if the pattern is not a valid regular expression.
+    pub fn try_rule(mut self, pattern: &str, style: Rune) -> Result<Self, regex::Error> {
+        self.rules.push((Regex::new(pattern)?, style));
+        Ok(self)
+    }
+
+    /// Convert text into runes, styling the text matched by each rule.
+    pub fn highlight(&self, text: &str) -> Runes {
+        self.highlight_with(text, Rune::new())
+    }
+
+    /// Convert text into runes styled with a base style, styling the text
+    /// matched by each rule over it.
+    pub fn highlight_with(&self, text: &str, base: Rune) -> Runes {
+        // The style of the character starting at each byte offset.
+        let mut styles = vec![base; text.len()];
+        for (pattern, style) in self.rules.iter() {
+            for found in pattern.find_iter(text) {
+                for rune in styles[found.range()].iter_mut() {
+                    *rune = layer(*rune, style);
+                }
+            }
+        }
+        Runes::new(styled_runes(text, |idx| styles[idx]))
+    }
+}
+
+/// Apply the styling set on a rule's rune over another rune.
+fn layer(mut rune: Rune, style: &Rune) -> Rune {
+    rune.fg = style.fg.or(rune.fg);
+    rune.bg = style.bg.or(rune.bg);
+    rune.bold |= style.bold;
+    rune.italic |= style.italic;
+    rune.underline |= style.underline;
+    rune.undercurl |= style.undercurl;
+    rune.dim |= style.dim;
+    rune.reverse |= style.reverse;
+    rune.crossed_out |= style.crossed_out;
+    rune.link = style.link.or(rune.link);
+    rune
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::Color, runes::Rune};
+
+    use super::Highlighter;
+
+    #[test]
+    fn test_rules() {
+        let highlighter = Highlighter::new()
+            .rule(Highlighter::URL, Rune::new().fg(Color::Blue).underline())
+            .rule("example", Rune::new().bold());
+        let runes = highlighter.highlight_with(
+            "see https://example.com/a.\tok",
+            Rune::new().fg(Color::Grey),
+        );
+        let text: String = runes.iter().filter_map(|r| r.content).collect();
+        assert_eq!(text, "see https://example.com/a.  ok");
+
+        let style = |idx: usize| {
+            let rune = runes[idx];
+            (rune.fg, rune.underline, rune.bold)
+        };
+        assert_eq!(style(0), (Some(Color::Grey), false, false));
+        assert_eq!(style(4), (Some(Color::Blue), true, false));
+        // Overlapping rules are layered.
+        assert_eq!(style(12), (Some(Color::Blue), true, true));
+        // The trailing period is not part of the URL.
+        assert_eq!(style(25), (Some(Color::Grey), false, false));
+        // Styles are kept through tab expansion.
+        assert_eq!(runes[29].fg, Some(Color::Grey));
+
+        assert!(Highlighter::new().try_rule("(", Rune::new()).is_err());
+    }
+
+    #[test]
+    fn test_ipv4() {
+        let highlighter = Highlighter::new().rule(Highlighter::IPV4, Rune::new().bold());
+        let runes = highlighter.highlight("from 192.168.1.20:8080 v1.2.3");
+        let bold: String = runes
+            .iter()
+            .filter(|r| r.bold)
+            .filter_map(|r| r.content)
+            .collect();
+        assert_eq!(bold, "192.168.1.20:8080");
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    prelude::Color,
+    runes::{Runes, ToRuneExt},
+};
+
+/// A single bucket of a histogram covering the range start..end. The last
+/// bin of a histogram also includes its end value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bin {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+}
+
+/// Group values into a number of equally sized bins spanning the range of the
+/// values. Values that are not finite are ignored.
+///
+/// Example:
+/// ```
+/// use arkham::components::bin;
+/// let bins = bin(&[1.0, 2.0, 2.5, 4.0], 3);
+/// assert_eq!(bins.iter().map(|b| b.count).collect::<Vec<_>>(), vec![1, 2, 1]);
+/// assert_eq!(bins[0].start, 1.0);
+/// assert_eq!(bins[2].end, 4.0);
+/// ```
+pub fn bin(values: &[f64], bins: usize) -> Vec<Bin> {
+    let values = values.iter().copied().filter(|v| v.is_finite());
+    let (min, max) = values
+        .clone()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    if bins == 0 || min > max {
+        return vec![];
+    }
+
+    let width = if max > min {
+        (max - min) / bins as f64
+    } else {
+        1.0
+    };
+    let mut result: Vec<Bin> = (0..bins)
+        .map(|i| Bin {
+            start: min + width * i as f64,
+            end: min + width * (i + 1) as f64,
+            count: 0,
+        })
+        .collect();
+    for value in values {
+        let idx = (((value - min) / width) as usize).min(bins - 1);
+        result[idx].count += 1;
+    }
+    result
+}
+
+/// Histogram renders the distribution of a set of values as horizontal bars,
+/// one row per bin. Each row is labeled with the range of the bin and the
+/// number of values it contains.
+///
+/// ```
+/// use arkham::{components::Histogram, prelude::*};
+///
+/// fn latency(ctx: &mut ViewContext) {
+///     let samples = vec![12.0, 15.5, 14.2, 30.1, 13.8, 16.0, 22.4];
+///     let size = ctx.size();
+///     ctx.component(size, Histogram::new(&samples, 5).precision(1));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bins: Vec<Bin>,
+    color: Option<Color>,
+    precision: usize,
+}
+
+impl Histogram {
+    /// Create a histogram grouping the values into a number of bins.
+    pub fn new(values: &[f64], bins: usize) -> Self {
+        Self::from_bins(bin(values, bins))
+    }
+
+    /// Create a histogram from bins that have already been counted.
+    pub fn from_bins(bins: Vec<Bin>) -> Self {
+        Self {
+            bins,
+            color: None,
+            precision: 0,
+        }
+    }
+
+    /// Set the color of the bars. Defaults to the theme's accent color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the number of decimal places shown in the bin labels.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// The bins displayed by the histogram.
+    pub fn bins(&self) -> &[Bin] {
+        &self.bins
+    }
+}
+
+impl Callable<()> for Histogram {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let size = ctx.size();
+        let p = self.precision;
+
+        let labels: Vec<(String, String)> = self
+            .bins
+            .iter()
+            .map(|b| (format!("{:.p$}", b.start), format!("{:.p$}", b.end)))
+            .collect();
+        let start_width = labels.iter().map(|l| l.0.len()).max().unwrap_or_default();
+        let end_width = labels.iter().map(|l| l.1.len()).max().unwrap_or_default();
+        let max = self.bins.iter().map(|b| b.count).max().unwrap_or_default();
+        let count_width = max.to_string().len();
+        let label_width = start_width + end_width + 4;
+        let bar_width = size.width.saturating_sub(label_width + count_width + 1);
+
+        for (row, (bin, (start, end))) in self.bins.iter().zip(labels).enumerate() {
+            if row >= size.height {
+                break;
+            }
+            let filled = if max == 0 {
+                0
+            } else {
+                (bin.count as f64 / max as f64 * bar_width as f64).round() as usize
+            };
+            let mut line = Runes::from(format!("{:>start_width$}-{:<end_width$} ", start, end));
+            line.add("│ ".to_runes().fg(theme.muted));
+            line.add(
+                "█"
+                    .repeat(filled)
+                    .to_runes()
+                    .fg(self.color.unwrap_or(theme.accent)),
+            );
+            line.add(format!(" {}", bin.count));
+            ctx.insert((0, row), line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bin, Histogram};
+
+    #[test]
+    fn test_bin_ranges() {
+        let bins = bin(&[0.0, 10.0, 5.0, f64::NAN, 9.9], 2);
+        assert_eq!(bins.len(), 2);
+        assert_eq!((bins[0].start, bins[0].end, bins[0].count), (0.0, 5.0, 1));
+        assert_eq!((bins[1].start, bins[1].end, bins[1].count), (5.0, 10.0, 3));
+        assert!(bin(&[], 4).is_empty());
+        assert_eq!(bin(&[3.0, 3.0], 2)[0].count, 2);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (16, 2)), Histogram::new(&[1.0, 2.0, 3.0, 3.0], 2));
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(2).collect();
+        assert!(lines[0].starts_with("1-2 │ ███ 1"));
+        assert!(lines[1].starts_with("2-3 │ ████████ 3"));
+    }
+}
@@ -0,0 +1,189 @@
+/// InputFormatter transforms or rejects text as it is typed into a
+/// TextInput, and reports whether the value of the input is valid.
+///
+/// Formatters are applied in the order they were added to the input, each
+/// receiving the text produced by the previous formatter.
+///
+/// ```
+/// use arkham::components::{InputFormatter, TextInput};
+///
+/// /// Converts typed text to uppercase.
+/// #[derive(Debug)]
+/// struct Uppercase;
+///
+/// impl InputFormatter for Uppercase {
+///     fn format(&self, _value: &str, _cursor: usize, text: String) -> Option<String> {
+///         Some(text.to_uppercase())
+///     }
+/// }
+///
+/// let input = TextInput::new().formatter(Uppercase);
+/// ```
+pub trait InputFormatter: std::fmt::Debug {
+    /// Called with the current value, the cursor position in characters,
+    /// and text about to be inserted at the cursor. Returns the text to
+    /// insert, or None to reject the keystroke.
+    fn format(&self, value: &str, cursor: usize, text: String) -> Option<String>;
+
+    /// Returns true if a value is acceptable. Inputs are valid unless a
+    /// formatter reports otherwise.
+    fn is_valid(&self, _value: &str) -> bool {
+        true
+    }
+}
+
+/// Accepts only digits. Negative and decimal numbers can optionally be
+/// allowed.
+///
+/// Example:
+/// ```
+/// use arkham::components::{InputFormatter, Numeric};
+/// let numeric = Numeric::new().negative().decimal();
+/// assert_eq!(numeric.format("", 0, "-".to_string()), Some("-".to_string()));
+/// assert_eq!(numeric.format("1.5", 3, ".".to_string()), None);
+/// assert_eq!(numeric.format("1", 1, "a".to_string()), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Numeric {
+    negative: bool,
+    decimal: bool,
+}
+
+impl Numeric {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow a leading minus sign.
+    pub fn negative(mut self) -> Self {
+        self.negative = true;
+        self
+    }
+
+    /// Allow a single decimal point.
+    pub fn decimal(mut self) -> Self {
+        self.decimal = true;
+        self
+    }
+}
+
+impl InputFormatter for Numeric {
+    fn format(&self, value: &str, cursor: usize, text: String) -> Option<String> {
+        let mut has_point = value.contains('.');
+        for (i, c) in text.chars().enumerate() {
+            match c {
+                '0'..='9' => {}
+                '-' if self.negative && cursor + i == 0 && !value.starts_with('-') => {}
+                '.' if self.decimal && !has_point => has_point = true,
+                _ => return None,
+            }
+        }
+        Some(text)
+    }
+
+    fn is_valid(&self, value: &str) -> bool {
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        !digits.is_empty() && digits != "."
+    }
+}
+
+/// Limits the number of characters in the input. Text beyond the limit is
+/// discarded.
+///
+/// Example:
+/// ```
+/// use arkham::components::{InputFormatter, MaxLength};
+/// assert_eq!(MaxLength(4).format("abc", 3, "de".to_string()), Some("d".to_string()));
+/// assert_eq!(MaxLength(4).format("abcd", 4, "e".to_string()), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxLength(pub usize);
+
+impl InputFormatter for MaxLength {
+    fn format(&self, value: &str, _cursor: usize, text: String) -> Option<String> {
+        let remaining = self.0.saturating_sub(value.chars().count());
+        let text: String = text.chars().take(remaining).collect();
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn is_valid(&self, value: &str) -> bool {
+        value.chars().count() <= self.0
+    }
+}
+
+/// Formats input to match a fixed pattern, such as a date. Letters, '#', and
+/// '9' in the pattern are slots which accept a digit. Any other character in
+/// the pattern is a separator, which is inserted automatically as digits are
+/// typed.
+///
+/// The input is valid once every slot has been filled. Text can only be
+/// typed at the end of the input.
+///
+/// Example:
+/// ```
+/// use arkham::components::{InputFormatter, Mask};
+/// let mask = Mask::new("YYYY-MM-DD");
+/// assert_eq!(mask.format("2024", 4, "0".to_string()), Some("-0".to_string()));
+/// assert_eq!(mask.format("2024", 4, "-".to_string()), Some("-".to_string()));
+/// assert_eq!(mask.format("2024", 4, "x".to_string()), None);
+/// assert!(mask.is_valid("2024-01-31"));
+/// assert!(!mask.is_valid("2024-01"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mask {
+    pattern: Vec<char>,
+}
+
+impl Mask {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.chars().collect(),
+        }
+    }
+
+    fn is_slot(c: char) -> bool {
+        c.is_ascii_alphabetic() || c == '#' || c == '9'
+    }
+}
+
+impl InputFormatter for Mask {
+    fn format(&self, value: &str, cursor: usize, text: String) -> Option<String> {
+        let mut pos = value.chars().count();
+        if cursor != pos {
+            return None;
+        }
+        let mut out = String::new();
+        for c in text.chars() {
+            // Typing a separator accepts it, typing a digit fills in any
+            // separators before the next slot.
+            loop {
+                let expected = *self.pattern.get(pos)?;
+                pos += 1;
+                if Mask::is_slot(expected) {
+                    if !c.is_ascii_digit() {
+                        return None;
+                    }
+                    out.push(c);
+                    break;
+                }
+                out.push(expected);
+                if c == expected {
+                    break;
+                }
+            }
+        }
+        (!out.is_empty()).then_some(out)
+    }
+
+    fn is_valid(&self, value: &str) -> bool {
+        let value: Vec<char> = value.chars().collect();
+        value.len() == self.pattern.len()
+            && value.iter().zip(&self.pattern).all(|(v, p)| {
+                if Mask::is_slot(*p) {
+                    v.is_ascii_digit()
+                } else {
+                    v == p
+                }
+            })
+    }
+}
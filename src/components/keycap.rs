@@ -0,0 +1,200 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    keymap::{KeyBinding, Keymap},
+    runes::{Rune, Runes},
+    symbols::{SymbolLevel, SymbolSet},
+    theme::Theme,
+};
+
+/// The label of each keycap for a binding. Terminals with Unicode symbols
+/// use the conventional modifier glyphs and arrows, others use names.
+fn labels(binding: KeyBinding, symbols: &SymbolSet) -> Vec<String> {
+    if symbols.level == SymbolLevel::Legacy {
+        return binding.names();
+    }
+    let mut labels: Vec<String> = [
+        (KeyModifiers::CONTROL, '⌃'),
+        (KeyModifiers::ALT, '⌥'),
+        (KeyModifiers::SHIFT, '⇧'),
+        (KeyModifiers::SUPER, '⌘'),
+    ]
+    .into_iter()
+    .filter(|(modifier, _)| binding.modifiers.contains(*modifier))
+    .map(|(_, glyph)| glyph.to_string())
+    .collect();
+    let key = match binding.code {
+        KeyCode::Up => symbols.arrow_up.to_string(),
+        KeyCode::Down => symbols.arrow_down.to_string(),
+        KeyCode::Left => symbols.arrow_left.to_string(),
+        KeyCode::Right => symbols.arrow_right.to_string(),
+        KeyCode::Enter => "⏎".to_string(),
+        KeyCode::Backspace => "⌫".to_string(),
+        _ => KeyBinding::from(binding.code).to_string(),
+    };
+    labels.push(key);
+    labels
+}
+
+/// Render a key binding as keycaps: each modifier and the key is drawn as a
+/// padded cap, joined by `+`, such as `⌃`+`K`.
+pub fn keycaps(binding: KeyBinding, theme: &Theme, symbols: &SymbolSet) -> Runes {
+    let mut runes = Runes::default();
+    for (idx, label) in labels(binding, symbols).into_iter().enumerate() {
+        if idx > 0 {
+            runes.add(Rune::new().content('+').fg(theme.muted));
+        }
+        runes.add(
+            Runes::from(format!(" {} ", label))
+                .fg(theme.fg)
+                .bg(theme.bg_tertiary),
+        );
+    }
+    runes
+}
+
+/// KeyHints draws a row of shortcut hints from a Keymap, each showing the
+/// keycaps for an action followed by its description, or its name if it has
+/// no description. Hints that do not fit are left out.
+///
+/// As the hints are read from the keymap, they always match the keys that
+/// are actually bound.
+///
+/// ```no_run
+/// use arkham::{components::KeyHints, prelude::*};
+///
+/// fn main() {
+///     let keymap = Keymap::new()
+///         .bind("save", (KeyCode::Char('s'), KeyModifiers::CONTROL))
+///         .describe("save", "Save")
+///         .bind("quit", KeyCode::Esc)
+///         .describe("quit", "Quit");
+///     App::new(root).insert_resource(keymap).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, keymap: Res<Keymap>) {
+///     let size = ctx.size();
+///     ctx.component(
+///         ((0, size.height - 1), (size.width, 1)),
+///         KeyHints::new(&keymap),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyHints {
+    keymap: Keymap,
+    actions: Option<Vec<String>>,
+}
+
+impl KeyHints {
+    pub fn new(keymap: &Keymap) -> Self {
+        Self {
+            keymap: keymap.clone(),
+            actions: None,
+        }
+    }
+
+    /// Only show hints for some actions, in the given order. By default
+    /// every action in the keymap is shown.
+    pub fn actions<I, S>(mut self, actions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.actions = Some(actions.into_iter().map(|a| a.to_string()).collect());
+        self
+    }
+
+    /// Draw the hints on the first row of a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let entries = match &self.actions {
+            Some(actions) => actions
+                .iter()
+                .filter_map(|action| self.keymap.entry(action))
+                .collect(),
+            None => self.keymap.entries(),
+        };
+
+        let right = rect.pos.x + rect.size.width;
+        let mut x = rect.pos.x;
+        for entry in entries {
+            let mut hint = keycaps(entry.binding, &theme, &symbols);
+            hint.add(" ");
+            hint.add(entry.description.unwrap_or(entry.action));
+            if x + hint.len() > right {
+                break;
+            }
+            let width = hint.len();
+            ctx.insert((x, rect.pos.y), hint);
+            x += width + 2;
+        }
+    }
+}
+
+impl Callable<()> for KeyHints {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{
+        keymap::{KeyBinding, Keymap},
+        symbols::SymbolSet,
+        theme::Theme,
+    };
+
+    use super::{keycaps, KeyHints};
+
+    fn text(runes: &[crate::runes::Rune]) -> String {
+        runes.iter().map(|r| r.content.unwrap_or(' ')).collect()
+    }
+
+    #[test]
+    fn test_keycaps() {
+        let theme = Theme::default();
+        let binding = KeyBinding::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
+        let caps = keycaps(binding, &theme, &SymbolSet::UNICODE);
+        assert_eq!(text(&caps), " ⌃ + K ");
+        assert_eq!(caps[0].bg, Some(theme.bg_tertiary));
+        assert_eq!(caps[3].bg, None);
+        assert_eq!(
+            text(&keycaps(binding, &theme, &SymbolSet::LEGACY)),
+            " Ctrl + K "
+        );
+        assert_eq!(
+            text(&keycaps(KeyCode::Up.into(), &theme, &SymbolSet::UNICODE)),
+            " ↑ "
+        );
+    }
+
+    #[test]
+    fn test_hints_follow_keymap() {
+        let keymap = Keymap::new()
+            .bind("save", (KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .describe("save", "Save")
+            .bind("quit", KeyCode::Esc)
+            .describe("quit", "Bye");
+        let draw = |hints: &KeyHints, width: usize| {
+            let mut ctx = crate::context::tests::context_fixture();
+            hints.draw(&mut ctx, ((0, 0), (width, 1)));
+            let text = ctx.view.render_text().replace('\0', " ");
+            text.lines().next().unwrap().trim_end().to_string()
+        };
+        let hints = KeyHints::new(&keymap);
+        assert_eq!(draw(&hints, 20), " ⌃ + S  Save");
+        keymap.set("save", KeyCode::F(2));
+        assert_eq!(draw(&hints, 20), " F2  Save   Esc  Bye");
+        assert_eq!(draw(&hints.clone().actions(["quit"]), 20), " Esc  Bye");
+    }
+}
@@ -0,0 +1,356 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::{boxed_component, BoxedComponent, Callable, FromContainer},
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::Runes,
+};
+
+use super::Block;
+
+/// The arrangement used by a Layout to tile its panes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LayoutPreset {
+    /// The first pane fills a portion of the width given by the ratio, the
+    /// remaining panes are stacked vertically beside it.
+    MainStack(f32),
+    /// Panes are arranged in a grid with as many columns as rows.
+    #[default]
+    Grid,
+    /// Panes are placed side by side with equal widths.
+    Columns,
+    /// Panes are placed above one another with equal heights.
+    Rows,
+}
+
+impl LayoutPreset {
+    /// Divide an area into a number of regions according to the preset.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{components::LayoutPreset, prelude::*};
+    /// let rects = LayoutPreset::MainStack(0.5).rects(((0, 0), (10, 4)), 3);
+    /// assert_eq!(rects[0], Rect::new((0, 0), (5, 4)));
+    /// assert_eq!(rects[1], Rect::new((5, 0), (5, 2)));
+    /// assert_eq!(rects[2], Rect::new((5, 2), (5, 2)));
+    /// ```
+    pub fn rects<R: Into<Rect>>(&self, area: R, count: usize) -> Vec<Rect> {
+        let area = area.into();
+        match (self, count) {
+            (_, 0) => vec![],
+            (_, 1) => vec![area],
+            (LayoutPreset::Columns, n) => split(area, n, true),
+            (LayoutPreset::Rows, n) => split(area, n, false),
+            (LayoutPreset::MainStack(ratio), n) => {
+                let main_width = (area.size.width as f32 * ratio.clamp(0.0, 1.0)) as usize;
+                let main = Rect::new(area.pos, (main_width, area.size.height));
+                let stack = Rect::new(
+                    (area.pos.x + main_width, area.pos.y),
+                    (area.size.width - main_width, area.size.height),
+                );
+                let mut rects = vec![main];
+                rects.extend(split(stack, n - 1, false));
+                rects
+            }
+            (LayoutPreset::Grid, n) => {
+                let columns = (n as f64).sqrt().ceil() as usize;
+                let rows = n.div_ceil(columns);
+                split(area, rows, false)
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(row, rect)| split(rect, columns.min(n - row * columns), true))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Divide an area into equal parts along one axis. Any remainder is given to
+/// the leading parts.
+fn split(area: Rect, count: usize, horizontal: bool) -> Vec<Rect> {
+    let total = if horizontal {
+        area.size.width
+    } else {
+        area.size.height
+    };
+    let mut offset = 0;
+    (0..count)
+        .map(|i| {
+            let len = total / count + usize::from(i < total % count);
+            let rect = if horizontal {
+                Rect::new((area.pos.x + offset, area.pos.y), (len, area.size.height))
+            } else {
+                Rect::new((area.pos.x, area.pos.y + offset), (area.size.width, len))
+            };
+            offset += len;
+            rect
+        })
+        .collect()
+}
+
+/// A region of a Layout rendering a single component.
+#[derive(Clone)]
+pub struct Pane {
+    id: String,
+    title: Option<Runes>,
+    content: BoxedComponent,
+}
+
+impl std::fmt::Debug for Pane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pane")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .finish()
+    }
+}
+
+impl Pane {
+    /// Create a pane with an identifier and the component rendered inside of
+    /// it. The component can be injected with resources like any other
+    /// component.
+    pub fn new<S, F, Args>(id: S, component: F) -> Self
+    where
+        S: ToString,
+        F: Callable<Args> + 'static,
+        Args: FromContainer + 'static,
+    {
+        Self {
+            id: id.to_string(),
+            title: None,
+            content: boxed_component(component),
+        }
+    }
+
+    /// Set a title displayed in the border of the pane.
+    pub fn title<R: Into<Runes>>(mut self, title: R) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct LayoutState {
+    preset: LayoutPreset,
+    panes: Vec<Pane>,
+    focused: usize,
+    zoomed: bool,
+}
+
+/// Layout tiles a set of panes according to a LayoutPreset, drawing a border
+/// around each pane and highlighting the focused pane.
+///
+/// The focused pane can be swapped into the main position or zoomed to fill
+/// the entire layout. Layout::handle_keys provides default key bindings:
+/// Tab and BackTab move focus, Ctrl+S swaps the focused pane with the main
+/// pane, and Ctrl+Z toggles zoom.
+///
+/// Layout is meant to be inserted as a resource, with panes registered once
+/// when the application starts.
+///
+/// ```no_run
+/// use arkham::{components::{Layout, LayoutPreset, Pane}, prelude::*};
+///
+/// fn main() {
+///     let layout = Layout::new(LayoutPreset::MainStack(0.6))
+///         .pane(Pane::new("editor", editor).title("Editor"))
+///         .pane(Pane::new("files", files).title("Files"))
+///         .pane(Pane::new("log", log).title("Log"));
+///     App::new(root).insert_resource(layout).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, layout: Res<Layout>, kb: Res<Keyboard>) {
+///     layout.handle_keys(&kb);
+///     let size = ctx.size();
+///     layout.draw(ctx, size);
+/// }
+///
+/// fn editor(ctx: &mut ViewContext) {}
+/// fn files(ctx: &mut ViewContext) {}
+/// fn log(ctx: &mut ViewContext) {}
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    state: Rc<RefCell<LayoutState>>,
+}
+
+impl Layout {
+    pub fn new(preset: LayoutPreset) -> Self {
+        let layout = Self::default();
+        layout.set_preset(preset);
+        layout
+    }
+
+    /// Add a pane to the end of the layout.
+    pub fn pane(self, pane: Pane) -> Self {
+        self.state.borrow_mut().panes.push(pane);
+        self
+    }
+
+    /// Change the arrangement of the panes.
+    pub fn set_preset(&self, preset: LayoutPreset) {
+        self.state.borrow_mut().preset = preset;
+    }
+
+    /// The identifiers of the panes in the order they are laid out.
+    pub fn panes(&self) -> Vec<String> {
+        let state = self.state.borrow();
+        state.panes.iter().map(|p| p.id.clone()).collect()
+    }
+
+    /// The identifier of the focused pane.
+    pub fn focused(&self) -> Option<String> {
+        let state = self.state.borrow();
+        state.panes.get(state.focused).map(|p| p.id.clone())
+    }
+
+    /// Focus the pane with an identifier.
+    pub fn focus(&self, id: &str) {
+        let mut state = self.state.borrow_mut();
+        if let Some(idx) = state.panes.iter().position(|p| p.id == id) {
+            state.focused = idx;
+        }
+    }
+
+    /// Move focus forward or backward through the panes, wrapping at either
+    /// end.
+    pub fn cycle_focus(&self, forward: bool) {
+        let mut state = self.state.borrow_mut();
+        let count = state.panes.len().max(1);
+        state.focused = if forward {
+            (state.focused + 1) % count
+        } else {
+            (state.focused + count - 1) % count
+        };
+    }
+
+    /// Swap the focused pane with the pane in the main position. Focus
+    /// follows the pane into the main position.
+    pub fn swap_main(&self) {
+        let mut state = self.state.borrow_mut();
+        let focused = state.focused;
+        if focused < state.panes.len() {
+            state.panes.swap(0, focused);
+            state.focused = 0;
+        }
+    }
+
+    /// Toggle displaying only the focused pane across the entire layout.
+    pub fn toggle_zoom(&self) {
+        let mut state = self.state.borrow_mut();
+        state.zoomed = !state.zoomed;
+    }
+
+    /// Returns true if the focused pane is zoomed.
+    pub fn is_zoomed(&self) -> bool {
+        self.state.borrow().zoomed
+    }
+
+    /// Apply the default key bindings. Returns true if the key was handled,
+    /// in which case the keyboard is reset so that panes do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        match kb.code() {
+            Some(KeyCode::Tab) => self.cycle_focus(true),
+            Some(KeyCode::BackTab) => self.cycle_focus(false),
+            Some(KeyCode::Char('s')) if kb.control() => self.swap_main(),
+            Some(KeyCode::Char('z')) if kb.control() => self.toggle_zoom(),
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the panes within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let (panes, focused, rects) = {
+            let state = self.state.borrow();
+            let rects = if state.zoomed {
+                vec![rect]
+            } else {
+                state.preset.rects(rect, state.panes.len())
+            };
+            (state.panes.clone(), state.focused, rects)
+        };
+
+        let visible: Vec<(usize, &Pane)> = if self.is_zoomed() {
+            panes.iter().enumerate().skip(focused).take(1).collect()
+        } else {
+            panes.iter().enumerate().collect()
+        };
+        for ((idx, pane), rect) in visible.into_iter().zip(rects) {
+            let mut block = Block::new().border_color(if idx == focused {
+                theme.focus
+            } else {
+                theme.border
+            });
+            if let Some(title) = &pane.title {
+                block = block.title(title.clone());
+            }
+            block.draw(ctx, rect);
+            (pane.content)(ctx, Block::inner(rect));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{context::ViewContext, geometry::Rect, input::Keyboard};
+
+    use super::{Layout, LayoutPreset, Pane};
+
+    fn a(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "a");
+    }
+
+    fn b(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "b");
+    }
+
+    #[test]
+    fn test_presets() {
+        let area = Rect::new((0, 0), (10, 5));
+        let rects = LayoutPreset::Columns.rects(area, 3);
+        let widths: Vec<usize> = rects.iter().map(|r| r.size.width).collect();
+        assert_eq!(widths, vec![4, 3, 3]);
+        assert_eq!(rects[2].pos.x, 7);
+
+        let rects = LayoutPreset::Grid.rects(area, 3);
+        assert_eq!(rects[0], Rect::new((0, 0), (5, 3)));
+        assert_eq!(rects[1], Rect::new((5, 0), (5, 3)));
+        assert_eq!(rects[2], Rect::new((0, 3), (10, 2)));
+        assert!(LayoutPreset::Rows.rects(area, 0).is_empty());
+    }
+
+    #[test]
+    fn test_swap_and_zoom() {
+        let layout = Layout::new(LayoutPreset::Columns)
+            .pane(Pane::new("a", a))
+            .pane(Pane::new("b", b));
+        let kb = Keyboard::new();
+        kb.set_key(KeyCode::Tab);
+        assert!(layout.handle_keys(&kb));
+        assert_eq!(layout.focused(), Some("b".to_string()));
+
+        kb.set_key(KeyCode::Char('s'));
+        kb.set_modifiers(KeyModifiers::CONTROL);
+        layout.handle_keys(&kb);
+        assert_eq!(layout.panes(), vec!["b", "a"]);
+
+        layout.toggle_zoom();
+        let mut ctx = crate::context::tests::context_fixture();
+        layout.draw(&mut ctx, ((0, 0), (10, 3)));
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(2).collect();
+        assert!(lines[0].starts_with("┌────────┐"));
+        assert!(lines[1].starts_with("│b\0"));
+    }
+}
@@ -0,0 +1,273 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{Rune, Runes},
+    search::{SearchFollower, SearchState},
+};
+
+#[derive(Debug, Default)]
+struct ListState {
+    items: Vec<Runes>,
+    selected: usize,
+    offset: usize,
+    /// The number of rows the list was last drawn with, used to move by a
+    /// page.
+    rows: usize,
+    search: SearchFollower,
+}
+
+impl ListState {
+    fn select(&mut self, idx: usize) {
+        self.selected = idx.min(self.items.len().saturating_sub(1));
+    }
+
+    /// Scroll the viewport so that the selected item is within it.
+    fn scroll_to_selected(&mut self, rows: usize) {
+        self.select(self.selected);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if rows > 0 && self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+        self.offset = self.offset.min(self.items.len().saturating_sub(rows));
+    }
+}
+
+/// List displays a scrollable list of items, one per row, with a selected
+/// item that is highlighted using the theme's selection colors. The list is
+/// scrolled when it is drawn so that the selected item is always visible.
+///
+/// List::handle_keys provides the key bindings: Up and Down move the
+/// selection by an item, Page Up and Page Down move it by a page, and Home
+/// and End move it to the first and last items. See List::search for
+/// searching the items.
+///
+/// ```no_run
+/// use arkham::{components::List, prelude::*};
+///
+/// fn main() {
+///     let files = List::new(["Cargo.toml", "README.md", "src/lib.rs"]);
+///     App::new(root).insert_resource(files).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, files: Res<List>, kb: Res<Keyboard>) {
+///     files.handle_keys(&kb);
+///     ctx.component(((0, 0), (30, 10)), files.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct List {
+    state: Rc<RefCell<ListState>>,
+}
+
+impl List {
+    pub fn new<I, R>(items: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<Runes>,
+    {
+        let list = Self::default();
+        list.set_items(items);
+        list
+    }
+
+    /// Replace the items in the list. The selected index is kept, moving to
+    /// the last item if the list became shorter.
+    pub fn set_items<I, R>(&self, items: I)
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<Runes>,
+    {
+        let mut state = self.state.borrow_mut();
+        state.items = items.into_iter().map(Into::into).collect();
+        state.search.invalidate();
+        let selected = state.selected;
+        state.select(selected);
+    }
+
+    /// Search the items with a shared SearchState. Matches are highlighted,
+    /// and the item containing the current match is selected as it changes.
+    /// List::handle_keys also applies the search key bindings.
+    pub fn search(self, search: &SearchState) -> Self {
+        self.state.borrow_mut().search.search = Some(search.clone());
+        self
+    }
+
+    /// The number of items in the list.
+    pub fn len(&self) -> usize {
+        self.state.borrow().items.len()
+    }
+
+    /// Returns true if the list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.state.borrow().items.is_empty()
+    }
+
+    /// The index of the selected item, or None if the list is empty.
+    pub fn selected(&self) -> Option<usize> {
+        let state = self.state.borrow();
+        (!state.items.is_empty()).then_some(state.selected)
+    }
+
+    /// The selected item, or None if the list is empty.
+    pub fn selected_item(&self) -> Option<Runes> {
+        let state = self.state.borrow();
+        state.items.get(state.selected).cloned()
+    }
+
+    /// Select the item at an index. Indexes past the end of the list select
+    /// the last item.
+    pub fn select(&self, idx: usize) {
+        self.state.borrow_mut().select(idx);
+    }
+
+    /// The index of the first visible item.
+    pub fn offset(&self) -> usize {
+        self.state.borrow().offset
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let search = self.state.borrow().search.search.clone();
+        if search.is_some_and(|search| search.handle_keys(kb)) {
+            return true;
+        }
+        let mut state = self.state.borrow_mut();
+        let page = state.rows.max(1);
+        let selected = match kb.code() {
+            Some(KeyCode::Up) => state.selected.saturating_sub(1),
+            Some(KeyCode::Down) => state.selected + 1,
+            Some(KeyCode::PageUp) => state.selected.saturating_sub(page),
+            Some(KeyCode::PageDown) => state.selected + page,
+            Some(KeyCode::Home) => 0,
+            Some(KeyCode::End) => usize::MAX,
+            _ => return false,
+        };
+        state.select(selected);
+        kb.reset();
+        true
+    }
+
+    /// Draw the list within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let mut state = self.state.borrow_mut();
+        let rows = rect.size.height;
+        state.rows = rows;
+        let ListState { items, search, .. } = &mut *state;
+        let text =
+            |item: &Runes| -> String { item.iter().map(|r| r.content.unwrap_or(' ')).collect() };
+        if let Some(idx) = search.follow(|| items.iter().map(text)) {
+            state.select(idx);
+        }
+        state.scroll_to_selected(rows);
+        let search = state.search.active();
+
+        for (row, item) in state.items.iter().enumerate().skip(state.offset).take(rows) {
+            let pos = (rect.pos.x, rect.pos.y + row - state.offset);
+            let mut runes = Runes::new(item.iter().take(rect.size.width).copied().collect());
+            if row == state.selected {
+                ctx.fill(
+                    (pos, (rect.size.width, 1)),
+                    Rune::new().bg(theme.bg_selection),
+                );
+                runes = runes.fg(theme.fg_selection).bg(theme.bg_selection);
+            }
+            if let Some(search) = &search {
+                runes = search.highlight(runes, &theme);
+            }
+            ctx.insert(pos, runes);
+        }
+    }
+}
+
+impl Callable<()> for List {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{input::Keyboard, search::SearchState};
+
+    use super::List;
+
+    #[test]
+    fn test_scroll_with_selection() {
+        let list = List::new((0..10).map(|i| format!("item {i}")));
+        let draw = |list: &List| {
+            let mut ctx = crate::context::tests::context_fixture();
+            list.draw(&mut ctx, ((0, 0), (10, 3)));
+            let text = ctx.view.render_text().replace('\0', " ");
+            text.lines()
+                .take(3)
+                .map(|l| l.trim_end().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(&list), vec!["item 0", "item 1", "item 2"]);
+
+        for _ in 0..4 {
+            list.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        }
+        assert_eq!(list.selected(), Some(4));
+        assert_eq!(draw(&list), vec!["item 2", "item 3", "item 4"]);
+
+        list.handle_keys(&Keyboard::with_key(KeyCode::PageUp));
+        assert_eq!(list.selected(), Some(1));
+        assert_eq!(draw(&list)[0], "item 1");
+
+        list.handle_keys(&Keyboard::with_key(KeyCode::End));
+        assert_eq!(draw(&list), vec!["item 7", "item 8", "item 9"]);
+        assert_eq!(list.offset(), 7);
+
+        list.set_items(["a", "b"]);
+        assert_eq!(list.selected(), Some(1));
+        assert_eq!(draw(&list), vec!["a", "b", ""]);
+    }
+
+    #[test]
+    fn test_highlight_selection() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let theme = ctx.theme();
+        let list = List::new(["one", "two"]);
+        list.select(1);
+        list.draw(&mut ctx, ((0, 0), (8, 2)));
+        assert_eq!(ctx.view[1][0].bg, Some(theme.bg_selection));
+        assert_eq!(ctx.view[1][7].bg, Some(theme.bg_selection));
+        assert_ne!(ctx.view[0][0].bg, Some(theme.bg_selection));
+        assert!(List::new(Vec::<String>::new()).selected().is_none());
+    }
+
+    #[test]
+    fn test_search() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let theme = ctx.theme();
+        let search = SearchState::new();
+        let list = List::new(["apple", "banana", "cherry", "grape"]).search(&search);
+        list.draw(&mut ctx, ((0, 0), (8, 4)));
+        assert_eq!(list.selected(), Some(0));
+
+        search.set_query("ap");
+        list.draw(&mut ctx, ((0, 0), (8, 4)));
+        assert_eq!(list.selected(), Some(0));
+        assert!(list.handle_keys(&Keyboard::with_key(KeyCode::Char('n'))));
+        list.draw(&mut ctx, ((0, 0), (8, 4)));
+        assert_eq!(list.selected(), Some(3));
+        assert_eq!(ctx.view[3][2].bg, Some(theme.warning));
+        assert_eq!(ctx.view[0][0].bg, Some(theme.warning));
+        assert_ne!(ctx.view[3][0].bg, Some(theme.warning));
+    }
+}
@@ -0,0 +1,212 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    runes::{wrap, Rune, Runes},
+};
+
+/// A single entry in a MessageList. The body can carry its own styling and
+/// an optional timestamp label is shown before it.
+#[derive(Debug, Clone)]
+pub struct Message {
+    body: Runes,
+    timestamp: Option<Runes>,
+}
+
+impl Message {
+    pub fn new<R: Into<Runes>>(body: R) -> Self {
+        Self {
+            body: body.into(),
+            timestamp: None,
+        }
+    }
+
+    /// Set a timestamp label displayed before the message body.
+    pub fn timestamp<R: Into<Runes>>(mut self, timestamp: R) -> Self {
+        self.timestamp = Some(timestamp.into());
+        self
+    }
+}
+
+impl<T: Into<Runes>> From<T> for Message {
+    fn from(value: T) -> Self {
+        Message::new(value)
+    }
+}
+
+#[derive(Debug, Default)]
+struct MessageListState {
+    messages: Vec<Message>,
+    offset: usize,
+    unlocked: bool,
+}
+
+/// MessageList displays a scrolling list of messages, such as a chat
+/// history or a stream of log output. Messages are wrapped to the width of
+/// the component.
+///
+/// The list follows the newest message as messages are added. Scrolling up
+/// stops the list from following, keeping older messages in view, and
+/// scrolling back to the bottom resumes following.
+///
+/// MessageList can be cloned cheaply and shared between threads, clones
+/// refer to the same list.
+///
+/// ```
+/// use arkham::{components::{Message, MessageList}, prelude::*};
+///
+/// fn chat(ctx: &mut ViewContext, messages: Res<MessageList>, kb: Res<Keyboard>) {
+///     if kb.code() == Some(KeyCode::Up) {
+///         messages.scroll_up(1);
+///     }
+///     if kb.code() == Some(KeyCode::Down) {
+///         messages.scroll_down(1);
+///     }
+///     let size = ctx.size();
+///     ctx.component(size, messages.get().clone());
+/// }
+///
+/// let messages = MessageList::new();
+/// messages.push(Message::new("Hello!").timestamp("12:00".to_runes().fg(Color::DarkGrey)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageList {
+    state: Arc<Mutex<MessageListState>>,
+}
+
+impl MessageList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a message to the end of the list.
+    pub fn push<M: Into<Message>>(&self, message: M) {
+        self.state.lock().unwrap().messages.push(message.into());
+    }
+
+    /// Remove all messages and resume following new messages.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.messages.clear();
+        state.offset = 0;
+        state.unlocked = false;
+    }
+
+    /// The number of messages in the list.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().messages.len()
+    }
+
+    /// Returns true if the list contains no messages.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Scroll towards older messages by a number of lines. The list stops
+    /// following new messages.
+    pub fn scroll_up(&self, lines: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.unlocked = true;
+        state.offset = state.offset.saturating_sub(lines);
+    }
+
+    /// Scroll towards newer messages by a number of lines. Reaching the
+    /// bottom of the list resumes following new messages.
+    pub fn scroll_down(&self, lines: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.offset += lines;
+    }
+
+    /// Jump to the newest message and resume following new messages.
+    pub fn scroll_to_bottom(&self) {
+        self.state.lock().unwrap().unlocked = false;
+    }
+
+    /// Returns true if the list is following the newest message.
+    pub fn is_following(&self) -> bool {
+        !self.state.lock().unwrap().unlocked
+    }
+}
+
+impl Callable<()> for MessageList {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        let mut state = self.state.lock().unwrap();
+
+        let mut lines: Vec<Vec<Rune>> = vec![];
+        for message in state.messages.iter() {
+            let indent = message.timestamp.as_ref().map(|ts| ts.len() + 1);
+            let indent = indent.unwrap_or_default();
+            let body = wrap(&message.body, size.width.saturating_sub(indent).max(1));
+            for (idx, line) in body.iter().enumerate() {
+                let mut runes = match (&message.timestamp, idx) {
+                    (Some(ts), 0) => [ts, &[Rune::new().content(' ')][..]].concat(),
+                    _ => vec![Rune::new().content(' '); indent],
+                };
+                runes.extend_from_slice(line);
+                lines.push(runes);
+            }
+        }
+
+        let bottom = lines.len().saturating_sub(size.height);
+        if !state.unlocked || state.offset >= bottom {
+            state.offset = bottom;
+            state.unlocked = false;
+        }
+
+        for (row, line) in lines.into_iter().skip(state.offset).enumerate() {
+            ctx.insert((0, row), Runes::new(line));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Message, MessageList};
+
+    fn render(list: &MessageList) -> Vec<String> {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (8, 2)), list.clone());
+        ctx.view
+            .render_text()
+            .lines()
+            .take(2)
+            .map(|line| line.chars().take(8).filter(|c| *c != '\0').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_follows_newest() {
+        let list = MessageList::new();
+        list.push("one");
+        list.push("two");
+        list.push("three");
+        assert_eq!(render(&list), vec!["two", "three"]);
+        list.push("four");
+        assert_eq!(render(&list), vec!["three", "four"]);
+    }
+
+    #[test]
+    fn test_scroll_up_stops_following() {
+        let list = MessageList::new();
+        list.push("one");
+        list.push("two");
+        list.push("three");
+        render(&list);
+        list.scroll_up(1);
+        list.push("four");
+        assert_eq!(render(&list), vec!["one", "two"]);
+        assert!(!list.is_following());
+        list.scroll_down(5);
+        assert_eq!(render(&list), vec!["three", "four"]);
+        assert!(list.is_following());
+    }
+
+    #[test]
+    fn test_wrap_with_timestamp() {
+        let list = MessageList::new();
+        list.push(Message::new("aaa bbb").timestamp("12"));
+        assert_eq!(render(&list), vec!["12 aaa", "   bbb"]);
+    }
+}
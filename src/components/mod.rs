@@ -0,0 +1,76 @@
+//! Reusable components built on top of the core primitives. Each component
+//! is a configurable structure that can be passed to ViewContext::component.
+mod accordion;
+mod badge;
+mod block;
+mod braille;
+mod canvas;
+mod chart;
+mod checkbox;
+#[cfg(feature = "syntax-highlighting")]
+mod code_block;
+mod file_browser;
+mod floating;
+mod gauge;
+#[cfg(feature = "regex")]
+mod highlighter;
+mod histogram;
+mod input_format;
+mod keycap;
+mod layout;
+mod list;
+mod message_list;
+mod multi_select;
+mod outline;
+mod pager;
+mod plot;
+mod progress;
+mod radio_group;
+mod scrollbar;
+mod segment_display;
+mod sparkline;
+mod spinner;
+mod split;
+#[cfg(feature = "system-stats")]
+mod system_stats;
+mod table;
+#[cfg(feature = "rope")]
+mod text_buffer;
+mod text_input;
+
+pub use accordion::{Accordion, AccordionSection};
+pub use badge::{badge, Badge, Severity};
+pub use block::{Block, BorderStyle, TitleAlignment};
+pub use canvas::Canvas;
+pub use chart::{Chart, ChartSeries};
+pub use checkbox::Checkbox;
+#[cfg(feature = "syntax-highlighting")]
+pub use code_block::CodeBlock;
+pub use file_browser::FileBrowser;
+pub use floating::{FloatingWindow, FloatingWindows, WindowMode};
+pub use gauge::Gauge;
+#[cfg(feature = "regex")]
+pub use highlighter::Highlighter;
+pub use histogram::{bin, Bin, Histogram};
+pub use input_format::{InputFormatter, Mask, MaxLength, Numeric};
+pub use keycap::{keycaps, KeyHints};
+pub use layout::{Layout, LayoutPreset, Pane};
+pub use list::List;
+pub use message_list::{Message, MessageList};
+pub use multi_select::MultiSelect;
+pub use outline::{Outline, OutlineNode};
+pub use pager::Pager;
+pub use plot::{Plot, Series};
+pub use progress::{MultiProgress, ProgressTask, TaskStatus};
+pub use radio_group::RadioGroup;
+pub use scrollbar::Scrollbar;
+pub use segment_display::SegmentDisplay;
+pub use sparkline::Sparkline;
+pub use spinner::{Spinner, SpinnerStyle};
+pub use split::{Split, SplitDirection};
+#[cfg(feature = "system-stats")]
+pub use system_stats::{CpuBars, MemoryGauge, NetworkSparkline, SystemSample, SystemStats};
+pub use table::{ColumnWidth, SortOrder, Table};
+#[cfg(feature = "rope")]
+pub use text_buffer::TextBuffer;
+pub use text_input::TextInput;
@@ -0,0 +1,293 @@
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::Callable, context::ViewContext, geometry::Rect, input::Keyboard, runes::Runes,
+};
+
+use super::TextInput;
+
+#[derive(Debug, Default)]
+struct MultiSelectState {
+    items: Vec<String>,
+    selected: BTreeSet<usize>,
+    cursor: usize,
+    offset: usize,
+}
+
+/// MultiSelect displays a list of items with a checkbox beside each one,
+/// along with a filter line used to search the list.
+///
+/// MultiSelect::handle_keys provides the key bindings: Up and Down move the
+/// cursor, Tab toggles the item under the cursor, Ctrl+A selects every item
+/// matching the filter, and Ctrl+D deselects them. Any other key, including
+/// Space, is passed to the filter line.
+///
+/// As with TextInput, App::disbale_q_to_quit is needed for `q` to be typed
+/// into the filter.
+///
+/// MultiSelect is meant to be inserted as a resource, or stored in state,
+/// and drawn with ViewContext::component.
+///
+/// ```no_run
+/// use arkham::{components::MultiSelect, prelude::*};
+///
+/// fn main() {
+///     let features = MultiSelect::new(["docs", "examples", "completions", "man pages"]);
+///     App::new(root)
+///         .disbale_q_to_quit()
+///         .insert_resource(features)
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, features: Res<MultiSelect>, kb: Res<Keyboard>) {
+///     if kb.code() == Some(KeyCode::Esc) {
+///         ctx.exit();
+///     }
+///     if kb.code() == Some(KeyCode::Enter) {
+///         let chosen = features.selected();
+///         ctx.insert((0, 11), format!("Installing {}", chosen.join(", ")));
+///     }
+///     features.handle_keys(&kb);
+///     ctx.component(((0, 0), (30, 10)), features.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiSelect {
+    state: Rc<RefCell<MultiSelectState>>,
+    filter: TextInput,
+}
+
+impl MultiSelect {
+    pub fn new<I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        Self {
+            state: Rc::new(RefCell::new(MultiSelectState {
+                items: items.into_iter().map(|i| i.to_string()).collect(),
+                ..MultiSelectState::default()
+            })),
+            filter: TextInput::new().placeholder("Type to filter"),
+        }
+    }
+
+    /// The selected items, in the order they appear in the list.
+    pub fn selected(&self) -> Vec<String> {
+        let state = self.state.borrow();
+        state
+            .selected
+            .iter()
+            .map(|&idx| state.items[idx].clone())
+            .collect()
+    }
+
+    /// The indexes of the selected items.
+    pub fn selected_indexes(&self) -> BTreeSet<usize> {
+        self.state.borrow().selected.clone()
+    }
+
+    /// Select or deselect the item at an index.
+    pub fn set_selected(&self, idx: usize, selected: bool) {
+        let mut state = self.state.borrow_mut();
+        if idx >= state.items.len() {
+            return;
+        }
+        if selected {
+            state.selected.insert(idx);
+        } else {
+            state.selected.remove(&idx);
+        }
+    }
+
+    /// The current text of the filter line.
+    pub fn filter(&self) -> String {
+        self.filter.value()
+    }
+
+    /// Replace the text of the filter line.
+    pub fn set_filter<S: AsRef<str>>(&self, filter: S) {
+        self.filter.set_value(filter);
+        self.state.borrow_mut().cursor = 0;
+    }
+
+    /// The indexes of the items that match the filter. Items match if they
+    /// contain the filter text, ignoring case.
+    pub fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.value().to_lowercase();
+        let state = self.state.borrow();
+        (0..state.items.len())
+            .filter(|&idx| state.items[idx].to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    /// The index of the item under the cursor.
+    pub fn current(&self) -> Option<usize> {
+        self.visible().get(self.state.borrow().cursor).copied()
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let visible = self.visible();
+        let handled = {
+            let mut state = self.state.borrow_mut();
+            match kb.code() {
+                Some(KeyCode::Up) => {
+                    state.cursor = state.cursor.saturating_sub(1);
+                    true
+                }
+                Some(KeyCode::Down) => {
+                    state.cursor = (state.cursor + 1).min(visible.len().saturating_sub(1));
+                    true
+                }
+                Some(KeyCode::Tab) => {
+                    if let Some(&idx) = visible.get(state.cursor) {
+                        if !state.selected.remove(&idx) {
+                            state.selected.insert(idx);
+                        }
+                    }
+                    true
+                }
+                Some(KeyCode::Char('a')) if kb.control() => {
+                    state.selected.extend(visible.iter().copied());
+                    true
+                }
+                Some(KeyCode::Char('d')) if kb.control() => {
+                    state.selected.retain(|idx| !visible.contains(idx));
+                    true
+                }
+                _ => false,
+            }
+        };
+        if handled {
+            kb.reset();
+            return true;
+        }
+
+        let previous = self.filter.value();
+        if !self.filter.handle_keys(kb) {
+            return false;
+        }
+        if self.filter.value() != previous {
+            self.state.borrow_mut().cursor = 0;
+        }
+        true
+    }
+
+    /// Draw the filter line and list within a region of a context. The list
+    /// is scrolled so that the cursor remains visible.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let visible = self.visible();
+
+        ctx.insert(rect.pos, Runes::from("/ ").fg(theme.muted));
+        let mut filter_rect = rect;
+        filter_rect.pos.x += 2;
+        filter_rect.size.width = rect.size.width.saturating_sub(2);
+        filter_rect.size.height = 1;
+        self.filter.draw(ctx, filter_rect);
+
+        let rows = rect.size.height - 1;
+        let mut state = self.state.borrow_mut();
+        state.cursor = state.cursor.min(visible.len().saturating_sub(1));
+        if state.cursor < state.offset {
+            state.offset = state.cursor;
+        } else if rows > 0 && state.cursor >= state.offset + rows {
+            state.offset = state.cursor + 1 - rows;
+        }
+
+        for (row, &idx) in visible.iter().skip(state.offset).take(rows).enumerate() {
+            let is_cursor = row + state.offset == state.cursor;
+            let check = if state.selected.contains(&idx) {
+                symbols.checkbox_on
+            } else {
+                symbols.checkbox_off
+            };
+            let pointer = if is_cursor { symbols.pointer } else { ' ' };
+            let line = format!("{} {} {}", pointer, check, state.items[idx]);
+            let mut runes = Runes::from(line);
+            if is_cursor {
+                runes = runes.fg(theme.fg_selection).bg(theme.bg_selection);
+            }
+            let pos = (rect.pos.x, rect.pos.y + row + 1);
+            ctx.insert(pos, runes);
+        }
+    }
+}
+
+impl Callable<()> for MultiSelect {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::input::Keyboard;
+
+    use super::MultiSelect;
+
+    fn press(select: &MultiSelect, code: KeyCode, modifiers: KeyModifiers) {
+        select.handle_keys(&Keyboard::with_key(code).with_modifiers(modifiers));
+    }
+
+    #[test]
+    fn test_toggle_and_filter() {
+        let select = MultiSelect::new(["alpha", "beta", "gamma", "delta"]);
+        press(&select, KeyCode::Down, KeyModifiers::NONE);
+        press(&select, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(select.selected(), vec!["beta"]);
+
+        for c in "ta".chars() {
+            press(&select, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(select.visible(), vec![1, 3]);
+        assert_eq!(select.current(), Some(1));
+        press(&select, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(select.selected(), vec!["beta", "delta"]);
+
+        press(&select, KeyCode::Backspace, KeyModifiers::NONE);
+        press(&select, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(select.visible().len(), 4);
+        press(&select, KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert!(select.selected().is_empty());
+    }
+
+    #[test]
+    fn test_filter_with_spaces() {
+        let select = MultiSelect::new(["man pages", "examples"]);
+        for c in "n p".chars() {
+            press(&select, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(select.filter(), "n p");
+        assert_eq!(select.visible(), vec![0]);
+        assert!(select.selected().is_empty());
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let select = MultiSelect::new(["one", "two", "three"]);
+        select.set_selected(1, true);
+        press(&select, KeyCode::Down, KeyModifiers::NONE);
+        press(&select, KeyCode::Down, KeyModifiers::NONE);
+        select.draw(&mut ctx, ((0, 0), (12, 3)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(3).collect();
+        assert_eq!(lines[1].trim_end(), "  ☒ two");
+        assert_eq!(lines[2].trim_end(), "❯ ☐ three");
+    }
+}
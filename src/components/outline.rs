@@ -0,0 +1,356 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::Callable, context::ViewContext, geometry::Rect, input::Keyboard, runes::Runes,
+};
+
+/// A row of an Outline, which may contain nested rows.
+#[derive(Debug, Clone, Default)]
+pub struct OutlineNode {
+    label: String,
+    cells: Vec<String>,
+    children: Vec<OutlineNode>,
+}
+
+impl OutlineNode {
+    pub fn new<S: ToString>(label: S) -> Self {
+        Self {
+            label: label.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the values displayed in the columns following the label.
+    pub fn cells<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.cells = cells.into_iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Add a nested row.
+    pub fn child(mut self, child: OutlineNode) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+/// A visible row of the outline.
+struct Row<'a> {
+    path: Vec<usize>,
+    depth: usize,
+    node: &'a OutlineNode,
+}
+
+#[derive(Debug, Default)]
+struct OutlineState {
+    headers: Vec<String>,
+    roots: Vec<OutlineNode>,
+    expanded: HashSet<Vec<usize>>,
+    selected: usize,
+    offset: usize,
+}
+
+impl OutlineState {
+    /// The rows that are visible given the expanded nodes, in display order.
+    fn rows(&self) -> Vec<Row<'_>> {
+        fn visit<'a>(
+            state: &OutlineState,
+            nodes: &'a [OutlineNode],
+            path: &mut Vec<usize>,
+            rows: &mut Vec<Row<'a>>,
+        ) {
+            for (idx, node) in nodes.iter().enumerate() {
+                path.push(idx);
+                rows.push(Row {
+                    path: path.clone(),
+                    depth: path.len() - 1,
+                    node,
+                });
+                if state.expanded.contains(path) {
+                    visit(state, &node.children, path, rows);
+                }
+                path.pop();
+            }
+        }
+        let mut rows = vec![];
+        visit(self, &self.roots, &mut vec![], &mut rows);
+        rows
+    }
+}
+
+/// Outline displays a tree of rows with aligned columns beside it, such as a
+/// process list with CPU and memory usage for each process and its children.
+///
+/// Outline::handle_keys provides the key bindings: Up and Down move the
+/// selection, Right expands the selected row, Left collapses it or selects
+/// its parent, and Enter or Space toggles it.
+///
+/// ```no_run
+/// use arkham::{components::{Outline, OutlineNode}, prelude::*};
+///
+/// fn main() {
+///     let outline = Outline::new(["Process", "CPU", "MEM"]).node(
+///         OutlineNode::new("init")
+///             .cells(["0.1", "12M"])
+///             .child(OutlineNode::new("sshd").cells(["0.0", "8M"]))
+///             .child(OutlineNode::new("cron").cells(["0.0", "3M"])),
+///     );
+///     App::new(root).insert_resource(outline).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, outline: Res<Outline>, kb: Res<Keyboard>) {
+///     outline.handle_keys(&kb);
+///     let size = ctx.size();
+///     ctx.component(size, outline.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Outline {
+    state: Rc<RefCell<OutlineState>>,
+}
+
+impl Outline {
+    /// Create an outline with a header for the tree column followed by a
+    /// header for each additional column.
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let outline = Self::default();
+        outline.state.borrow_mut().headers = headers.into_iter().map(|h| h.to_string()).collect();
+        outline
+    }
+
+    /// Add a top level row.
+    pub fn node(self, node: OutlineNode) -> Self {
+        self.state.borrow_mut().roots.push(node);
+        self
+    }
+
+    /// Replace the rows of the outline, such as when refreshing data.
+    /// Expanded rows remain expanded if their position in the tree is
+    /// unchanged.
+    pub fn set_nodes(&self, nodes: Vec<OutlineNode>) {
+        let mut state = self.state.borrow_mut();
+        state.roots = nodes;
+        let count = state.rows().len();
+        state.selected = state.selected.min(count.saturating_sub(1));
+    }
+
+    /// Expand or collapse the row at a path of child indexes.
+    pub fn set_expanded(&self, path: &[usize], expanded: bool) {
+        let mut state = self.state.borrow_mut();
+        if expanded {
+            state.expanded.insert(path.to_vec());
+        } else {
+            state.expanded.remove(path);
+        }
+    }
+
+    /// Returns true if the row at a path of child indexes is expanded.
+    pub fn is_expanded(&self, path: &[usize]) -> bool {
+        self.state.borrow().expanded.contains(path)
+    }
+
+    /// The path of child indexes leading to the selected row.
+    pub fn selected(&self) -> Option<Vec<usize>> {
+        let state = self.state.borrow();
+        let rows = state.rows();
+        rows.get(state.selected).map(|row| row.path.clone())
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let mut state = self.state.borrow_mut();
+        let (count, path, has_children) = {
+            let rows = state.rows();
+            let row = rows.get(state.selected);
+            (
+                rows.len(),
+                row.map(|r| r.path.clone()).unwrap_or_default(),
+                row.is_some_and(|r| !r.node.children.is_empty()),
+            )
+        };
+        let expanded = state.expanded.contains(&path);
+        match kb.code() {
+            Some(KeyCode::Up) => state.selected = state.selected.saturating_sub(1),
+            Some(KeyCode::Down) => {
+                state.selected = (state.selected + 1).min(count.saturating_sub(1))
+            }
+            Some(KeyCode::Right) if has_children => {
+                state.expanded.insert(path);
+            }
+            Some(KeyCode::Left) if expanded => {
+                state.expanded.remove(&path);
+            }
+            Some(KeyCode::Left) if path.len() > 1 => {
+                let parent = &path[..path.len() - 1];
+                if let Some(idx) = state.rows().iter().position(|r| r.path == parent) {
+                    state.selected = idx;
+                }
+            }
+            Some(KeyCode::Enter) | Some(KeyCode::Char(' ')) if has_children => {
+                if !state.expanded.remove(&path) {
+                    state.expanded.insert(path);
+                }
+            }
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the outline within a region of a context. The header remains
+    /// fixed while the rows scroll to keep the selection visible.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let mut state = self.state.borrow_mut();
+
+        let visible_rows = rect.size.height - 1;
+        if state.selected < state.offset {
+            state.offset = state.selected;
+        } else if visible_rows > 0 && state.selected >= state.offset + visible_rows {
+            state.offset = state.selected + 1 - visible_rows;
+        }
+
+        let rows = state.rows();
+        let labels: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let marker = match (
+                    row.node.children.is_empty(),
+                    state.expanded.contains(&row.path),
+                ) {
+                    (true, _) => ' ',
+                    (false, true) => symbols.arrow_down,
+                    (false, false) => symbols.arrow_right,
+                };
+                format!("{}{} {}", "  ".repeat(row.depth), marker, row.node.label)
+            })
+            .collect();
+
+        // The tree column is as wide as its widest row, the remaining columns
+        // are as wide as their header or widest cell.
+        let mut widths = vec![labels
+            .iter()
+            .map(|l| l.chars().count())
+            .chain(state.headers.first().map(|h| h.chars().count()))
+            .max()
+            .unwrap_or_default()];
+        for idx in 1..state.headers.len() {
+            widths.push(
+                rows.iter()
+                    .filter_map(|row| row.node.cells.get(idx - 1))
+                    .map(|cell| cell.chars().count())
+                    .fold(state.headers[idx].chars().count(), usize::max),
+            );
+        }
+
+        let line = |cells: Vec<&str>| -> String {
+            let mut out = String::new();
+            for (idx, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+                if idx == 0 {
+                    out.push_str(&format!("{:<width$}", cell, width = width));
+                } else {
+                    out.push_str(&format!("  {:>width$}", cell, width = width));
+                }
+            }
+            out.chars().take(rect.size.width).collect()
+        };
+
+        let header = line(state.headers.iter().map(String::as_str).collect());
+        ctx.insert(rect.pos, Runes::from(header).fg(theme.accent).bold());
+
+        for (idx, row) in rows
+            .iter()
+            .enumerate()
+            .skip(state.offset)
+            .take(visible_rows)
+        {
+            let mut cells = vec![labels[idx].as_str()];
+            cells.extend(row.node.cells.iter().map(String::as_str));
+            let text = format!("{:<width$}", line(cells), width = rect.size.width);
+            let mut runes = Runes::from(text);
+            if idx == state.selected {
+                runes = runes.fg(theme.fg_selection).bg(theme.bg_selection);
+            }
+            ctx.insert((rect.pos.x, rect.pos.y + idx - state.offset + 1), runes);
+        }
+    }
+}
+
+impl Callable<()> for Outline {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::input::Keyboard;
+
+    use super::{Outline, OutlineNode};
+
+    fn outline() -> Outline {
+        Outline::new(["Name", "CPU"])
+            .node(
+                OutlineNode::new("init")
+                    .cells(["1.5"])
+                    .child(OutlineNode::new("sshd").cells(["0.2"]))
+                    .child(OutlineNode::new("cron").cells(["12.0"])),
+            )
+            .node(OutlineNode::new("kthreadd").cells(["0.0"]))
+    }
+
+    fn press(outline: &Outline, code: KeyCode) {
+        outline.handle_keys(&Keyboard::with_key(code));
+    }
+
+    #[test]
+    fn test_navigation() {
+        let outline = outline();
+        press(&outline, KeyCode::Down);
+        assert_eq!(outline.selected(), Some(vec![1]));
+        press(&outline, KeyCode::Up);
+        press(&outline, KeyCode::Right);
+        assert!(outline.is_expanded(&[0]));
+        press(&outline, KeyCode::Down);
+        press(&outline, KeyCode::Down);
+        assert_eq!(outline.selected(), Some(vec![0, 1]));
+        press(&outline, KeyCode::Left);
+        assert_eq!(outline.selected(), Some(vec![0]));
+        press(&outline, KeyCode::Left);
+        assert!(!outline.is_expanded(&[0]));
+    }
+
+    #[test]
+    fn test_draw_columns() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let outline = outline();
+        outline.set_expanded(&[0], true);
+        outline.draw(&mut ctx, ((0, 0), (20, 5)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(5).collect();
+        assert_eq!(lines[0].trim_end(), "Name         CPU");
+        assert_eq!(lines[1].trim_end(), "↓ init       1.5");
+        assert_eq!(lines[2].trim_end(), "    sshd     0.2");
+        assert_eq!(lines[3].trim_end(), "    cron    12.0");
+        assert_eq!(lines[4].trim_end(), "  kthreadd   0.0");
+    }
+}
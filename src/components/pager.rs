@@ -0,0 +1,364 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{wrap, Runes},
+    search::{SearchFollower, SearchState},
+};
+
+#[derive(Debug, Default)]
+struct PagerState {
+    text: String,
+    /// The byte offset each line of the text starts at.
+    lines: Vec<usize>,
+    /// The width lines are wrapped to. Changing it discards the wrapped
+    /// lines.
+    width: usize,
+    /// The wrapped rows of the lines near the viewport, by line index.
+    wrapped: HashMap<usize, Rc<Vec<Runes>>>,
+    /// The line at the top of the viewport, and the row within it.
+    top: (usize, usize),
+    /// The number of rows the pager was last drawn with, used to move by a
+    /// page.
+    rows: usize,
+    search: SearchFollower,
+    /// Whether URLs and file paths are made links as lines are wrapped.
+    detect_links: bool,
+}
+
+impl PagerState {
+    fn set_text(&mut self, text: String) {
+        self.lines = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        self.text = text;
+        self.wrapped.clear();
+        self.top = (0, 0);
+        self.search.invalidate();
+    }
+
+    /// The text of a line, without its line ending.
+    fn line(&self, idx: usize) -> &str {
+        let start = self.lines[idx];
+        let end = self
+            .lines
+            .get(idx + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        self.text[start..end].trim_end_matches('\r')
+    }
+
+    /// The rows of a line wrapped to the current width, wrapping it if it
+    /// has not been wrapped yet. Empty lines take a single row.
+    fn rows_of(&mut self, idx: usize) -> Rc<Vec<Runes>> {
+        if let Some(rows) = self.wrapped.get(&idx) {
+            return rows.clone();
+        }
+        let mut runes = Runes::from(self.line(idx));
+        if self.detect_links {
+            runes = runes.detect_links();
+        }
+        let mut rows: Vec<Runes> = wrap(&runes, self.width.max(1))
+            .into_iter()
+            .map(|row| Runes::new(row.to_vec()))
+            .collect();
+        if rows.is_empty() {
+            rows.push(Runes::default());
+        }
+        let rows = Rc::new(rows);
+        self.wrapped.insert(idx, rows.clone());
+        rows
+    }
+
+    /// Move the top of the viewport by a number of rows, stopping at the
+    /// first and last rows of the document. Only the lines passed over are
+    /// wrapped.
+    fn scroll(&mut self, delta: isize) {
+        let (mut line, mut row) = self.top;
+        if delta > 0 {
+            for _ in 0..delta {
+                if row + 1 < self.rows_of(line).len() {
+                    row += 1;
+                } else if line + 1 < self.lines.len() {
+                    line += 1;
+                    row = 0;
+                } else {
+                    break;
+                }
+            }
+        } else {
+            for _ in 0..delta.unsigned_abs() {
+                if row > 0 {
+                    row -= 1;
+                } else if line > 0 {
+                    line -= 1;
+                    row = self.rows_of(line).len() - 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        self.top = (line, row);
+    }
+
+    /// Discard wrapped lines far from the viewport, so that memory use does
+    /// not grow as a large document is scrolled through.
+    fn evict(&mut self) {
+        let margin = self.rows.max(1) * 2;
+        let first = self.top.0.saturating_sub(margin);
+        let last = self.top.0 + self.rows + margin;
+        self.wrapped.retain(|idx, _| (first..=last).contains(idx));
+    }
+}
+
+/// Pager displays a long document, wrapping its lines to the width it is
+/// drawn with and scrolling through it by rows.
+///
+/// Lines are only wrapped as they are displayed or scrolled past, and lines
+/// far from the viewport are discarded, so documents of many megabytes can
+/// be displayed without wrapping the whole document every frame. Changing
+/// the width the pager is drawn with discards the wrapped lines, keeping the
+/// top line of the viewport in place.
+///
+/// Pager::handle_keys provides the key bindings: Up and Down scroll by a
+/// row, Page Up and Page Down scroll by a page, and Home and End scroll to
+/// the start and end of the document. See Pager::search for searching the
+/// document.
+///
+/// ```no_run
+/// use arkham::{components::Pager, prelude::*};
+///
+/// fn main() {
+///     let text = std::fs::read_to_string("server.log").unwrap();
+///     App::new(root).insert_resource(Pager::new(text)).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, pager: Res<Pager>, kb: Res<Keyboard>) {
+///     pager.handle_keys(&kb);
+///     ctx.component(ctx.size(), pager.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Pager {
+    state: Rc<RefCell<PagerState>>,
+}
+
+impl Pager {
+    pub fn new<S: ToString>(text: S) -> Self {
+        let pager = Self::default();
+        pager.set_text(text);
+        pager
+    }
+
+    /// Search the document with a shared SearchState. Matches are
+    /// highlighted, and the pager scrolls to bring the current match to the
+    /// top of the viewport as it changes. Pager::handle_keys also applies
+    /// the search key bindings.
+    pub fn search(self, search: &SearchState) -> Self {
+        self.state.borrow_mut().search.search = Some(search.clone());
+        self
+    }
+
+    /// Make the URLs and file paths in the document links, so that they can
+    /// be followed with the LinkHints plugin. See Runes::detect_links.
+    pub fn detect_links(self) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.detect_links = true;
+            state.wrapped.clear();
+        }
+        self
+    }
+
+    /// Replace the document, scrolling back to the start.
+    pub fn set_text<S: ToString>(&self, text: S) {
+        self.state.borrow_mut().set_text(text.to_string());
+    }
+
+    /// The number of lines in the document, before wrapping.
+    pub fn line_count(&self) -> usize {
+        self.state.borrow().lines.len()
+    }
+
+    /// The index of the line at the top of the viewport.
+    pub fn top_line(&self) -> usize {
+        self.state.borrow().top.0
+    }
+
+    /// Scroll so that a line is at the top of the viewport.
+    pub fn scroll_to_line(&self, line: usize) {
+        let mut state = self.state.borrow_mut();
+        state.top = (line.min(state.lines.len() - 1), 0);
+    }
+
+    /// Scroll by a number of rows.
+    pub fn scroll_by(&self, rows: isize) {
+        self.state.borrow_mut().scroll(rows);
+    }
+
+    /// Scroll so that the end of the document is at the bottom of the
+    /// viewport.
+    pub fn scroll_to_end(&self) {
+        let mut state = self.state.borrow_mut();
+        let last = state.lines.len() - 1;
+        state.top = (last, state.rows_of(last).len() - 1);
+        let rows = state.rows as isize;
+        state.scroll(1 - rows.max(1));
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let search = self.state.borrow().search.search.clone();
+        if search.is_some_and(|search| search.handle_keys(kb)) {
+            return true;
+        }
+        let page = self.state.borrow().rows.max(1) as isize;
+        match kb.code() {
+            Some(KeyCode::Up) => self.scroll_by(-1),
+            Some(KeyCode::Down) => self.scroll_by(1),
+            Some(KeyCode::PageUp) => self.scroll_by(-page),
+            Some(KeyCode::PageDown) => self.scroll_by(page),
+            Some(KeyCode::Home) => self.scroll_to_line(0),
+            Some(KeyCode::End) => self.scroll_to_end(),
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the visible part of the document within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let mut state = self.state.borrow_mut();
+        if state.width != rect.size.width {
+            state.width = rect.size.width;
+            state.wrapped.clear();
+            state.top.1 = 0;
+        }
+        state.rows = rect.size.height;
+        let mut search = std::mem::take(&mut state.search);
+        if let Some(line) = search.follow(|| (0..state.lines.len()).map(|idx| state.line(idx))) {
+            state.top = (line, 0);
+        }
+        state.search = search;
+
+        let theme = ctx.theme();
+        let search = state.search.active();
+        let (mut line, mut row) = state.top;
+        let mut y = 0;
+        while y < rect.size.height && line < state.lines.len() {
+            let rows = state.rows_of(line);
+            for runes in rows.iter().skip(row).take(rect.size.height - y) {
+                let runes = match &search {
+                    Some(search) => search.highlight(runes.clone(), &theme),
+                    None => runes.clone(),
+                };
+                ctx.insert((rect.pos.x, rect.pos.y + y), runes);
+                y += 1;
+            }
+            line += 1;
+            row = 0;
+        }
+        state.evict();
+    }
+}
+
+impl Callable<()> for Pager {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{input::Keyboard, search::SearchState, theme::Theme};
+
+    use super::Pager;
+
+    fn draw(pager: &Pager, width: usize) -> Vec<String> {
+        let mut ctx = crate::context::tests::context_fixture();
+        pager.draw(&mut ctx, ((0, 0), (width, 3)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        text.lines()
+            .take(3)
+            .map(|l| l.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_wraps_visible_lines() {
+        let text: Vec<String> = (0..10_000).map(|i| format!("line {} of text", i)).collect();
+        let pager = Pager::new(text.join("\n"));
+        assert_eq!(draw(&pager, 8), vec!["line 0", "of text", "line 1"]);
+        assert!(pager.state.borrow().wrapped.len() < 10);
+
+        pager.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        assert_eq!(draw(&pager, 8), vec!["of text", "line 1", "of text"]);
+
+        // Changing the width rewraps from the top line.
+        assert_eq!(
+            draw(&pager, 20),
+            vec!["line 0 of text", "line 1 of text", "line 2 of text"]
+        );
+
+        pager.scroll_to_line(5000);
+        draw(&pager, 20);
+        assert!(pager.state.borrow().wrapped.len() < 20);
+        pager.handle_keys(&Keyboard::with_key(KeyCode::End));
+        assert_eq!(
+            draw(&pager, 20),
+            vec![
+                "line 9997 of text",
+                "line 9998 of text",
+                "line 9999 of text"
+            ]
+        );
+        pager.handle_keys(&Keyboard::with_key(KeyCode::PageUp));
+        assert_eq!(pager.top_line(), 9994);
+    }
+
+    #[test]
+    fn test_detect_links() {
+        let pager = Pager::new("see\nhttps://docs.rs/arkham now").detect_links();
+        let mut ctx = crate::context::tests::context_fixture();
+        pager.draw(&mut ctx, ((0, 0), (10, 3)));
+        // Links keep their URL when wrapped across rows.
+        assert_eq!(ctx.view[0][0].link, None);
+        assert_eq!(
+            ctx.view[2][0].url().as_deref(),
+            Some("https://docs.rs/arkham")
+        );
+    }
+
+    #[test]
+    fn test_empty_lines() {
+        let pager = Pager::new("a\n\nb");
+        assert_eq!(draw(&pager, 5), vec!["a", "", "b"]);
+        assert_eq!(pager.line_count(), 3);
+    }
+
+    #[test]
+    fn test_search() {
+        let search = SearchState::new();
+        search.set_query("beta");
+        let pager = Pager::new("alpha\nbeta\ngamma\ndelta\nbeta two").search(&search);
+        let mut ctx = crate::context::tests::context_fixture();
+        pager.draw(&mut ctx, ((0, 0), (10, 3)));
+        assert_eq!(pager.top_line(), 1);
+        assert_eq!(search.match_count(), 2);
+        assert_eq!(ctx.view[0][0].bg, Some(Theme::default().warning));
+
+        assert!(pager.handle_keys(&Keyboard::with_key(KeyCode::Char('n'))));
+        assert_eq!(draw(&pager, 10), vec!["beta two", "", ""]);
+        assert_eq!(pager.top_line(), 4);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    prelude::Color,
+    runes::{Rune, Runes},
+};
+
+use super::braille::BrailleGrid;
+
+/// A named set of (x, y) points drawn by a Plot.
+#[derive(Debug, Clone)]
+pub struct Series {
+    name: String,
+    points: Vec<(f64, f64)>,
+    color: Option<Color>,
+    marker: Option<char>,
+}
+
+impl Series {
+    pub fn new<S: ToString>(name: S, points: Vec<(f64, f64)>) -> Self {
+        Self {
+            name: name.to_string(),
+            points,
+            color: None,
+            marker: None,
+        }
+    }
+
+    /// Set the color of the series. By default series are colored using the
+    /// theme's accent and status colors in turn.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw a character at each data point in addition to the line
+    /// connecting them.
+    pub fn marker(mut self, marker: char) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+}
+
+/// Plot renders one or more series as lines on a braille canvas, giving a
+/// resolution of two by four points per cell. Axes are labeled with the
+/// range of the data and scale automatically unless bounds are given.
+///
+/// When enabled, a legend listing each series is drawn along the top row.
+///
+/// ```
+/// use arkham::{components::{Plot, Series}, prelude::*};
+///
+/// fn dashboard(ctx: &mut ViewContext) {
+///     let cpu = (0..60).map(|i| (i as f64, (i as f64 / 5.0).sin() * 50.0 + 50.0));
+///     let size = ctx.size();
+///     ctx.component(
+///         size,
+///         Plot::new()
+///             .series(Series::new("cpu", cpu.collect()).color(Color::Green))
+///             .y_bounds(0.0, 100.0)
+///             .legend(),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Plot {
+    series: Vec<Series>,
+    x_bounds: Option<(f64, f64)>,
+    y_bounds: Option<(f64, f64)>,
+    legend: bool,
+    precision: usize,
+}
+
+impl Plot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a series to the plot.
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Fix the range of the x axis instead of scaling it to the data.
+    pub fn x_bounds(mut self, min: f64, max: f64) -> Self {
+        self.x_bounds = Some((min, max));
+        self
+    }
+
+    /// Fix the range of the y axis instead of scaling it to the data.
+    pub fn y_bounds(mut self, min: f64, max: f64) -> Self {
+        self.y_bounds = Some((min, max));
+        self
+    }
+
+    /// Display a legend listing each series along the top of the plot.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Set the number of decimal places shown in the axis labels.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// The range covered by one component of the plotted points.
+    fn bounds(&self, f: impl Fn(&(f64, f64)) -> f64) -> (f64, f64) {
+        let (min, max) = self
+            .series
+            .iter()
+            .flat_map(|s| s.points.iter().map(&f))
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+        if min > max {
+            (0.0, 1.0)
+        } else if min == max {
+            (min - 1.0, max + 1.0)
+        } else {
+            (min, max)
+        }
+    }
+}
+
+impl Callable<()> for Plot {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let size = ctx.size();
+        let palette = [
+            theme.accent,
+            theme.success,
+            theme.warning,
+            theme.error,
+            theme.info,
+        ];
+        let color = |idx: usize| self.series[idx].color.unwrap_or(palette[idx % 5]);
+
+        let top = usize::from(self.legend);
+        if size.height < top + 3 {
+            return;
+        }
+        if self.legend {
+            let mut legend = Runes::default();
+            for (idx, series) in self.series.iter().enumerate() {
+                let marker = series.marker.unwrap_or(ctx.symbols().bullet);
+                legend.add(Runes::from(marker).fg(color(idx)));
+                legend.add(format!(" {}  ", series.name));
+            }
+            let x = size.width.saturating_sub(legend.len().saturating_sub(2));
+            ctx.insert((x, 0), legend);
+        }
+
+        let p = self.precision;
+        let (x_min, x_max) = self.x_bounds.unwrap_or_else(|| self.bounds(|p| p.0));
+        let (y_min, y_max) = self.y_bounds.unwrap_or_else(|| self.bounds(|p| p.1));
+        let (y_top, y_bottom) = (format!("{:.p$}", y_max), format!("{:.p$}", y_min));
+        let label_width = y_top.chars().count().max(y_bottom.chars().count());
+        if size.width < label_width + 2 {
+            return;
+        }
+        let axis_row = size.height - 2;
+        let muted = Rune::new().fg(theme.muted);
+
+        ctx.insert((0, top), format!("{:>label_width$}", y_top));
+        ctx.insert((0, axis_row - 1), format!("{:>label_width$}", y_bottom));
+        ctx.fill(
+            ((label_width, top), (1, axis_row - top)),
+            muted.content('│'),
+        );
+        ctx.fill(
+            ((label_width, axis_row), (size.width - label_width, 1)),
+            muted.content('─'),
+        );
+        ctx.set_rune((label_width, axis_row), muted.content('└'));
+        let x_right = format!("{:.p$}", x_max);
+        ctx.insert((label_width, axis_row + 1), format!("{:.p$}", x_min));
+        ctx.insert(
+            (size.width.saturating_sub(x_right.len()), axis_row + 1),
+            x_right,
+        );
+
+        let (left, width, height) = (
+            label_width + 1,
+            size.width - label_width - 1,
+            axis_row - top,
+        );
+        let mut grid = BrailleGrid::new(width, height);
+        let (dot_width, dot_height) = (grid.dot_width(), grid.dot_height());
+        // Points outside of the bounds are kept, so lines to them are drawn
+        // up to the edge of the plot, but are clamped well short of the
+        // limits of i64. Points which are not finite break the line.
+        let limit = (i32::MAX / 2) as f64;
+        let to_dot = |(x, y): &(f64, f64)| {
+            if !x.is_finite() || !y.is_finite() {
+                return None;
+            }
+            let dx = (x - x_min) / (x_max - x_min) * (dot_width - 1) as f64;
+            let dy = (1.0 - (y - y_min) / (y_max - y_min)) * (dot_height - 1) as f64;
+            Some((
+                dx.round().clamp(-limit, limit) as i64,
+                dy.round().clamp(-limit, limit) as i64,
+            ))
+        };
+        for (idx, series) in self.series.iter().enumerate() {
+            let points: Vec<Option<(i64, i64)>> = series.points.iter().map(to_dot).collect();
+            for run in points.split(|point| point.is_none()) {
+                let run: Vec<(i64, i64)> = run.iter().flatten().copied().collect();
+                match run.as_slice() {
+                    [point] => grid.set(point.0, point.1, Some(color(idx))),
+                    run => {
+                        for pair in run.windows(2) {
+                            grid.line(pair[0], pair[1], Some(color(idx)));
+                        }
+                    }
+                }
+            }
+        }
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(rune) = grid.rune(x, y) {
+                    ctx.insert((left + x, top + y), rune);
+                }
+            }
+        }
+
+        for (idx, series) in self.series.iter().enumerate() {
+            let Some(marker) = series.marker else {
+                continue;
+            };
+            for (x, y) in series.points.iter().filter_map(to_dot) {
+                if x >= 0 && y >= 0 && (x as usize) < dot_width && (y as usize) < dot_height {
+                    let pos = (left + x as usize / 2, top + y as usize / 4);
+                    ctx.set_rune(pos, Rune::new().content(marker).fg(color(idx)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Color;
+
+    use super::{Plot, Series};
+
+    #[test]
+    fn test_axes_and_legend() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let plot = Plot::new()
+            .series(Series::new("a", vec![(0.0, 0.0), (10.0, 5.0)]).color(Color::Blue))
+            .legend();
+        ctx.component((0, (12, 5)), plot);
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(5).collect();
+        assert!(lines[0].ends_with("● a\0\0\0\0\0\0\0\0"));
+        assert!(lines[1].starts_with("5│"));
+        assert!(lines[2].starts_with("0│"));
+        assert!(lines[3].starts_with("\0└──────────"));
+        assert_eq!(lines[4].replace('\0', " ").trim_end(), " 0        10");
+        assert_eq!(ctx.view[2][2].fg, Some(Color::Blue));
+        assert_eq!(ctx.view[1][11].fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_marker() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let plot = Plot::new()
+            .series(Series::new("a", vec![(0.0, 0.0), (1.0, 1.0)]).marker('x'))
+            .y_bounds(0.0, 2.0);
+        ctx.component((0, (10, 4)), plot);
+        assert_eq!(ctx.view[1][2].content, Some('x'));
+    }
+
+    #[test]
+    fn test_values_out_of_bounds() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let plot = Plot::new()
+            .series(Series::new(
+                "a",
+                vec![
+                    (0.0, 0.0),
+                    (1.0, f64::INFINITY),
+                    (2.0, f64::NAN),
+                    (3.0, 1e300),
+                    (4.0, 0.5),
+                ],
+            ))
+            .y_bounds(0.0, 1.0);
+        ctx.component((0, (12, 5)), plot);
+        let text = ctx.view.render_text();
+        assert!(text.chars().any(|c| ('⠁'..='⣿').contains(&c)));
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use crate::{
+    app::Renderer,
+    container::Callable,
+    context::ViewContext,
+    runes::{Runes, ToRuneExt},
+    symbols::{partial_fill, SymbolSet},
+};
+
+/// The state of a task tracked by MultiProgress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+struct TaskState {
+    name: String,
+    progress: Option<f32>,
+    message: String,
+    status: TaskStatus,
+}
+
+#[derive(Debug)]
+struct Shared {
+    tasks: Mutex<Vec<TaskState>>,
+    renderer: Mutex<Option<Renderer>>,
+}
+
+impl Shared {
+    fn update(&self, id: usize, f: impl FnOnce(&mut TaskState)) {
+        if let Some(task) = self.tasks.lock().unwrap().get_mut(id) {
+            f(task);
+        }
+        if let Some(renderer) = self.renderer.lock().unwrap().as_ref() {
+            renderer.render();
+        }
+    }
+}
+
+/// MultiProgress displays a list of named tasks, each with a status icon,
+/// progress bar, percentage, and message.
+///
+/// Tasks are added with MultiProgress::add_task, which returns a handle
+/// that can be sent to other threads. Background workers use the handle to
+/// report progress directly. If a Renderer is attached the application
+/// rerenders whenever a task is updated.
+///
+/// ```no_run
+/// use arkham::{components::MultiProgress, prelude::*};
+///
+/// fn main() {
+///     let mut app = App::new(root);
+///     let progress = MultiProgress::new().renderer(app.get_renderer());
+///     let task = progress.add_task("Downloading");
+///     std::thread::spawn(move || {
+///         for i in 0..=100 {
+///             task.set_progress(i as f32 / 100.0);
+///             std::thread::sleep(std::time::Duration::from_millis(20));
+///         }
+///         task.finish();
+///     });
+///     app = app.insert_resource(progress);
+///     app.run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, progress: Res<MultiProgress>) {
+///     let size = ctx.size();
+///     ctx.component(size, progress.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiProgress {
+    shared: Arc<Shared>,
+    started: Instant,
+    bar_width: usize,
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                tasks: Mutex::new(vec![]),
+                renderer: Mutex::new(None),
+            }),
+            started: Instant::now(),
+            bar_width: 20,
+        }
+    }
+}
+
+impl MultiProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach a renderer which is signaled whenever a task is updated.
+    pub fn renderer(self, renderer: Renderer) -> Self {
+        *self.shared.renderer.lock().unwrap() = Some(renderer);
+        self
+    }
+
+    /// Set the width of each task's progress bar. Defaults to 20.
+    pub fn bar_width(mut self, width: usize) -> Self {
+        self.bar_width = width;
+        self
+    }
+
+    /// Add a new running task and return a handle used to update it.
+    pub fn add_task<S: ToString>(&self, name: S) -> ProgressTask {
+        let mut tasks = self.shared.tasks.lock().unwrap();
+        tasks.push(TaskState {
+            name: name.to_string(),
+            progress: None,
+            message: String::new(),
+            status: TaskStatus::Running,
+        });
+        ProgressTask {
+            id: tasks.len() - 1,
+            shared: self.shared.clone(),
+        }
+    }
+
+    fn bar(&self, progress: f32, symbols: &SymbolSet) -> String {
+        let bars = symbols.bars_horizontal;
+        partial_fill(progress as f64, self.bar_width, bars)
+            .into_iter()
+            .map(|c| if c == bars[0] { symbols.block_light } else { c })
+            .collect()
+    }
+}
+
+impl Callable<()> for MultiProgress {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let tasks = self.shared.tasks.lock().unwrap().clone();
+        let frame = (self.started.elapsed().as_millis() / 80) as usize % symbols.spinner.len();
+        let name_width = tasks.iter().map(|t| t.name.chars().count()).max();
+        let name_width = name_width.unwrap_or_default();
+
+        for (row, task) in tasks.iter().enumerate() {
+            let (icon, color) = match task.status {
+                TaskStatus::Running => (symbols.spinner[frame], theme.accent),
+                TaskStatus::Done => (symbols.tick, theme.success),
+                TaskStatus::Failed => (symbols.cross, theme.error),
+            };
+            let mut line = Runes::from(icon).fg(color);
+            line.add(format!(" {:<width$}", task.name, width = name_width));
+            if let Some(progress) = task.progress {
+                line.add(" ");
+                line.add(self.bar(progress, &symbols).to_runes().fg(color));
+                line.add(format!(
+                    " {:>3}%",
+                    (progress.clamp(0.0, 1.0) * 100.0).round()
+                ));
+            }
+            if !task.message.is_empty() {
+                line.add(" ");
+                line.add(task.message.to_runes().fg(theme.muted));
+            }
+            ctx.insert((0, row), line);
+        }
+    }
+}
+
+/// A handle to a single task within a MultiProgress. Handles can be cloned
+/// and sent between threads.
+#[derive(Debug, Clone)]
+pub struct ProgressTask {
+    id: usize,
+    shared: Arc<Shared>,
+}
+
+impl ProgressTask {
+    /// Set the task's progress as a fraction between 0.0 and 1.0.
+    pub fn set_progress(&self, progress: f32) {
+        self.shared
+            .update(self.id, |task| task.progress = Some(progress));
+    }
+
+    /// Set the task's progress from a position within a known length.
+    pub fn set_position(&self, position: u64, length: u64) {
+        self.set_progress(if length == 0 {
+            1.0
+        } else {
+            position as f32 / length as f32
+        });
+    }
+
+    /// Set a status message displayed after the progress bar.
+    pub fn set_message<S: ToString>(&self, message: S) {
+        let message = message.to_string();
+        self.shared.update(self.id, |task| task.message = message);
+    }
+
+    /// Mark the task as successfully completed.
+    pub fn finish(&self) {
+        self.shared.update(self.id, |task| {
+            task.status = TaskStatus::Done;
+            if task.progress.is_some() {
+                task.progress = Some(1.0);
+            }
+        });
+    }
+
+    /// Mark the task as failed with a message describing the failure.
+    pub fn fail<S: ToString>(&self, message: S) {
+        let message = message.to_string();
+        self.shared.update(self.id, |task| {
+            task.status = TaskStatus::Failed;
+            task.message = message;
+        });
+    }
+
+    /// The current status of the task.
+    pub fn status(&self) -> TaskStatus {
+        self.shared.tasks.lock().unwrap()[self.id].status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiProgress, TaskStatus};
+
+    #[test]
+    fn test_render_tasks() {
+        let progress = MultiProgress::new().bar_width(4);
+        let a = progress.add_task("a");
+        let b = progress.add_task("bb");
+        a.set_progress(0.5);
+        b.fail("oops");
+        assert_eq!(b.status(), TaskStatus::Failed);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (20, 2)), progress.clone());
+        let text = ctx.view.render_text();
+        let lines: Vec<&str> = text.lines().take(2).collect();
+        assert!(lines[0][3..].starts_with(" a  ██░░  50%"));
+        assert!(lines[1].starts_with("✖ bb oops"));
+
+        a.set_progress(0.3);
+        ctx.component((0, (20, 2)), progress.clone());
+        let text = ctx.view.render_text();
+        assert!(text.lines().next().unwrap()[3..].starts_with(" a  █▎░░  30%"));
+    }
+
+    #[test]
+    fn test_update_from_thread() {
+        let progress = MultiProgress::new();
+        let task = progress.add_task("work");
+        std::thread::spawn(move || task.finish()).join().unwrap();
+        assert_eq!(
+            progress.shared.tasks.lock().unwrap()[0].status,
+            TaskStatus::Done
+        );
+    }
+}
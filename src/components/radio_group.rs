@@ -0,0 +1,155 @@
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::{Callable, State},
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{Rune, Runes},
+};
+
+/// RadioGroup displays a set of options, one per row, of which exactly one
+/// is selected. The index of the selected option is held in application
+/// state.
+///
+/// RadioGroup::handle_keys provides the key bindings: Up and Down select the
+/// previous and next options, and Space selects the next option, wrapping
+/// around to the first.
+///
+/// ```no_run
+/// use arkham::{components::RadioGroup, prelude::*};
+///
+/// fn main() {
+///     App::new(root).insert_state(0_usize).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, size: State<usize>, kb: Res<Keyboard>) {
+///     let sizes = RadioGroup::new(["Small", "Medium", "Large"], size.clone());
+///     sizes.handle_keys(&kb);
+///     ctx.component(((0, 0), (20, 3)), sizes);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RadioGroup {
+    options: Vec<Runes>,
+    selected: State<usize>,
+    focused: bool,
+}
+
+impl RadioGroup {
+    /// Create a group bound to the index of the selected option in
+    /// application state.
+    pub fn new<I, R>(options: I, selected: State<usize>) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<Runes>,
+    {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected,
+            focused: true,
+        }
+    }
+
+    /// Set whether the group has focus. Groups without focus do not respond
+    /// to keys and are drawn without the focus color. Groups have focus by
+    /// default.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// The index of the selected option. Indexes past the end of the
+    /// options select the last option.
+    pub fn selected(&self) -> usize {
+        (*self.selected.get()).min(self.options.len().saturating_sub(1))
+    }
+
+    /// Select the option at an index.
+    pub fn select(&self, idx: usize) {
+        *self.selected.get_mut() = idx.min(self.options.len().saturating_sub(1));
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        if !self.focused || self.options.is_empty() {
+            return false;
+        }
+        let selected = self.selected();
+        match kb.code() {
+            Some(KeyCode::Up) => self.select(selected.saturating_sub(1)),
+            Some(KeyCode::Down) => self.select(selected + 1),
+            Some(KeyCode::Char(' ')) => self.select((selected + 1) % self.options.len()),
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the options within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let selected = self.selected();
+        for (idx, option) in self.options.iter().enumerate().take(rect.size.height) {
+            let mut runes = if idx == selected {
+                Runes::from(Rune::new().content(symbols.radio_on).fg(theme.accent))
+            } else {
+                Runes::from(Rune::new().content(symbols.radio_off).fg(theme.muted))
+            };
+            runes.add(" ");
+            if idx == selected && self.focused {
+                runes.add(option.clone().fg(theme.focus));
+            } else {
+                runes.add(option.clone());
+            }
+            ctx.insert(
+                (rect.pos.x, rect.pos.y + idx),
+                Runes::new(runes.iter().take(rect.size.width).copied().collect()),
+            );
+        }
+    }
+}
+
+impl Callable<()> for RadioGroup {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{container::State, input::Keyboard};
+
+    use super::RadioGroup;
+
+    #[test]
+    fn test_select() {
+        let state = State::new(0);
+        let group = RadioGroup::new(["a", "b", "c"], state.clone());
+        group.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        group.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        group.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        assert_eq!(*state.get(), 2);
+        group.handle_keys(&Keyboard::with_key(KeyCode::Char(' ')));
+        assert_eq!(*state.get(), 0);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        group.draw(&mut ctx, ((0, 0), (5, 3)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(3).map(str::trim_end).collect();
+        assert_eq!(lines, vec!["◉ a", "◯ b", "◯ c"]);
+
+        *state.get_mut() = 9;
+        assert_eq!(group.selected(), 2);
+        assert!(!group
+            .focused(false)
+            .handle_keys(&Keyboard::with_key(KeyCode::Up)));
+    }
+}
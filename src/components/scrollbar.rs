@@ -0,0 +1,287 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use crate::{
+    container::{Callable, Res},
+    context::ViewContext,
+    geometry::{Pos, Rect},
+    input::Mouse,
+    pointer::CursorShape,
+    runes::Rune,
+};
+
+#[derive(Debug)]
+struct ScrollbarState {
+    total: usize,
+    visible: usize,
+    offset: usize,
+    /// The region of the screen the track was last drawn in.
+    track: Rect,
+    /// While dragging, the row within the thumb that was pressed.
+    grab: Option<usize>,
+}
+
+impl Default for ScrollbarState {
+    fn default() -> Self {
+        Self {
+            total: 0,
+            visible: 0,
+            offset: 0,
+            track: Rect::zero(),
+            grab: None,
+        }
+    }
+}
+
+impl ScrollbarState {
+    fn max_offset(&self) -> usize {
+        self.total.saturating_sub(self.visible)
+    }
+
+    fn set_offset(&mut self, offset: usize) {
+        self.offset = offset.min(self.max_offset());
+    }
+
+    /// The start and length of the thumb on a track of a given length.
+    fn thumb(&self, length: usize) -> (usize, usize) {
+        if self.total <= self.visible || length == 0 {
+            return (0, length);
+        }
+        let size = (length * self.visible / self.total).clamp(1, length);
+        let travel = length - size;
+        let start = (travel * self.offset + self.max_offset() / 2) / self.max_offset();
+        (start.min(travel), size)
+    }
+}
+
+/// Scrollbar displays a vertical track indicating the portion of some
+/// content that is visible, and can be dragged with the mouse to scroll.
+///
+/// When drawn, the scrollbar applies the event of the Mouse resource, which
+/// requires App::enable_mouse: dragging the thumb scrolls to follow the
+/// mouse, pressing the track above or below the thumb scrolls by a page, and
+/// the scroll wheel scrolls by a line. Events used by the scrollbar are
+/// consumed. Events can also be applied directly with
+/// Scrollbar::handle_mouse. While the mouse is over the thumb, terminals
+/// which support it show a move pointer.
+///
+/// The scrollbar applies events as it is drawn, so content drawn before it
+/// in the same frame uses the previous offset. The application renders
+/// again after each mouse event, which displays the new offset.
+///
+/// ```no_run
+/// use arkham::{components::Scrollbar, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .enable_mouse()
+///         .insert_resource(Scrollbar::new(200, 20))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, scrollbar: Res<Scrollbar>) {
+///     let size = ctx.size();
+///     let offset = scrollbar.offset();
+///     for row in 0..size.height {
+///         ctx.insert((0, row), format!("Line {}", offset + row));
+///     }
+///     ctx.component(((size.width - 1, 0), (1, size.height)), scrollbar.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scrollbar {
+    state: Rc<RefCell<ScrollbarState>>,
+}
+
+impl Scrollbar {
+    /// Create a scrollbar for content of a total length, of which a number
+    /// of lines are visible at once.
+    pub fn new(total: usize, visible: usize) -> Self {
+        let scrollbar = Self::default();
+        scrollbar.set_content(total, visible);
+        scrollbar
+    }
+
+    /// Update the total length of the content and the number of lines
+    /// visible at once. The offset is kept within the new bounds.
+    pub fn set_content(&self, total: usize, visible: usize) {
+        let mut state = self.state.borrow_mut();
+        state.total = total;
+        state.visible = visible;
+        let offset = state.offset;
+        state.set_offset(offset);
+    }
+
+    /// The first visible line of the content.
+    pub fn offset(&self) -> usize {
+        self.state.borrow().offset
+    }
+
+    /// Scroll so that a line is the first visible line.
+    pub fn set_offset(&self, offset: usize) {
+        self.state.borrow_mut().set_offset(offset);
+    }
+
+    /// Returns true while the thumb is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.state.borrow().grab.is_some()
+    }
+
+    /// Apply a mouse event. Returns true if the event scrolled, or began or
+    /// ended a drag.
+    pub fn handle_mouse(&self, event: &MouseEvent) -> bool {
+        let pos = Pos::new(event.column as usize, event.row as usize);
+        let mut state = self.state.borrow_mut();
+        let track = state.track;
+        let row = pos.y.saturating_sub(track.pos.y);
+        let (start, size) = state.thumb(track.size.height);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) if track.contains(pos) => {
+                let page = state.visible.max(1);
+                if row < start {
+                    let offset = state.offset.saturating_sub(page);
+                    state.set_offset(offset);
+                } else if row >= start + size {
+                    let offset = state.offset + page;
+                    state.set_offset(offset);
+                } else {
+                    state.grab = Some(row - start);
+                }
+                true
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let Some(grab) = state.grab else {
+                    return false;
+                };
+                let travel = track.size.height.saturating_sub(size);
+                if travel > 0 {
+                    let start = row.saturating_sub(grab).min(travel);
+                    let offset = (start * state.max_offset() + travel / 2) / travel;
+                    state.set_offset(offset);
+                }
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if state.grab.is_some() => {
+                state.grab = None;
+                true
+            }
+            MouseEventKind::ScrollUp if track.contains(pos) => {
+                let offset = state.offset.saturating_sub(1);
+                state.set_offset(offset);
+                true
+            }
+            MouseEventKind::ScrollDown if track.contains(pos) => {
+                let offset = state.offset + 1;
+                state.set_offset(offset);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Draw the scrollbar down the first column of a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let track = Rect::new(rect.pos, (rect.size.width.min(1), rect.size.height));
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        self.state.borrow_mut().track = Rect::new(ctx.origin() + track.pos, track.size);
+        if let Some(mouse) = ctx.container.borrow().get::<Res<Mouse>>() {
+            if mouse.event().is_some_and(|event| self.handle_mouse(&event)) {
+                mouse.reset();
+            }
+        }
+
+        let state = self.state.borrow();
+        let (start, size) = state.thumb(track.size.height);
+        ctx.cursor_hint(
+            Rect::new((track.pos.x, track.pos.y + start), (track.size.width, size)),
+            CursorShape::Move,
+        );
+        let thumb_color = if state.grab.is_some() {
+            theme.focus
+        } else {
+            theme.fg
+        };
+        for (row, pos) in track.cells().enumerate() {
+            let rune = if row >= start && row < start + size {
+                Rune::new().content(symbols.block_full).fg(thumb_color)
+            } else {
+                Rune::new().content(symbols.block_light).fg(theme.muted)
+            };
+            ctx.set_rune(pos, rune);
+        }
+    }
+}
+
+impl Callable<()> for Scrollbar {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use super::Scrollbar;
+
+    fn mouse(kind: MouseEventKind, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 3,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_draw_thumb() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let scrollbar = Scrollbar::new(40, 10);
+        scrollbar.set_offset(30);
+        scrollbar.draw(&mut ctx, ((3, 0), (1, 8)));
+        let track: String = (0..8).filter_map(|y| ctx.view[y][3].content).collect();
+        assert_eq!(track, "░░░░░░██");
+    }
+
+    #[test]
+    fn test_drag_and_page() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let scrollbar = Scrollbar::new(40, 10);
+        scrollbar.draw(&mut ctx, ((3, 0), (1, 8)));
+
+        let left = MouseButton::Left;
+        assert!(scrollbar.handle_mouse(&mouse(MouseEventKind::Down(left), 1)));
+        assert!(scrollbar.is_dragging());
+        assert!(scrollbar.handle_mouse(&mouse(MouseEventKind::Drag(left), 4)));
+        assert_eq!(scrollbar.offset(), 15);
+        assert!(scrollbar.handle_mouse(&mouse(MouseEventKind::Up(left), 4)));
+        assert!(!scrollbar.is_dragging());
+
+        assert!(scrollbar.handle_mouse(&mouse(MouseEventKind::Down(left), 7)));
+        assert_eq!(scrollbar.offset(), 25);
+        assert!(scrollbar.handle_mouse(&mouse(MouseEventKind::ScrollDown, 7)));
+        assert_eq!(scrollbar.offset(), 26);
+        assert!(!scrollbar.handle_mouse(&mouse(MouseEventKind::ScrollDown, 9)));
+    }
+
+    #[test]
+    fn test_scroll_with_mouse_resource() {
+        use crate::{container::Res, context::ViewContext, testing::TestApp};
+
+        let root = |ctx: &mut ViewContext, scrollbar: Res<Scrollbar>| {
+            ctx.component(((3, 0), (1, 8)), scrollbar.get().clone());
+        };
+        let scrollbar = Scrollbar::new(40, 10);
+        let mut app = TestApp::new(root, (4, 8)).insert_resource(scrollbar.clone());
+        app.render();
+        app.mouse(mouse(MouseEventKind::ScrollDown, 2));
+        assert_eq!(scrollbar.offset(), 1);
+        app.mouse(mouse(MouseEventKind::Down(MouseButton::Left), 7));
+        assert_eq!(scrollbar.offset(), 11);
+    }
+}
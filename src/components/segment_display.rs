@@ -0,0 +1,187 @@
+use crate::{
+    container::Callable, context::ViewContext, geometry::Size, prelude::Color, runes::Rune,
+};
+
+// Segment bits, following the conventional a-g labeling starting at the top
+// and moving clockwise, with g as the middle bar.
+const A: u8 = 0x01;
+const B: u8 = 0x02;
+const C: u8 = 0x04;
+const D: u8 = 0x08;
+const E: u8 = 0x10;
+const F: u8 = 0x20;
+const G: u8 = 0x40;
+
+fn segments(c: char) -> Option<u8> {
+    Some(match c {
+        '0' => A | B | C | D | E | F,
+        '1' => B | C,
+        '2' => A | B | D | E | G,
+        '3' => A | B | C | D | G,
+        '4' => B | C | F | G,
+        '5' => A | C | D | F | G,
+        '6' => A | C | D | E | F | G,
+        '7' => A | B | C,
+        '8' => A | B | C | D | E | F | G,
+        '9' => A | B | C | D | F | G,
+        '-' => G,
+        ' ' => 0,
+        _ => return None,
+    })
+}
+
+/// SegmentDisplay renders numbers as large seven segment digits drawn with
+/// block characters, for clocks, counters, and scoreboards.
+///
+/// Digits, spaces, and the '-', ':', and '.' characters are supported, other
+/// characters are skipped. Each digit is three cells wide and five cells tall
+/// by default.
+///
+/// ```
+/// use arkham::{components::SegmentDisplay, prelude::*};
+///
+/// fn clock(ctx: &mut ViewContext) {
+///     let display = SegmentDisplay::new("12:45")
+///         .color(Color::Green)
+///         .unlit(Color::DarkGrey);
+///     let size = display.size();
+///     ctx.component(size, display);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentDisplay {
+    value: String,
+    digit_size: Size,
+    color: Option<Color>,
+    unlit: Option<Color>,
+}
+
+impl SegmentDisplay {
+    pub fn new<S: ToString>(value: S) -> Self {
+        Self {
+            value: value.to_string(),
+            digit_size: Size::new(3, 5),
+            color: None,
+            unlit: None,
+        }
+    }
+
+    /// Set the size of each digit. Digits are at least three cells wide and
+    /// three cells tall.
+    pub fn digit_size<S: Into<Size>>(mut self, size: S) -> Self {
+        let size = size.into();
+        self.digit_size = Size::new(size.width.max(3), size.height.max(3));
+        self
+    }
+
+    /// Set the color of lit segments. Defaults to the current foreground
+    /// color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw unlit segments in a color, mimicking an LED display.
+    pub fn unlit(mut self, color: Color) -> Self {
+        self.unlit = Some(color);
+        self
+    }
+
+    /// The area needed to display the full value.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{components::SegmentDisplay, prelude::*};
+    /// assert_eq!(SegmentDisplay::new("12:45").size(), Size::new(17, 5));
+    /// ```
+    pub fn size(&self) -> Size {
+        let width: usize = self.glyphs().map(|(_, w)| w + 1).sum();
+        Size::new(width.saturating_sub(1), self.digit_size.height)
+    }
+
+    /// The displayable characters of the value and their widths.
+    fn glyphs(&self) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.value.chars().filter_map(|c| match c {
+            ':' | '.' => Some((c, 1)),
+            c => segments(c).map(|_| (c, self.digit_size.width)),
+        })
+    }
+
+    /// Returns true if the segments light the cell at a position within a
+    /// digit.
+    fn lit(&self, segments: u8, x: usize, y: usize) -> bool {
+        let Size { width, height } = self.digit_size;
+        let mid = (height - 1) / 2;
+        let on = |segment: u8| segments & segment != 0;
+        (y == 0 && on(A))
+            || (y == mid && on(G))
+            || (y == height - 1 && on(D))
+            || (x == 0 && y <= mid && on(F))
+            || (x == 0 && y >= mid && on(E))
+            || (x == width - 1 && y <= mid && on(B))
+            || (x == width - 1 && y >= mid && on(C))
+    }
+}
+
+impl Callable<()> for SegmentDisplay {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let mut lit = Rune::new().content('█');
+        lit.fg = self.color.or(ctx.style().fg);
+        let unlit = self.unlit.map(|c| Rune::new().content('█').fg(c));
+        let height = self.digit_size.height;
+        let mid = (height - 1) / 2;
+
+        let mut left = 0;
+        for (c, width) in self.glyphs() {
+            match c {
+                ':' => {
+                    ctx.insert((left, mid - 1), lit);
+                    ctx.insert((left, mid + 1), lit);
+                }
+                '.' => ctx.insert((left, height - 1), lit),
+                c => {
+                    let on = segments(c).unwrap_or_default();
+                    for y in 0..height {
+                        for x in 0..width {
+                            if self.lit(on, x, y) {
+                                ctx.insert((left + x, y), lit);
+                            } else if let Some(unlit) = unlit.filter(|_| self.lit(0x7f, x, y)) {
+                                ctx.insert((left + x, y), unlit);
+                            }
+                        }
+                    }
+                }
+            }
+            left += width + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SegmentDisplay;
+
+    #[test]
+    fn test_digits() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component((0, (20, 5)), SegmentDisplay::new("4:7"));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(5).map(|l| l.trim_end()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "█ █   ███",
+                "█ █ █   █",
+                "███     █",
+                "  █ █   █",
+                "  █     █"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_digit_size() {
+        let display = SegmentDisplay::new("1.5").digit_size((4, 7));
+        assert_eq!(display.size(), (11, 7).into());
+    }
+}
@@ -0,0 +1,133 @@
+use crossterm::style::Color;
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    runes::Rune,
+    symbols::{braille, partial_fill, SymbolLevel},
+};
+
+/// Sparkline displays a series of values as a compact bar chart, one column
+/// per value, with each bar rising from the bottom of the area. Bars are
+/// drawn with partial block characters, so they are finer than a cell.
+///
+/// Values are scaled to the largest value in the series, or to a fixed
+/// maximum set with Sparkline::max. When there are more values than
+/// columns, the most recent values are shown.
+///
+/// ```no_run
+/// use arkham::{components::Sparkline, prelude::*};
+///
+/// fn root(ctx: &mut ViewContext) {
+///     let requests = [3.0, 5.0, 2.0, 8.0, 13.0, 7.0];
+///     ctx.component(((0, 0), (20, 2)), Sparkline::new(requests));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sparkline {
+    values: Vec<f64>,
+    max: Option<f64>,
+    color: Option<Color>,
+    braille: bool,
+}
+
+impl Sparkline {
+    pub fn new<I, V>(values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<f64>,
+    {
+        Self {
+            values: values.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Scale the bars to a fixed maximum rather than the largest value.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the color of the bars. Defaults to the theme's info color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw the bars with braille dots rather than blocks. Terminals limited
+    /// to legacy symbols use the SymbolSet bars instead.
+    pub fn braille(mut self) -> Self {
+        self.braille = true;
+        self
+    }
+
+    /// Draw the sparkline within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let bars: &[char] = if self.braille && symbols.level != SymbolLevel::Legacy {
+            &braille::VERTICAL
+        } else {
+            symbols.bars_vertical
+        };
+        let color = self.color.unwrap_or(theme.info);
+        let max = self
+            .max
+            .unwrap_or_else(|| self.values.iter().copied().fold(0.0, f64::max));
+        if max <= 0.0 || rect.size.height == 0 {
+            return;
+        }
+
+        let start = self.values.len().saturating_sub(rect.size.width);
+        for (x, &value) in self.values[start..].iter().enumerate() {
+            let fill = partial_fill(value / max, rect.size.height, bars);
+            for (row, c) in fill.into_iter().enumerate() {
+                if c == bars[0] {
+                    break;
+                }
+                ctx.insert(
+                    (rect.pos.x + x, rect.pos.y + rect.size.height - 1 - row),
+                    Rune::new().content(c).fg(color),
+                );
+            }
+        }
+    }
+}
+
+impl Callable<()> for Sparkline {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sparkline;
+
+    fn draw(sparkline: &Sparkline, height: usize) -> Vec<String> {
+        let mut ctx = crate::context::tests::context_fixture();
+        sparkline.draw(&mut ctx, ((0, 0), (4, height)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        text.lines()
+            .take(height)
+            .map(|l| l.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let sparkline = Sparkline::new([0, 1, 4, 8]);
+        assert_eq!(draw(&sparkline, 1), vec![" ▁▄█"]);
+        assert_eq!(draw(&sparkline, 2), vec!["   █", " ▂██"]);
+        assert_eq!(draw(&sparkline.clone().max(16.0), 1), vec![" ▁▂▄"]);
+        assert_eq!(draw(&sparkline.clone().braille(), 1), vec![" ⣀⣤⣿"]);
+
+        // Only the most recent values fit.
+        let sparkline = Sparkline::new([8, 8, 0, 0, 0, 8]);
+        assert_eq!(draw(&sparkline, 1), vec!["   █"]);
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    runes::{Rune, Runes},
+    symbols::{SymbolLevel, SymbolSet},
+};
+
+/// The frames a Spinner cycles through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpinnerStyle {
+    /// The spinner of the SymbolSet resource, a braille dot circling a
+    /// square by default.
+    #[default]
+    Dots,
+    /// A line rotating through `- \ | /`.
+    Line,
+    /// A full braille cell with a single dot missing.
+    Braille,
+}
+
+const LINE: [char; 4] = ['-', '\\', '|', '/'];
+const BRAILLE: [char; 8] = ['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
+
+impl SpinnerStyle {
+    /// The frames of the spinner. Styles using braille fall back to the
+    /// SymbolSet spinner on terminals limited to legacy symbols.
+    fn frames(self, symbols: &SymbolSet) -> &'static [char] {
+        match self {
+            SpinnerStyle::Dots => symbols.spinner,
+            SpinnerStyle::Line => &LINE,
+            SpinnerStyle::Braille if symbols.level == SymbolLevel::Legacy => symbols.spinner,
+            SpinnerStyle::Braille => &BRAILLE,
+        }
+    }
+}
+
+/// The instant all spinners are timed from, so that spinners created on
+/// every frame still advance, and spinners drawn together stay in step.
+fn epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
+/// Spinner displays an animated indicator for work of unknown length,
+/// optionally followed by a label. The spinner requests renders as it is
+/// drawn, so it advances on its own while it is displayed.
+///
+/// ```no_run
+/// use arkham::{components::{Spinner, SpinnerStyle}, prelude::*};
+///
+/// fn main() {
+///     App::new(root).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.component(
+///         ((0, 0), (20, 1)),
+///         Spinner::new().style(SpinnerStyle::Line).label("Loading"),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    style: SpinnerStyle,
+    interval: Duration,
+    label: Option<Runes>,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self {
+            style: SpinnerStyle::default(),
+            interval: Duration::from_millis(80),
+            label: None,
+        }
+    }
+}
+
+impl Spinner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the frames the spinner cycles through.
+    pub fn style(mut self, style: SpinnerStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the time each frame is displayed for. Defaults to 80ms.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Set text displayed after the spinner.
+    pub fn label<R: Into<Runes>>(mut self, label: R) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// The index of the frame displayed at a time since the epoch, and the
+    /// time until the next frame.
+    fn frame_at(&self, elapsed: Duration, frames: usize) -> (usize, Duration) {
+        let interval = self.interval.as_nanos();
+        let elapsed = elapsed.as_nanos();
+        let frame = (elapsed / interval) as usize % frames;
+        let next = Duration::from_nanos((interval - elapsed % interval) as u64);
+        (frame, next)
+    }
+
+    /// Draw the spinner within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.width == 0 || rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let frames = self.style.frames(&ctx.symbols());
+        let (frame, next) = self.frame_at(epoch().elapsed(), frames.len());
+        ctx.insert(
+            rect.pos,
+            Rune::new().content(frames[frame]).fg(theme.accent),
+        );
+        if let Some(label) = &self.label {
+            let width = rect.size.width.saturating_sub(2);
+            let label = Runes::new(label.iter().take(width).copied().collect());
+            ctx.insert((rect.pos.x + 2, rect.pos.y), label);
+        }
+        ctx.animate(next);
+    }
+}
+
+impl Callable<()> for Spinner {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{animation::Animation, container::Res, symbols::SymbolSet};
+
+    use super::{Spinner, SpinnerStyle, LINE};
+
+    #[test]
+    fn test_frames() {
+        let spinner = Spinner::new().interval(Duration::from_millis(100));
+        let ms = Duration::from_millis;
+        assert_eq!(spinner.frame_at(ms(0), 4), (0, ms(100)));
+        assert_eq!(spinner.frame_at(ms(250), 4), (2, ms(50)));
+        assert_eq!(spinner.frame_at(ms(420), 4), (0, ms(80)));
+
+        let symbols = SymbolSet::LEGACY;
+        assert_eq!(SpinnerStyle::Braille.frames(&symbols), symbols.spinner);
+        assert_eq!(SpinnerStyle::Line.frames(&symbols), &LINE);
+    }
+
+    #[test]
+    fn test_draw_requests_render() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let animation = Animation::new();
+        ctx.container.borrow_mut().bind(Res::new(animation.clone()));
+        Spinner::new()
+            .style(SpinnerStyle::Line)
+            .label("Loading")
+            .draw(&mut ctx, ((0, 0), (6, 1)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let line = text.lines().next().unwrap();
+        assert!(LINE.contains(&line.chars().next().unwrap()));
+        assert_eq!(&line[1..6], " Load");
+        assert!(animation.is_pending());
+    }
+}
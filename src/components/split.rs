@@ -0,0 +1,321 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use crate::{
+    container::{Callable, FromContainer, Res},
+    context::ViewContext,
+    geometry::{Pos, Rect},
+    input::Mouse,
+    pointer::CursorShape,
+    runes::Rune,
+};
+
+/// The direction a Split divides its area in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// The panes are placed side by side with a vertical divider between
+    /// them.
+    #[default]
+    Horizontal,
+    /// The panes are placed above one another with a horizontal divider
+    /// between them.
+    Vertical,
+}
+
+#[derive(Debug)]
+struct SplitState {
+    direction: SplitDirection,
+    ratio: f32,
+    min_size: usize,
+    /// The region of the screen the split was last drawn in.
+    area: Rect,
+    dragging: bool,
+}
+
+impl SplitState {
+    /// The length of the area along the split direction.
+    fn extent(&self, area: Rect) -> usize {
+        match self.direction {
+            SplitDirection::Horizontal => area.size.width,
+            SplitDirection::Vertical => area.size.height,
+        }
+    }
+
+    /// The offset of the divider from the start of an area.
+    fn divider(&self, area: Rect) -> usize {
+        let extent = self.extent(area).saturating_sub(1);
+        let offset = (extent as f32 * self.ratio).round() as usize;
+        offset
+            .min(extent.saturating_sub(self.min_size))
+            .max(self.min_size.min(extent))
+    }
+
+    /// The regions of the first pane, the divider, and the second pane.
+    fn rects(&self, area: Rect) -> (Rect, Rect, Rect) {
+        let offset = self.divider(area);
+        let (x, y) = (area.pos.x, area.pos.y);
+        let (width, height) = (area.size.width, area.size.height);
+        match self.direction {
+            SplitDirection::Horizontal => (
+                Rect::new((x, y), (offset, height)),
+                Rect::new((x + offset, y), (1.min(width), height)),
+                Rect::new(
+                    (x + offset + 1, y),
+                    (width.saturating_sub(offset + 1), height),
+                ),
+            ),
+            SplitDirection::Vertical => (
+                Rect::new((x, y), (width, offset)),
+                Rect::new((x, y + offset), (width, 1.min(height))),
+                Rect::new(
+                    (x, y + offset + 1),
+                    (width, height.saturating_sub(offset + 1)),
+                ),
+            ),
+        }
+    }
+}
+
+/// Split divides an area into two panes separated by a divider, which can be
+/// dragged with the mouse to resize the panes.
+///
+/// When drawn, the split applies the event of the Mouse resource, which
+/// requires App::enable_mouse: pressing the left button on the divider
+/// begins a drag, moving the mouse resizes the panes, and releasing the
+/// button ends the drag. Events used by the split are consumed. Events can
+/// also be applied directly with Split::handle_mouse. While the mouse is
+/// over the divider, terminals which support it show a resize pointer.
+///
+/// ```no_run
+/// use arkham::{components::{Split, SplitDirection}, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .enable_mouse()
+///         .insert_resource(Split::new(SplitDirection::Horizontal))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, split: Res<Split>) {
+///     let size = ctx.size();
+///     split.draw(ctx, size, sidebar, content);
+/// }
+///
+/// fn sidebar(ctx: &mut ViewContext) {
+///     ctx.insert(0, "Files");
+/// }
+///
+/// fn content(ctx: &mut ViewContext) {
+///     ctx.insert(0, "Editor");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Split {
+    state: Rc<RefCell<SplitState>>,
+}
+
+impl Split {
+    pub fn new(direction: SplitDirection) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(SplitState {
+                direction,
+                ratio: 0.5,
+                min_size: 1,
+                area: Rect::zero(),
+                dragging: false,
+            })),
+        }
+    }
+
+    /// Set the smallest size either pane can be resized to. Defaults to one
+    /// cell.
+    pub fn min_size(self, size: usize) -> Self {
+        self.state.borrow_mut().min_size = size;
+        self
+    }
+
+    /// The position of the divider as a fraction of the split's size.
+    pub fn ratio(&self) -> f32 {
+        self.state.borrow().ratio
+    }
+
+    /// Move the divider to a fraction of the split's size.
+    pub fn set_ratio(&self, ratio: f32) {
+        self.state.borrow_mut().ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    /// Returns true while the divider is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.state.borrow().dragging
+    }
+
+    /// The regions of the first pane, the divider, and the second pane
+    /// within an area.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{components::{Split, SplitDirection}, prelude::*};
+    /// let split = Split::new(SplitDirection::Horizontal);
+    /// let (first, divider, second) = split.rects(((0, 0), (11, 4)));
+    /// assert_eq!(first, Rect::new((0, 0), (5, 4)));
+    /// assert_eq!(divider, Rect::new((5, 0), (1, 4)));
+    /// assert_eq!(second, Rect::new((6, 0), (5, 4)));
+    /// ```
+    pub fn rects<R: Into<Rect>>(&self, area: R) -> (Rect, Rect, Rect) {
+        self.state.borrow().rects(area.into())
+    }
+
+    /// Apply a mouse event. Returns true if the event was used to drag the
+    /// divider.
+    pub fn handle_mouse(&self, event: &MouseEvent) -> bool {
+        let pos = Pos::new(event.column as usize, event.row as usize);
+        let mut state = self.state.borrow_mut();
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let (_, divider, _) = state.rects(state.area);
+                state.dragging = divider.contains(pos);
+                state.dragging
+            }
+            MouseEventKind::Drag(MouseButton::Left) if state.dragging => {
+                let (start, pos) = match state.direction {
+                    SplitDirection::Horizontal => (state.area.pos.x, pos.x),
+                    SplitDirection::Vertical => (state.area.pos.y, pos.y),
+                };
+                let extent = state.extent(state.area).saturating_sub(1).max(1);
+                state.ratio = (pos.saturating_sub(start) as f32 / extent as f32).clamp(0.0, 1.0);
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if state.dragging => {
+                state.dragging = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Draw the divider and both panes within a region of a context. The
+    /// panes are component functions and can be injected with arguments.
+    pub fn draw<R, A, AArgs, B, BArgs>(&self, ctx: &mut ViewContext, rect: R, first: A, second: B)
+    where
+        R: Into<Rect>,
+        A: Callable<AArgs>,
+        AArgs: FromContainer,
+        B: Callable<BArgs>,
+        BArgs: FromContainer,
+    {
+        let rect = rect.into();
+        self.state.borrow_mut().area = Rect::new(ctx.origin() + rect.pos, rect.size);
+        if let Some(mouse) = ctx.container.borrow().get::<Res<Mouse>>() {
+            if mouse.event().is_some_and(|event| self.handle_mouse(&event)) {
+                mouse.reset();
+            }
+        }
+        let (first_rect, divider, second_rect) = self.rects(rect);
+        let (direction, dragging) = {
+            let state = self.state.borrow();
+            (state.direction, state.dragging)
+        };
+
+        let theme = ctx.theme();
+        let border = ctx.symbols().border;
+        let (line, shape) = match direction {
+            SplitDirection::Horizontal => (border.vertical, CursorShape::ColResize),
+            SplitDirection::Vertical => (border.horizontal, CursorShape::RowResize),
+        };
+        let color = if dragging { theme.focus } else { theme.border };
+        for pos in divider.cells() {
+            ctx.set_rune(pos, Rune::new().content(line).fg(color));
+        }
+        ctx.cursor_hint(divider, shape);
+        ctx.component(first_rect, first);
+        ctx.component(second_rect, second);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::context::ViewContext;
+
+    use super::{Split, SplitDirection};
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_drag_divider() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let split = Split::new(SplitDirection::Horizontal);
+        let draw = |ctx: &mut ViewContext, split: &Split| {
+            let split = split.clone();
+            ctx.component(((2, 1), (11, 4)), move |ctx: &mut ViewContext| {
+                let size = ctx.size();
+                split.draw(
+                    ctx,
+                    size,
+                    |ctx: &mut ViewContext| ctx.insert(0, "a"),
+                    |ctx: &mut ViewContext| ctx.insert(0, "b"),
+                );
+            });
+        };
+        draw(&mut ctx, &split);
+        assert_eq!(ctx.view[1][7].content, Some('│'));
+        assert_eq!(ctx.view[1][8].content, Some('b'));
+
+        let left = MouseButton::Left;
+        assert!(!split.handle_mouse(&mouse(MouseEventKind::Down(left), 6, 2)));
+        assert!(split.handle_mouse(&mouse(MouseEventKind::Down(left), 7, 2)));
+        assert!(split.is_dragging());
+        assert!(split.handle_mouse(&mouse(MouseEventKind::Drag(left), 4, 2)));
+        assert!(split.handle_mouse(&mouse(MouseEventKind::Up(left), 4, 2)));
+        assert!(!split.is_dragging());
+        assert_eq!(split.ratio(), 0.2);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        draw(&mut ctx, &split);
+        assert_eq!(ctx.view[1][4].content, Some('│'));
+    }
+
+    #[test]
+    fn test_min_size() {
+        let split = Split::new(SplitDirection::Vertical).min_size(2);
+        split.set_ratio(0.0);
+        let (first, divider, second) = split.rects(((0, 0), (4, 10)));
+        assert_eq!(first.size.height, 2);
+        assert_eq!(divider.pos.y, 2);
+        assert_eq!(second.size.height, 7);
+    }
+
+    #[test]
+    fn test_drag_with_mouse_resource() {
+        use crate::{container::Res, testing::TestApp};
+
+        let root = |ctx: &mut ViewContext, split: Res<Split>| {
+            let size = ctx.size();
+            split.draw(
+                ctx,
+                size,
+                |_: &mut ViewContext| {},
+                |_: &mut ViewContext| {},
+            );
+        };
+        let split = Split::new(SplitDirection::Horizontal);
+        let mut app = TestApp::new(root, (11, 2)).insert_resource(split.clone());
+        app.render();
+        let left = MouseButton::Left;
+        app.mouse(mouse(MouseEventKind::Down(left), 5, 0));
+        let view = app.mouse(mouse(MouseEventKind::Drag(left), 8, 0));
+        assert_eq!(view[0][8].content, Some('│'));
+        app.mouse(mouse(MouseEventKind::Up(left), 8, 0));
+        assert!(!split.is_dragging());
+    }
+}
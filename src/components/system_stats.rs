@@ -0,0 +1,373 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crossterm::style::Color;
+use sysinfo::{Networks, System};
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    runes::{Runes, ToRuneExt},
+    symbols::{partial_fill, SymbolSet},
+    theme::Theme,
+};
+
+use super::Sparkline;
+
+/// The number of network samples kept for the sparkline.
+const HISTORY: usize = 120;
+
+/// A single reading of system usage.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemSample {
+    /// The usage of each CPU core between 0.0 and 1.0.
+    pub cpus: Vec<f64>,
+    /// Memory in use, in bytes.
+    pub memory_used: u64,
+    /// Total memory, in bytes.
+    pub memory_total: u64,
+    /// Network bytes received and transmitted per second.
+    pub network: u64,
+}
+
+#[derive(Debug, Default)]
+struct StatsState {
+    sample: SystemSample,
+    network_history: VecDeque<u64>,
+    system: Option<System>,
+    networks: Option<Networks>,
+    refreshed: Option<Instant>,
+}
+
+/// SystemStats samples CPU, memory, and network usage and provides widgets
+/// to display them: CpuBars, MemoryGauge, and NetworkSparkline.
+///
+/// SystemStats::refresh reads usage with the sysinfo crate, on every
+/// platform it supports. Samples can also be recorded directly with
+/// SystemStats::record, for instance from another source of metrics.
+///
+/// SystemStats is meant to be inserted as a resource and refreshed
+/// periodically. SystemStats::refresh_every can be called each time a
+/// component is drawn, along with ViewContext::animate to draw it again.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use arkham::{components::SystemStats, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_resource(SystemStats::new())
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, stats: Res<SystemStats>) {
+///     stats.refresh_every(Duration::from_secs(1));
+///     ctx.animate(Duration::from_secs(1));
+///     let cores = stats.sample().cpus.len();
+///     ctx.component(((0, 0), (40, cores)), stats.cpu_bars());
+///     ctx.component(((0, cores + 1), (40, 1)), stats.memory_gauge());
+///     ctx.component(((0, cores + 3), (40, 4)), stats.network_sparkline());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SystemStats {
+    state: Rc<RefCell<StatsState>>,
+}
+
+impl SystemStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most recent sample.
+    pub fn sample(&self) -> SystemSample {
+        self.state.borrow().sample.clone()
+    }
+
+    /// Network usage for recent samples, oldest first.
+    pub fn network_history(&self) -> Vec<u64> {
+        self.state
+            .borrow()
+            .network_history
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Record a sample of system usage.
+    pub fn record(&self, sample: SystemSample) {
+        let mut state = self.state.borrow_mut();
+        if state.network_history.len() == HISTORY {
+            state.network_history.pop_front();
+        }
+        state.network_history.push_back(sample.network);
+        state.sample = sample;
+    }
+
+    /// Sample the current system usage. CPU and network usage are measured
+    /// since the previous refresh, so the first refresh reports no usage.
+    ///
+    /// On platforms sysinfo does not support the sample reports no usage.
+    pub fn refresh(&self) {
+        let now = Instant::now();
+        let (cpus, memory_used, memory_total, network) = {
+            let mut state = self.state.borrow_mut();
+            let elapsed = state.refreshed.map(|at| now.duration_since(at));
+            state.refreshed = Some(now);
+
+            let system = state.system.get_or_insert_with(System::new);
+            system.refresh_cpu_usage();
+            system.refresh_memory();
+            let cpus = system
+                .cpus()
+                .iter()
+                .map(|cpu| match elapsed {
+                    Some(_) => (cpu.cpu_usage() as f64 / 100.0).clamp(0.0, 1.0),
+                    None => 0.0,
+                })
+                .collect();
+            let (memory_used, memory_total) = (system.used_memory(), system.total_memory());
+
+            let networks = state
+                .networks
+                .get_or_insert_with(Networks::new_with_refreshed_list);
+            networks.refresh(true);
+            let transferred: u64 = networks
+                .iter()
+                .filter(|(name, _)| !matches!(name.as_str(), "lo" | "lo0"))
+                .map(|(_, data)| data.received() + data.transmitted())
+                .sum();
+            let network = match elapsed {
+                Some(elapsed) => (transferred as f64 / elapsed.as_secs_f64().max(0.001)) as u64,
+                None => 0,
+            };
+            (cpus, memory_used, memory_total, network)
+        };
+
+        self.record(SystemSample {
+            cpus,
+            memory_used,
+            memory_total,
+            network,
+        });
+    }
+
+    /// Sample the current system usage if at least an interval has passed
+    /// since the previous refresh. Returns true if a sample was taken.
+    ///
+    /// CPU usage is only accurate when samples are at least
+    /// sysinfo::MINIMUM_CPU_UPDATE_INTERVAL apart.
+    pub fn refresh_every(&self, interval: Duration) -> bool {
+        let due = self
+            .state
+            .borrow()
+            .refreshed
+            .is_none_or(|at| at.elapsed() >= interval);
+        if due {
+            self.refresh();
+        }
+        due
+    }
+
+    /// A widget displaying a bar for each CPU core.
+    pub fn cpu_bars(&self) -> CpuBars {
+        CpuBars {
+            cpus: self.sample().cpus,
+        }
+    }
+
+    /// A widget displaying memory usage.
+    pub fn memory_gauge(&self) -> MemoryGauge {
+        let sample = self.sample();
+        MemoryGauge {
+            used: sample.memory_used,
+            total: sample.memory_total,
+        }
+    }
+
+    /// A widget displaying recent network usage.
+    pub fn network_sparkline(&self) -> NetworkSparkline {
+        NetworkSparkline {
+            history: self.network_history(),
+        }
+    }
+}
+
+/// Format a number of bytes with a binary unit suffix.
+fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "K", "M", "G", "T"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, units[0])
+    } else {
+        format!("{:.1}{}", value, units[unit])
+    }
+}
+
+/// The color of a bar based on how full it is.
+fn usage_color(theme: &Theme, usage: f64) -> Color {
+    if usage >= 0.8 {
+        theme.error
+    } else if usage >= 0.5 {
+        theme.warning
+    } else {
+        theme.success
+    }
+}
+
+fn bar(usage: f64, width: usize, symbols: &SymbolSet) -> String {
+    let bars = symbols.bars_horizontal;
+    partial_fill(usage, width, bars)
+        .into_iter()
+        .map(|c| if c == bars[0] { symbols.block_light } else { c })
+        .collect()
+}
+
+/// Displays the usage of each CPU core as a horizontal bar, one core per
+/// row.
+#[derive(Debug, Clone, Default)]
+pub struct CpuBars {
+    cpus: Vec<f64>,
+}
+
+impl CpuBars {
+    pub fn new(cpus: Vec<f64>) -> Self {
+        Self { cpus }
+    }
+}
+
+impl Callable<()> for CpuBars {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let size = ctx.size();
+        let label_width = format!("{}", self.cpus.len().saturating_sub(1)).len() + 3;
+        let bar_width = size.width.saturating_sub(label_width + 6);
+        for (idx, &usage) in self.cpus.iter().take(size.height).enumerate() {
+            let mut line = Runes::from(format!("{:<label_width$} ", format!("cpu{}", idx)));
+            line.add(
+                bar(usage, bar_width, &symbols)
+                    .to_runes()
+                    .fg(usage_color(&theme, usage)),
+            );
+            line.add(format!(" {:>3.0}%", usage * 100.0));
+            ctx.insert((0, idx), line);
+        }
+    }
+}
+
+/// Displays memory usage as a horizontal bar followed by the amount used.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryGauge {
+    used: u64,
+    total: u64,
+}
+
+impl MemoryGauge {
+    pub fn new(used: u64, total: u64) -> Self {
+        Self { used, total }
+    }
+}
+
+impl Callable<()> for MemoryGauge {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let usage = if self.total == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.total as f64
+        };
+        let label = format!(" {}/{}", format_bytes(self.used), format_bytes(self.total));
+        let bar_width = ctx.size().width.saturating_sub(label.len() + 4);
+        let mut line = Runes::from("mem ");
+        line.add(
+            bar(usage, bar_width, &symbols)
+                .to_runes()
+                .fg(usage_color(&theme, usage)),
+        );
+        line.add(label);
+        ctx.insert(0, line);
+    }
+}
+
+/// Displays recent network usage as vertical bars scaled to the busiest
+/// sample, with the current rate on the first row.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkSparkline {
+    history: Vec<u64>,
+}
+
+impl NetworkSparkline {
+    pub fn new(history: Vec<u64>) -> Self {
+        Self { history }
+    }
+}
+
+impl Callable<()> for NetworkSparkline {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let theme = ctx.theme();
+        let size = ctx.size();
+        let current = self.history.last().copied().unwrap_or_default();
+        ctx.insert(0, format!("net {}/s", format_bytes(current)));
+
+        Sparkline::new(self.history.iter().map(|&value| value as f64))
+            .color(theme.info)
+            .draw(ctx, ((0, 1), (size.width, size.height.saturating_sub(1))));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{SystemSample, SystemStats};
+
+    #[test]
+    fn test_refresh_every() {
+        let stats = SystemStats::new();
+        assert!(stats.refresh_every(Duration::from_secs(60)));
+        assert!(!stats.refresh_every(Duration::from_secs(60)));
+        let sample = stats.sample();
+        assert!(sample.cpus.iter().all(|&usage| usage == 0.0));
+        assert_eq!(sample.network, 0);
+        assert_eq!(stats.network_history().len(), 1);
+        assert!(stats.refresh_every(Duration::ZERO));
+        assert!(stats
+            .sample()
+            .cpus
+            .iter()
+            .all(|usage| (0.0..=1.0).contains(usage)));
+    }
+
+    #[test]
+    fn test_widgets() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let stats = SystemStats::new();
+        stats.record(SystemSample {
+            cpus: vec![0.5, 1.0],
+            memory_used: 512 * 1024 * 1024,
+            memory_total: 1024 * 1024 * 1024,
+            network: 2048,
+        });
+        ctx.component(((0, 0), (16, 2)), stats.cpu_bars());
+        ctx.component(((0, 2), (20, 1)), stats.memory_gauge());
+        ctx.component(((0, 3), (4, 2)), stats.network_sparkline());
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(5).collect();
+        assert_eq!(lines[0].trim_end(), "cpu0 ███░░░  50%");
+        assert_eq!(lines[1].trim_end(), "cpu1 ██████ 100%");
+        assert_eq!(lines[2].trim_end(), "mem ██░░ 512.0M/1.0G");
+        assert_eq!(lines[4].trim_end(), "█");
+    }
+}
@@ -0,0 +1,601 @@
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    measure,
+    runes::Runes,
+    search::{SearchFollower, SearchState},
+};
+
+use super::{badge, Severity};
+
+/// Chooses the severity of a cell drawn as a badge, or None to draw the cell
+/// as plain text. See Table::badge_column.
+type BadgeFn = fn(&str) -> Option<Severity>;
+
+/// The direction rows are sorted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// How the width of a table column is determined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// Wide enough for the header and every cell in the column.
+    #[default]
+    Auto,
+    /// A fixed number of cells.
+    Fixed(usize),
+    /// A percentage of the table's width.
+    Percent(u16),
+    /// A share of the width left over once every other column is sized,
+    /// divided between fill columns in proportion to their weights.
+    Fill(u16),
+}
+
+#[derive(Debug, Default)]
+struct TableState {
+    headers: Vec<String>,
+    constraints: Vec<ColumnWidth>,
+    /// The width the table was last drawn with.
+    width: usize,
+    rows: Vec<Vec<String>>,
+    sort: Option<(usize, SortOrder)>,
+    /// Indexes into rows in display order.
+    order: Vec<usize>,
+    selected: usize,
+    offset: usize,
+    first_column: usize,
+    /// Columns whose cells are drawn as badges, with the function choosing
+    /// the severity of each cell.
+    badges: Vec<(usize, BadgeFn)>,
+    search: SearchFollower,
+}
+
+impl TableState {
+    fn resort(&mut self) {
+        self.search.invalidate();
+        self.order = (0..self.rows.len()).collect();
+        if let Some((column, order)) = self.sort {
+            let rows = &self.rows;
+            self.order.sort_by(|&a, &b| {
+                let a = rows[a].get(column).map(String::as_str).unwrap_or("");
+                let b = rows[b].get(column).map(String::as_str).unwrap_or("");
+                let ordering = compare(a, b);
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+    }
+
+    /// The width needed for a column's header, the sort indicator, and
+    /// every cell.
+    fn content_width(&self, idx: usize) -> usize {
+        let severity = self.badge_severity(idx);
+        self.rows
+            .iter()
+            .filter_map(|row| row.get(idx))
+            .map(|cell| match severity.and_then(|f| f(cell)) {
+                Some(_) => measure::width(cell) + 2,
+                None => measure::width(cell),
+            })
+            .fold(measure::width(&self.headers[idx]) + 2, usize::max)
+    }
+
+    /// The function choosing the severity of cells in a badge column.
+    fn badge_severity(&self, idx: usize) -> Option<BadgeFn> {
+        self.badges
+            .iter()
+            .find(|(column, _)| *column == idx)
+            .map(|(_, f)| *f)
+    }
+
+    /// The width of each column in a table of a given width.
+    fn widths(&self, total: usize) -> Vec<usize> {
+        let constraint = |idx: usize| self.constraints.get(idx).copied().unwrap_or_default();
+        let mut widths: Vec<usize> = (0..self.headers.len())
+            .map(|idx| match constraint(idx) {
+                ColumnWidth::Auto => self.content_width(idx),
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Percent(percent) => total * percent as usize / 100,
+                ColumnWidth::Fill(_) => 0,
+            })
+            .collect();
+
+        let fills: Vec<(usize, usize)> = (0..widths.len())
+            .filter_map(|idx| match constraint(idx) {
+                ColumnWidth::Fill(weight) => Some((idx, weight as usize)),
+                _ => None,
+            })
+            .collect();
+        let weights: usize = fills.iter().map(|(_, weight)| weight).sum();
+        if weights > 0 {
+            let separators = widths.len().saturating_sub(1);
+            let mut remaining = total.saturating_sub(widths.iter().sum::<usize>() + separators);
+            let mut weights_left = weights;
+            for (idx, weight) in fills {
+                let width = remaining * weight / weights_left.max(1);
+                widths[idx] = width;
+                remaining -= width;
+                weights_left -= weight;
+            }
+        }
+        widths
+    }
+}
+
+/// Compare two cells, ordering numbers before text. Numbers are compared
+/// numerically and text lexically, so mixed columns still sort in a total
+/// order.
+fn compare(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Table displays rows of text in aligned columns beneath a header.
+///
+/// Columns are sized to fit their contents by default. Table::widths sets a
+/// fixed width, a percentage of the table's width, or a share of the
+/// remaining width for each column, and text that does not fit is clipped.
+///
+/// The header remains fixed while the rows scroll. Rows can be sorted by any
+/// column, and when the columns are wider than the table it scrolls
+/// horizontally one column at a time.
+///
+/// Table::handle_keys provides the key bindings: Up and Down move the
+/// selected row, Left and Right scroll horizontally, and the number keys
+/// sort by the corresponding column, reversing the order if the table is
+/// already sorted by it. Table::column_at can be used to sort by a column
+/// that was clicked. See Table::search for searching the rows.
+///
+/// ```no_run
+/// use arkham::{components::Table, prelude::*};
+///
+/// fn main() {
+///     let table = Table::new(["Name", "Size", "Modified"])
+///         .row(["readme.md", "2048", "2024-01-02"])
+///         .row(["main.rs", "512", "2024-02-10"]);
+///     App::new(root).insert_resource(table).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, table: Res<Table>, kb: Res<Keyboard>) {
+///     table.handle_keys(&kb);
+///     let size = ctx.size();
+///     ctx.component(size, table.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    state: Rc<RefCell<TableState>>,
+}
+
+impl Table {
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let table = Self::default();
+        table.state.borrow_mut().headers = headers.into_iter().map(|h| h.to_string()).collect();
+        table
+    }
+
+    /// Search the rows with a shared SearchState. Matches are highlighted,
+    /// and the row containing the current match is selected as it changes.
+    /// Table::handle_keys also applies the search key bindings.
+    pub fn search(self, search: &SearchState) -> Self {
+        self.state.borrow_mut().search.search = Some(search.clone());
+        self
+    }
+
+    /// Set how the width of each column is determined, in column order.
+    /// Columns without a constraint are sized to fit their contents.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::components::{ColumnWidth, Table};
+    ///
+    /// let table = Table::new(["Name", "Description", "Size"]).widths([
+    ///     ColumnWidth::Percent(25),
+    ///     ColumnWidth::Fill(1),
+    ///     ColumnWidth::Fixed(8),
+    /// ]);
+    /// ```
+    pub fn widths<I>(self, widths: I) -> Self
+    where
+        I: IntoIterator<Item = ColumnWidth>,
+    {
+        self.state.borrow_mut().constraints = widths.into_iter().collect();
+        self
+    }
+
+    /// Set how the width of a single column is determined.
+    pub fn set_column_width(&self, column: usize, width: ColumnWidth) {
+        let mut state = self.state.borrow_mut();
+        if column >= state.headers.len() {
+            return;
+        }
+        if state.constraints.len() <= column {
+            state.constraints.resize(column + 1, ColumnWidth::Auto);
+        }
+        state.constraints[column] = width;
+    }
+
+    /// Draw the cells of a column as badges. The function returns the
+    /// severity of each cell, or None to draw the cell as plain text.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::components::{Severity, Table};
+    ///
+    /// let table = Table::new(["Service", "Status"])
+    ///     .row(["api", "up"])
+    ///     .row(["db", "down"])
+    ///     .badge_column(1, |status| match status {
+    ///         "up" => Some(Severity::Success),
+    ///         "down" => Some(Severity::Error),
+    ///         _ => None,
+    ///     });
+    /// ```
+    pub fn badge_column(self, column: usize, severity: BadgeFn) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.badges.retain(|(c, _)| *c != column);
+            state.badges.push((column, severity));
+        }
+        self
+    }
+
+    /// Add a row to the end of the table.
+    pub fn row<I, S>(self, row: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.push_row(row);
+        self
+    }
+
+    /// Add a row to the end of the table.
+    pub fn push_row<I, S>(&self, row: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let mut state = self.state.borrow_mut();
+        state
+            .rows
+            .push(row.into_iter().map(|c| c.to_string()).collect());
+        state.resort();
+    }
+
+    /// Replace every row in the table. The current sort is kept.
+    pub fn set_rows(&self, rows: Vec<Vec<String>>) {
+        let mut state = self.state.borrow_mut();
+        state.rows = rows;
+        state.resort();
+        state.selected = state.selected.min(state.rows.len().saturating_sub(1));
+    }
+
+    /// Sort the rows by a column. If the table is already sorted by the
+    /// column the order is reversed.
+    pub fn sort_by(&self, column: usize) {
+        let mut state = self.state.borrow_mut();
+        if column >= state.headers.len() {
+            return;
+        }
+        state.sort = match state.sort {
+            Some((c, SortOrder::Ascending)) if c == column => Some((column, SortOrder::Descending)),
+            _ => Some((column, SortOrder::Ascending)),
+        };
+        state.resort();
+    }
+
+    /// The column and order the rows are sorted by.
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.state.borrow().sort
+    }
+
+    /// The index of the selected row, in the order the rows were added.
+    pub fn selected(&self) -> Option<usize> {
+        let state = self.state.borrow();
+        state.order.get(state.selected).copied()
+    }
+
+    /// The cells of the selected row.
+    pub fn selected_row(&self) -> Option<Vec<String>> {
+        let idx = self.selected()?;
+        Some(self.state.borrow().rows[idx].clone())
+    }
+
+    /// The column displayed at a horizontal offset from the left edge of the
+    /// table, taking horizontal scrolling into account.
+    pub fn column_at(&self, x: usize) -> Option<usize> {
+        let state = self.state.borrow();
+        let mut left = 0;
+        for (idx, width) in state
+            .widths(state.width)
+            .into_iter()
+            .enumerate()
+            .skip(state.first_column)
+        {
+            if x < left + width {
+                return Some(idx);
+            }
+            left += width + 1;
+            if x < left {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        let search = self.state.borrow().search.search.clone();
+        if search.is_some_and(|search| search.handle_keys(kb)) {
+            return true;
+        }
+        let code = kb.code();
+        if let Some(KeyCode::Char(c @ '1'..='9')) = code {
+            self.sort_by(c as usize - '1' as usize);
+            kb.reset();
+            return true;
+        }
+        let mut state = self.state.borrow_mut();
+        match code {
+            Some(KeyCode::Up) => state.selected = state.selected.saturating_sub(1),
+            Some(KeyCode::Down) => {
+                state.selected = (state.selected + 1).min(state.rows.len().saturating_sub(1))
+            }
+            Some(KeyCode::Left) => state.first_column = state.first_column.saturating_sub(1),
+            Some(KeyCode::Right) => {
+                state.first_column =
+                    (state.first_column + 1).min(state.headers.len().saturating_sub(1))
+            }
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the table within a region of a context.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let mut state = self.state.borrow_mut();
+        state.width = rect.size.width;
+        let widths = state.widths(rect.size.width);
+
+        let TableState {
+            rows,
+            order,
+            search,
+            ..
+        } = &mut *state;
+        // Cells are joined with tabs so that matches do not span columns.
+        if let Some(row) = search.follow(|| order.iter().map(|&idx| rows[idx].join("\t"))) {
+            state.selected = row;
+        }
+        let search = state.search.active();
+
+        let rows = rect.size.height - 1;
+        if state.selected < state.offset {
+            state.offset = state.selected;
+        } else if rows > 0 && state.selected >= state.offset + rows {
+            state.offset = state.selected + 1 - rows;
+        }
+
+        let mut x = rect.pos.x;
+        let right = rect.pos.x + rect.size.width;
+        for (idx, width) in widths.iter().enumerate().skip(state.first_column) {
+            if x >= right {
+                break;
+            }
+            if *width == 0 {
+                x += 1;
+                continue;
+            }
+            let clip = (*width).min(right - x);
+            let mut header = state.headers[idx].clone();
+            match state.sort {
+                Some((c, SortOrder::Ascending)) if c == idx => {
+                    header = format!("{} {}", header, symbols.arrow_up)
+                }
+                Some((c, SortOrder::Descending)) if c == idx => {
+                    header = format!("{} {}", header, symbols.arrow_down)
+                }
+                _ => {}
+            }
+            let header: String = header.chars().take(clip).collect();
+            ctx.insert((x, rect.pos.y), Runes::from(header).fg(theme.accent).bold());
+
+            let severity = state.badge_severity(idx);
+            for (row, &data) in state.order.iter().skip(state.offset).take(rows).enumerate() {
+                let cell = state.rows[data].get(idx).map(String::as_str).unwrap_or("");
+                let pos = (x, rect.pos.y + row + 1);
+                if let Some(severity) = severity.and_then(|f| f(cell)) {
+                    let runes = badge(cell, severity).to_runes(&theme);
+                    ctx.insert(pos, Runes::new(runes.iter().take(clip).copied().collect()));
+                } else {
+                    let cell: String = cell.chars().take(clip).collect();
+                    match &search {
+                        Some(search) => ctx.insert(pos, search.highlight(cell.into(), &theme)),
+                        None => ctx.insert(pos, cell.as_str()),
+                    }
+                }
+            }
+            x += width + 1;
+        }
+
+        if let Some(row) = state.selected.checked_sub(state.offset) {
+            if row < rows && !state.rows.is_empty() {
+                let line = ((rect.pos.x, rect.pos.y + row + 1), (rect.size.width, 1));
+                for pos in Rect::from(line).cells() {
+                    if let Some(rune) = ctx.view.get_mut(pos.y).and_then(|l| l.get_mut(pos.x)) {
+                        rune.fg = Some(theme.fg_selection);
+                        rune.bg = Some(theme.bg_selection);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Callable<()> for Table {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{input::Keyboard, search::SearchState};
+
+    use super::{ColumnWidth, Severity, SortOrder, Table};
+
+    fn table() -> Table {
+        Table::new(["Name", "Size"])
+            .row(["b", "10"])
+            .row(["a", "9"])
+            .row(["c", "100"])
+    }
+
+    #[test]
+    fn test_sort() {
+        let table = table();
+        table.handle_keys(&Keyboard::with_key(KeyCode::Char('2')));
+        assert_eq!(table.sort(), Some((1, SortOrder::Ascending)));
+        assert_eq!(table.selected_row(), Some(vec!["a".into(), "9".into()]));
+        table.sort_by(1);
+        assert_eq!(table.selected(), Some(2));
+        table.sort_by(0);
+        assert_eq!(table.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_sort_mixed_column() {
+        let table = Table::new(["Value"]);
+        for cell in ["1a", "10", "b", "2", "NaN", "-3", "1a", "x"] {
+            table.push_row([cell]);
+        }
+        table.sort_by(0);
+        let cells: Vec<String> = (0..8)
+            .map(|_| {
+                let cell = table.selected_row().unwrap().remove(0);
+                table.handle_keys(&Keyboard::with_key(KeyCode::Down));
+                cell
+            })
+            .collect();
+        assert_eq!(cells, ["-3", "2", "10", "NaN", "1a", "1a", "b", "x"]);
+    }
+
+    #[test]
+    fn test_draw_scrolls() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let table = table();
+        table.sort_by(0);
+        table.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        table.handle_keys(&Keyboard::with_key(KeyCode::Down));
+        table.draw(&mut ctx, ((0, 0), (14, 3)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(3).collect();
+        assert_eq!(lines[0].trim_end(), "Name ↑ Size");
+        assert_eq!(lines[1].trim_end(), "b      10");
+        assert_eq!(lines[2].trim_end(), "c      100");
+        assert_eq!(ctx.view[2][0].bg, Some(ctx.theme().bg_selection));
+
+        table.handle_keys(&Keyboard::with_key(KeyCode::Right));
+        assert_eq!(table.column_at(0), Some(1));
+        assert_eq!(table.column_at(7), None);
+    }
+
+    #[test]
+    fn test_search() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let theme = ctx.theme();
+        let search = SearchState::new();
+        let table = table().search(&search);
+        for c in "/1".chars() {
+            assert!(table.handle_keys(&Keyboard::with_key(KeyCode::Char(c))));
+        }
+        // The number key was typed into the query rather than sorting.
+        assert_eq!(table.sort(), None);
+        table.handle_keys(&Keyboard::with_key(KeyCode::Enter));
+        table.draw(&mut ctx, ((0, 0), (14, 4)));
+        assert_eq!(table.selected(), Some(0));
+
+        table.handle_keys(&Keyboard::with_key(KeyCode::Char('n')));
+        let mut ctx = crate::context::tests::context_fixture();
+        table.draw(&mut ctx, ((0, 0), (14, 4)));
+        assert_eq!(table.selected(), Some(2));
+        assert_eq!(ctx.view[1][7].bg, Some(theme.warning));
+        assert_ne!(ctx.view[1][8].bg, Some(theme.warning));
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let table = Table::new(["A", "B", "C", "D"]).widths([
+            ColumnWidth::Fixed(4),
+            ColumnWidth::Percent(20),
+            ColumnWidth::Fill(1),
+            ColumnWidth::Fill(2),
+        ]);
+        // 4 + 6 + 3 separators leaves 17 cells for the fill columns.
+        assert_eq!(table.state.borrow().widths(30), vec![4, 6, 5, 12]);
+
+        table.set_column_width(3, ColumnWidth::Auto);
+        assert_eq!(table.state.borrow().widths(30), vec![4, 6, 14, 3]);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        let table = Table::new(["Name", "Size"])
+            .widths([ColumnWidth::Fixed(3), ColumnWidth::Fill(1)])
+            .row(["readme", "10"]);
+        table.draw(&mut ctx, ((0, 0), (10, 2)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(2).collect();
+        assert_eq!(lines[0].trim_end(), "Nam Size");
+        assert_eq!(lines[1].trim_end(), "rea 10");
+        assert_eq!(table.column_at(5), Some(1));
+    }
+
+    #[test]
+    fn test_badge_column() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let theme = ctx.theme();
+        let table = Table::new(["Job", "State"])
+            .row(["test", "?"])
+            .row(["build", "ok"])
+            .badge_column(1, |state| (state == "ok").then_some(Severity::Success));
+        table.draw(&mut ctx, ((0, 0), (14, 3)));
+        let text = ctx.view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().take(3).collect();
+        assert_eq!(lines[1].trim_end(), "test  ?");
+        assert_eq!(lines[2].trim_end(), "build  ok");
+        assert_eq!(ctx.view[2][6].bg, Some(theme.success));
+        assert_eq!(ctx.view[2][9].bg, Some(theme.success));
+    }
+}
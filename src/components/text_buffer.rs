@@ -0,0 +1,258 @@
+use std::{fmt, ops::Range};
+
+use ropey::Rope;
+
+/// A change made to a TextBuffer, recorded so that it can be undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Edit {
+    Insert { at: usize, text: String },
+    Delete { at: usize, text: String },
+}
+
+impl Edit {
+    /// The edit that reverses this one.
+    fn inverse(&self) -> Edit {
+        match self {
+            Edit::Insert { at, text } => Edit::Delete {
+                at: *at,
+                text: text.clone(),
+            },
+            Edit::Delete { at, text } => Edit::Insert {
+                at: *at,
+                text: text.clone(),
+            },
+        }
+    }
+}
+
+/// TextBuffer stores text in a rope, so that edits anywhere in a large
+/// document are cheap, along with the history of edits made to it.
+///
+/// Positions are measured in characters. Lines are split at line breaks,
+/// and the lines returned do not include their line ending.
+///
+/// Consecutive single line insertions, such as typing, are merged into a
+/// single step of the undo history.
+///
+/// This requires the `rope` feature.
+///
+/// ```
+/// use arkham::components::TextBuffer;
+///
+/// let mut buffer = TextBuffer::from("fn main() {\n}");
+/// buffer.insert(12, "    todo!();\n");
+/// assert_eq!(buffer.line(1), "    todo!();");
+/// buffer.delete(0..3);
+/// assert_eq!(buffer.line(0), "main() {");
+/// buffer.undo();
+/// buffer.undo();
+/// assert_eq!(buffer.to_string(), "fn main() {\n}");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextBuffer {
+    rope: Rope,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl TextBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of characters in the buffer.
+    pub fn len_chars(&self) -> usize {
+        self.rope.len_chars()
+    }
+
+    /// The number of lines in the buffer. An empty buffer has a single
+    /// empty line, as does the text after a trailing line feed.
+    pub fn len_lines(&self) -> usize {
+        self.rope.len_lines()
+    }
+
+    /// Returns true if the buffer contains no text.
+    pub fn is_empty(&self) -> bool {
+        self.len_chars() == 0
+    }
+
+    /// The text of a line without its line ending, or an empty string if
+    /// the line is past the end of the buffer.
+    pub fn line(&self, idx: usize) -> String {
+        if idx >= self.len_lines() {
+            return String::new();
+        }
+        let line = self.rope.line(idx).to_string();
+        line.trim_end_matches(['\n', '\r']).to_string()
+    }
+
+    /// The text within a range of characters. The range is clamped to the
+    /// buffer.
+    pub fn slice(&self, range: Range<usize>) -> String {
+        let range = self.clamp(range);
+        self.rope.slice(range).to_string()
+    }
+
+    /// The index of the line containing a character.
+    pub fn char_to_line(&self, char_idx: usize) -> usize {
+        self.rope.char_to_line(char_idx.min(self.len_chars()))
+    }
+
+    /// The index of the first character of a line.
+    pub fn line_to_char(&self, line: usize) -> usize {
+        self.rope.line_to_char(line.min(self.len_lines()))
+    }
+
+    /// Insert text before a character. Positions past the end of the buffer
+    /// insert at the end.
+    pub fn insert(&mut self, char_idx: usize, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let at = char_idx.min(self.len_chars());
+        self.apply(&Edit::Insert {
+            at,
+            text: text.to_string(),
+        });
+        self.redo.clear();
+        if let Some(Edit::Insert {
+            at: last,
+            text: prev,
+        }) = self.undo.last_mut()
+        {
+            if *last + prev.chars().count() == at && !text.contains('\n') && !prev.ends_with('\n') {
+                prev.push_str(text);
+                return;
+            }
+        }
+        self.undo.push(Edit::Insert {
+            at,
+            text: text.to_string(),
+        });
+    }
+
+    /// Delete a range of characters. The range is clamped to the buffer.
+    pub fn delete(&mut self, range: Range<usize>) {
+        let range = self.clamp(range);
+        if range.is_empty() {
+            return;
+        }
+        let edit = Edit::Delete {
+            at: range.start,
+            text: self.rope.slice(range).to_string(),
+        };
+        self.apply(&edit);
+        self.redo.clear();
+        self.undo.push(edit);
+    }
+
+    /// Undo the most recent edit. Returns false if there was nothing to
+    /// undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+        self.apply(&edit.inverse());
+        self.redo.push(edit);
+        true
+    }
+
+    /// Redo the most recently undone edit. Returns false if there was
+    /// nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+        self.apply(&edit);
+        self.undo.push(edit);
+        true
+    }
+
+    /// Replace the text, clearing the edit history.
+    pub fn set_text(&mut self, text: &str) {
+        self.rope = Rope::from_str(text);
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn apply(&mut self, edit: &Edit) {
+        match edit {
+            Edit::Insert { at, text } => self.rope.insert(*at, text),
+            Edit::Delete { at, text } => self.rope.remove(*at..*at + text.chars().count()),
+        }
+    }
+
+    fn clamp(&self, range: Range<usize>) -> Range<usize> {
+        let len = self.len_chars();
+        range.start.min(len)..range.end.clamp(range.start.min(len), len)
+    }
+}
+
+impl From<&str> for TextBuffer {
+    fn from(text: &str) -> Self {
+        Self {
+            rope: Rope::from_str(text),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<String> for TextBuffer {
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+impl fmt::Display for TextBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.rope.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextBuffer;
+
+    #[test]
+    fn test_edits() {
+        let mut buffer = TextBuffer::from("one\ntwo\r\nthree");
+        assert_eq!(buffer.len_lines(), 3);
+        assert_eq!(buffer.line(1), "two");
+        assert_eq!(buffer.line(9), "");
+        assert_eq!(buffer.char_to_line(5), 1);
+        assert_eq!(buffer.line_to_char(2), 9);
+
+        buffer.delete(3..100);
+        assert_eq!(buffer.to_string(), "one");
+        buffer.insert(99, "!");
+        assert_eq!(buffer.slice(2..10), "e!");
+        assert!(buffer.undo());
+        assert!(buffer.undo());
+        assert_eq!(buffer.to_string(), "one\ntwo\r\nthree");
+        assert!(!buffer.undo());
+        assert!(buffer.redo());
+        assert_eq!(buffer.to_string(), "one");
+    }
+
+    #[test]
+    fn test_typing_is_one_undo_step() {
+        let mut buffer = TextBuffer::new();
+        for (idx, c) in "hello".chars().enumerate() {
+            buffer.insert(idx, &c.to_string());
+        }
+        buffer.insert(5, "\n");
+        buffer.insert(6, "w");
+        assert_eq!(buffer.to_string(), "hello\nw");
+        buffer.undo();
+        buffer.undo();
+        assert_eq!(buffer.to_string(), "hello");
+        buffer.undo();
+        assert!(buffer.is_empty());
+        // A new edit clears the redo history.
+        buffer.insert(0, "x");
+        assert!(!buffer.redo());
+    }
+}
@@ -0,0 +1,445 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    components::InputFormatter, container::Callable, context::ViewContext, geometry::Rect,
+    input::Keyboard, pointer::CursorShape, runes::Runes,
+};
+
+/// The number of killed strings kept for yanking.
+const KILL_RING_SIZE: usize = 16;
+
+#[derive(Debug, Default)]
+struct TextInputState {
+    value: Vec<char>,
+    cursor: usize,
+    kill_ring: Vec<String>,
+    placeholder: Option<Runes>,
+    blurred: bool,
+    formatters: Vec<Box<dyn InputFormatter>>,
+}
+
+impl TextInputState {
+    /// Remove the characters between two positions, adding them to the
+    /// kill ring.
+    fn kill(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        let killed: String = self.value.drain(start..end).collect();
+        if self.kill_ring.len() == KILL_RING_SIZE {
+            self.kill_ring.remove(0);
+        }
+        self.kill_ring.push(killed);
+        self.cursor = start;
+    }
+
+    /// Insert text typed at the cursor, after passing it through the
+    /// formatters.
+    fn type_text(&mut self, text: String) {
+        let value: String = self.value.iter().collect();
+        let text = self
+            .formatters
+            .iter()
+            .try_fold(text, |text, f| f.format(&value, self.cursor, text));
+        if let Some(text) = text {
+            self.insert(&text);
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        for c in text.chars() {
+            self.value.insert(self.cursor, c);
+            self.cursor += 1;
+        }
+    }
+
+    /// The start of the word before the cursor, where words are separated by
+    /// whitespace.
+    fn rubout_start(&self) -> usize {
+        let mut pos = self.cursor;
+        while pos > 0 && self.value[pos - 1].is_whitespace() {
+            pos -= 1;
+        }
+        while pos > 0 && !self.value[pos - 1].is_whitespace() {
+            pos -= 1;
+        }
+        pos
+    }
+
+    /// The start of the previous alphanumeric word.
+    fn word_back(&self) -> usize {
+        let mut pos = self.cursor;
+        while pos > 0 && !self.value[pos - 1].is_alphanumeric() {
+            pos -= 1;
+        }
+        while pos > 0 && self.value[pos - 1].is_alphanumeric() {
+            pos -= 1;
+        }
+        pos
+    }
+
+    /// The end of the next alphanumeric word.
+    fn word_forward(&self) -> usize {
+        let mut pos = self.cursor;
+        while pos < self.value.len() && !self.value[pos].is_alphanumeric() {
+            pos += 1;
+        }
+        while pos < self.value.len() && self.value[pos].is_alphanumeric() {
+            pos += 1;
+        }
+        pos
+    }
+}
+
+/// TextInput is a single line text field with readline style editing.
+///
+/// TextInput::handle_keys provides the key bindings:
+///
+/// - Left, Right, Home, End, Ctrl+A and Ctrl+E move the cursor.
+/// - Alt+B and Alt+F move backward and forward by word.
+/// - Backspace and Delete remove a character.
+/// - Ctrl+W kills the word before the cursor.
+/// - Ctrl+U and Ctrl+K kill to the start and end of the line.
+/// - Ctrl+Y yanks the most recently killed text back at the cursor.
+///
+/// Pasted text is inserted at the cursor, with line breaks replaced by
+/// spaces.
+///
+/// Typed, pasted, and yanked text can be restricted or transformed by adding
+/// formatters, such as Numeric, MaxLength, or Mask.
+///
+/// TextInput keeps its value between frames and is meant to be inserted as a
+/// resource, or stored in state, and drawn with ViewContext::component.
+///
+/// By default App quits when `q` is pressed, before components receive the
+/// key, so `q` cannot be typed into an input. Applications with text inputs
+/// should call App::disbale_q_to_quit and quit in another way, such as on
+/// Esc.
+///
+/// ```no_run
+/// use arkham::{components::TextInput, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .disbale_q_to_quit()
+///         .insert_resource(TextInput::new().placeholder("Search"))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, input: Res<TextInput>, kb: Res<Keyboard>) {
+///     if kb.code() == Some(KeyCode::Esc) {
+///         ctx.exit();
+///     }
+///     input.handle_keys(&kb);
+///     ctx.component(((0, 0), (30, 1)), input.get().clone());
+///     ctx.insert((0, 2), format!("Searching for {}", input.value()));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    state: Rc<RefCell<TextInputState>>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set text displayed while the input is empty.
+    pub fn placeholder<R: Into<Runes>>(self, placeholder: R) -> Self {
+        self.state.borrow_mut().placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Add a formatter which transforms or rejects typed text. See
+    /// InputFormatter.
+    pub fn formatter<F: InputFormatter + 'static>(self, formatter: F) -> Self {
+        self.state.borrow_mut().formatters.push(Box::new(formatter));
+        self
+    }
+
+    /// Returns true if every formatter accepts the current value. This can
+    /// be used to highlight invalid fields in a form.
+    pub fn is_valid(&self) -> bool {
+        let state = self.state.borrow();
+        let value: String = state.value.iter().collect();
+        state.formatters.iter().all(|f| f.is_valid(&value))
+    }
+
+    /// The current text of the input.
+    pub fn value(&self) -> String {
+        self.state.borrow().value.iter().collect()
+    }
+
+    /// Replace the text of the input, moving the cursor to the end.
+    pub fn set_value<S: AsRef<str>>(&self, value: S) {
+        let mut state = self.state.borrow_mut();
+        state.value = value.as_ref().chars().collect();
+        state.cursor = state.value.len();
+    }
+
+    /// The position of the cursor, in characters from the start of the text.
+    pub fn cursor(&self) -> usize {
+        self.state.borrow().cursor
+    }
+
+    /// Move the cursor to a position, in characters from the start of the
+    /// text.
+    pub fn set_cursor(&self, cursor: usize) {
+        let mut state = self.state.borrow_mut();
+        state.cursor = cursor.min(state.value.len());
+    }
+
+    /// Killed text, oldest first. The most recently killed text is yanked
+    /// with Ctrl+Y.
+    pub fn kill_ring(&self) -> Vec<String> {
+        self.state.borrow().kill_ring.clone()
+    }
+
+    /// Set whether the input has focus. An input without focus does not
+    /// display its cursor or respond to keys. Inputs have focus by default.
+    pub fn set_focused(&self, focused: bool) {
+        self.state.borrow_mut().blurred = !focused;
+    }
+
+    /// Returns true if the input has focus.
+    pub fn is_focused(&self) -> bool {
+        !self.state.borrow().blurred
+    }
+
+    /// Apply the editing key bindings. Returns true if the key was handled,
+    /// in which case the keyboard is reset so that other components do not
+    /// respond to it.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        if !self.is_focused() {
+            return false;
+        }
+        if let Some(text) = kb.pasted() {
+            let text = text.replace("\r\n", " ").replace(['\r', '\n'], " ");
+            self.state.borrow_mut().type_text(text);
+            kb.reset();
+            return true;
+        }
+        let Some(code) = kb.code() else {
+            return false;
+        };
+        let mut state = self.state.borrow_mut();
+        let len = state.value.len();
+        let cursor = state.cursor;
+        match code {
+            KeyCode::Char('a') if kb.control() => state.cursor = 0,
+            KeyCode::Char('e') if kb.control() => state.cursor = len,
+            KeyCode::Char('b') if kb.alt() => state.cursor = state.word_back(),
+            KeyCode::Char('f') if kb.alt() => state.cursor = state.word_forward(),
+            KeyCode::Char('w') if kb.control() => {
+                let start = state.rubout_start();
+                state.kill(start, cursor);
+            }
+            KeyCode::Char('u') if kb.control() => state.kill(0, cursor),
+            KeyCode::Char('k') if kb.control() => state.kill(cursor, len),
+            KeyCode::Char('y') if kb.control() => {
+                if let Some(text) = state.kill_ring.last().cloned() {
+                    state.type_text(text);
+                }
+            }
+            KeyCode::Char(c) if !kb.control() && !kb.alt() => {
+                state.type_text(c.to_string());
+            }
+            KeyCode::Backspace if cursor > 0 => {
+                state.value.remove(cursor - 1);
+                state.cursor -= 1;
+            }
+            KeyCode::Delete if cursor < len => {
+                state.value.remove(cursor);
+            }
+            KeyCode::Left => state.cursor = cursor.saturating_sub(1),
+            KeyCode::Right => state.cursor = (cursor + 1).min(len),
+            KeyCode::Home => state.cursor = 0,
+            KeyCode::End => state.cursor = len,
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Draw the input within a region of a context. Text that does not fit
+    /// is scrolled horizontally so that the cursor remains visible.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        let theme = ctx.theme();
+        let state = self.state.borrow();
+        let width = rect.size.width;
+        if width == 0 {
+            return;
+        }
+        ctx.cursor_hint(rect, CursorShape::Text);
+
+        // The cells each character occupies, as wide characters take two.
+        let cells: Vec<Runes> = state
+            .value
+            .iter()
+            .map(|c| Runes::from(c.to_string()))
+            .collect();
+        let cursor_cell = cells
+            .get(state.cursor)
+            .cloned()
+            .unwrap_or_else(|| Runes::from(" "));
+
+        // Scroll as little as possible to keep the cursor visible.
+        let mut offset = state.cursor;
+        let mut used = cursor_cell.len();
+        while offset > 0 && used + cells[offset - 1].len() <= width {
+            offset -= 1;
+            used += cells[offset].len();
+        }
+
+        if state.value.is_empty() {
+            if let Some(placeholder) = &state.placeholder {
+                ctx.insert(rect.pos, placeholder.clone().fg(theme.muted));
+            }
+        } else {
+            let mut visible = vec![];
+            for cell in &cells[offset..] {
+                if visible.len() + cell.len() > width {
+                    break;
+                }
+                visible.extend_from_slice(cell);
+            }
+            ctx.insert(rect.pos, Runes::new(visible));
+        }
+
+        if !state.blurred && used <= width {
+            let mut pos = rect.pos;
+            pos.x += cells[offset..state.cursor]
+                .iter()
+                .map(|c| c.len())
+                .sum::<usize>();
+            ctx.insert(pos, cursor_cell.fg(theme.fg_selection).bg(theme.focus));
+        }
+    }
+}
+
+impl Callable<()> for TextInput {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{
+        components::{Mask, MaxLength, Numeric},
+        input::Keyboard,
+    };
+
+    use super::TextInput;
+
+    fn press(input: &TextInput, code: KeyCode, modifiers: KeyModifiers) {
+        input.handle_keys(&Keyboard::with_key(code).with_modifiers(modifiers));
+    }
+
+    #[test]
+    fn test_word_motion() {
+        let input = TextInput::new();
+        input.set_value("foo bar-baz");
+        press(&input, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(input.cursor(), 8);
+        press(&input, KeyCode::Char('b'), KeyModifiers::ALT);
+        assert_eq!(input.cursor(), 4);
+        press(&input, KeyCode::Char('f'), KeyModifiers::ALT);
+        assert_eq!(input.cursor(), 7);
+        press(&input, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(input.cursor(), 0);
+        press(&input, KeyCode::Char('e'), KeyModifiers::CONTROL);
+        assert_eq!(input.cursor(), 11);
+    }
+
+    #[test]
+    fn test_kill_and_yank() {
+        let input = TextInput::new();
+        input.set_value("one two three");
+        press(&input, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "one two ");
+        input.set_cursor(3);
+        press(&input, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "one");
+        press(&input, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "");
+        assert_eq!(input.kill_ring(), vec!["three", " two ", "one"]);
+
+        press(&input, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        press(&input, KeyCode::Char('!'), KeyModifiers::SHIFT);
+        assert_eq!(input.value(), "one!");
+    }
+
+    #[test]
+    fn test_draw_scrolls_to_cursor() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let input = TextInput::new();
+        input.set_value("abcdefgh");
+        input.draw(&mut ctx, ((0, 0), (5, 1)));
+        assert_eq!(&ctx.view.render_text()[..5], "efgh ");
+        assert_eq!(ctx.view[0][4].bg, Some(ctx.theme().focus));
+    }
+
+    #[test]
+    fn test_draw_wide_characters() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let input = TextInput::new();
+        input.set_value("中文字");
+        input.draw(&mut ctx, ((0, 0), (5, 1)));
+        let focus = Some(ctx.theme().focus);
+        let row: String = ctx.view[0][..6]
+            .iter()
+            .map(|r| r.content.unwrap_or('.'))
+            .collect();
+        assert_eq!(row, "文.字. .");
+        assert_eq!(ctx.view[0][4].bg, focus);
+
+        let mut ctx = crate::context::tests::context_fixture();
+        input.set_cursor(1);
+        input.draw(&mut ctx, ((0, 0), (5, 1)));
+        let row: String = ctx.view[0][..6]
+            .iter()
+            .map(|r| r.content.unwrap_or('.'))
+            .collect();
+        assert_eq!(row, "中.文...");
+        assert_eq!((ctx.view[0][2].bg, ctx.view[0][3].bg), (focus, focus));
+        assert!(ctx.view[0][3].continuation);
+    }
+
+    #[test]
+    fn test_formatters() {
+        let input = TextInput::new()
+            .formatter(Mask::new("YYYY-MM-DD"))
+            .formatter(MaxLength(7));
+        for c in "2024x01".chars() {
+            press(&input, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(input.value(), "2024-01");
+        assert!(!input.is_valid());
+
+        let input = TextInput::new().formatter(Numeric::new());
+        press(&input, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(!input.is_valid());
+        press(&input, KeyCode::Char('4'), KeyModifiers::NONE);
+        assert_eq!(input.value(), "4");
+        assert!(input.is_valid());
+    }
+
+    #[test]
+    fn test_paste() {
+        let input = TextInput::new().formatter(MaxLength(12));
+        input.set_value("say ");
+        let kb = Keyboard::with_paste("hello\r\nworld!");
+        assert!(input.handle_keys(&kb));
+        assert_eq!(input.value(), "say hello wo");
+        assert_eq!(kb.pasted(), None);
+    }
+}
@@ -6,19 +6,25 @@ use std::sync::{Arc, RwLock};
 
 use std::{
     any::{Any, TypeId},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::Deref,
 };
 
 pub type ContainerRef = Rc<RefCell<Container>>;
 
-use crate::context::ViewContext;
+use crate::{context::ViewContext, geometry::Rect};
 
 /// The container stores typed resource and state objects and provides
 /// them to component functions.
 #[derive(Default, Debug)]
 pub struct Container {
     bindings: HashMap<TypeId, Box<dyn Any>>,
+    /// The type name of each binding.
+    names: HashMap<TypeId, &'static str>,
+    /// The bindings which hold a default provided by the framework, rather
+    /// than a value bound by the application.
+    defaults: HashSet<TypeId>,
+    frame: HashMap<TypeId, std::rc::Rc<dyn Any>>,
 }
 
 impl Container {
@@ -26,8 +32,49 @@ impl Container {
     /// object to functions executed by Container::call.
     ///
     /// App::insert_ressource and App::isnert_state proxies to this function.
+    ///
+    /// Panics if a value of the same type is already bound, since the first
+    /// value would otherwise be silently discarded. Use
+    /// Container::bind_replace to intentionally override a binding. Defaults
+    /// bound with Container::bind_default are replaced without panicking.
     pub(crate) fn bind<T: Any>(&mut self, val: T) {
-        self.bindings.insert(val.type_id(), Box::new(val));
+        let id = TypeId::of::<T>();
+        if self.bindings.contains_key(&id) && !self.defaults.contains(&id) {
+            panic!(
+                "{} is already bound, use App::replace_resource or App::replace_state to override it",
+                std::any::type_name::<T>()
+            );
+        }
+        self.bind_replace(val);
+    }
+
+    /// Insert a type binding into the container, replacing any existing
+    /// binding of the same type.
+    pub(crate) fn bind_replace<T: Any>(&mut self, val: T) {
+        self.defaults.remove(&TypeId::of::<T>());
+        self.bindings.insert(TypeId::of::<T>(), Box::new(val));
+        self.names
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
+    }
+
+    /// Bind a default value provided by the framework, if no value of the
+    /// same type is bound. The default is only created when it is needed,
+    /// and can be replaced by binding a value with Container::bind, so that
+    /// applications can supply their own resources in place of the
+    /// framework's.
+    pub(crate) fn bind_default<T: Any, F: FnOnce() -> T>(&mut self, default: F) {
+        if !self.bindings.contains_key(&TypeId::of::<T>()) {
+            self.bind_replace(default());
+            self.defaults.insert(TypeId::of::<T>());
+        }
+    }
+
+    /// The type names of every bound resource and state object, sorted
+    /// alphabetically.
+    pub fn type_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.names.values().copied().collect();
+        names.sort_unstable();
+        names
     }
 
     /// Get an object from the store by its type. This is a utility function
@@ -38,8 +85,69 @@ impl Container {
             .get(&TypeId::of::<T>())
             .and_then(|boxed| boxed.downcast_ref())
     }
+
+    /// Insert a value into the frame scope, replacing any value of the same
+    /// type. Frame scoped values are injected with Scoped and are removed
+    /// once the frame has been rendered.
+    pub(crate) fn provide<T: Any>(&mut self, val: T) {
+        self.frame.insert(TypeId::of::<T>(), std::rc::Rc::new(val));
+    }
+
+    /// Remove all values from the frame scope.
+    pub(crate) fn clear_frame(&mut self) {
+        self.frame.clear();
+    }
+
+    /// Get a value provided to the current frame by its type.
+    pub fn get_scoped<T: Any>(&self) -> Option<Scoped<T>> {
+        self.frame
+            .get(&TypeId::of::<T>())
+            .and_then(|val| val.clone().downcast().ok())
+            .map(Scoped)
+    }
+
+    /// Get an object from the store by its type, returning an error naming
+    /// the type if it has not been bound.
+    pub fn try_get<T: Any>(&self) -> Result<&T, InjectionError> {
+        self.get::<T>()
+            .ok_or(InjectionError::NotFound(std::any::type_name::<T>()))
+    }
+}
+
+/// An error produced when a value cannot be provided to a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InjectionError {
+    /// No value of the named type has been bound to the container.
+    NotFound(&'static str),
+    /// No value of the named type has been provided for the current frame.
+    NotProvided(&'static str),
+    /// The named value is already borrowed in a way that conflicts with the
+    /// requested access.
+    AlreadyBorrowed(&'static str),
+}
+
+impl std::fmt::Display for InjectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InjectionError::NotFound(name) => write!(
+                f,
+                "{} has not been bound, insert it with App::insert_resource or App::insert_state",
+                name
+            ),
+            InjectionError::NotProvided(name) => write!(
+                f,
+                "{} has not been provided for this frame, provide it with ViewContext::provide",
+                name
+            ),
+            InjectionError::AlreadyBorrowed(name) => {
+                write!(f, "{} is already borrowed", name)
+            }
+        }
+    }
 }
 
+impl std::error::Error for InjectionError {}
+
 /// A wrapper for state objcets. This internally holds a reference counted
 /// poitner to the object and is used when injecting itno functions.
 #[cfg(not(feature = "sync"))]
@@ -71,11 +179,11 @@ impl<T> State<T> {
     /// assert_eq!(state.get().0, 6);
     /// ```
     #[cfg(feature = "sync")]
-    pub fn get_mut(&self) -> std::sync::RwLockWriteGuard<T> {
+    pub fn get_mut(&self) -> std::sync::RwLockWriteGuard<'_, T> {
         self.0.write().unwrap()
     }
     #[cfg(not(feature = "sync"))]
-    pub fn get_mut(&self) -> std::cell::RefMut<T> {
+    pub fn get_mut(&self) -> std::cell::RefMut<'_, T> {
         RefCell::borrow_mut(&self.0)
     }
 
@@ -89,13 +197,51 @@ impl<T> State<T> {
     /// assert_eq!(state.get().0, 4);
     /// ```
     #[cfg(feature = "sync")]
-    pub fn get(&self) -> std::sync::RwLockReadGuard<T> {
+    pub fn get(&self) -> std::sync::RwLockReadGuard<'_, T> {
         self.0.read().unwrap()
     }
     #[cfg(not(feature = "sync"))]
-    pub fn get(&self) -> std::cell::Ref<T> {
+    pub fn get(&self) -> std::cell::Ref<'_, T> {
         RefCell::borrow(&self.0)
     }
+
+    /// Returns a mutable reference to the underlying state object, or an
+    /// error if it is currently borrowed elsewhere.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// struct MyState(i32);
+    ///
+    /// let state = State::new(MyState(4));
+    /// let value = state.get();
+    /// assert!(state.try_get_mut().is_err());
+    /// ```
+    #[cfg(feature = "sync")]
+    pub fn try_get_mut(&self) -> Result<std::sync::RwLockWriteGuard<'_, T>, InjectionError> {
+        self.0
+            .try_write()
+            .map_err(|_| InjectionError::AlreadyBorrowed(std::any::type_name::<T>()))
+    }
+    #[cfg(not(feature = "sync"))]
+    pub fn try_get_mut(&self) -> Result<std::cell::RefMut<'_, T>, InjectionError> {
+        RefCell::try_borrow_mut(&self.0)
+            .map_err(|_| InjectionError::AlreadyBorrowed(std::any::type_name::<T>()))
+    }
+
+    /// Returns an immutable reference to the underlying state object, or an
+    /// error if it is currently borrowed mutably.
+    #[cfg(feature = "sync")]
+    pub fn try_get(&self) -> Result<std::sync::RwLockReadGuard<'_, T>, InjectionError> {
+        self.0
+            .try_read()
+            .map_err(|_| InjectionError::AlreadyBorrowed(std::any::type_name::<T>()))
+    }
+    #[cfg(not(feature = "sync"))]
+    pub fn try_get(&self) -> Result<std::cell::Ref<'_, T>, InjectionError> {
+        RefCell::try_borrow(&self.0)
+            .map_err(|_| InjectionError::AlreadyBorrowed(std::any::type_name::<T>()))
+    }
 }
 
 impl<T: ?Sized> Clone for State<T> {
@@ -106,7 +252,14 @@ impl<T: ?Sized> Clone for State<T> {
 
 impl<T: ?Sized + 'static> FromContainer for State<T> {
     fn from_container(container: &Container) -> Self {
-        container.get::<Self>().expect("type not found").clone()
+        Self::try_from_container(container).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_from_container(container: &Container) -> Result<Self, InjectionError> {
+        container
+            .get::<Self>()
+            .cloned()
+            .ok_or(InjectionError::NotFound(std::any::type_name::<T>()))
     }
 }
 
@@ -133,6 +286,33 @@ impl<T> Res<T> {
 }
 
 impl<T: ?Sized> Res<T> {
+    /// Create a resource from a boxed value. Unlike Res::new this accepts
+    /// unsized values, allowing a trait object to be bound as a resource and
+    /// injected as Res<dyn Trait>.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// trait Storage {
+    ///     fn load(&self) -> String;
+    /// }
+    ///
+    /// struct Memory;
+    ///
+    /// impl Storage for Memory {
+    ///     fn load(&self) -> String {
+    ///         "saved".to_string()
+    ///     }
+    /// }
+    ///
+    /// let storage: Res<dyn Storage> = Res::from_box(Box::new(Memory));
+    /// assert_eq!(storage.load(), "saved");
+    /// ```
+    pub fn from_box(val: Box<T>) -> Self {
+        Res(val.into())
+    }
+
     pub fn get(&self) -> &T {
         self.0.as_ref()
     }
@@ -164,10 +344,71 @@ impl<T: ?Sized> Deref for Res<T> {
 
 impl<T: ?Sized + 'static> FromContainer for Res<T> {
     fn from_container(container: &Container) -> Self {
+        Self::try_from_container(container).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_from_container(container: &Container) -> Result<Self, InjectionError> {
         container
             .get::<Self>()
-            .expect(&format!("type not found: {}", std::any::type_name::<T>()))
-            .clone()
+            .cloned()
+            .ok_or(InjectionError::NotFound(std::any::type_name::<T>()))
+    }
+}
+
+/// A wrapper for values provided to a single frame with
+/// ViewContext::provide. Scoped values can be injected into any component
+/// rendered after the value was provided during the same frame.
+///
+/// This is useful for data computed at the top of a view which would
+/// otherwise need to be stored in a resource or passed down by hand.
+///
+/// Example:
+/// ```
+/// use arkham::prelude::*;
+///
+/// struct Visible(Vec<String>);
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.provide(Visible(vec!["a".to_string(), "b".to_string()]));
+///     ctx.component(((0, 0), (10, 1)), count);
+/// }
+///
+/// fn count(ctx: &mut ViewContext, visible: Scoped<Visible>) {
+///     ctx.insert(0, format!("{} items", visible.0.len()));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Scoped<T: ?Sized>(std::rc::Rc<T>);
+
+impl<T: ?Sized> Scoped<T> {
+    pub fn get(&self) -> &T {
+        self.0.as_ref()
+    }
+}
+
+impl<T: ?Sized> Clone for Scoped<T> {
+    fn clone(&self) -> Scoped<T> {
+        Scoped(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> Deref for Scoped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: 'static> FromContainer for Scoped<T> {
+    fn from_container(container: &Container) -> Self {
+        Self::try_from_container(container).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_from_container(container: &Container) -> Result<Self, InjectionError> {
+        container
+            .get_scoped::<T>()
+            .ok_or(InjectionError::NotProvided(std::any::type_name::<T>()))
     }
 }
 
@@ -188,10 +429,42 @@ where
     }
 }
 
+/// A type erased component that renders itself into a region of a context.
+/// Used by components that store other components, such as layouts and
+/// window managers.
+pub(crate) type BoxedComponent = std::rc::Rc<dyn Fn(&mut ViewContext, Rect)>;
+
+/// Box a component so it can be stored alongside components of other types.
+/// Arguments are injected from the container each time it is rendered.
+pub(crate) fn boxed_component<F, Args>(f: F) -> BoxedComponent
+where
+    F: Callable<Args> + 'static,
+    Args: FromContainer + 'static,
+{
+    struct Shared<F>(std::rc::Rc<F>);
+
+    impl<F: Callable<Args>, Args> Callable<Args> for Shared<F> {
+        fn call(&self, view: &mut ViewContext, args: Args) {
+            self.0.call(view, args)
+        }
+    }
+
+    let f = std::rc::Rc::new(f);
+    std::rc::Rc::new(move |ctx: &mut ViewContext, rect: Rect| {
+        ctx.component(rect, Shared(f.clone()))
+    })
+}
+
 /// FromContainer must be implmented for objects that can be injected into
 /// component functions. This includes the Res and State structs.
-pub trait FromContainer {
+pub trait FromContainer: Sized {
     fn from_container(container: &Container) -> Self;
+
+    /// Build the value from the container, returning an error describing
+    /// any value that could not be provided instead of panicking.
+    fn try_from_container(container: &Container) -> Result<Self, InjectionError> {
+        Ok(Self::from_container(container))
+    }
 }
 
 impl FromContainer for () {
@@ -234,6 +507,11 @@ macro_rules! tuple_from_tm {
                 fn from_container(container: &Container) -> Self {
                     ($($T::from_container(container),)+)
                 }
+
+                #[inline]
+                fn try_from_container(container: &Container) -> Result<Self, InjectionError> {
+                    Ok(($($T::try_from_container(container)?,)+))
+                }
             }
         };
     }
@@ -250,3 +528,73 @@ tuple_from_tm! { A B C D E F G H I }
 tuple_from_tm! { A B C D E F G H I J }
 tuple_from_tm! { A B C D E F G H I J K }
 tuple_from_tm! { A B C D E F G H I J K L }
+
+#[cfg(test)]
+mod tests {
+    use super::{Container, Res};
+
+    #[test]
+    #[should_panic(expected = "Res<i32> is already bound")]
+    fn test_duplicate_binding() {
+        let mut container = Container::default();
+        container.bind(Res::new(1));
+        container.bind(Res::new(2));
+    }
+
+    #[test]
+    fn test_frame_scope() {
+        let mut container = Container::default();
+        container.provide(String::from("a"));
+        assert_eq!(container.get_scoped::<String>().unwrap().as_str(), "a");
+        container.clear_frame();
+        assert!(container.get_scoped::<String>().is_none());
+    }
+
+    #[test]
+    fn test_trait_object() {
+        use crate::container::FromContainer;
+
+        trait Greeter {
+            fn greet(&self) -> String;
+        }
+
+        struct English;
+
+        impl Greeter for English {
+            fn greet(&self) -> String {
+                "hello".to_string()
+            }
+        }
+
+        let mut container = Container::default();
+        container.bind(Res::<dyn Greeter>::from_box(Box::new(English)));
+        let greeter = Res::<dyn Greeter>::from_container(&container);
+        assert_eq!(greeter.greet(), "hello");
+    }
+
+    #[test]
+    fn test_bind_replace() {
+        let mut container = Container::default();
+        container.bind(Res::new(1));
+        container.bind_replace(Res::new(2));
+        assert_eq!(*container.get::<Res<i32>>().unwrap().get(), 2);
+    }
+
+    #[test]
+    fn test_bind_default() {
+        let mut container = Container::default();
+        container.bind(Res::new(1));
+        container.bind_default(|| Res::new(2));
+        assert_eq!(*container.get::<Res<i32>>().unwrap().get(), 1);
+
+        // A default can be replaced once, after which it is the
+        // application's value.
+        container.bind_default(|| Res::new(String::from("default")));
+        container.bind(Res::new(String::from("app")));
+        assert_eq!(container.get::<Res<String>>().unwrap().as_str(), "app");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            container.bind(Res::new(String::from("again")));
+        }));
+        assert!(result.is_err());
+    }
+}
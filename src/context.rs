@@ -1,17 +1,58 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use crossterm::style::Color;
 
 use crate::{
-    container::{Callable, FromContainer},
+    animation::Animation,
+    container::{Callable, FromContainer, InjectionError, Res},
+    input::{Keyboard, Mouse},
+    layout_checks,
+    overlay::{Overlay, Placement},
+    pointer::{CursorHints, CursorShape},
+    profiler, provenance,
     stack::Stack,
+    style::Style,
+    symbols::SymbolSet,
+    terminal::TerminalBackground,
+    theme::{Theme, ThemeVariant},
+    zoom::Zoom,
 };
 
 use super::{
     container::Container,
     geometry::{Pos, Rect, Size},
-    runes::Rune,
+    runes::{wrap, Rune, RuneSlice, Runes},
     view::View,
 };
 
+thread_local! {
+    /// The number of error boundaries currently rendering.
+    static BOUNDARIES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns true while a component is rendering within an error boundary,
+/// where panics are caught and displayed rather than ending the
+/// application.
+pub(crate) fn in_error_boundary() -> bool {
+    BOUNDARIES.with(|b| b.get() > 0)
+}
+
+/// The message a panic was raised with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "component panicked".to_string()
+    }
+}
+
 /// ViewContext represents the display context for a given area.
 /// it maintains the drawing state for the region internally and is used
 /// to generate a final view that is eventually rendered.
@@ -20,6 +61,10 @@ pub struct ViewContext {
     pub container: Rc<RefCell<Container>>,
     pub(crate) should_exit: bool,
     pub(crate) rerender: bool,
+    pub(crate) style: Style,
+    pub(crate) background: Option<Color>,
+    pub(crate) overlays: Vec<Overlay>,
+    pub(crate) origin: Pos,
 }
 
 impl std::ops::DerefMut for ViewContext {
@@ -48,6 +93,10 @@ impl ViewContext {
             container,
             rerender: false,
             should_exit: false,
+            style: Style::default(),
+            background: None,
+            overlays: vec![],
+            origin: Pos::from(0),
         }
     }
 
@@ -72,6 +121,10 @@ impl ViewContext {
             view: View::new(size.into()),
             position: Pos::from(0),
             alignment: crate::stack::StackAlignment::Top,
+            style: self.style,
+            background: self.background,
+            overlays: vec![],
+            origin: self.origin,
         }
     }
 
@@ -85,6 +138,10 @@ impl ViewContext {
             view: View::new(size.into()),
             position: Pos::from(0),
             alignment: crate::stack::StackAlignment::Left,
+            style: self.style,
+            background: self.background,
+            overlays: vec![],
+            origin: self.origin,
         }
     }
 
@@ -99,13 +156,557 @@ impl ViewContext {
         R: Into<Rect>,
     {
         let rect = rect.into();
-        let mut context = ViewContext::new(self.container.clone(), rect.size);
-        let args = Args::from_container(&self.container.borrow());
-        f.call(&mut context, args);
+        layout_checks::enter::<F>();
+        profiler::enter::<F>();
+        layout_checks::check_rect(rect, self.size());
+        let mut context = self.child(rect);
+        match self.inject::<Args>() {
+            Ok(args) => f.call(&mut context, args),
+            Err(error) => context.diagnostic(&error),
+        }
+        layout_checks::exit();
+        profiler::exit();
+        provenance::stamp::<F>(&mut context.view);
+        self.view.apply(rect.pos, &context.view);
+        self.overlays
+            .extend(context.overlays.into_iter().map(|o| o.offset(rect.pos)));
+        self.rerender = context.rerender;
+    }
+
+    /// Execute a component function, catching any panic it raises while
+    /// rendering. If the component panics, or its arguments cannot be
+    /// injected, whatever it drew is discarded and the fallback is rendered
+    /// in its region with the error message instead. The rest of the
+    /// interface continues to render normally.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn dashboard(ctx: &mut ViewContext) {
+    ///     ctx.error_boundary(
+    ///         ((0, 0), (30, 3)),
+    ///         |_: &mut ViewContext| panic!("plugin crashed"),
+    ///         |ctx: &mut ViewContext, error: &str| {
+    ///             ctx.insert((0, 0), format!("Error: {}", error));
+    ///         },
+    ///     );
+    /// }
+    /// ```
+    pub fn error_boundary<F, Args, R, B>(&mut self, rect: R, f: F, fallback: B)
+    where
+        F: Callable<Args>,
+        Args: FromContainer,
+        R: Into<Rect>,
+        B: Fn(&mut ViewContext, &str),
+    {
+        let rect = rect.into();
+        let depth = layout_checks::depth();
+        let profiler_depth = profiler::depth();
+        layout_checks::enter::<F>();
+        profiler::enter::<F>();
+        layout_checks::check_rect(rect, self.size());
+        let mut context = self.child(rect);
+        BOUNDARIES.with(|b| b.set(b.get() + 1));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let args = self.inject::<Args>().map_err(|e| e.to_string())?;
+            f.call(&mut context, args);
+            Ok(context)
+        }))
+        .unwrap_or_else(|payload| Err(panic_message(payload.as_ref())));
+        BOUNDARIES.with(|b| b.set(b.get() - 1));
+        // Components within the boundary that panicked never exited.
+        layout_checks::unwind(depth);
+        profiler::unwind(profiler_depth);
+
+        let mut context = result.unwrap_or_else(|error| {
+            let mut context = self.child(rect);
+            fallback(&mut context, &error);
+            context
+        });
+        provenance::stamp::<F>(&mut context.view);
         self.view.apply(rect.pos, &context.view);
+        self.overlays
+            .extend(context.overlays.into_iter().map(|o| o.offset(rect.pos)));
         self.rerender = context.rerender;
     }
 
+    /// A context for a component drawn within a region of this context.
+    fn child(&self, rect: Rect) -> ViewContext {
+        let mut context = ViewContext::new(self.container.clone(), rect.size);
+        context.style = self.style;
+        context.background = self.background_at(rect.pos);
+        context.origin = self.origin + rect.pos;
+        context
+    }
+
+    /// Execute a component function as a popup anchored to a position in
+    /// this context, such as the cursor of a text input. The popup is drawn
+    /// over the rest of the screen after the frame has been rendered, on the
+    /// row below the anchor, or above it if there is not enough room below.
+    /// It is shifted left if it would extend past the edge of the screen.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn search(ctx: &mut ViewContext) {
+    ///     ctx.insert((0, 0), "> ca");
+    ///     ctx.popup((4, 0), (10, 2), |ctx: &mut ViewContext| {
+    ///         ctx.insert((0, 0), "cargo");
+    ///         ctx.insert((0, 1), "cat");
+    ///     });
+    /// }
+    /// ```
+    pub fn popup<F, Args, P, S>(&mut self, anchor: P, size: S, f: F)
+    where
+        F: Callable<Args>,
+        Args: FromContainer,
+        P: Into<Pos>,
+        S: Into<Size>,
+    {
+        let anchor = Rect::new(anchor.into(), (1, 1));
+        self.popup_with(Placement::default(), anchor, size, f);
+    }
+
+    /// Execute a component function as a popup positioned beside a region of
+    /// this context, such as a tooltip for a button or the menu of a
+    /// dropdown. See Placement for how the popup is kept on screen.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn toolbar(ctx: &mut ViewContext) {
+    ///     ctx.insert((0, 0), "[Save]");
+    ///     let placement = Placement::new(Side::Right).gap(1);
+    ///     ctx.popup_with(placement, ((0, 0), (6, 1)), (12, 1), |ctx: &mut ViewContext| {
+    ///         ctx.insert((0, 0), "Ctrl+S");
+    ///     });
+    /// }
+    /// ```
+    pub fn popup_with<F, Args, R, S>(&mut self, placement: Placement, anchor: R, size: S, f: F)
+    where
+        F: Callable<Args>,
+        Args: FromContainer,
+        R: Into<Rect>,
+        S: Into<Size>,
+    {
+        layout_checks::enter::<F>();
+        profiler::enter::<F>();
+        let mut context = ViewContext::new(self.container.clone(), size.into());
+        context.style = self.style;
+        context.background = self.background;
+        match self.inject::<Args>() {
+            Ok(args) => f.call(&mut context, args),
+            Err(error) => context.diagnostic(&error),
+        }
+        layout_checks::exit();
+        profiler::exit();
+        provenance::stamp::<F>(&mut context.view);
+        self.overlays.extend(context.overlays);
+        self.overlays
+            .push(Overlay::new(anchor.into(), placement, context.view));
+        self.rerender |= context.rerender;
+    }
+
+    /// Execute a component function as a modal dialog centered on the
+    /// screen. The modal is drawn over the rest of the screen after the
+    /// frame has been rendered, and everything beneath it is dimmed.
+    ///
+    /// The modal captures input: once it has been rendered the keyboard and
+    /// mouse are reset, so components rendered after it do not react to
+    /// them. Open the modal before rendering the components behind it so
+    /// that it receives input first; it is still displayed above them.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn root(ctx: &mut ViewContext, confirming: State<bool>) {
+    ///     if *confirming.get() {
+    ///         ctx.modal((20, 3), |ctx: &mut ViewContext, kb: Res<Keyboard>| {
+    ///             ctx.insert((1, 1), "Delete? (y/n)");
+    ///             if kb.char().is_some() {
+    ///                 *confirming.get_mut() = false;
+    ///             }
+    ///         });
+    ///     }
+    ///     ctx.component(ctx.size(), list);
+    /// }
+    ///
+    /// fn list(ctx: &mut ViewContext, kb: Res<Keyboard>) {
+    ///     // Keys pressed while the modal is open are not received here.
+    ///     ctx.insert((0, 0), "Files");
+    /// }
+    /// ```
+    pub fn modal<F, Args, S>(&mut self, size: S, f: F)
+    where
+        F: Callable<Args>,
+        Args: FromContainer,
+        S: Into<Size>,
+    {
+        layout_checks::enter::<F>();
+        profiler::enter::<F>();
+        let mut context = ViewContext::new(self.container.clone(), size.into());
+        context.style = self.style;
+        context.background = self.background;
+        match self.inject::<Args>() {
+            Ok(args) => f.call(&mut context, args),
+            Err(error) => context.diagnostic(&error),
+        }
+        layout_checks::exit();
+        profiler::exit();
+        provenance::stamp::<F>(&mut context.view);
+        self.overlays.push(Overlay::modal(context.view));
+        self.overlays.extend(context.overlays);
+        self.rerender |= context.rerender;
+
+        let container = self.container.borrow();
+        if let Some(kb) = container.get::<Res<Keyboard>>() {
+            kb.reset();
+        }
+        if let Some(mouse) = container.get::<Res<Mouse>>() {
+            mouse.reset();
+        }
+    }
+
+    /// Draw the popups opened during the frame over the view, in the order
+    /// they were opened. Modals dim everything drawn before them.
+    pub(crate) fn draw_overlays(&mut self) {
+        let screen = self.size();
+        for overlay in std::mem::take(&mut self.overlays) {
+            if overlay.modal {
+                self.dim_background();
+            }
+            let pos = overlay.position(screen);
+            self.view.apply(pos, &overlay.view);
+        }
+    }
+
+    /// Provide a value to all components rendered after it during the
+    /// current frame. Components receive the value by injecting
+    /// Scoped<T>. The value is dropped once the frame has been rendered.
+    pub fn provide<T: Any>(&self, value: T) {
+        self.container.borrow_mut().provide(value);
+    }
+
+    /// Build the arguments for a component from the container. Failures are
+    /// returned as errors rather than panicking mid render.
+    pub(crate) fn inject<Args: FromContainer>(&self) -> Result<Args, InjectionError> {
+        let container = self
+            .container
+            .try_borrow()
+            .map_err(|_| InjectionError::AlreadyBorrowed("Container"))?;
+        Args::try_from_container(&container)
+    }
+
+    /// Replace the contents of the context with a panel describing an error
+    /// that prevented a component from rendering.
+    pub(crate) fn diagnostic(&mut self, error: &InjectionError) {
+        let size = self.size();
+        self.view
+            .fill_all(Rune::new().content(' ').bg(Color::DarkRed));
+        let message = Runes::from(format!("{} {}", crate::symbols::WARNING, error))
+            .fg(Color::White)
+            .bg(Color::DarkRed);
+        self.view.insert_wrapped(size, &message);
+    }
+
+    /// Execute a component function which can be displayed fullscreen. The
+    /// component is drawn in the given region normally, but while its
+    /// identifier is zoomed through the Zoom resource it is instead drawn
+    /// over the entire screen.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn dashboard(ctx: &mut ViewContext) {
+    ///     ctx.zoomable("status", ((0, 0), (20, 3)), |ctx: &mut ViewContext| {
+    ///         ctx.insert((0, 0), "All systems normal");
+    ///     });
+    /// }
+    /// ```
+    pub fn zoomable<F, Args, R>(&mut self, id: &str, rect: R, f: F)
+    where
+        F: Callable<Args>,
+        Args: FromContainer,
+        R: Into<Rect>,
+    {
+        let rect = rect.into();
+        let zoom = self
+            .container
+            .borrow()
+            .get::<Res<Zoom>>()
+            .map(|zoom| zoom.get().clone());
+        match zoom {
+            Some(zoom) if zoom.register(id) => {
+                layout_checks::enter::<F>();
+                profiler::enter::<F>();
+                let mut context = ViewContext::new(self.container.clone(), zoom.size());
+                context.style = self.style;
+                context.fill_all(Rune {
+                    content: Some(' '),
+                    bg: self.background_at(rect.pos),
+                    ..Rune::default()
+                });
+                match self.inject::<Args>() {
+                    Ok(args) => f.call(&mut context, args),
+                    Err(error) => context.diagnostic(&error),
+                }
+                layout_checks::exit();
+                profiler::exit();
+                provenance::stamp::<F>(&mut context.view);
+                self.overlays.extend(context.overlays);
+                zoom.set_view(context.view);
+                self.rerender |= context.rerender;
+            }
+            _ => self.component(rect, f),
+        }
+    }
+
+    /// The position of the context's top left corner on the screen. Mouse
+    /// events report screen positions, so components use this to determine
+    /// which part of what they drew was clicked.
+    ///
+    /// Components drawn within a Stack are positioned relative to the
+    /// context the stack was created from.
+    pub fn origin(&self) -> Pos {
+        self.origin
+    }
+
+    /// Request a mouse pointer shape while the mouse is over a region of
+    /// this context, such as a resize cursor over a divider. Where regions
+    /// overlap, the region requested last is used.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn link(ctx: &mut ViewContext) {
+    ///     ctx.insert((0, 0), "Open");
+    ///     ctx.cursor_hint(((0, 0), (4, 1)), CursorShape::Pointer);
+    /// }
+    /// ```
+    pub fn cursor_hint<R: Into<Rect>>(&self, rect: R, shape: CursorShape) {
+        let rect = rect.into();
+        if let Some(hints) = self.container.borrow().get::<Res<CursorHints>>() {
+            hints.add(Rect::new(self.origin + rect.pos, rect.size), shape);
+        }
+    }
+
+    /// Request that the application renders again after a delay, for
+    /// components that change over time. See the Animation resource.
+    ///
+    /// Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use arkham::prelude::*;
+    ///
+    /// fn blink(ctx: &mut ViewContext, idle: Res<Idle>) {
+    ///     if idle.since_input().as_millis() / 500 % 2 == 0 {
+    ///         ctx.insert((0, 0), "_");
+    ///     }
+    ///     ctx.animate(Duration::from_millis(500));
+    /// }
+    /// ```
+    pub fn animate(&self, delay: Duration) {
+        if let Some(animation) = self.container.borrow().get::<Res<Animation>>() {
+            animation.request(delay);
+        }
+    }
+
+    /// The style currently in effect for this context. Styles are set with
+    /// ViewContext::with_style and are inherited by all child components.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Apply style overrides to everything rendered within the given
+    /// function. Components executed inside the function inherit the
+    /// merged style, allowing a whole subtree to be restyled without passing
+    /// colors to each component.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn danger_zone(ctx: &mut ViewContext) {
+    ///     ctx.with_style(Style::new().fg(Color::Red), |ctx| {
+    ///         ctx.component((0, (20, 1)), warning);
+    ///     });
+    /// }
+    ///
+    /// fn warning(ctx: &mut ViewContext) {
+    ///     let fg = ctx.style().fg.unwrap_or(Color::White);
+    ///     ctx.insert(0, "Careful".to_runes().fg(fg));
+    /// }
+    /// ```
+    pub fn with_style<F>(&mut self, style: Style, f: F)
+    where
+        F: FnOnce(&mut ViewContext),
+    {
+        let previous = self.style;
+        self.style = previous.merge(style);
+        f(self);
+        self.style = previous;
+    }
+
+    /// Set the default style for this context. Text inserted without
+    /// explicit colors will use the style's colors, and the style is
+    /// inherited by all child components.
+    ///
+    /// When no text color is available text will be colored to contrast with
+    /// the background it is drawn on, including backgrounds set with
+    /// ViewContext::fill_all.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// fn panel(ctx: &mut ViewContext, theme: Res<Theme>) {
+    ///     ctx.set_default_style(Style::new().fg(theme.fg).bg(theme.bg_primary));
+    ///     ctx.insert(0, "Uses the theme colors");
+    /// }
+    /// ```
+    pub fn set_default_style(&mut self, style: Style) {
+        self.style = self.style.merge(style);
+    }
+
+    /// Insert a string at the specific position in the view. This behaves
+    /// like View::insert, except unset colors are filled in from the
+    /// context's default style. See ViewContext::set_default_style.
+    pub fn insert<'a, P: Into<Pos>, S: Into<RuneSlice<'a>>>(&mut self, pos: P, value: S) {
+        let runes: RuneSlice = value.into();
+        let theme = runes.iter().any(Rune::has_tokens).then(|| self.theme());
+        let (style, background) = (self.style, self.background);
+        self.view.insert_with(pos, runes, |rune, cell| {
+            let rune = match &theme {
+                Some(theme) => rune.resolve_tokens(theme),
+                None => rune,
+            };
+            style.resolve(rune, cell.bg.or(background))
+        });
+    }
+
+    /// Insert a string wrapped within a region of the view. This behaves like
+    /// View::insert_wrapped, except unset colors are filled in from the
+    /// context's default style.
+    ///
+    /// Returns the number of lines that were used.
+    pub fn insert_wrapped<'a, R: Into<Rect>, S: Into<RuneSlice<'a>>>(
+        &mut self,
+        rect: R,
+        value: S,
+    ) -> usize {
+        let rect = rect.into();
+        let runes: RuneSlice = value.into();
+        let lines = wrap(&runes, rect.size.width);
+        for (row, line) in lines.iter().take(rect.size.height).enumerate() {
+            self.insert((rect.pos.x, rect.pos.y + row), RuneSlice::new(line));
+        }
+        lines.len().min(rect.size.height)
+    }
+
+    /// Fill the entire view context with a rune. If the rune has a
+    /// background color it becomes the background that text inserted into
+    /// this context, and its child components, contrasts with.
+    pub fn fill_all<R>(&mut self, rune: R)
+    where
+        R: Into<Rune>,
+    {
+        let mut rune = rune.into();
+        if rune.has_tokens() {
+            rune = rune.resolve_tokens(&self.theme());
+        }
+        self.background = rune.bg.or(self.background);
+        self.view.fill_all(rune);
+    }
+
+    /// Draw a drop shadow for a region by darkening the cells one column to
+    /// the right of and one row below it. This visually lifts overlays, such
+    /// as modals, from the content behind them.
+    ///
+    /// The shadow should be drawn before the overlay itself.
+    pub fn shadow<R>(&mut self, rect: R)
+    where
+        R: Into<Rect>,
+    {
+        let rect = rect.into();
+        let (right, bottom) = (rect.pos.x + rect.size.width, rect.pos.y + rect.size.height);
+        self.view
+            .darken(((right, rect.pos.y + 1), (1, rect.size.height)), 0.6);
+        self.view.darken(
+            (
+                (rect.pos.x + 1, bottom),
+                (rect.size.width.saturating_sub(1), 1),
+            ),
+            0.6,
+        );
+    }
+
+    /// Dim a region of the context, draining its colors toward grey and
+    /// darkening them. This gives disabled or inactive panes a consistent
+    /// look. Text without a color is dimmed from the theme's foreground
+    /// color.
+    pub fn dim<R>(&mut self, rect: R)
+    where
+        R: Into<Rect>,
+    {
+        let rect = rect.into();
+        let fg = self.theme().fg;
+        for Pos { x, y } in rect.cells() {
+            if let Some(r) = self.view.get_mut(y).and_then(|row| row.get_mut(x)) {
+                r.fg = r.fg.or(Some(fg));
+            }
+        }
+        self.view.dim(rect);
+    }
+
+    /// Dim everything drawn in the context so far, as is done behind
+    /// modals.
+    pub fn dim_background(&mut self) {
+        let size = self.size();
+        self.dim(size);
+    }
+
+    /// The Theme resource bound to the application. If none was inserted,
+    /// the theme matching the terminal's background is used, which is the
+    /// dark theme unless the background was detected to be light. Built-in
+    /// components use this to pick colors.
+    pub(crate) fn theme(&self) -> Theme {
+        let container = self.container.borrow();
+        match container.get::<Res<Theme>>() {
+            Some(theme) => theme.get().clone(),
+            None => Theme::from_variant(
+                container
+                    .get::<Res<TerminalBackground>>()
+                    .map_or(ThemeVariant::Dark, |background| background.variant()),
+            ),
+        }
+    }
+
+    /// The SymbolSet resource bound to the application, or the Unicode set
+    /// if none was inserted.
+    pub(crate) fn symbols(&self) -> SymbolSet {
+        self.container
+            .borrow()
+            .get::<Res<SymbolSet>>()
+            .map(|symbols| *symbols.get())
+            .unwrap_or_default()
+    }
+
+    /// The background color at a position, falling back to the background
+    /// inherited by the context.
+    fn background_at(&self, pos: Pos) -> Option<Color> {
+        self.view
+            .get(pos.y)
+            .and_then(|row| row.get(pos.x))
+            .and_then(|rune| rune.bg)
+            .or(self.background)
+    }
+
     /// Set a specific rune to a specific position. This function can be used
     /// to set a signle character. To set multiple runes at a time see the
     /// View::insert function.
@@ -114,10 +715,31 @@ impl ViewContext {
         P: Into<Pos>,
     {
         let Pos { x, y } = pos.into();
+        let rune = if rune.has_tokens() {
+            rune.resolve_tokens(&self.theme())
+        } else {
+            rune
+        };
         if let Some(r) = self.view.get_mut(y).and_then(|row| row.get_mut(x)) {
             *r = rune;
         }
     }
+
+    /// Replace the color tokens of every rune in the view with the colors
+    /// they name in the theme. Runes drawn through other means than
+    /// ViewContext::insert, such as View::fill, are resolved this way once
+    /// the frame has been drawn. See Rune::fg_token.
+    pub(crate) fn resolve_tokens(&mut self) {
+        if !self.view.iter().flatten().any(Rune::has_tokens) {
+            return;
+        }
+        let theme = self.theme();
+        for rune in self.view.iter_mut().flatten() {
+            if rune.has_tokens() {
+                *rune = rune.resolve_tokens(&theme);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -131,4 +753,221 @@ pub mod tests {
     pub fn context_fixture() -> ViewContext {
         ViewContext::new(Rc::new(RefCell::new(Container::default())), (20, 20).into())
     }
+
+    #[test]
+    fn test_with_style_inherited() {
+        use crate::prelude::{Color, Style};
+
+        let mut ctx = context_fixture();
+        ctx.with_style(Style::new().fg(Color::Red), |ctx| {
+            ctx.with_style(Style::new().bg(Color::Blue), |ctx| {
+                ctx.component((0, (5, 1)), |ctx: &mut ViewContext| {
+                    assert_eq!(ctx.style(), Style::new().fg(Color::Red).bg(Color::Blue));
+                });
+            });
+            assert_eq!(ctx.style(), Style::new().fg(Color::Red));
+        });
+        assert_eq!(ctx.style(), Style::default());
+    }
+
+    #[test]
+    fn test_default_style() {
+        use crate::prelude::{Color, Rune, RuneSlice, Style, ToRuneExt};
+
+        let mut ctx = context_fixture();
+        ctx.set_default_style(Style::new().fg(Color::Green));
+        ctx.insert(0, "a");
+        ctx.insert((1, 0), "b".to_runes().fg(Color::Blue));
+        assert_eq!(ctx.view[0][0].fg, Some(Color::Green));
+        assert_eq!(ctx.view[0][1].fg, Some(Color::Blue));
+
+        // Borrowed runes are styled as they are written, and the cells after
+        // wide characters keep their own background.
+        let label = "中x".to_runes();
+        ctx.view.fill(((4, 1), (1, 1)), Rune::new().bg(Color::Red));
+        ctx.set_default_style(Style::new().bg(Color::Yellow));
+        ctx.insert((2, 1), RuneSlice::new(&label));
+        assert_eq!(label[0].fg, None);
+        assert_eq!(ctx.view[1][2].bg, Some(Color::Yellow));
+        assert_eq!(ctx.view[1][4].content, Some('x'));
+        assert_eq!(ctx.view[1][4].bg, Some(Color::Red));
+        assert_eq!(ctx.view[1][4].fg, Some(Color::Green));
+    }
+
+    #[test]
+    fn test_fill_all_contrast() {
+        use crate::prelude::Color;
+
+        let mut ctx = context_fixture();
+        ctx.fill_all(Color::White);
+        ctx.insert(0, "a");
+        ctx.component(((0, 1), (5, 1)), |ctx: &mut ViewContext| {
+            ctx.insert(0, "b");
+        });
+        assert_eq!(ctx.view[0][0].fg, Some(Color::Black));
+        assert_eq!(ctx.view[1][0].fg, Some(Color::Black));
+        assert_eq!(ctx.view[1][0].bg, Some(Color::White));
+    }
+
+    #[test]
+    fn test_dim() {
+        use crate::prelude::Color;
+
+        let mut ctx = context_fixture();
+        ctx.fill_all(Color::Blue);
+        ctx.insert(0, "ab");
+        ctx.dim(((1, 0), (1, 1)));
+        assert_eq!(ctx.view[0][0].bg, Some(Color::Blue));
+        assert_ne!(ctx.view[0][1].bg, Some(Color::Blue));
+        assert_ne!(ctx.view[0][1].fg, ctx.view[0][0].fg);
+
+        ctx.dim_background();
+        assert_ne!(ctx.view[5][5].bg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_zoomable() {
+        use crate::{container::Res, zoom::Zoom};
+
+        let mut ctx = context_fixture();
+        let zoom = Zoom::new();
+        ctx.container.borrow_mut().bind(Res::new(zoom.clone()));
+        let panel = |ctx: &mut ViewContext| {
+            let size = ctx.size();
+            ctx.insert(0, format!("{}x{}", size.width, size.height));
+        };
+
+        zoom.begin_frame((20, 20).into());
+        ctx.zoomable("a", ((2, 2), (5, 1)), panel);
+        assert_eq!(ctx.view[2][2].content, Some('5'));
+        assert!(zoom.take_view().is_none());
+
+        zoom.toggle();
+        assert_eq!(zoom.zoomed(), Some("a".to_string()));
+        zoom.begin_frame((20, 20).into());
+        ctx.zoomable("a", ((2, 2), (5, 1)), panel);
+        let view = zoom.take_view().unwrap();
+        assert_eq!(view[0][0].content, Some('2'));
+        assert_eq!(view[0][5].content, Some(' '));
+    }
+
+    #[test]
+    fn test_popup_drawn_over_siblings() {
+        let mut ctx = context_fixture();
+        ctx.component(((2, 16), (10, 2)), |ctx: &mut ViewContext| {
+            ctx.popup((3, 1), (5, 3), |ctx: &mut ViewContext| {
+                ctx.insert(0, "popup");
+            });
+        });
+        ctx.component(((0, 14), (20, 1)), |ctx: &mut ViewContext| {
+            ctx.insert(0, "x".repeat(20));
+        });
+        assert_eq!(ctx.view[14][5].content, Some('x'));
+        ctx.draw_overlays();
+        assert_eq!(ctx.view[14][5].content, Some('p'));
+        assert!(ctx.overlays.is_empty());
+    }
+
+    #[test]
+    fn test_error_boundary() {
+        let mut ctx = context_fixture();
+        let fallback = |ctx: &mut ViewContext, error: &str| ctx.insert(0, error);
+        ctx.error_boundary(
+            ((0, 0), (20, 1)),
+            |ctx: &mut ViewContext| {
+                ctx.insert(0, "partial");
+                ctx.component((0, (5, 1)), |_: &mut ViewContext| {
+                    panic!("failed to load");
+                });
+            },
+            fallback,
+        );
+        ctx.error_boundary(
+            ((0, 1), (20, 1)),
+            |ctx: &mut ViewContext| ctx.insert(0, "ok"),
+            fallback,
+        );
+        ctx.error_boundary(
+            ((0, 2), (20, 1)),
+            |_: &mut ViewContext, _: crate::container::Res<u8>| {},
+            fallback,
+        );
+        let lines: Vec<String> = ctx
+            .view
+            .render_text()
+            .replace('\0', " ")
+            .lines()
+            .map(|l| l.trim_end().to_string())
+            .collect();
+        assert_eq!(lines[0], "failed to load");
+        assert_eq!(lines[1], "ok");
+        assert!(lines[2].contains("u8"));
+        assert!(!super::in_error_boundary());
+    }
+
+    #[test]
+    fn test_missing_resource_diagnostic() {
+        use crate::container::Res;
+
+        struct Missing;
+
+        let mut ctx = context_fixture();
+        ctx.component((0, (20, 10)), |ctx: &mut ViewContext, _: Res<Missing>| {
+            ctx.insert(0, "unreachable");
+        });
+        let text: String = ctx.view.render_text().split('\n').collect();
+        assert!(text.starts_with("⚠"));
+        assert!(text.contains("Missing"));
+        assert!(text.contains("has not been"));
+        assert_eq!(ctx.view[0][0].bg, Some(crate::prelude::Color::DarkRed));
+    }
+
+    #[test]
+    fn test_modal_captures_input() {
+        use crossterm::event::KeyCode;
+
+        use crate::{
+            container::{Res, State},
+            input::Keyboard,
+            prelude::Color,
+            testing::TestApp,
+        };
+
+        #[derive(Default)]
+        struct Keys {
+            modal: Vec<char>,
+            background: Vec<char>,
+        }
+
+        let root = |ctx: &mut ViewContext, keys: State<Keys>| {
+            ctx.fill_all(Color::White);
+            ctx.modal(
+                (4, 2),
+                |ctx: &mut ViewContext, kb: Res<Keyboard>, keys: State<Keys>| {
+                    ctx.fill_all(Color::Blue);
+                    ctx.insert(0, "Hi");
+                    keys.get_mut().modal.extend(kb.char());
+                },
+            );
+            let size = ctx.size();
+            ctx.component(size, |ctx: &mut ViewContext, kb: Res<Keyboard>| {
+                ctx.insert((0, 0), "List");
+                keys.get_mut().background.extend(kb.char());
+            });
+        };
+        let mut app = TestApp::new(root, (10, 6)).insert_state(Keys::default());
+        let view = app.press(KeyCode::Char('a'));
+        let text = view.render_text().replace('\0', " ");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(&lines[2][3..5], "Hi");
+        assert_eq!(&lines[0][..4], "List");
+        assert_ne!(view[0][0].bg, Some(Color::White));
+        assert_eq!(view[2][3].bg, Some(Color::Blue));
+
+        let container = app.container();
+        let container = container.borrow();
+        let keys = container.get::<State<Keys>>().unwrap().get();
+        assert!(keys.modal.contains(&'a'));
+        assert!(keys.background.is_empty());
+    }
 }
@@ -0,0 +1,210 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    container::{boxed_component, BoxedComponent, Callable, FromContainer},
+    context::ViewContext,
+    geometry::{Rect, Size},
+};
+
+/// A corner of the screen a Dock is attached to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// A small component docked to a corner of the screen and drawn above
+/// everything else, such as a playback status or a timer.
+#[derive(Clone)]
+pub struct Dock {
+    id: String,
+    corner: Corner,
+    size: Size,
+    margin: Size,
+    visible: bool,
+    content: BoxedComponent,
+}
+
+impl std::fmt::Debug for Dock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dock")
+            .field("id", &self.id)
+            .field("corner", &self.corner)
+            .field("size", &self.size)
+            .field("margin", &self.margin)
+            .field("visible", &self.visible)
+            .finish()
+    }
+}
+
+impl Dock {
+    /// Create a dock with an identifier, a size, and the component rendered
+    /// inside of it. The component can be injected with resources like any
+    /// other component.
+    pub fn new<S, Z, F, Args>(id: S, size: Z, component: F) -> Self
+    where
+        S: ToString,
+        Z: Into<Size>,
+        F: Callable<Args> + 'static,
+        Args: FromContainer + 'static,
+    {
+        Self {
+            id: id.to_string(),
+            corner: Corner::default(),
+            size: size.into(),
+            margin: Size::new(1, 0),
+            visible: true,
+            content: boxed_component(component),
+        }
+    }
+
+    /// Set the corner the dock is attached to. Defaults to the bottom right.
+    pub fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Set the horizontal and vertical distance between the dock and the
+    /// edges of the screen. Defaults to one column.
+    pub fn margin<Z: Into<Size>>(mut self, margin: Z) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    /// Start with the dock hidden.
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+
+    /// The region the dock occupies on a screen of a given size.
+    fn rect(&self, screen: Size) -> Rect {
+        let width = self.size.width.min(screen.width);
+        let height = self.size.height.min(screen.height);
+        let right = screen.width.saturating_sub(width + self.margin.width);
+        let bottom = screen.height.saturating_sub(height + self.margin.height);
+        let left = self.margin.width.min(right);
+        let top = self.margin.height.min(bottom);
+        let pos = match self.corner {
+            Corner::TopLeft => (left, top),
+            Corner::TopRight => (right, top),
+            Corner::BottomLeft => (left, bottom),
+            Corner::BottomRight => (right, bottom),
+        };
+        Rect::new(pos, (width, height))
+    }
+}
+
+/// Docks can be used as an injectable resource to show, hide, and add docked
+/// components. Docks are drawn by the application after the root component,
+/// so they remain visible regardless of what the root component displays.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root)
+///         .dock(Dock::new("timer", (12, 1), timer).corner(Corner::TopRight))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, docks: Res<Docks>, kb: Res<Keyboard>) {
+///     if kb.char() == Some('t') {
+///         docks.toggle("timer");
+///     }
+///     ctx.insert((0, 0), "Press t to toggle the timer");
+/// }
+///
+/// fn timer(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Time: 00:42");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Docks {
+    docks: Rc<RefCell<Vec<Dock>>>,
+}
+
+impl Docks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a dock, replacing any dock with the same identifier.
+    pub fn add(&self, dock: Dock) {
+        let mut docks = self.docks.borrow_mut();
+        docks.retain(|d| d.id != dock.id);
+        docks.push(dock);
+    }
+
+    /// Remove the dock with an identifier.
+    pub fn remove(&self, id: &str) {
+        self.docks.borrow_mut().retain(|d| d.id != id);
+    }
+
+    /// Show or hide the dock with an identifier.
+    pub fn set_visible(&self, id: &str, visible: bool) {
+        let mut docks = self.docks.borrow_mut();
+        if let Some(dock) = docks.iter_mut().find(|d| d.id == id) {
+            dock.visible = visible;
+        }
+    }
+
+    /// Toggle the visibility of the dock with an identifier.
+    pub fn toggle(&self, id: &str) {
+        let visible = self.is_visible(id);
+        self.set_visible(id, !visible);
+    }
+
+    /// Returns true if the dock with an identifier is shown.
+    pub fn is_visible(&self, id: &str) -> bool {
+        self.docks.borrow().iter().any(|d| d.id == id && d.visible)
+    }
+
+    /// Draw all visible docks within a context.
+    pub(crate) fn draw(&self, ctx: &mut ViewContext) {
+        let screen = ctx.size();
+        let docks = self.docks.borrow().clone();
+        for dock in docks.iter().filter(|d| d.visible) {
+            (dock.content)(ctx, dock.rect(screen));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::ViewContext;
+
+    use super::{Corner, Dock, Docks};
+
+    fn status(ctx: &mut ViewContext) {
+        ctx.insert(0, "ok");
+    }
+
+    #[test]
+    fn test_corners() {
+        let dock = Dock::new("a", (4, 2), status).margin((1, 1));
+        assert_eq!(dock.rect((20, 10).into()), ((15, 7), (4, 2)).into());
+        let dock = dock.corner(Corner::TopLeft);
+        assert_eq!(dock.rect((20, 10).into()), ((1, 1), (4, 2)).into());
+        let dock = dock.corner(Corner::TopRight).margin((0, 0));
+        assert_eq!(dock.rect((3, 10).into()), ((0, 0), (3, 2)).into());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let docks = Docks::new();
+        docks.add(Dock::new("a", (2, 1), status).corner(Corner::TopLeft));
+        docks.toggle("a");
+        assert!(!docks.is_visible("a"));
+        docks.draw(&mut ctx);
+        assert_eq!(ctx.view[0][1].content, None);
+        docks.toggle("a");
+        docks.draw(&mut ctx);
+        assert_eq!(ctx.view[0][1].content, Some('o'));
+    }
+}
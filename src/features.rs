@@ -0,0 +1,141 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// The environment variable the application reads feature flags from when
+/// it starts. See Features::apply_env.
+pub(crate) const FEATURES_VAR: &str = "ARKHAM_FEATURES";
+
+/// Features is an injectable resource holding named feature flags, which
+/// components and plugins query to gate behavior at runtime. This makes it
+/// possible to ship experimental features disabled, and enable them for
+/// some users without a separate build.
+///
+/// Flags are enabled with App::enable_feature. When the application starts
+/// the `ARKHAM_FEATURES` environment variable is applied on top of these,
+/// so flags can be switched on or off from the shell, and flags can be
+/// changed while the application runs with Features::set. Flags which have
+/// never been set are disabled.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root)
+///         .enable_feature("compact_list")
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, features: Res<Features>) {
+///     if features.enabled("experimental_mouse") {
+///         ctx.insert((0, 0), "Mouse support is enabled");
+///     }
+/// }
+/// ```
+///
+/// Running the application with `ARKHAM_FEATURES=experimental_mouse` enables
+/// the experimental feature, and `ARKHAM_FEATURES=-compact_list` disables the
+/// feature enabled in code.
+#[derive(Debug, Clone, Default)]
+pub struct Features {
+    flags: Rc<RefCell<HashMap<String, bool>>>,
+}
+
+impl Features {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable a flag.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let features = Features::new().enable("beta");
+    /// assert!(features.enabled("beta"));
+    /// assert!(!features.enabled("other"));
+    /// ```
+    pub fn enable(self, name: &str) -> Self {
+        self.set(name, true);
+        self
+    }
+
+    /// Disable a flag. Since flags are disabled unless they have been
+    /// enabled this is only needed to override an earlier setting.
+    pub fn disable(self, name: &str) -> Self {
+        self.set(name, false);
+        self
+    }
+
+    /// Returns true if a flag is enabled.
+    pub fn enabled(&self, name: &str) -> bool {
+        self.flags.borrow().get(name).copied().unwrap_or(false)
+    }
+
+    /// Enable or disable a flag while the application is running. The change
+    /// is seen by every copy of the resource, and takes effect the next time
+    /// the view is rendered.
+    pub fn set(&self, name: &str, enabled: bool) {
+        self.flags.borrow_mut().insert(name.to_string(), enabled);
+    }
+
+    /// Apply a list of flags, such as one read from an environment variable.
+    /// Flags are separated by commas or whitespace. Each flag is enabled,
+    /// unless it is prefixed with `-` or `!`, which disables it.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let features = Features::new().enable("search");
+    /// features.apply("preview, -search");
+    /// assert!(features.enabled("preview"));
+    /// assert!(!features.enabled("search"));
+    /// ```
+    pub fn apply(&self, flags: &str) {
+        for flag in flags
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|flag| !flag.is_empty())
+        {
+            match flag.strip_prefix(['-', '!']) {
+                Some(name) => self.set(name, false),
+                None => self.set(flag, true),
+            }
+        }
+    }
+
+    /// Apply the flags held in an environment variable, if it is set. See
+    /// Features::apply.
+    pub fn apply_env(&self, var: &str) {
+        if let Ok(flags) = std::env::var(var) {
+            self.apply(&flags);
+        }
+    }
+
+    /// The names of the enabled flags, in alphabetical order.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .flags
+            .borrow()
+            .iter()
+            .filter(|(_, enabled)| **enabled)
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Features;
+
+    #[test]
+    fn test_apply() {
+        let features = Features::new().enable("a").enable("b").disable("c");
+        features.apply(" -a,!b c  d,,");
+        assert_eq!(features.names(), vec!["c", "d"]);
+
+        let shared = features.clone();
+        shared.set("a", true);
+        assert!(features.enabled("a"));
+    }
+}
@@ -0,0 +1,288 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{geometry::Pos, runes::Rune, view::View};
+
+/// Frame can be used as an injectable resource that provides access to the
+/// most recently rendered frame. It can be used to find what was drawn at a
+/// given position, such as the item beneath the cursor.
+///
+/// Identifiers attached with Rune::id or View::set_id are preserved in the
+/// frame and can be retrieved with Frame::id_at.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn root(ctx: &mut ViewContext, frame: Res<Frame>) {
+///     ctx.insert((0, 0), "First".to_runes().id(1));
+///     ctx.insert((0, 1), "Second".to_runes().id(2));
+///     if let Some(id) = frame.id_at((2, 1)) {
+///         ctx.insert((0, 3), format!("Item {} is at 2,1", id));
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Frame {
+    view: Rc<RefCell<View>>,
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Self {
+            view: Rc::new(RefCell::new(View::new((0, 0)))),
+        }
+    }
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the stored frame with a newly rendered view.
+    pub(crate) fn set(&self, view: &View) {
+        self.view.borrow_mut().clone_from(view);
+    }
+
+    /// Returns the rune rendered at a position in the last frame.
+    pub fn rune_at<P: Into<Pos>>(&self, pos: P) -> Option<Rune> {
+        let Pos { x, y } = pos.into();
+        self.view
+            .borrow()
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+    }
+
+    /// Returns the identifier attached to the rune rendered at a position in
+    /// the last frame.
+    pub fn id_at<P: Into<Pos>>(&self, pos: P) -> Option<u32> {
+        self.rune_at(pos).and_then(|rune| rune.id)
+    }
+
+    /// Returns the name of the component that drew the rune at a position
+    /// in the last frame. Components are named by their type, which for
+    /// functions is their path. This requires the `debug` feature.
+    #[cfg(feature = "debug")]
+    pub fn writer_at<P: Into<Pos>>(&self, pos: P) -> Option<&'static str> {
+        self.rune_at(pos)
+            .and_then(|rune| crate::provenance::writer(&rune))
+    }
+
+    /// Returns each component that drew part of the last frame with the
+    /// smallest region containing everything it drew. Regions that overlap
+    /// point to components drawing over one another. This requires the
+    /// `debug` feature.
+    #[cfg(feature = "debug")]
+    pub fn writer_regions(&self) -> Vec<(&'static str, crate::geometry::Rect)> {
+        crate::provenance::regions(&self.view.borrow())
+    }
+}
+
+/// A frame that took longer to render than the frame budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowFrame {
+    /// The time taken to render the frame.
+    pub duration: Duration,
+    /// The frame budget that was exceeded.
+    pub budget: Duration,
+    /// The component that took the longest to render, excluding the
+    /// components it rendered, and its render time. This is only recorded
+    /// when the `profiling` feature is enabled.
+    pub slowest: Option<(&'static str, Duration)>,
+}
+
+impl std::fmt::Display for SlowFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "frame took {:.1?}, over the budget of {:.1?}",
+            self.duration, self.budget
+        )?;
+        if let Some((name, time)) = self.slowest {
+            write!(f, ", slowest component: {} ({:.1?})", name, time)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct FrameStatsState {
+    frames: u64,
+    last: Duration,
+    max: Duration,
+    total: Duration,
+    budget: Option<Duration>,
+    slowest: Option<(&'static str, Duration)>,
+    /// The last frame over budget and when it was rendered.
+    slow_frame: Option<(SlowFrame, Instant)>,
+}
+
+/// FrameStats is an injectable resource with timing statistics for rendered
+/// frames. The time for a frame covers running the plugins and rendering
+/// every component, but not writing the frame to the terminal.
+///
+/// When a frame budget is set with App::frame_budget, a warning is logged
+/// for every frame that exceeds it. With the `profiling` feature enabled
+/// the slowest component of each frame is recorded, and a warning banner is
+/// displayed at the top of the screen for a few seconds after a slow frame.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root)
+///         .frame_budget(Duration::from_millis(16))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, stats: Res<FrameStats>) {
+///     ctx.insert((0, 0), format!("Last frame: {:?}", stats.last()));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    state: Rc<RefCell<FrameStatsState>>,
+}
+
+impl FrameStats {
+    pub fn new(budget: Option<Duration>) -> Self {
+        let stats = Self::default();
+        stats.state.borrow_mut().budget = budget;
+        stats
+    }
+
+    /// The number of frames rendered.
+    pub fn frames(&self) -> u64 {
+        self.state.borrow().frames
+    }
+
+    /// The time taken to render the last frame.
+    pub fn last(&self) -> Duration {
+        self.state.borrow().last
+    }
+
+    /// The longest time taken to render a frame.
+    pub fn max(&self) -> Duration {
+        self.state.borrow().max
+    }
+
+    /// The average time taken to render a frame.
+    pub fn average(&self) -> Duration {
+        let state = self.state.borrow();
+        if state.frames == 0 {
+            return Duration::ZERO;
+        }
+        state.total / state.frames as u32
+    }
+
+    /// The frame budget set with App::frame_budget.
+    pub fn budget(&self) -> Option<Duration> {
+        self.state.borrow().budget
+    }
+
+    /// The slowest component of the last frame and its render time. This is
+    /// only recorded when the `profiling` feature is enabled.
+    pub fn slowest_component(&self) -> Option<(&'static str, Duration)> {
+        self.state.borrow().slowest
+    }
+
+    /// The most recent frame that exceeded the budget.
+    pub fn last_slow_frame(&self) -> Option<SlowFrame> {
+        self.state.borrow().slow_frame.map(|(frame, _)| frame)
+    }
+
+    /// Record the render time of a frame. Returns the frame if it exceeded
+    /// the budget.
+    pub(crate) fn record(
+        &self,
+        duration: Duration,
+        slowest: Option<(&'static str, Duration)>,
+    ) -> Option<SlowFrame> {
+        let mut state = self.state.borrow_mut();
+        state.frames += 1;
+        state.last = duration;
+        state.max = state.max.max(duration);
+        state.total += duration;
+        state.slowest = slowest;
+        let budget = state.budget?;
+        if duration <= budget {
+            return None;
+        }
+        let frame = SlowFrame {
+            duration,
+            budget,
+            slowest,
+        };
+        state.slow_frame = Some((frame, Instant::now()));
+        Some(frame)
+    }
+
+    /// The slow frame to display a warning for, if one was rendered
+    /// recently.
+    #[cfg(feature = "profiling")]
+    pub(crate) fn warning(&self) -> Option<SlowFrame> {
+        const WARNING_DURATION: Duration = Duration::from_secs(3);
+        self.state
+            .borrow()
+            .slow_frame
+            .filter(|(_, at)| at.elapsed() < WARNING_DURATION)
+            .map(|(frame, _)| frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{container::Res, context::ViewContext, runes::Rune, testing::TestApp, view::View};
+
+    use super::{Frame, FrameStats};
+
+    #[test]
+    fn test_id_at() {
+        let frame = Frame::new();
+        let mut view = View::new((4, 2));
+        view.set_id(((1, 1), (2, 1)), 5);
+        view.insert((0, 0), Rune::new().content('a').id(3));
+        frame.set(&view);
+        assert_eq!(frame.id_at((0, 0)), Some(3));
+        assert_eq!(frame.id_at((2, 1)), Some(5));
+        assert_eq!(frame.id_at((3, 1)), None);
+        assert_eq!(frame.id_at((9, 9)), None);
+    }
+
+    #[test]
+    fn test_frame_budget() {
+        fn slow(ctx: &mut ViewContext) {
+            std::thread::sleep(Duration::from_millis(5));
+            ctx.insert((0, 0), "slow");
+        }
+        let mut app = TestApp::new(slow, (60, 2)).frame_budget(Duration::from_millis(1));
+        app.render();
+        let view = app.render();
+        #[cfg(feature = "profiling")]
+        assert!(crate::testing::frame_text(view).starts_with(" ⚠ frame took"));
+        #[cfg(not(feature = "profiling"))]
+        assert_eq!(crate::testing::frame_text(view), "slow");
+        let container = app.container();
+        let container = container.borrow();
+        let stats = container.get::<Res<FrameStats>>().unwrap();
+        assert_eq!(stats.frames(), 2);
+        assert!(stats.last() >= Duration::from_millis(5));
+        assert!(stats.max() >= stats.average());
+        let slow_frame = stats.last_slow_frame().unwrap();
+        assert_eq!(slow_frame.budget, Duration::from_millis(1));
+        assert_eq!(slow_frame.duration, stats.last());
+
+        let fast = FrameStats::new(Some(Duration::from_secs(1)));
+        assert!(fast.record(Duration::from_millis(2), None).is_none());
+        assert!(fast.last_slow_frame().is_none());
+        assert_eq!(fast.average(), Duration::from_millis(2));
+    }
+}
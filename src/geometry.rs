@@ -4,7 +4,8 @@ use std::ops::{Add, AddAssign, Sub};
 ///
 /// *NOTE* Most functions accept a value that can be converted into a Pos.
 /// For these a simple tuple of coordinates is sufficient.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pos {
     pub x: usize,
     pub y: usize,
@@ -24,6 +25,22 @@ impl Pos {
     pub fn new(x: usize, y: usize) -> Self {
         Self { x, y }
     }
+
+    /// Move the position by a signed amount. Coordinates are clamped so they
+    /// never move beyond the top-left corner of the screen.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    /// let mut pos = Pos::new(3,1);
+    /// pos.offset(2,-4);
+    /// assert_eq!(pos, Pos::new(5,0));
+    /// ```
+    pub fn offset(&mut self, dx: i32, dy: i32) {
+        self.x = (self.x as i32 + dx).max(0) as usize;
+        self.y = (self.y as i32 + dy).max(0) as usize;
+    }
 }
 
 impl From<(usize, usize)> for Pos {
@@ -79,7 +96,8 @@ impl AddAssign<Pos> for Pos {
 /// assert_eq!(s.width, 3);
 /// assert_eq!(s.height, 2);
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size {
     pub width: usize,
     pub height: usize,
@@ -89,6 +107,22 @@ impl Size {
     pub fn new(width: usize, height: usize) -> Self {
         Self { width, height }
     }
+
+    /// Change the size by a signed amount. Dimensions are clamped so they
+    /// never shrink below zero.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    /// let mut size = Size::new(10,2);
+    /// size.grow(5,-4);
+    /// assert_eq!(size, Size::new(15,0));
+    /// ```
+    pub fn grow(&mut self, dw: i32, dh: i32) {
+        self.width = (self.width as i32 + dw).max(0) as usize;
+        self.height = (self.height as i32 + dh).max(0) as usize;
+    }
 }
 
 impl Add<Size> for Size {
@@ -173,7 +207,23 @@ impl From<i32> for Size {
 
 /// An area of the screen with a given size and postiion. The position
 /// represents the top-left corner of the rectangle.
-#[derive(Debug, Clone, Copy)]
+///
+/// Geometry types can be compared and hashed, which makes them usable as
+/// keys for memoized layout:
+///
+/// ```
+/// use arkham::prelude::*;
+/// use std::collections::HashMap;
+///
+/// let mut layouts = HashMap::new();
+/// layouts.insert(Rect::new((0, 0), (10, 5)), "sidebar");
+/// assert_eq!(layouts.get(&Rect::new(0, (10, 5))), Some(&"sidebar"));
+/// ```
+///
+/// With the `serde` feature enabled Pos, Size, and Rect can also be
+/// serialized and deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub pos: Pos,
     pub size: Size,
@@ -217,8 +267,7 @@ impl Rect {
     /// assert_eq!(rect.pos.x, 5);
     /// ```
     pub fn translate(&mut self, x: i32, y: i32) {
-        self.pos.x = (self.pos.x as i32 + x).max(0) as usize;
-        self.pos.y = (self.pos.y as i32 + y).max(0) as usize;
+        self.pos.offset(x, y);
     }
 
     /// Change the Rect's size without altering its position.
@@ -240,10 +289,91 @@ impl Rect {
     /// Pads the rect increasing (or decreasing) its size while
     /// attempting to maintain the center point
     pub fn pad(&mut self, width: i32, height: i32) {
-        self.size.width = (self.size.width as i32 + width).max(0) as usize;
-        self.size.height = (self.size.height as i32 + height).max(0) as usize;
-        self.pos.x = (self.pos.x as i32 + width / -2).max(0) as usize;
-        self.pos.y = (self.pos.y as i32 + height / -2).max(0) as usize;
+        self.size.grow(width, height);
+        self.pos.offset(width / -2, height / -2);
+    }
+
+    /// The range of row indexes (y coordinates) covered by the Rect.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// let rect = Rect::new((2,1), (3,2));
+    /// assert_eq!(rect.rows(), 1..3);
+    /// ```
+    pub fn rows(&self) -> std::ops::Range<usize> {
+        self.pos.y..self.pos.y.saturating_add(self.size.height)
+    }
+
+    /// The range of column indexes (x coordinates) covered by the Rect.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// let rect = Rect::new((2,1), (3,2));
+    /// assert_eq!(rect.columns(), 2..5);
+    /// ```
+    pub fn columns(&self) -> std::ops::Range<usize> {
+        self.pos.x..self.pos.x.saturating_add(self.size.width)
+    }
+
+    /// Returns an iterator over every position contained in the Rect. Positions
+    /// are returned row by row, from left to right.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// let rect = Rect::new((1,1), (2,2));
+    /// let cells: Vec<Pos> = rect.cells().collect();
+    /// assert_eq!(cells, vec![Pos::new(1,1), Pos::new(2,1), Pos::new(1,2), Pos::new(2,2)]);
+    /// ```
+    pub fn cells(&self) -> impl Iterator<Item = Pos> {
+        let columns = self.columns();
+        self.rows()
+            .flat_map(move |y| columns.clone().map(move |x| Pos::new(x, y)))
+    }
+
+    /// The position at the center of the Rect. For even dimensions the center
+    /// is rounded towards the top-left corner.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// let rect = Rect::new((2,2), (5,4));
+    /// assert_eq!(rect.center(), Pos::new(4,3));
+    /// ```
+    pub fn center(&self) -> Pos {
+        Pos::new(
+            self.pos.x + self.size.width.saturating_sub(1) / 2,
+            self.pos.y + self.size.height.saturating_sub(1) / 2,
+        )
+    }
+
+    /// Returns true if the given position falls within the Rect.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use arkham::prelude::*;
+    ///
+    /// let rect = Rect::new((2,2), (5,4));
+    /// assert!(rect.contains((6,5)));
+    /// assert!(!rect.contains((7,5)));
+    /// ```
+    pub fn contains<P>(&self, pos: P) -> bool
+    where
+        P: Into<Pos>,
+    {
+        let pos = pos.into();
+        self.columns().contains(&pos.x) && self.rows().contains(&pos.y)
     }
 }
 
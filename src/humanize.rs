@@ -0,0 +1,144 @@
+//! Helpers for formatting times, durations, and sizes for display.
+//!
+//! The formatted values are short enough to be used in list items and table
+//! cells. Only the standard library is used; chrono's DateTime can be passed
+//! anywhere a SystemTime is accepted, so chrono is not required.
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//! use arkham::humanize;
+//!
+//! let modified = SystemTime::now() - Duration::from_secs(180);
+//! assert_eq!(humanize::humantime(modified), "3m ago");
+//! assert_eq!(humanize::bytes(1536), "1.5 KiB");
+//! assert_eq!(humanize::duration(Duration::from_secs(200)), "3m 20s");
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+/// Format a time relative to now, such as `3m ago` or `in 2h`. Times within
+/// a few seconds of now are formatted as `just now`.
+///
+/// The time can be a SystemTime or anything convertible to one, such as a
+/// chrono DateTime.
+pub fn humantime<T: Into<SystemTime>>(time: T) -> String {
+    let time = time.into();
+    let now = SystemTime::now();
+    match now.duration_since(time) {
+        Ok(elapsed) => ago(elapsed),
+        Err(err) => until(err.duration()),
+    }
+}
+
+/// Format the time elapsed since an event, such as `3m ago`.
+pub fn ago(elapsed: Duration) -> String {
+    match largest_unit(elapsed) {
+        Some(amount) => format!("{} ago", amount),
+        None => "just now".to_string(),
+    }
+}
+
+/// Format the time remaining until an event, such as `in 3m`.
+pub fn until(remaining: Duration) -> String {
+    match largest_unit(remaining) {
+        Some(amount) => format!("in {}", amount),
+        None => "just now".to_string(),
+    }
+}
+
+/// The duration in its largest whole unit, such as `3m`, or None if it is
+/// under five seconds.
+fn largest_unit(duration: Duration) -> Option<String> {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const YEAR: u64 = 365 * DAY;
+
+    let secs = duration.as_secs();
+    let amount = match secs {
+        0..=4 => return None,
+        s if s < MINUTE => format!("{}s", s),
+        s if s < HOUR => format!("{}m", s / MINUTE),
+        s if s < DAY => format!("{}h", s / HOUR),
+        s if s < YEAR => format!("{}d", s / DAY),
+        s => format!("{}y", s / YEAR),
+    };
+    Some(amount)
+}
+
+/// Format a duration using its two largest units, such as `1h 5m`, `3m 20s`,
+/// or `4.2s`. Durations under a second are formatted in milliseconds.
+pub fn duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours >= 24 {
+        format!("{}d {}h", hours / 24, hours % 24)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else if secs >= 10 {
+        format!("{}s", secs)
+    } else if secs > 0 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// Format a number of bytes using binary units, such as `512 B`, `1.5 KiB`,
+/// or `42 MiB`. Values under ten are shown with one decimal place.
+pub fn bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value < 10.0 {
+        format!("{:.1} {}", value, UNITS[unit])
+    } else {
+        format!("{:.0} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{ago, bytes, duration, humantime};
+
+    #[test]
+    fn test_relative_time() {
+        let secs = Duration::from_secs;
+        assert_eq!(ago(secs(2)), "just now");
+        assert_eq!(ago(secs(42)), "42s ago");
+        assert_eq!(ago(secs(185)), "3m ago");
+        assert_eq!(ago(secs(7200)), "2h ago");
+        assert_eq!(ago(secs(86400 * 3)), "3d ago");
+        assert_eq!(ago(secs(86400 * 800)), "2y ago");
+        assert_eq!(humantime(SystemTime::now() + secs(3600 * 5 + 30)), "in 5h");
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(duration(Duration::from_millis(350)), "350ms");
+        assert_eq!(duration(Duration::from_millis(4200)), "4.2s");
+        assert_eq!(duration(Duration::from_secs(42)), "42s");
+        assert_eq!(duration(Duration::from_secs(200)), "3m 20s");
+        assert_eq!(duration(Duration::from_secs(3900)), "1h 5m");
+        assert_eq!(duration(Duration::from_secs(86400 + 7200)), "1d 2h");
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(512), "512 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(42 * 1024 * 1024), "42 MiB");
+        assert_eq!(bytes(u64::MAX), "16 EiB");
+    }
+}
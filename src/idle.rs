@@ -0,0 +1,137 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+struct IdleState {
+    last_input: Instant,
+    timeout: Option<Duration>,
+    idle: bool,
+}
+
+/// Idle can be used as an injectable resource that tracks the time since the
+/// user last interacted with the application.
+///
+/// When an idle timeout is configured with App::idle_timeout the application
+/// becomes idle once no input has been received for that duration. Plugins
+/// are notified through Plugin::on_idle and Plugin::on_active, and the
+/// application is rerendered so components can respond, for instance by
+/// dimming the screen.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root)
+///         .idle_timeout(Duration::from_secs(300))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, idle: Res<Idle>) {
+///     if idle.is_idle() {
+///         ctx.insert((0, 0), "Press any key to continue");
+///     } else {
+///         ctx.insert((0, 0), "Hello World");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Idle {
+    state: Rc<RefCell<IdleState>>,
+}
+
+impl Default for Idle {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl Idle {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(IdleState {
+                last_input: Instant::now(),
+                timeout,
+                idle: false,
+            })),
+        }
+    }
+
+    /// The time elapsed since input was last received.
+    pub fn since_input(&self) -> Duration {
+        self.state.borrow().last_input.elapsed()
+    }
+
+    /// The duration without input after which the application becomes idle.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.state.borrow().timeout
+    }
+
+    /// Returns true if the application is idle.
+    pub fn is_idle(&self) -> bool {
+        self.state.borrow().idle
+    }
+
+    /// The time remaining until the application becomes idle, or None if
+    /// there is no timeout or the application is already idle.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        let state = self.state.borrow();
+        match state.timeout {
+            Some(timeout) if !state.idle => {
+                Some(timeout.saturating_sub(state.last_input.elapsed()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Record that input was received. Returns true if the application was
+    /// idle and has become active.
+    pub(crate) fn touch(&self) -> bool {
+        let mut state = self.state.borrow_mut();
+        state.last_input = Instant::now();
+        std::mem::replace(&mut state.idle, false)
+    }
+
+    /// Check whether the idle timeout has elapsed. Returns true only when
+    /// the application has just become idle.
+    pub(crate) fn check(&self) -> bool {
+        let mut state = self.state.borrow_mut();
+        match state.timeout {
+            Some(timeout) if !state.idle && state.last_input.elapsed() >= timeout => {
+                state.idle = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Idle;
+
+    #[test]
+    fn test_idle_transitions() {
+        let idle = Idle::new(Some(Duration::ZERO));
+        assert!(!idle.is_idle());
+        assert!(idle.check());
+        assert!(idle.is_idle());
+        assert!(!idle.check());
+        assert!(idle.touch());
+        assert!(!idle.is_idle());
+        assert!(!idle.touch());
+
+        let idle = Idle::default();
+        assert!(!idle.check());
+        assert_eq!(idle.remaining(), None);
+
+        let idle = Idle::new(Some(Duration::from_secs(60)));
+        assert!(idle.remaining().unwrap() > Duration::from_secs(59));
+    }
+}
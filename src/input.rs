@@ -1,20 +1,35 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::{geometry::Pos, keymap::KeyBinding};
 
 /// Keyboard can be used as an injectable resource that provides information
 /// about the current keyboard state. This is the primary mechanism by which
 /// applications can respond to keyboard input from users.
+///
+/// A Keyboard can also be constructed directly to test components that
+/// inject it without running the application:
+///
+/// ```
+/// use arkham::prelude::*;
+///
+/// let kb = Keyboard::with_key(KeyCode::Char('q')).with_modifiers(KeyModifiers::CONTROL);
+/// assert_eq!(kb.char(), Some('q'));
+/// assert!(kb.control());
+/// ```
 #[derive(Debug)]
 pub struct Keyboard {
     key: Rc<RefCell<Option<KeyCode>>>,
     modifiers: Rc<RefCell<KeyModifiers>>,
+    paste: Rc<RefCell<Option<String>>>,
 }
 impl Default for Keyboard {
     fn default() -> Self {
         Self {
             key: Rc::new(RefCell::new(None)),
             modifiers: Rc::new(RefCell::new(KeyModifiers::empty())),
+            paste: Rc::new(RefCell::new(None)),
         }
     }
 }
@@ -24,13 +39,38 @@ impl Keyboard {
         Self::default()
     }
 
+    /// Create a keyboard with a specific keycode pressed.
+    pub fn with_key(k: KeyCode) -> Self {
+        let kb = Self::new();
+        kb.set_key(k);
+        kb
+    }
+
+    /// Set the modifier keys that are pressed along with the key.
+    pub fn with_modifiers(self, modifiers: KeyModifiers) -> Self {
+        self.set_modifiers(modifiers);
+        self
+    }
+
+    /// Create a keyboard with text pasted.
+    pub fn with_paste<S: ToString>(text: S) -> Self {
+        let kb = Self::new();
+        kb.set_paste(text);
+        kb
+    }
+
+    /// Set the keyboard state to indicate text was pasted.
+    pub fn set_paste<S: ToString>(&self, text: S) {
+        *self.paste.borrow_mut() = Some(text.to_string());
+    }
+
     /// Set the keyboard state to indicate a specific keycode is pressed
-    pub(crate) fn set_key(&self, k: KeyCode) {
+    pub fn set_key(&self, k: KeyCode) {
         *self.key.borrow_mut() = Some(k);
     }
 
     /// Set the keyboard state to indicate specific modifier keys are pressed
-    pub(crate) fn set_modifiers(&self, modifiers: KeyModifiers) {
+    pub fn set_modifiers(&self, modifiers: KeyModifiers) {
         *self.modifiers.borrow_mut() = modifiers;
     }
 
@@ -39,6 +79,14 @@ impl Keyboard {
     /// registering the keypress event
     pub fn reset(&self) {
         *self.key.borrow_mut() = None;
+        *self.paste.borrow_mut() = None;
+    }
+
+    /// Returns the text pasted into the terminal, or None if nothing was
+    /// pasted. Pasted text is received all at once rather than as individual
+    /// key presses, and is consumed with Keyboard::reset like a key press.
+    pub fn pasted(&self) -> Option<String> {
+        self.paste.borrow().clone()
     }
 
     /// Retruns the keycode that is current pressed, or None if there are
@@ -57,6 +105,11 @@ impl Keyboard {
         }
     }
 
+    /// Returns the modifier keys that are currently pressed.
+    pub fn modifiers(&self) -> KeyModifiers {
+        *self.modifiers.borrow()
+    }
+
     /// Returns true if the shift key is current pressed
     pub fn shift(&self) -> bool {
         self.modifiers.borrow().contains(KeyModifiers::SHIFT)
@@ -87,3 +140,328 @@ impl Keyboard {
         self.modifiers.borrow().contains(KeyModifiers::META)
     }
 }
+
+/// What the key pressed after one of the macro keys selects the register
+/// for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MacroPrompt {
+    Record,
+    Replay,
+}
+
+#[derive(Debug, Default)]
+struct MacroState {
+    registers: HashMap<char, Vec<KeyBinding>>,
+    recording: Option<(char, Vec<KeyBinding>)>,
+    prompt: Option<MacroPrompt>,
+    last: Option<char>,
+}
+
+/// Macros can be used as an injectable resource holding keyboard macros:
+/// sequences of key presses recorded into named registers, which can be
+/// replayed as if they were typed again.
+///
+/// Macros work like those of vim. Pressing the key bound to the
+/// `Macros::RECORD` action of the Keymap, followed by a letter, starts
+/// recording into the register named by the letter, and pressing the record
+/// key again stops recording. Pressing the key bound to `Macros::REPLAY`,
+/// followed by a letter, replays the register, and pressing the replay key
+/// twice replays the last register replayed. The application receives each
+/// replayed key in turn, rendering a frame for each.
+///
+/// Macros are disabled unless both actions are bound in a Keymap. Binding
+/// the `q` key requires App::disbale_q_to_quit.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     let keymap = Keymap::new()
+///         .bind(Macros::RECORD, KeyCode::Char('q'))
+///         .bind(Macros::REPLAY, KeyCode::Char('@'));
+///     App::new(root)
+///         .disbale_q_to_quit()
+///         .insert_resource(keymap)
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, macros: Res<Macros>) {
+///     if let Some(register) = macros.recording() {
+///         ctx.insert((0, 0), format!("recording @{}", register));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Macros {
+    state: Rc<RefCell<MacroState>>,
+}
+
+impl Macros {
+    /// The Keymap action which starts and stops recording a macro.
+    pub const RECORD: &'static str = "record_macro";
+    /// The Keymap action which replays a macro.
+    pub const REPLAY: &'static str = "replay_macro";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The register a macro is being recorded into, if one is being
+    /// recorded.
+    pub fn recording(&self) -> Option<char> {
+        self.state
+            .borrow()
+            .recording
+            .as_ref()
+            .map(|(name, _)| *name)
+    }
+
+    /// The keys recorded into a register.
+    pub fn register(&self, name: char) -> Option<Vec<KeyBinding>> {
+        self.state.borrow().registers.get(&name).cloned()
+    }
+
+    /// Set the keys held in a register, such as a macro saved by a previous
+    /// run of the application.
+    pub fn set_register(&self, name: char, keys: Vec<KeyBinding>) {
+        self.state.borrow_mut().registers.insert(name, keys);
+    }
+
+    /// Process a key pressed by the user, given the Keymap action bound to
+    /// it, returning the keys the application should receive in response.
+    /// Keys which control macros are consumed, replaying a macro returns
+    /// its keys, and any other key is returned as it is, after being
+    /// recorded if a macro is being recorded.
+    pub(crate) fn process(&self, key: KeyBinding, action: Option<&str>) -> Vec<KeyBinding> {
+        let mut state = self.state.borrow_mut();
+        if let Some(prompt) = state.prompt.take() {
+            let mut name = match key.code {
+                KeyCode::Char(c) if (key.modifiers - KeyModifiers::SHIFT).is_empty() => Some(c),
+                _ => None,
+            };
+            if prompt == MacroPrompt::Replay && action == Some(Self::REPLAY) {
+                name = state.last;
+            }
+            match (prompt, name) {
+                (MacroPrompt::Record, Some(name)) => state.recording = Some((name, vec![])),
+                (MacroPrompt::Replay, Some(name)) => {
+                    let keys = state.registers.get(&name).cloned().unwrap_or_default();
+                    state.last = Some(name);
+                    if let Some((_, recorded)) = &mut state.recording {
+                        recorded.extend(keys.iter().copied());
+                    }
+                    return keys;
+                }
+                _ => {}
+            }
+            return vec![];
+        }
+        match action {
+            Some(Self::RECORD) => {
+                match state.recording.take() {
+                    Some((name, keys)) => {
+                        state.registers.insert(name, keys);
+                    }
+                    None => state.prompt = Some(MacroPrompt::Record),
+                }
+                vec![]
+            }
+            Some(Self::REPLAY) => {
+                state.prompt = Some(MacroPrompt::Replay);
+                vec![]
+            }
+            _ => {
+                if let Some((_, recorded)) = &mut state.recording {
+                    recorded.push(key);
+                }
+                vec![key]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MouseState {
+    event: Option<MouseEvent>,
+    position: Option<Pos>,
+    pressed: Vec<MouseButton>,
+}
+
+/// Mouse can be used as an injectable resource that provides information
+/// about the mouse, similar to Keyboard. Mouse events are only received once
+/// mouse capture has been enabled with App::enable_mouse.
+///
+/// The event received for the current frame is available through
+/// Mouse::event, and is cleared once the frame has been rendered. The
+/// position of the mouse and the buttons held down are kept between frames.
+///
+/// ```
+/// use arkham::prelude::*;
+///
+/// let mouse = Mouse::with_event(MouseEvent {
+///     kind: MouseEventKind::Down(MouseButton::Left),
+///     column: 4,
+///     row: 2,
+///     modifiers: KeyModifiers::NONE,
+/// });
+/// assert_eq!(mouse.position(), Some(Pos::new(4, 2)));
+/// assert!(mouse.clicked(MouseButton::Left));
+/// assert!(mouse.is_pressed(MouseButton::Left));
+/// ```
+#[derive(Debug, Default)]
+pub struct Mouse {
+    state: Rc<RefCell<MouseState>>,
+}
+
+impl Mouse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a mouse which has received an event.
+    pub fn with_event(event: MouseEvent) -> Self {
+        let mouse = Self::new();
+        mouse.set_event(event);
+        mouse
+    }
+
+    /// Set the event received for the current frame, updating the position
+    /// and the buttons held down.
+    pub fn set_event(&self, event: MouseEvent) {
+        let mut state = self.state.borrow_mut();
+        state.position = Some(Pos::new(event.column as usize, event.row as usize));
+        match event.kind {
+            MouseEventKind::Down(button) | MouseEventKind::Drag(button)
+                if !state.pressed.contains(&button) =>
+            {
+                state.pressed.push(button)
+            }
+            MouseEventKind::Up(button) => state.pressed.retain(|b| *b != button),
+            MouseEventKind::Moved => state.pressed.clear(),
+            _ => {}
+        }
+        state.event = Some(event);
+    }
+
+    /// Clears the event for the current frame. This can be used after
+    /// handling an event within a component to prevent further components
+    /// from responding to it.
+    pub fn reset(&self) {
+        self.state.borrow_mut().event = None;
+    }
+
+    /// Returns the event received for the current frame, or None if the
+    /// frame was not rendered in response to the mouse.
+    pub fn event(&self) -> Option<MouseEvent> {
+        self.state.borrow().event
+    }
+
+    /// Returns the last known position of the mouse on the screen.
+    pub fn position(&self) -> Option<Pos> {
+        self.state.borrow().position
+    }
+
+    /// Returns true if a button is held down.
+    pub fn is_pressed(&self, button: MouseButton) -> bool {
+        self.state.borrow().pressed.contains(&button)
+    }
+
+    /// Returns true if a button was pressed during the current frame.
+    pub fn clicked(&self, button: MouseButton) -> bool {
+        matches!(self.event(), Some(MouseEvent { kind: MouseEventKind::Down(b), .. }) if b == button)
+    }
+
+    /// Returns the horizontal and vertical distance scrolled during the
+    /// current frame. Scrolling down or to the right is positive.
+    pub fn scroll(&self) -> (i32, i32) {
+        match self.event().map(|event| event.kind) {
+            Some(MouseEventKind::ScrollUp) => (0, -1),
+            Some(MouseEventKind::ScrollDown) => (0, 1),
+            Some(MouseEventKind::ScrollLeft) => (-1, 0),
+            Some(MouseEventKind::ScrollRight) => (1, 0),
+            _ => (0, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{container::Res, context::ViewContext};
+
+    use super::{Keyboard, Macros, Mouse};
+
+    fn counter(ctx: &mut ViewContext, kb: Res<Keyboard>) {
+        if kb.char() == Some('+') && kb.shift() {
+            ctx.insert(0, "up");
+            kb.reset();
+        }
+    }
+
+    #[test]
+    fn test_injected_keyboard() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let kb = Keyboard::with_key(KeyCode::Char('+')).with_modifiers(KeyModifiers::SHIFT);
+        ctx.container.borrow_mut().bind(Res::new(kb));
+        ctx.component(((0, 0), (5, 1)), counter);
+        assert_eq!(ctx.view[0][0].content, Some('u'));
+
+        let container = ctx.container.borrow();
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        assert_eq!(kb.code(), None);
+        assert!(kb.shift());
+    }
+
+    #[test]
+    fn test_mouse_state() {
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let event = |kind| MouseEvent {
+            kind,
+            column: 3,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        };
+        let mouse = Mouse::with_event(event(MouseEventKind::Drag(MouseButton::Right)));
+        assert!(mouse.is_pressed(MouseButton::Right));
+        assert!(!mouse.clicked(MouseButton::Right));
+        mouse.reset();
+        assert!(mouse.event().is_none());
+        assert!(mouse.is_pressed(MouseButton::Right));
+
+        mouse.set_event(event(MouseEventKind::Up(MouseButton::Right)));
+        assert!(!mouse.is_pressed(MouseButton::Right));
+        mouse.set_event(event(MouseEventKind::ScrollUp));
+        assert_eq!(mouse.scroll(), (0, -1));
+        assert_eq!(mouse.position(), Some((3, 1).into()));
+    }
+
+    #[test]
+    fn test_macros() {
+        let key = |c| KeyCode::Char(c).into();
+        let macros = Macros::new();
+        assert!(macros.process(key('q'), Some(Macros::RECORD)).is_empty());
+        assert!(macros.process(key('a'), None).is_empty());
+        assert_eq!(macros.recording(), Some('a'));
+        assert_eq!(macros.process(key('x'), None), vec![key('x')]);
+        assert_eq!(macros.process(key('y'), None), vec![key('y')]);
+        assert!(macros.process(key('q'), Some(Macros::RECORD)).is_empty());
+        assert_eq!(macros.recording(), None);
+        assert_eq!(macros.register('a'), Some(vec![key('x'), key('y')]));
+
+        assert!(macros.process(key('@'), Some(Macros::REPLAY)).is_empty());
+        assert_eq!(macros.process(key('a'), None), vec![key('x'), key('y')]);
+        macros.process(key('@'), Some(Macros::REPLAY));
+        assert_eq!(
+            macros.process(key('@'), Some(Macros::REPLAY)),
+            vec![key('x'), key('y')]
+        );
+
+        // Escape cancels choosing a register.
+        macros.process(key('@'), Some(Macros::REPLAY));
+        assert!(macros.process(KeyCode::Esc.into(), None).is_empty());
+        assert_eq!(macros.process(key('a'), None), vec![key('a')]);
+    }
+}
@@ -0,0 +1,248 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::input::Keyboard;
+
+/// A key along with the modifier keys held with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Returns true if the binding's key is pressed. Shift is ignored for
+    /// character keys, as it is already reflected in the character.
+    pub fn matches(&self, kb: &Keyboard) -> bool {
+        if kb.code() != Some(self.code) {
+            return false;
+        }
+        let mut modifiers = kb.modifiers();
+        let mut expected = self.modifiers;
+        if matches!(self.code, KeyCode::Char(_)) {
+            modifiers.remove(KeyModifiers::SHIFT);
+            expected.remove(KeyModifiers::SHIFT);
+        }
+        modifiers == expected
+    }
+
+    /// The names of the modifier keys and the key, in the order they are
+    /// displayed, such as `["Ctrl", "S"]`.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+            (KeyModifiers::SUPER, "Super"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| self.modifiers.contains(*modifier))
+        .map(|(_, name)| name.to_string())
+        .collect();
+        names.push(key_name(self.code));
+        names
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<(KeyCode, KeyModifiers)> for KeyBinding {
+    fn from((code, modifiers): (KeyCode, KeyModifiers)) -> Self {
+        Self::new(code, modifiers)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.names().join("+"))
+    }
+}
+
+/// The display name of a key.
+fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_uppercase().to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        code => format!("{:?}", code),
+    }
+}
+
+/// A named action and the key it is bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapEntry {
+    pub action: String,
+    pub binding: KeyBinding,
+    pub description: Option<String>,
+}
+
+/// Keymap can be used as an injectable resource which maps named actions to
+/// keys. Components check for actions rather than specific keys, so bindings
+/// can be changed in one place, and shortcut hints drawn from the keymap
+/// always show the keys that are actually bound.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     let keymap = Keymap::new()
+///         .bind("save", (KeyCode::Char('s'), KeyModifiers::CONTROL))
+///         .describe("save", "Save the document")
+///         .bind("quit", KeyCode::Esc);
+///     App::new(root).insert_resource(keymap).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, keymap: Res<Keymap>, kb: Res<Keyboard>) {
+///     if keymap.pressed("save", &kb) {
+///         ctx.insert((0, 1), "Saved");
+///     }
+///     if let Some(binding) = keymap.binding("save") {
+///         ctx.insert((0, 0), format!("Press {} to save", binding));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    entries: Rc<RefCell<Vec<KeymapEntry>>>,
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind an action to a key, replacing any existing binding for the
+    /// action.
+    pub fn bind<S: ToString, B: Into<KeyBinding>>(self, action: S, binding: B) -> Self {
+        self.set(action, binding);
+        self
+    }
+
+    /// Set a description of an action, used by shortcut hints in place of
+    /// the action's name.
+    pub fn describe<S: ToString, D: ToString>(self, action: S, description: D) -> Self {
+        let action = action.to_string();
+        if let Some(entry) = self
+            .entries
+            .borrow_mut()
+            .iter_mut()
+            .find(|entry| entry.action == action)
+        {
+            entry.description = Some(description.to_string());
+        }
+        self
+    }
+
+    /// Bind an action to a key while the application is running, replacing
+    /// any existing binding for the action.
+    pub fn set<S: ToString, B: Into<KeyBinding>>(&self, action: S, binding: B) {
+        let action = action.to_string();
+        let binding = binding.into();
+        let mut entries = self.entries.borrow_mut();
+        match entries.iter_mut().find(|entry| entry.action == action) {
+            Some(entry) => entry.binding = binding,
+            None => entries.push(KeymapEntry {
+                action,
+                binding,
+                description: None,
+            }),
+        }
+    }
+
+    /// The key bound to an action.
+    pub fn binding(&self, action: &str) -> Option<KeyBinding> {
+        self.entry(action).map(|entry| entry.binding)
+    }
+
+    /// The binding and description of an action.
+    pub fn entry(&self, action: &str) -> Option<KeymapEntry> {
+        self.entries
+            .borrow()
+            .iter()
+            .find(|entry| entry.action == action)
+            .cloned()
+    }
+
+    /// Every bound action, in the order they were bound.
+    pub fn entries(&self) -> Vec<KeymapEntry> {
+        self.entries.borrow().clone()
+    }
+
+    /// Returns true if the key bound to an action is pressed.
+    pub fn pressed(&self, action: &str, kb: &Keyboard) -> bool {
+        self.binding(action)
+            .is_some_and(|binding| binding.matches(kb))
+    }
+
+    /// The action bound to the pressed key, if any.
+    pub fn action(&self, kb: &Keyboard) -> Option<String> {
+        self.entries
+            .borrow()
+            .iter()
+            .find(|entry| entry.binding.matches(kb))
+            .map(|entry| entry.action.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::input::Keyboard;
+
+    use super::{KeyBinding, Keymap};
+
+    #[test]
+    fn test_bindings() {
+        let keymap = Keymap::new()
+            .bind("save", (KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .bind("quit", KeyCode::Esc)
+            .describe("quit", "Quit");
+        let kb = Keyboard::with_key(KeyCode::Char('s')).with_modifiers(KeyModifiers::CONTROL);
+        assert!(keymap.pressed("save", &kb));
+        assert!(!keymap.pressed("quit", &kb));
+        assert_eq!(keymap.action(&kb).as_deref(), Some("save"));
+        assert!(!keymap.pressed("save", &Keyboard::with_key(KeyCode::Char('s'))));
+
+        keymap.set("save", KeyCode::F(2));
+        assert_eq!(keymap.binding("save").unwrap().to_string(), "F2");
+        assert_eq!(keymap.entries().len(), 2);
+        assert_eq!(
+            keymap.entry("quit").unwrap().description.as_deref(),
+            Some("Quit")
+        );
+
+        let binding = KeyBinding::new(
+            KeyCode::Char('k'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        assert_eq!(binding.to_string(), "Ctrl+Alt+K");
+        let upper = KeyBinding::from(KeyCode::Char('G'));
+        assert!(upper
+            .matches(&Keyboard::with_key(KeyCode::Char('G')).with_modifiers(KeyModifiers::SHIFT)));
+    }
+}
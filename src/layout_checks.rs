@@ -0,0 +1,172 @@
+//! Layout invariant checks, enabled with the `layout-checks` feature.
+//!
+//! While enabled, every component rendered during a frame is checked to
+//! ensure its region has a non-zero size and fits within its parent, and
+//! that nothing it inserts is clipped at the edge of its region. Violations
+//! are collected during the frame and reported together, naming the
+//! component responsible, when the frame is finished. In debug builds the
+//! report is raised as an assertion failure.
+//!
+//! When the feature is disabled these functions do nothing.
+
+use crate::geometry::{Pos, Rect, Size};
+
+#[cfg(feature = "layout-checks")]
+mod checks {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static COMPONENTS: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+        static VIOLATIONS: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+    }
+
+    pub fn enter(name: &'static str) {
+        COMPONENTS.with(|c| c.borrow_mut().push(name));
+    }
+
+    pub fn exit() {
+        COMPONENTS.with(|c| c.borrow_mut().pop());
+    }
+
+    pub fn depth() -> usize {
+        COMPONENTS.with(|c| c.borrow().len())
+    }
+
+    pub fn unwind(depth: usize) {
+        COMPONENTS.with(|c| c.borrow_mut().truncate(depth));
+    }
+
+    pub fn report(message: String) {
+        let name = COMPONENTS.with(|c| c.borrow().last().copied().unwrap_or("root"));
+        VIOLATIONS.with(|v| v.borrow_mut().push(format!("{}: {}", name, message)));
+    }
+
+    pub fn take() -> Vec<String> {
+        VIOLATIONS.with(|v| std::mem::take(&mut *v.borrow_mut()))
+    }
+}
+
+/// Record that a component has started rendering.
+#[inline]
+pub(crate) fn enter<F>() {
+    #[cfg(feature = "layout-checks")]
+    checks::enter(std::any::type_name::<F>());
+}
+
+/// Record that the most recently entered component has finished rendering.
+#[inline]
+pub(crate) fn exit() {
+    #[cfg(feature = "layout-checks")]
+    checks::exit();
+}
+
+/// The number of components currently rendering.
+#[inline]
+pub(crate) fn depth() -> usize {
+    #[cfg(feature = "layout-checks")]
+    return checks::depth();
+    #[cfg(not(feature = "layout-checks"))]
+    0
+}
+
+/// Discard components entered after a given depth which did not exit
+/// because they panicked.
+#[inline]
+pub(crate) fn unwind(_depth: usize) {
+    #[cfg(feature = "layout-checks")]
+    checks::unwind(_depth);
+}
+
+/// Check that a component region is non-zero and fits within its parent.
+#[inline]
+pub(crate) fn check_rect(rect: Rect, parent: Size) {
+    #[cfg(feature = "layout-checks")]
+    {
+        if rect.size.width == 0 || rect.size.height == 0 {
+            checks::report(format!("rendered with a zero size {:?}", rect.size));
+        }
+        if rect.pos.x + rect.size.width > parent.width
+            || rect.pos.y + rect.size.height > parent.height
+        {
+            checks::report(format!(
+                "region {:?} {:?} does not fit within its parent {:?}",
+                rect.pos, rect.size, parent
+            ));
+        }
+    }
+    #[cfg(not(feature = "layout-checks"))]
+    let _ = (rect, parent);
+}
+
+/// Check that an insert of a given length at a position fits within a view.
+#[inline]
+pub(crate) fn check_insert(pos: Pos, len: usize, size: Size) {
+    #[cfg(feature = "layout-checks")]
+    if len > 0 && (pos.y >= size.height || pos.x + len > size.width) {
+        checks::report(format!(
+            "inserted {} runes at {:?} outside of its region {:?}",
+            len, pos, size
+        ));
+    }
+    #[cfg(not(feature = "layout-checks"))]
+    let _ = (pos, len, size);
+}
+
+/// Take the violations recorded during the frame.
+#[cfg(feature = "layout-checks")]
+pub(crate) fn take_violations() -> Vec<String> {
+    checks::take()
+}
+
+/// Finish a frame, failing a debug assertion if any invariants were
+/// violated while it was rendered.
+#[inline]
+pub(crate) fn finish_frame() {
+    #[cfg(feature = "layout-checks")]
+    {
+        let violations = take_violations();
+        debug_assert!(
+            violations.is_empty(),
+            "layout invariants violated:\n  {}",
+            violations.join("\n  ")
+        );
+    }
+}
+
+#[cfg(all(test, feature = "layout-checks"))]
+mod tests {
+    use crate::{context::ViewContext, stack::StackAlignment};
+
+    use super::take_violations;
+
+    fn overflowing(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "too long for the region");
+    }
+
+    #[test]
+    fn test_reports_component() {
+        let _ = take_violations();
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component(((0, 0), (5, 1)), overflowing);
+        ctx.component(((18, 0), (5, 1)), |_: &mut ViewContext| {});
+        ctx.component(((0, 0), (0, 1)), |_: &mut ViewContext| {});
+        let violations = take_violations();
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].contains("overflowing: inserted 23 runes"));
+        assert!(violations[1].contains("does not fit within its parent"));
+        assert!(violations[2].contains("zero size"));
+    }
+
+    #[test]
+    fn test_stack_children() {
+        let _ = take_violations();
+        let ctx = crate::context::tests::context_fixture();
+        let mut stack = ctx.vertical_stack((5, 2));
+        stack.alignment(StackAlignment::Left);
+        stack.component((5, 1), |_: &mut ViewContext| {});
+        stack.component((5, 2), |_: &mut ViewContext| {});
+        let violations = take_violations();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("does not fit within its parent"));
+    }
+}
@@ -1,14 +1,37 @@
+mod animation;
+mod ansi;
 mod app;
+mod color;
+pub mod components;
 mod container;
 mod context;
+mod dock;
+mod features;
+mod frame;
 mod geometry;
+pub mod humanize;
+mod idle;
 mod input;
+mod keymap;
+mod layout_checks;
+mod links;
+pub mod measure;
+mod overlay;
 pub mod plugins;
+mod pointer;
+mod profiler;
+pub mod prompts;
+mod provenance;
 mod runes;
+mod search;
 mod stack;
+mod style;
 pub mod symbols;
+mod terminal;
+pub mod testing;
 mod theme;
 mod view;
+mod zoom;
 
 pub mod internal {
     pub use super::container::{Container, ContainerRef};
@@ -17,22 +40,35 @@ pub mod internal {
 
 pub mod prelude {
     pub use super::{
+        animation::Animation,
         app::{App, Renderer, Terminal},
-        container::{Callable, FromContainer, Res, State},
+        color::{ColorBlindness, ColorFilter},
+        container::{Callable, FromContainer, InjectionError, Res, Scoped, State},
         context::ViewContext,
+        dock::{Corner, Dock, Docks},
+        features::Features,
+        frame::{Frame, FrameStats, SlowFrame},
         geometry::{Pos, Rect, Size},
-        input::Keyboard,
-        runes::{Rune, Runes, ToRuneExt},
+        idle::Idle,
+        input::{Keyboard, Macros, Mouse},
+        keymap::{KeyBinding, Keymap, KeymapEntry},
+        overlay::{Placement, Side},
+        pointer::CursorShape,
+        runes::{Rune, RuneSlice, Runes, ToRuneExt},
+        search::{SearchMatch, SearchState},
         stack::StackAlignment,
-        theme::Theme,
+        style::Style,
+        terminal::TerminalBackground,
+        theme::{Theme, ThemeVariant},
+        zoom::Zoom,
     };
-    pub use crossterm::event::KeyCode;
+    pub use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
     pub use crossterm::style::Color;
 }
 
 #[cfg(test)]
 pub mod tests {
-    pub fn print_render_text(s: &String) {
+    pub fn print_render_text(s: &str) {
         println!("{}", s.replace('\0', " "));
     }
 }
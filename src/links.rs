@@ -0,0 +1,159 @@
+use std::{ops::Range, path::PathBuf};
+
+/// Characters that may surround a link in prose, such as brackets and
+/// quotes, and are not part of it.
+const OPENING: &[char] = &['(', '<', '[', '{', '"', '\'', '`'];
+const CLOSING: &[char] = &[
+    ')', '>', ']', '}', '"', '\'', '`', '.', ',', ';', ':', '!', '?',
+];
+
+/// Find the URLs and file paths within text, returning the range of
+/// characters each occupies and the URL it links to. File paths link to a
+/// `file://` URL of the absolute path, with the line number, if the path is
+/// followed by one, as a `#L` fragment.
+pub(crate) fn detect(text: &[char]) -> Vec<(Range<usize>, String)> {
+    let mut links = vec![];
+    let mut start = 0;
+    while start < text.len() {
+        if text[start].is_whitespace() {
+            start += 1;
+            continue;
+        }
+        let end = (start..text.len())
+            .find(|&idx| text[idx].is_whitespace())
+            .unwrap_or(text.len());
+        let (mut first, mut last) = (start, end);
+        while first < last && OPENING.contains(&text[first]) {
+            first += 1;
+        }
+        while last > first && CLOSING.contains(&text[last - 1]) {
+            last -= 1;
+        }
+        let token: String = text[first..last].iter().collect();
+        if let Some(url) = url(&token).or_else(|| file_url(&token)) {
+            links.push((first..last, url));
+        }
+        start = end;
+    }
+    links
+}
+
+/// The token if it is an http or https URL.
+fn url(token: &str) -> Option<String> {
+    let rest = token
+        .strip_prefix("https://")
+        .or_else(|| token.strip_prefix("http://"))?;
+    (!rest.is_empty()).then(|| token.to_string())
+}
+
+/// The file URL for a token that looks like a file path, optionally followed
+/// by a line and column such as `src/main.rs:12:5`.
+///
+/// Paths must contain a slash and a letter, and either start with `/`, `./`,
+/// `../` or `~/`, or end with a file name that has an extension, so that
+/// text such as `and/or` or `2024/01/02` is not mistaken for a path.
+fn file_url(token: &str) -> Option<String> {
+    let mut parts = token.split(':');
+    let path = parts.next()?;
+    let line = match parts.next() {
+        Some(line) => Some(line.parse::<usize>().ok()?),
+        None => None,
+    };
+    if parts.any(|column| column.parse::<usize>().is_err()) {
+        return None;
+    }
+
+    let valid = |c: char| c.is_alphanumeric() || "._-/~+@".contains(c);
+    if path.len() < 2
+        || !path.contains('/')
+        || !path.chars().all(valid)
+        || !path.chars().any(char::is_alphabetic)
+    {
+        return None;
+    }
+    let prefixed = ["/", "./", "../", "~/"].iter().any(|p| path.starts_with(p));
+    let name = path.rsplit('/').next().unwrap_or("");
+    let has_extension = name
+        .rfind('.')
+        .is_some_and(|dot| dot > 0 && dot + 1 < name.len());
+    if !prefixed && !has_extension {
+        return None;
+    }
+
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var_os("HOME")?).join(rest),
+        None => PathBuf::from(path),
+    };
+    let path = if path.is_absolute() {
+        path
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    let mut url = format!("file://{}", path.display());
+    if let Some(line) = line {
+        url.push_str(&format!("#L{}", line));
+    }
+    Some(url)
+}
+
+/// The path and line number of a `file://` URL created for a detected file
+/// path, or None if the URL is not a file URL.
+pub(crate) fn file_location(url: &str) -> Option<(PathBuf, Option<usize>)> {
+    let rest = url.strip_prefix("file://")?;
+    match rest.rsplit_once("#L") {
+        Some((path, line)) => Some((PathBuf::from(path), line.parse().ok())),
+        None => Some((PathBuf::from(rest), None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect, file_location};
+
+    fn links(text: &str) -> Vec<(String, String)> {
+        let chars: Vec<char> = text.chars().collect();
+        detect(&chars)
+            .into_iter()
+            .map(|(range, url)| (chars[range].iter().collect(), url))
+            .collect()
+    }
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(
+            links("see (https://docs.rs/arkham). or http://x.io"),
+            vec![
+                (
+                    "https://docs.rs/arkham".to_string(),
+                    "https://docs.rs/arkham".to_string()
+                ),
+                ("http://x.io".to_string(), "http://x.io".to_string()),
+            ]
+        );
+        assert!(links("https:// ftp://host").is_empty());
+    }
+
+    #[test]
+    fn test_paths() {
+        let cwd = std::env::current_dir().unwrap();
+        let found = links("error at src/main.rs:42:7, see /etc/hosts and ./run");
+        assert_eq!(found[0].0, "src/main.rs:42:7");
+        assert_eq!(
+            found[0].1,
+            format!("file://{}#L42", cwd.join("src/main.rs").display())
+        );
+        assert_eq!(found[1].1, "file:///etc/hosts");
+        assert_eq!(found[2].0, "./run");
+        assert!(links("and/or 2024/01/02 1/2 x/y .hidden main.rs").is_empty());
+
+        assert_eq!(
+            file_location(&found[0].1),
+            Some((cwd.join("src/main.rs"), Some(42)))
+        );
+        assert_eq!(
+            file_location("file:///etc/hosts"),
+            Some(("/etc/hosts".into(), None))
+        );
+        assert_eq!(file_location("https://docs.rs"), None);
+    }
+}
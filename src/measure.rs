@@ -0,0 +1,113 @@
+//! Memoized text measurement.
+//!
+//! Measuring a string converts it to runes, which escapes control
+//! characters and expands tabs, and wrapping it finds the line breaks.
+//! Components that size themselves to their content measure the same
+//! strings every frame, so the results are cached. The cache is kept per
+//! thread and is cleared once it grows too large.
+//!
+//! ```
+//! use arkham::measure;
+//!
+//! assert_eq!(measure::width("one\nthree"), 5);
+//! assert_eq!(measure::width("a\tb"), 5);
+//! assert_eq!(measure::line_count("hello there world", 11), 2);
+//! ```
+
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::runes::{tab_width, wrap, Runes};
+
+/// The most strings kept in the cache before it is cleared.
+const CAPACITY: usize = 4096;
+
+#[derive(Debug)]
+struct Measurement {
+    /// The tab width the text was converted with.
+    tab_width: usize,
+    runes: Runes,
+    width: usize,
+    /// The number of lines the text wraps to at each width it has been
+    /// wrapped at.
+    lines: Vec<(usize, usize)>,
+}
+
+impl Measurement {
+    fn new(text: &str) -> Self {
+        let runes = Runes::from(text);
+        let width = runes
+            .split(|r| r.content == Some('\n'))
+            .map(<[_]>::len)
+            .max()
+            .unwrap_or(0);
+        Self {
+            tab_width: tab_width(),
+            runes,
+            width,
+            lines: vec![],
+        }
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<String, Measurement>> = RefCell::new(HashMap::new());
+}
+
+/// Call a function with the measurement for a string, measuring it if it
+/// is not cached or the tab width has changed since it was measured.
+fn measurement<T>(text: &str, f: impl FnOnce(&mut Measurement) -> T) -> T {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if !matches!(cache.get(text), Some(m) if m.tab_width == tab_width()) {
+            if cache.len() >= CAPACITY {
+                cache.clear();
+            }
+            cache.insert(text.to_string(), Measurement::new(text));
+        }
+        f(cache.get_mut(text).unwrap())
+    })
+}
+
+/// The number of cells the widest line of a string takes when it is
+/// inserted into a view.
+pub fn width(text: &str) -> usize {
+    measurement(text, |m| m.width)
+}
+
+/// The number of lines a string takes when it is wrapped to a width, as
+/// with ViewContext::insert_wrapped.
+pub fn line_count(text: &str, width: usize) -> usize {
+    measurement(text, |m| {
+        if let Some((_, count)) = m.lines.iter().find(|(w, _)| *w == width) {
+            return *count;
+        }
+        let count = wrap(&m.runes, width).len();
+        m.lines.push((width, count));
+        count
+    })
+}
+
+/// Remove every cached measurement on the current thread.
+pub fn clear() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_count, width, CACHE};
+
+    #[test]
+    fn test_measure() {
+        super::clear();
+        assert_eq!(width("abc"), 3);
+        assert_eq!(width("\x07"), 2);
+        assert_eq!(line_count("aaa bbb ccc", 7), 2);
+        assert_eq!(line_count("aaa bbb ccc", 3), 3);
+        assert_eq!(line_count("aaa bbb ccc", 7), 2);
+        CACHE.with(|cache| {
+            let cache = cache.borrow();
+            assert_eq!(cache.len(), 3);
+            assert_eq!(cache["aaa bbb ccc"].lines, vec![(7, 2), (3, 3)]);
+        });
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    geometry::{Pos, Rect, Size},
+    view::View,
+};
+
+/// The side of an anchor a floating element is placed on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Side {
+    #[default]
+    Below,
+    Above,
+    Right,
+    Left,
+}
+
+impl Side {
+    fn opposite(self) -> Self {
+        match self {
+            Side::Below => Side::Above,
+            Side::Above => Side::Below,
+            Side::Right => Side::Left,
+            Side::Left => Side::Right,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Side::Below | Side::Above)
+    }
+}
+
+/// Placement positions floating elements, such as popups, tooltips, and
+/// menus, beside an anchor region so that they remain on screen.
+///
+/// The element is placed on the preferred side of the anchor and aligned
+/// with its top left corner. If there is not enough room on that side it is
+/// flipped to the opposite side, or to whichever side has more room when it
+/// fits on neither. It is then shifted along the anchor to keep it on
+/// screen, and finally clamped to the screen.
+///
+/// Example:
+/// ```
+/// use arkham::prelude::*;
+///
+/// let screen = Size::new(20, 10);
+/// let anchor = Rect::new((16, 8), (4, 1));
+/// let rect = Placement::new(Side::Below).resolve(anchor, (6, 3), screen);
+/// assert_eq!(rect, Rect::new((14, 5), (6, 3)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    side: Side,
+    gap: usize,
+    flip: bool,
+    shift: bool,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self::new(Side::default())
+    }
+}
+
+impl Placement {
+    pub fn new(side: Side) -> Self {
+        Self {
+            side,
+            gap: 0,
+            flip: true,
+            shift: true,
+        }
+    }
+
+    /// Set the distance between the anchor and the element.
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Keep the element on the preferred side even when it does not fit.
+    pub fn no_flip(mut self) -> Self {
+        self.flip = false;
+        self
+    }
+
+    /// Keep the element aligned with the anchor even when it extends past
+    /// the edge of the screen. It is still clamped to the screen.
+    pub fn no_shift(mut self) -> Self {
+        self.shift = false;
+        self
+    }
+
+    /// The room available for the element on a side of an anchor.
+    fn room(&self, side: Side, anchor: Rect, screen: Size) -> usize {
+        let room = match side {
+            Side::Below => screen
+                .height
+                .saturating_sub(anchor.pos.y + anchor.size.height),
+            Side::Above => anchor.pos.y,
+            Side::Right => screen
+                .width
+                .saturating_sub(anchor.pos.x + anchor.size.width),
+            Side::Left => anchor.pos.x,
+        };
+        room.saturating_sub(self.gap)
+    }
+
+    /// The side the element is placed on.
+    fn side(&self, anchor: Rect, size: Size, screen: Size) -> Side {
+        let extent = if self.side.is_vertical() {
+            size.height
+        } else {
+            size.width
+        };
+        let preferred = self.room(self.side, anchor, screen);
+        if !self.flip || preferred >= extent {
+            return self.side;
+        }
+        let opposite = self.room(self.side.opposite(), anchor, screen);
+        if opposite >= extent || opposite > preferred {
+            self.side.opposite()
+        } else {
+            self.side
+        }
+    }
+
+    /// The region an element of a given size occupies when placed beside an
+    /// anchor on a screen of a given size.
+    pub fn resolve<A, S, Z>(&self, anchor: A, size: S, screen: Z) -> Rect
+    where
+        A: Into<Rect>,
+        S: Into<Size>,
+        Z: Into<Size>,
+    {
+        let (anchor, size, screen) = (anchor.into(), size.into(), screen.into());
+        let side = self.side(anchor, size, screen);
+        let mut pos = match side {
+            Side::Below => Pos::new(anchor.pos.x, anchor.pos.y + anchor.size.height + self.gap),
+            Side::Above => Pos::new(
+                anchor.pos.x,
+                anchor.pos.y.saturating_sub(self.gap + size.height),
+            ),
+            Side::Right => Pos::new(anchor.pos.x + anchor.size.width + self.gap, anchor.pos.y),
+            Side::Left => Pos::new(
+                anchor.pos.x.saturating_sub(self.gap + size.width),
+                anchor.pos.y,
+            ),
+        };
+        if self.shift && side.is_vertical() {
+            pos.x = pos.x.min(screen.width.saturating_sub(size.width));
+        } else if self.shift {
+            pos.y = pos.y.min(screen.height.saturating_sub(size.height));
+        }
+        pos.x = pos.x.min(screen.width);
+        pos.y = pos.y.min(screen.height);
+        let size = Size::new(
+            size.width.min(screen.width - pos.x),
+            size.height.min(screen.height - pos.y),
+        );
+        Rect::new(pos, size)
+    }
+}
+
+/// A view drawn above the rest of the frame once every component has been
+/// rendered, such as a completion popup. Overlays are positioned relative
+/// to an anchor region rather than the component that created them, so they
+/// are not clipped to that component's region.
+#[derive(Debug, Clone)]
+pub(crate) struct Overlay {
+    /// The anchor region, relative to the context the overlay belongs to.
+    pub anchor: Rect,
+    pub placement: Placement,
+    pub view: View,
+    /// Modal overlays are centered on the screen, ignoring the anchor, and
+    /// dim everything drawn beneath them.
+    pub modal: bool,
+}
+
+impl Overlay {
+    pub(crate) fn new(anchor: Rect, placement: Placement, view: View) -> Self {
+        Self {
+            anchor,
+            placement,
+            view,
+            modal: false,
+        }
+    }
+
+    /// An overlay centered on the screen which dims everything beneath it.
+    pub(crate) fn modal(view: View) -> Self {
+        Self {
+            anchor: Rect::new((0, 0), (0, 0)),
+            placement: Placement::default(),
+            view,
+            modal: true,
+        }
+    }
+
+    /// Move the anchor into the coordinates of a parent context.
+    pub(crate) fn offset(mut self, by: Pos) -> Self {
+        self.anchor.pos += by;
+        self
+    }
+
+    /// The position the overlay is drawn at on a screen of a given size.
+    pub(crate) fn position(&self, screen: Size) -> Pos {
+        if self.modal {
+            let size = self.view.size();
+            return Pos::new(
+                screen.width.saturating_sub(size.width) / 2,
+                screen.height.saturating_sub(size.height) / 2,
+            );
+        }
+        self.placement
+            .resolve(self.anchor, self.view.size(), screen)
+            .pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{Rect, Size};
+
+    use super::{Placement, Side};
+
+    #[test]
+    fn test_flip() {
+        let screen = Size::new(20, 10);
+        let below = Placement::new(Side::Below);
+        let anchor = |x, y| Rect::new((x, y), (1, 1));
+        assert_eq!(
+            below.resolve(anchor(2, 2), (6, 3), screen).pos,
+            (2, 3).into()
+        );
+        assert_eq!(
+            below.resolve(anchor(2, 7), (6, 3), screen).pos,
+            (2, 4).into()
+        );
+        assert_eq!(
+            below.gap(1).resolve(anchor(2, 6), (6, 3), screen).pos,
+            (2, 2).into()
+        );
+        assert_eq!(
+            below.no_flip().resolve(anchor(2, 7), (6, 3), screen),
+            Rect::new((2, 8), (6, 2))
+        );
+        // Neither side fits, so the side with more room is used.
+        assert_eq!(
+            below.resolve(anchor(2, 6), (6, 8), screen).pos,
+            (2, 0).into()
+        );
+        let right = Placement::new(Side::Right);
+        assert_eq!(
+            right.resolve(anchor(16, 2), (6, 3), screen).pos,
+            (10, 2).into()
+        );
+    }
+
+    #[test]
+    fn test_shift_and_clamp() {
+        let screen = Size::new(20, 10);
+        let anchor = Rect::new((18, 2), (1, 1));
+        let below = Placement::new(Side::Below);
+        assert_eq!(below.resolve(anchor, (6, 3), screen).pos, (14, 3).into());
+        assert_eq!(
+            below.no_shift().resolve(anchor, (6, 3), screen),
+            Rect::new((18, 3), (2, 3))
+        );
+        let right = Placement::new(Side::Right);
+        assert_eq!(
+            right.resolve(Rect::new((2, 8), (1, 1)), (4, 4), screen).pos,
+            (3, 6).into()
+        );
+    }
+}
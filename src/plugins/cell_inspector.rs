@@ -0,0 +1,287 @@
+use std::cell::RefCell;
+
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Color,
+};
+
+use crate::{
+    color::contrast,
+    container::{ContainerRef, Res},
+    context::ViewContext,
+    geometry::{Pos, Size},
+    input::{Keyboard, Mouse},
+    plugins::Plugin,
+    provenance,
+    runes::{Rune, Runes},
+};
+
+/// The widest the inspector panel is drawn.
+const MAX_WIDTH: usize = 48;
+
+struct InspectorState {
+    active: bool,
+    pos: Pos,
+}
+
+/// CellInspector is a debugging aid which describes the rune drawn at a
+/// position on screen: its character, colors, attributes, identifier and
+/// link, and the component that drew it. This makes it quick to find out
+/// why a cell is the wrong color or which component is drawing over
+/// another.
+///
+/// Pressing the trigger key, F12 by default, toggles the inspector. While
+/// it is open the inspected cell follows the mouse, and can be moved with
+/// the arrow keys, which are not passed on to the application.
+///
+/// The component that drew a cell is only recorded when the `debug`
+/// feature is enabled.
+///
+/// ```no_run
+/// use arkham::{plugins::CellInspector, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(CellInspector::new())
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Press F12 to inspect".to_runes().fg(Color::Blue));
+/// }
+/// ```
+pub struct CellInspector {
+    key: (KeyCode, KeyModifiers),
+    state: RefCell<InspectorState>,
+}
+
+impl Default for CellInspector {
+    fn default() -> Self {
+        Self {
+            key: (KeyCode::F(12), KeyModifiers::NONE),
+            state: RefCell::new(InspectorState {
+                active: false,
+                pos: Pos::new(0, 0),
+            }),
+        }
+    }
+}
+
+impl CellInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key which toggles the inspector.
+    pub fn key(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.key = (code, modifiers);
+        self
+    }
+
+    /// The lines describing a rune.
+    fn describe(rune: &Rune) -> Vec<(&'static str, String)> {
+        let content = match rune.text() {
+            Some(text) if text.chars().count() > 1 => {
+                let points: Vec<String> = text
+                    .chars()
+                    .map(|c| format!("U+{:04X}", c as u32))
+                    .collect();
+                format!("{:?} {}", text, points.join(" "))
+            }
+            Some(_) | None => match rune.content {
+                Some(c) => format!("{:?} U+{:04X}", c, c as u32),
+                None => "empty".to_string(),
+            },
+        };
+        let mut attributes = vec![];
+        for (set, name) in [
+            (rune.bold, "bold"),
+            (rune.italic, "italic"),
+            (rune.underline, "underline"),
+            (rune.undercurl, "undercurl"),
+            (rune.dim, "dim"),
+            (rune.reverse, "reverse"),
+            (rune.crossed_out, "crossed out"),
+        ] {
+            if set {
+                attributes.push(name);
+            }
+        }
+        let attributes = if attributes.is_empty() {
+            "none".to_string()
+        } else {
+            attributes.join(" ")
+        };
+        let writer = if cfg!(feature = "debug") {
+            provenance::writer(rune).unwrap_or("unknown").to_string()
+        } else {
+            "requires the debug feature".to_string()
+        };
+        vec![
+            ("char", content),
+            ("fg", color_name(rune.fg)),
+            ("bg", color_name(rune.bg)),
+            ("attrs", attributes),
+            (
+                "id",
+                rune.id.map_or("none".to_string(), |id| id.to_string()),
+            ),
+            ("link", rune.url().unwrap_or_else(|| "none".to_string())),
+            ("drawn by", writer),
+        ]
+    }
+
+    fn draw(&self, ctx: &mut ViewContext, pos: Pos) {
+        let screen = ctx.size();
+        let Some(rune) = ctx.view.get(pos.y).and_then(|row| row.get(pos.x)).copied() else {
+            return;
+        };
+        let theme = ctx.theme();
+        let lines = Self::describe(&rune);
+
+        // Mark the inspected cell.
+        ctx.view[pos.y][pos.x] = Rune {
+            bg: Some(theme.focus),
+            fg: contrast(theme.focus),
+            ..rune
+        };
+
+        let title = format!(" Cell {},{}", pos.x, pos.y);
+        let width = lines
+            .iter()
+            .map(|(_, value)| value.chars().count() + 11)
+            .chain([title.chars().count() + 1])
+            .max()
+            .unwrap_or(0)
+            .min(MAX_WIDTH)
+            .min(screen.width);
+        let size = Size::new(width, (lines.len() + 1).min(screen.height));
+        // Keep the panel away from the inspected cell.
+        let x = if pos.x < screen.width / 2 {
+            screen.width - size.width
+        } else {
+            0
+        };
+        let y = if pos.y < screen.height / 2 {
+            screen.height - size.height
+        } else {
+            0
+        };
+
+        ctx.fill(
+            ((x, y), size),
+            Rune::new().content(' ').bg(theme.bg_secondary),
+        );
+        let clip = |runes: Runes| Runes::new(runes.iter().take(width).copied().collect());
+        ctx.insert(
+            (x, y),
+            clip(
+                Runes::from(title)
+                    .fg(theme.accent)
+                    .bg(theme.bg_secondary)
+                    .bold(),
+            ),
+        );
+        for (row, (label, value)) in lines.into_iter().enumerate().take(size.height - 1) {
+            let mut runes = Runes::from(format!(" {:<9}", label))
+                .fg(theme.muted)
+                .bg(theme.bg_secondary);
+            runes.add(Runes::from(value).fg(theme.fg).bg(theme.bg_secondary));
+            ctx.insert((x, y + row + 1), clip(runes));
+        }
+    }
+}
+
+/// A short description of a color, with RGB colors in hex.
+fn color_name(color: Option<Color>) -> String {
+    match color {
+        None => "default".to_string(),
+        Some(Color::Rgb { r, g, b }) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Some(Color::AnsiValue(v)) => format!("ansi {}", v),
+        Some(color) => format!("{:?}", color),
+    }
+}
+
+impl Plugin for CellInspector {
+    fn before_render(&self, _ctx: &mut ViewContext, container: ContainerRef) {
+        let container = container.borrow();
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        let mut state = self.state.borrow_mut();
+        if kb.code() == Some(self.key.0) && kb.modifiers() == self.key.1 {
+            state.active = !state.active;
+            kb.reset();
+            return;
+        }
+        if !state.active {
+            return;
+        }
+        if let Some(pos) = container
+            .get::<Res<Mouse>>()
+            .and_then(|mouse| mouse.position())
+        {
+            state.pos = pos;
+        }
+        let pos = &mut state.pos;
+        match kb.code() {
+            Some(KeyCode::Left) => pos.x = pos.x.saturating_sub(1),
+            Some(KeyCode::Right) => pos.x += 1,
+            Some(KeyCode::Up) => pos.y = pos.y.saturating_sub(1),
+            Some(KeyCode::Down) => pos.y += 1,
+            _ => return,
+        }
+        kb.reset();
+    }
+
+    fn after_render(&self, ctx: &mut ViewContext, _container: ContainerRef) {
+        let mut state = self.state.borrow_mut();
+        if !state.active {
+            return;
+        }
+        let size = ctx.size();
+        state.pos.x = state.pos.x.min(size.width.saturating_sub(1));
+        state.pos.y = state.pos.y.min(size.height.saturating_sub(1));
+        self.draw(ctx, state.pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::{event::KeyCode, style::Color};
+
+    use crate::{context::ViewContext, runes::ToRuneExt, testing::TestApp};
+
+    use super::CellInspector;
+
+    #[test]
+    fn test_inspect() {
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| {
+                ctx.insert(
+                    (0, 0),
+                    "ab".to_runes()
+                        .fg(Color::Rgb {
+                            r: 255,
+                            g: 0,
+                            b: 16,
+                        })
+                        .bold(),
+                );
+            },
+            (40, 12),
+        )
+        .insert_plugin(CellInspector::new());
+        app.render();
+        app.press(KeyCode::F(12));
+        let view = app.press(KeyCode::Right);
+        let text = crate::testing::frame_text(view);
+        assert!(text.contains("Cell 1,0"));
+        assert!(text.contains("'b' U+0062"));
+        assert!(text.contains("#ff0010"));
+        assert!(text.contains("bold"));
+        assert_ne!(view[0][1].bg, None);
+
+        let view = app.press(KeyCode::F(12));
+        assert!(!crate::testing::frame_text(view).contains("Cell"));
+    }
+}
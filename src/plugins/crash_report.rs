@@ -0,0 +1,306 @@
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    container::{ContainerRef, Res},
+    context::ViewContext,
+    input::Keyboard,
+    plugins::Plugin,
+};
+
+#[cfg(feature = "log")]
+use super::logview::ArkhamLogger;
+
+use super::screenshot::timestamp;
+
+/// The application state captured for a report. It is shared with the
+/// panic hook, which may run on any thread.
+#[derive(Default)]
+struct Snapshot {
+    /// The most recent frames as plain text, oldest first.
+    frames: VecDeque<String>,
+    types: Vec<&'static str>,
+    size: (usize, usize),
+    #[cfg(feature = "log")]
+    logger: Option<&'static ArkhamLogger>,
+}
+
+/// The settings used to write a report.
+#[derive(Clone)]
+struct ReportConfig {
+    directory: PathBuf,
+    version: Option<String>,
+    records: usize,
+}
+
+impl ReportConfig {
+    /// Write a report bundle, returning the directory it was written to.
+    fn write(&self, snapshot: &Snapshot, reason: &str) -> std::io::Result<PathBuf> {
+        let stamp = timestamp(SystemTime::now());
+        let mut dir = self.directory.join(format!("report-{stamp}"));
+        let mut n = 1;
+        while dir.exists() {
+            dir = self.directory.join(format!("report-{stamp}-{n}"));
+            n += 1;
+        }
+        std::fs::create_dir_all(&dir)?;
+
+        let mut report = String::new();
+        let _ = writeln!(report, "reason: {reason}");
+        if let Some(version) = &self.version {
+            let _ = writeln!(report, "version: {version}");
+        }
+        let _ = writeln!(report, "arkham: {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(
+            report,
+            "os: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        let _ = writeln!(report, "size: {}x{}", snapshot.size.0, snapshot.size.1);
+        for var in ["TERM", "COLORTERM", "TERM_PROGRAM", "TERM_PROGRAM_VERSION"] {
+            if let Ok(value) = std::env::var(var) {
+                let _ = writeln!(report, "{}: {}", var.to_lowercase(), value);
+            }
+        }
+        let _ = writeln!(report, "\nresources:");
+        for name in snapshot.types.iter() {
+            let _ = writeln!(report, "  {name}");
+        }
+        std::fs::write(dir.join("report.txt"), report)?;
+
+        let mut frames = String::new();
+        let count = snapshot.frames.len();
+        for (idx, frame) in snapshot.frames.iter().enumerate() {
+            let _ = writeln!(frames, "--- frame {} of {} ---\n{}", idx + 1, count, frame);
+        }
+        std::fs::write(dir.join("frames.txt"), frames)?;
+
+        #[cfg(feature = "log")]
+        if let Some(logger) = snapshot.logger {
+            let mut log = logger.recent(self.records).join("\n");
+            log.push('\n');
+            std::fs::write(dir.join("log.txt"), log)?;
+        }
+        Ok(dir)
+    }
+}
+
+/// CrashReport writes a bundle of diagnostic information to a directory when
+/// the application panics, or when a key is pressed if one is set, so that
+/// users can attach it to a bug report.
+///
+/// Each bundle is a directory named `report-YYYYMMDD-HHMMSS` containing:
+///
+/// - `report.txt`: the reason for the report, the application and arkham
+///   versions, the operating system, the terminal size and environment, and
+///   the type of every bound resource and state object.
+/// - `frames.txt`: the most recent frames as plain text.
+/// - `log.txt`: the most recent log records, when the LogPlugin is used.
+///
+/// ```no_run
+/// use arkham::{plugins::CrashReport, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(
+///             CrashReport::new()
+///                 .directory("crash-reports")
+///                 .version(env!("CARGO_PKG_VERSION"))
+///                 .key(KeyCode::F(9), KeyModifiers::NONE),
+///         )
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Press F9 to write a report");
+/// }
+/// ```
+pub struct CrashReport {
+    config: ReportConfig,
+    frames: usize,
+    key: Option<(KeyCode, KeyModifiers)>,
+    on_panic: bool,
+    snapshot: Arc<Mutex<Snapshot>>,
+}
+
+impl Default for CrashReport {
+    fn default() -> Self {
+        Self {
+            config: ReportConfig {
+                directory: PathBuf::from("."),
+                version: None,
+                records: 200,
+            },
+            frames: 10,
+            key: None,
+            on_panic: true,
+            snapshot: Arc::new(Mutex::new(Snapshot::default())),
+        }
+    }
+}
+
+impl CrashReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the directory reports are written to. Defaults to the working
+    /// directory.
+    pub fn directory<P: AsRef<Path>>(mut self, directory: P) -> Self {
+        self.config.directory = directory.as_ref().to_path_buf();
+        self
+    }
+
+    /// Set the application version included in reports.
+    pub fn version<S: ToString>(mut self, version: S) -> Self {
+        self.config.version = Some(version.to_string());
+        self
+    }
+
+    /// Set the number of recent frames included in reports. Defaults to 10.
+    pub fn frames(mut self, count: usize) -> Self {
+        self.frames = count;
+        self
+    }
+
+    /// Set the number of recent log records included in reports. Defaults
+    /// to 200.
+    pub fn records(mut self, count: usize) -> Self {
+        self.config.records = count;
+        self
+    }
+
+    /// Set a key which writes a report on demand.
+    pub fn key(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.key = Some((code, modifiers));
+        self
+    }
+
+    /// Do not write a report when the application panics.
+    pub fn no_panic_hook(mut self) -> Self {
+        self.on_panic = false;
+        self
+    }
+
+    /// Write a report immediately, returning the directory it was written
+    /// to.
+    pub fn write(&self, reason: &str) -> std::io::Result<PathBuf> {
+        self.config.write(&self.snapshot.lock().unwrap(), reason)
+    }
+}
+
+impl Plugin for CrashReport {
+    fn build(&mut self, _container: ContainerRef) {
+        if !self.on_panic {
+            return;
+        }
+        let config = self.config.clone();
+        let snapshot = self.snapshot.clone();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            // Panics caught by an error boundary do not end the application.
+            if !crate::context::in_error_boundary() {
+                // The snapshot may be locked if the panic occurred while it
+                // was being updated.
+                if let Ok(snapshot) = snapshot.try_lock() {
+                    let _ = config.write(&snapshot, &info.to_string());
+                }
+            }
+            previous(info);
+        }));
+    }
+
+    fn before_render(&self, _ctx: &mut ViewContext, container: ContainerRef) {
+        let container = container.borrow();
+        {
+            let mut snapshot = self.snapshot.lock().unwrap();
+            snapshot.types = container.type_names();
+            #[cfg(feature = "log")]
+            if snapshot.logger.is_none() {
+                snapshot.logger = container
+                    .get::<Res<&'static ArkhamLogger>>()
+                    .map(|logger| *logger.get());
+            }
+        }
+
+        let Some((code, modifiers)) = self.key else {
+            return;
+        };
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        if kb.code() == Some(code) && kb.modifiers() == modifiers {
+            let _ = self.write("requested by user");
+            kb.reset();
+        }
+    }
+
+    fn after_render(&self, ctx: &mut ViewContext, _container: ContainerRef) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let size = ctx.size();
+        snapshot.size = (size.width, size.height);
+        snapshot
+            .frames
+            .push_back(crate::testing::frame_text(&ctx.view));
+        while snapshot.frames.len() > self.frames {
+            snapshot.frames.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{context::ViewContext, testing::TestApp};
+
+    use super::CrashReport;
+
+    #[test]
+    fn test_write_on_key() {
+        let dir = std::env::temp_dir().join(format!("arkham-report-{}", std::process::id()));
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext, count: crate::container::State<usize>| {
+                *count.get_mut() += 1;
+                ctx.insert((0, 0), format!("frame {}", count.get()));
+            },
+            (20, 2),
+        )
+        .insert_state(0usize)
+        .insert_plugin(
+            CrashReport::new()
+                .directory(&dir)
+                .version("1.2.3")
+                .frames(2)
+                .key(KeyCode::F(9), KeyModifiers::NONE)
+                .no_panic_hook(),
+        );
+        app.render();
+        app.render();
+        app.render();
+        app.press(KeyCode::F(9));
+
+        let reports: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(reports.len(), 1);
+        let report = std::fs::read_to_string(reports[0].join("report.txt")).unwrap();
+        assert!(report.starts_with("reason: requested by user\nversion: 1.2.3\n"));
+        assert!(report.contains("size: 20x2"));
+        assert!(report.contains("arkham::container::State<usize>"));
+        let frames = std::fs::read_to_string(reports[0].join("frames.txt")).unwrap();
+        assert_eq!(
+            frames,
+            "--- frame 1 of 2 ---\nframe 2\n--- frame 2 of 2 ---\nframe 3\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
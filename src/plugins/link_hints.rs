@@ -0,0 +1,355 @@
+use std::{cell::RefCell, ffi::OsString, path::PathBuf};
+
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Color,
+};
+
+use crate::{
+    container::{ContainerRef, Res},
+    context::ViewContext,
+    geometry::Pos,
+    input::Keyboard,
+    plugins::Plugin,
+    runes::Runes,
+};
+
+/// The keys used to build hint labels, in the order they are assigned.
+const HINT_KEYS: &str = "asdfghjkl";
+
+/// A function that opens a link's URL.
+type Handler = Box<dyn Fn(&str)>;
+
+/// A link visible on screen and the label used to select it.
+struct Hint {
+    label: String,
+    url: String,
+}
+
+#[derive(Default)]
+struct HintState {
+    active: bool,
+    typed: String,
+    hints: Vec<Hint>,
+}
+
+/// LinkHints allows hyperlinks to be followed from the keyboard. Pressing
+/// the trigger key, Ctrl+O by default, labels every link on screen with a
+/// short sequence of keys. Typing a label opens its link, while Escape or
+/// any key that does not match a label cancels.
+///
+/// Links are opened with the operating system's default handler unless a
+/// handler is registered with LinkHints::on_open, or an opener is registered
+/// for the link's scheme with LinkHints::opener. See Rune::link for creating
+/// links, and Runes::detect_links for turning URLs and file paths within
+/// text into links.
+///
+/// ```no_run
+/// use arkham::{plugins::LinkHints, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(LinkHints::new().opener("file", |url| {
+///             // Open files in an editor running outside of the terminal.
+///             if let Some((path, line)) = LinkHints::file_location(url) {
+///                 let target = format!("{}:{}", path.display(), line.unwrap_or(1));
+///                 let _ = std::process::Command::new("code").args(["-g", &target]).spawn();
+///             }
+///         }))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Documentation".to_runes().link("https://docs.rs"));
+///     ctx.insert((0, 1), "panicked at src/main.rs:12:5".to_runes().detect_links());
+/// }
+/// ```
+pub struct LinkHints {
+    trigger: (KeyCode, KeyModifiers),
+    handler: Handler,
+    /// Handlers for links with particular schemes, such as `file`.
+    openers: Vec<(String, Handler)>,
+    state: RefCell<HintState>,
+}
+
+impl Default for LinkHints {
+    fn default() -> Self {
+        Self {
+            trigger: (KeyCode::Char('o'), KeyModifiers::CONTROL),
+            handler: Box::new(open_url),
+            openers: vec![],
+            state: RefCell::new(HintState::default()),
+        }
+    }
+}
+
+impl LinkHints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key which displays the hint labels.
+    pub fn trigger(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.trigger = (code, modifiers);
+        self
+    }
+
+    /// Handle selected links with a function instead of opening them with
+    /// the operating system.
+    pub fn on_open<H: Fn(&str) + 'static>(mut self, handler: H) -> Self {
+        self.handler = Box::new(handler);
+        self
+    }
+
+    /// Handle selected links with a scheme, such as `file` or `https`, with
+    /// a function. Links with other schemes are handled as before.
+    pub fn opener<H: Fn(&str) + 'static>(mut self, scheme: &str, handler: H) -> Self {
+        self.openers.retain(|(s, _)| s != scheme);
+        self.openers.push((scheme.to_string(), Box::new(handler)));
+        self
+    }
+
+    /// The path and line number linked to by a `file://` URL, such as the
+    /// links created for file paths by Runes::detect_links. Returns None for
+    /// other URLs.
+    pub fn file_location(url: &str) -> Option<(PathBuf, Option<usize>)> {
+        crate::links::file_location(url)
+    }
+
+    /// Open a link with the opener registered for its scheme, or the
+    /// default handler.
+    fn open(&self, url: &str) {
+        let scheme = url.split_once(':').map_or("", |(scheme, _)| scheme);
+        match self.openers.iter().find(|(s, _)| s == scheme) {
+            Some((_, opener)) => opener(url),
+            None => (self.handler)(url),
+        }
+    }
+}
+
+/// The URL schemes the default handler opens. Links are usually taken from
+/// text on screen, such as log output, so other schemes, which may run
+/// programs, are ignored.
+const OPEN_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// What the operating system is asked to open for a link: the path of a
+/// file link, if the file exists, as the handler may not understand the
+/// line number, or the URL if it has one of OPEN_SCHEMES. Returns None for
+/// anything else.
+fn open_target(url: &str) -> Option<OsString> {
+    if let Some((path, _)) = crate::links::file_location(url) {
+        return path.exists().then(|| path.into_os_string());
+    }
+    let (scheme, _) = url.split_once(':')?;
+    let known = OPEN_SCHEMES.iter().any(|s| s.eq_ignore_ascii_case(scheme));
+    (known && !url.chars().any(|c| c.is_control() || c.is_whitespace())).then(|| url.into())
+}
+
+/// Open a URL with the operating system's default handler. See open_target.
+///
+/// On Windows the handler is invoked with rundll32 rather than through
+/// `cmd /C start`, as cmd would interpret characters such as `&` within the
+/// URL as further commands.
+fn open_url(url: &str) {
+    let Some(target) = open_target(url) else {
+        return;
+    };
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let _ = command.arg(target).spawn();
+}
+
+/// Labels for a number of hints. Every label has the same length, so that
+/// no label is the prefix of another.
+fn labels(count: usize) -> Vec<String> {
+    let keys: Vec<char> = HINT_KEYS.chars().collect();
+    let mut len = 1;
+    while keys.len().pow(len) < count {
+        len += 1;
+    }
+    (0..count)
+        .map(|mut n| {
+            let mut label = vec![];
+            for _ in 0..len {
+                label.push(keys[n % keys.len()]);
+                n /= keys.len();
+            }
+            label.into_iter().rev().collect()
+        })
+        .collect()
+}
+
+impl Plugin for LinkHints {
+    fn before_render(&self, _ctx: &mut ViewContext, container: ContainerRef) {
+        let container = container.borrow();
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        let mut state = self.state.borrow_mut();
+        if !state.active {
+            if kb.code() == Some(self.trigger.0) && kb.modifiers() == self.trigger.1 {
+                state.active = true;
+                state.typed.clear();
+                kb.reset();
+            }
+            return;
+        }
+
+        match kb.code() {
+            None => return,
+            Some(KeyCode::Char(c)) => {
+                state.typed.push(c);
+                let selected = state
+                    .hints
+                    .iter()
+                    .find(|hint| hint.label == state.typed)
+                    .map(|hint| hint.url.clone());
+                if let Some(url) = selected {
+                    state.active = false;
+                    drop(state);
+                    self.open(&url);
+                } else if !state
+                    .hints
+                    .iter()
+                    .any(|hint| hint.label.starts_with(&state.typed))
+                {
+                    state.active = false;
+                }
+            }
+            Some(KeyCode::Backspace) => {
+                state.typed.pop();
+            }
+            _ => state.active = false,
+        }
+        kb.reset();
+    }
+
+    fn after_render(&self, ctx: &mut ViewContext, _container: ContainerRef) {
+        let mut state = self.state.borrow_mut();
+        state.hints.clear();
+        if !state.active {
+            return;
+        }
+
+        // A hint is placed at the start of each run of runes sharing a link.
+        let mut links = vec![];
+        for (y, row) in ctx.view.iter().enumerate() {
+            let mut previous = None;
+            for (x, rune) in row.iter().enumerate() {
+                if rune.link.is_some() && rune.link != previous {
+                    if let Some(url) = rune.url() {
+                        links.push((Pos::new(x, y), url));
+                    }
+                }
+                previous = rune.link;
+            }
+        }
+        if links.is_empty() {
+            state.active = false;
+            return;
+        }
+
+        let theme = ctx.theme();
+        let labels = labels(links.len());
+        for ((pos, url), label) in links.into_iter().zip(labels) {
+            if label.starts_with(&state.typed) {
+                let runes = Runes::from(&label)
+                    .fg(Color::Black)
+                    .bg(theme.warning)
+                    .bold();
+                ctx.insert(pos, runes);
+            }
+            state.hints.push(Hint { label, url });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{context::ViewContext, runes::ToRuneExt, testing::TestApp};
+
+    use super::{labels, open_target, LinkHints};
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(labels(3), vec!["a", "s", "d"]);
+        let labels = labels(10);
+        assert_eq!(labels[0], "aa");
+        assert_eq!(labels[9], "sa");
+    }
+
+    #[test]
+    fn test_open_target() {
+        assert_eq!(
+            open_target("https://x/&calc"),
+            Some("https://x/&calc".into())
+        );
+        assert_eq!(open_target("javascript:alert(1)"), None);
+        assert_eq!(open_target("https://x/ --flag"), None);
+        assert_eq!(open_target("file:///does/not/exist#L3"), None);
+        let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        assert_eq!(
+            open_target(&format!("file://{}#L3", manifest)),
+            Some(manifest.into())
+        );
+    }
+
+    #[test]
+    fn test_follow_link() {
+        let opened = Rc::new(RefCell::new(vec![]));
+        let hints = {
+            let opened = opened.clone();
+            LinkHints::new().on_open(move |url| opened.borrow_mut().push(url.to_string()))
+        };
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| {
+                ctx.insert((0, 0), "one".to_runes().link("https://one.example"));
+                ctx.insert((4, 0), "two".to_runes().link("https://two.example"));
+            },
+            (10, 1),
+        )
+        .insert_plugin(hints);
+
+        let view = app.press_with(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        assert_eq!(crate::testing::frame_text(view), "ane swo");
+        app.press(KeyCode::Char('s'));
+        assert_eq!(*opened.borrow(), vec!["https://two.example"]);
+        assert_eq!(crate::testing::frame_text(app.render()), "one two");
+    }
+
+    #[test]
+    fn test_opener_for_detected_path() {
+        let opened = Rc::new(RefCell::new(vec![]));
+        let hints = {
+            let opened = opened.clone();
+            LinkHints::new()
+                .on_open(|_| panic!("the file opener should be used"))
+                .opener("file", move |url| {
+                    opened
+                        .borrow_mut()
+                        .push(LinkHints::file_location(url).unwrap())
+                })
+        };
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| {
+                ctx.insert((0, 0), "at /tmp/app.rs:3".to_runes().detect_links());
+            },
+            (20, 1),
+        )
+        .insert_plugin(hints);
+
+        app.press_with(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        app.press(KeyCode::Char('a'));
+        assert_eq!(*opened.borrow(), vec![("/tmp/app.rs".into(), Some(3))]);
+    }
+}
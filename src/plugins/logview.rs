@@ -1,21 +1,114 @@
 use crate::{container::ContainerRef, plugins::Plugin, prelude::*};
 use log::{Level, LevelFilter, Metadata, Record};
-use std::collections::VecDeque;
-use std::sync::atomic::AtomicUsize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{atomic::AtomicBool, Arc, Mutex};
-
-#[derive(Default)]
-pub struct LogViewState {}
+use std::time::{Duration, Instant};
 
 pub struct LogRecord {
     pub level: Level,
+    pub target: String,
     pub message: String,
+    /// The structured key-value pairs attached to the message.
+    pub fields: Vec<(String, String)>,
     pub time: chrono::DateTime<chrono::Local>,
+    /// The number of identical consecutive messages this record represents.
+    pub count: usize,
+}
+
+/// Filtering applied to records before they are stored.
+struct LoggerConfig {
+    level: LevelFilter,
+    /// Level overrides for modules, matched against the record target.
+    modules: Vec<(String, LevelFilter)>,
+    /// The most records stored for a single target within a period.
+    rate_limit: Option<(usize, Duration)>,
+    /// The start of the current period, the records logged, and the records
+    /// dropped within it for each target.
+    windows: HashMap<String, (Instant, usize, usize)>,
+}
+
+impl Default for LoggerConfig {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            modules: vec![],
+            rate_limit: None,
+            windows: HashMap::new(),
+        }
+    }
+}
+
+impl LoggerConfig {
+    /// The level for a target. The override for the most specific module
+    /// containing the target is used if there is one.
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                target == module
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+
+    /// The most verbose level any target is logged at.
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, Ord::max)
+    }
+
+    /// Count a record against the rate limit for its target. Returns whether
+    /// the record should be stored, and the number of records that were
+    /// dropped during the previous period if it has just ended.
+    fn admit(&mut self, target: &str, now: Instant) -> (bool, usize) {
+        let Some((limit, period)) = self.rate_limit else {
+            return (true, 0);
+        };
+        let window = self
+            .windows
+            .entry(target.to_string())
+            .or_insert((now, 0, 0));
+        let mut dropped = 0;
+        if now.duration_since(window.0) >= period {
+            dropped = window.2;
+            *window = (now, 0, 0);
+        }
+        if window.1 < limit {
+            window.1 += 1;
+            (true, dropped)
+        } else {
+            window.2 += 1;
+            (false, dropped)
+        }
+    }
+}
+
+/// Collects the key-value pairs of a record as strings.
+#[derive(Default)]
+struct Fields(Vec<(String, String)>);
+
+impl<'kvs> log::kv::VisitSource<'kvs> for Fields {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
 }
 
 #[derive(Default, Clone)]
 pub struct ArkhamLogger {
     records: Arc<Mutex<VecDeque<LogRecord>>>,
+    config: Arc<Mutex<LoggerConfig>>,
 }
 
 impl ArkhamLogger {
@@ -25,23 +118,91 @@ impl ArkhamLogger {
         log::set_max_level(LevelFilter::Info);
         Ok(logger)
     }
+
+    /// The most recent records formatted as lines of text, oldest first.
+    pub(crate) fn recent(&self, count: usize) -> Vec<String> {
+        let records = self.records.lock().unwrap();
+        records
+            .iter()
+            .skip(records.len().saturating_sub(count))
+            .map(|r| {
+                let mut line = format!(
+                    "{} {:<5} {} {}",
+                    r.time.format("%H:%M:%S"),
+                    r.level,
+                    r.target,
+                    r.message
+                );
+                for (key, value) in r.fields.iter() {
+                    line.push_str(&format!(" {}={}", key, value));
+                }
+                if r.count > 1 {
+                    line.push_str(&format!(" (×{})", r.count));
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Update the filtering configuration and the maximum level passed to
+    /// the logger by the log crate.
+    fn configure<F: FnOnce(&mut LoggerConfig)>(&self, f: F) {
+        let mut config = self.config.lock().unwrap();
+        f(&mut config);
+        log::set_max_level(config.max_level());
+    }
+
+    /// Store a record, merging it into the previous record if the level,
+    /// target, and message are identical.
+    fn push(&self, level: Level, target: &str, message: String, fields: Vec<(String, String)>) {
+        let mut rcs = self.records.lock().unwrap();
+        let time = chrono::Local::now();
+        if let Some(last) = rcs.back_mut() {
+            if last.level == level
+                && last.target == target
+                && last.message == message
+                && last.fields == fields
+            {
+                last.count += 1;
+                last.time = time;
+                return;
+            }
+        }
+        rcs.push_back(LogRecord {
+            level,
+            target: target.to_string(),
+            message,
+            fields,
+            time,
+            count: 1,
+        });
+        if rcs.len() > 500 {
+            rcs.pop_front();
+        }
+    }
 }
 
 impl log::Log for ArkhamLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Info
+        metadata.level() <= self.config.lock().unwrap().level_for(metadata.target())
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            let mut rcs = self.records.lock().unwrap();
-            rcs.push_back(LogRecord {
-                level: record.level(),
-                message: record.args().to_string(),
-                time: chrono::Local::now(),
-            });
-            if rcs.len() > 500 {
-                rcs.pop_front();
+            let target = record.target();
+            let (admit, dropped) = self.config.lock().unwrap().admit(target, Instant::now());
+            if dropped > 0 {
+                self.push(
+                    Level::Warn,
+                    target,
+                    format!("{} messages from {} were rate limited", dropped, target),
+                    vec![],
+                );
+            }
+            if admit {
+                let mut fields = Fields::default();
+                let _ = record.key_values().visit(&mut fields);
+                self.push(record.level(), target, record.args().to_string(), fields.0);
             }
         }
     }
@@ -49,11 +210,52 @@ impl log::Log for ArkhamLogger {
     fn flush(&self) {}
 }
 
+/// LogPlugin captures messages from the log crate and displays them in a
+/// panel toggled with the `~` key.
+///
+/// Identical consecutive messages are shown once with a count. The plugin
+/// builder can also adjust the level logged for individual modules and
+/// limit how many messages a noisy module can log.
+///
+/// Each message is shown with its target. Key-value pairs logged with a
+/// message, such as `info!(user = name; "logged in")`, are summarized and
+/// can be expanded by pressing `f`. Pressing `/` searches the log, only
+/// displaying messages whose text or target contains the query, with the
+/// matches highlighted. Pressing `t` switches between the time each message
+/// was logged and how long ago that was.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use arkham::{plugins::LogPlugin, prelude::*};
+/// use log::LevelFilter;
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(
+///             LogPlugin::new()
+///                 .module_level("my_app::network", LevelFilter::Debug)
+///                 .module_level("hyper", LevelFilter::Warn)
+///                 .rate_limit(20, Duration::from_secs(1)),
+///         )
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Press ~ to view the log");
+/// }
+/// ```
 pub struct LogPlugin {
     logger: &'static ArkhamLogger,
     log_open: AtomicBool,
     offset: AtomicUsize,
     locked: AtomicBool,
+    show_fields: AtomicBool,
+    relative_time: AtomicBool,
+    target_filter: String,
+    search: SearchState,
+    #[cfg(feature = "regex")]
+    highlighter: Option<crate::components::Highlighter>,
 }
 
 impl Default for LogPlugin {
@@ -63,10 +265,177 @@ impl Default for LogPlugin {
             log_open: AtomicBool::new(false),
             offset: AtomicUsize::new(0),
             locked: AtomicBool::new(true),
+            show_fields: AtomicBool::new(false),
+            relative_time: AtomicBool::new(false),
+            target_filter: String::new(),
+            search: SearchState::new(),
+            #[cfg(feature = "regex")]
+            highlighter: None,
         }
     }
 }
 
+impl LogPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the level logged for modules without an override. Defaults to
+    /// Info.
+    pub fn level(self, level: LevelFilter) -> Self {
+        self.logger.configure(|config| config.level = level);
+        self
+    }
+
+    /// Set the level logged for a module and its submodules, such as
+    /// `my_app::network`. The most specific module matching a message's
+    /// target is used.
+    pub fn module_level(self, module: &str, level: LevelFilter) -> Self {
+        self.logger.configure(|config| {
+            config.modules.retain(|(m, _)| m != module);
+            config.modules.push((module.to_string(), level));
+        });
+        self
+    }
+
+    /// Store at most a number of messages from each target within a period.
+    /// Messages over the limit are dropped, and a warning noting how many
+    /// were dropped is logged once the period ends.
+    pub fn rate_limit(self, max: usize, period: Duration) -> Self {
+        self.logger
+            .configure(|config| config.rate_limit = Some((max, period)));
+        self
+    }
+
+    /// Only display messages whose target matches a glob pattern, such as
+    /// `my_app::net*`.
+    pub fn target_filter(mut self, pattern: &str) -> Self {
+        self.target_filter = pattern.to_string();
+        self
+    }
+
+    /// Display how long ago each message was logged, such as `3m ago`,
+    /// rather than the time it was logged. This can also be toggled in the
+    /// log view by pressing `t`.
+    pub fn relative_time(self) -> Self {
+        self.relative_time.store(true, Ordering::SeqCst);
+        self
+    }
+
+    /// Style messages with a Highlighter, such as to color URLs or error
+    /// keywords. This requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub fn highlighter(mut self, highlighter: crate::components::Highlighter) -> Self {
+        self.highlighter = Some(highlighter);
+        self
+    }
+
+    /// The runes a record's message is displayed with. URLs and file paths
+    /// in the message are made links, which can be followed with the
+    /// LinkHints plugin.
+    fn message(&self, record: &LogRecord, theme: &Theme) -> Runes {
+        #[cfg(feature = "regex")]
+        if let Some(highlighter) = &self.highlighter {
+            return highlighter
+                .highlight_with(&record.message, Rune::new().fg(theme.fg))
+                .detect_links();
+        }
+        record
+            .message
+            .clone()
+            .to_runes()
+            .fg(theme.fg)
+            .detect_links()
+    }
+
+    /// Draw a record starting at a row, returning the number of rows used.
+    fn draw_record(
+        &self,
+        ctx: &mut ViewContext,
+        row: usize,
+        entry: &LogRecord,
+        theme: &Theme,
+    ) -> usize {
+        let show_fields = self.show_fields.load(Ordering::SeqCst);
+        let relative_time = self.relative_time.load(Ordering::SeqCst);
+        ctx.component(((2, row), (6, 1)), level(entry.level, theme));
+        let time = if relative_time {
+            format!("{:>8}", crate::humanize::humantime(entry.time))
+        } else {
+            entry.time.format("%H:%M:%S").to_string()
+        };
+        ctx.insert((9, row), time.to_runes().fg(theme.muted));
+        let mut line = Runes::from(format!("{} ", entry.target)).fg(theme.accent);
+        line.add(self.message(entry, theme));
+        if entry.count > 1 {
+            line.add(format!(" ×{}", entry.count).to_runes().fg(theme.muted));
+        }
+        if !entry.fields.is_empty() && !show_fields {
+            let summary = format!(" ▸ {} fields", entry.fields.len());
+            line.add(summary.to_runes().fg(theme.muted));
+        }
+        ctx.insert((18, row), self.search.highlight(line, theme));
+        if !show_fields {
+            return 1;
+        }
+        for (i, (key, value)) in entry.fields.iter().enumerate() {
+            let mut field = Runes::from(key.clone()).fg(theme.info);
+            field.add(format!(" = {}", value).to_runes().fg(theme.fg));
+            ctx.insert((20, row + i + 1), field);
+        }
+        1 + entry.fields.len()
+    }
+
+    /// The number of rows a record occupies in the log view.
+    fn rows(&self, record: &LogRecord) -> usize {
+        if self.show_fields.load(Ordering::SeqCst) {
+            1 + record.fields.len()
+        } else {
+            1
+        }
+    }
+
+    /// The indexes of the records matching the target filter and the
+    /// search.
+    fn visible(&self, records: &VecDeque<LogRecord>) -> Vec<usize> {
+        let pattern = &self.target_filter;
+        (0..records.len())
+            .filter(|&idx| {
+                let record = &records[idx];
+                (pattern.is_empty() || glob_match(pattern, &record.target))
+                    && (self.search.is_match(&record.target)
+                        || self.search.is_match(&record.message))
+            })
+            .collect()
+    }
+}
+
+/// Match text against a pattern where `*` matches any sequence of
+/// characters and `?` matches a single character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` and the text position it was tried at.
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 impl Plugin for LogPlugin {
     fn build(&mut self, container: ContainerRef) {
         let _ = log::set_logger(self.logger);
@@ -76,34 +445,51 @@ impl Plugin for LogPlugin {
     fn before_render(&self, _ctx: &mut ViewContext, args: ContainerRef) {
         let args = args.borrow();
         let kb = args.get::<Res<Keyboard>>().unwrap();
-        let mut open = self.log_open.load(std::sync::atomic::Ordering::SeqCst);
+        let mut open = self.log_open.load(Ordering::SeqCst);
+
+        if open && self.search.is_editing() {
+            self.search.handle_keys(kb);
+            self.locked.store(true, Ordering::SeqCst);
+            return;
+        }
+
         if kb.char() == Some('~') {
             open = !open;
-            self.locked.store(true, std::sync::atomic::Ordering::SeqCst);
-            self.log_open
-                .store(open, std::sync::atomic::Ordering::SeqCst);
+            self.locked.store(true, Ordering::SeqCst);
+            self.log_open.store(open, Ordering::SeqCst);
             kb.reset();
         }
 
         if open {
+            if self.search.handle_keys(kb) {
+                self.locked.store(true, Ordering::SeqCst);
+            }
+
+            if kb.char() == Some('f') {
+                self.show_fields.fetch_xor(true, Ordering::SeqCst);
+                kb.reset();
+            }
+
+            if kb.char() == Some('t') {
+                self.relative_time.fetch_xor(true, Ordering::SeqCst);
+                kb.reset();
+            }
+
             if kb.char() == Some('j') || kb.code() == Some(KeyCode::Down) {
-                self.locked
-                    .store(false, std::sync::atomic::Ordering::SeqCst);
-                let offset = self.offset.load(std::sync::atomic::Ordering::SeqCst);
-                if offset < self.logger.records.lock().unwrap().len() - 1 {
-                    self.offset
-                        .store(offset + 1, std::sync::atomic::Ordering::SeqCst);
+                self.locked.store(false, Ordering::SeqCst);
+                let offset = self.offset.load(Ordering::SeqCst);
+                let len = self.visible(&self.logger.records.lock().unwrap()).len();
+                if offset + 1 < len {
+                    self.offset.store(offset + 1, Ordering::SeqCst);
                 }
                 kb.reset();
             }
 
             if kb.char() == Some('k') || kb.code() == Some(KeyCode::Up) {
-                self.locked
-                    .store(false, std::sync::atomic::Ordering::SeqCst);
-                let offset = self.offset.load(std::sync::atomic::Ordering::SeqCst);
+                self.locked.store(false, Ordering::SeqCst);
+                let offset = self.offset.load(Ordering::SeqCst);
                 if offset > 0 {
-                    self.offset
-                        .store(offset - 1, std::sync::atomic::Ordering::SeqCst);
+                    self.offset.store(offset - 1, Ordering::SeqCst);
                 }
                 kb.reset();
             }
@@ -111,83 +497,186 @@ impl Plugin for LogPlugin {
     }
 
     fn after_render(&self, ctx: &mut ViewContext, _args: ContainerRef) {
-        let len = self.logger.records.lock().unwrap().len();
-        let height = ctx.height() - 2;
-        if self.locked.load(std::sync::atomic::Ordering::SeqCst) {
-            if len > height {
-                self.offset
-                    .store(len - height, std::sync::atomic::Ordering::SeqCst);
-            } else {
-                self.offset.store(0, std::sync::atomic::Ordering::SeqCst);
+        let records = self.logger.records.lock().unwrap();
+        let visible = self.visible(&records);
+        let height = ctx.height().saturating_sub(2);
+        if self.locked.load(Ordering::SeqCst) {
+            // Scroll back from the newest record until the view is full.
+            let mut offset = visible.len();
+            let mut rows = 0;
+            while offset > 0 {
+                rows += self.rows(&records[visible[offset - 1]]);
+                if rows > height {
+                    break;
+                }
+                offset -= 1;
             }
+            self.offset.store(offset, Ordering::SeqCst);
         }
 
-        if self.log_open.load(std::sync::atomic::Ordering::SeqCst) {
+        if self.log_open.load(Ordering::SeqCst) {
+            let theme = ctx.theme();
             let size = ctx.size();
-            ctx.fill_all(Color::Rgb { r: 0, g: 0, b: 0 });
-            ctx.fill(
-                ((0, 0), (size.width, 1)),
-                Color::Rgb {
-                    r: 30,
-                    g: 30,
-                    b: 30,
-                },
-            );
-            ctx.insert(0, "  Log view".to_runes().bold());
-            ctx.component(
-                ((0, 2), size - Size::new(0, 2)),
-                logview(self.offset.load(std::sync::atomic::Ordering::SeqCst)),
-            );
-        }
-    }
-}
-
-fn logview(offset: usize) -> impl Fn(&mut ViewContext, Res<&ArkhamLogger>) {
-    move |ctx: &mut ViewContext, logger: Res<&ArkhamLogger>| {
-        let records = logger.records.lock().unwrap();
-        for (idx, entry) in records.iter().skip(offset).enumerate() {
-            ctx.component(((2, idx), (6, 1)), level(entry.level));
+            ctx.fill_all(theme.bg_secondary);
+            ctx.fill(((0, 0), (size.width, 1)), theme.bg_primary);
             ctx.insert(
-                (9, idx),
-                entry
-                    .time
-                    .format("%H:%M:%S")
-                    .to_string()
-                    .to_runes()
-                    .fg(Color::DarkGrey),
+                0,
+                Runes::cached("arkham.logview.title", || "  Log view".to_runes().bold()),
             );
-            ctx.insert((18, idx), entry.message.clone());
+            self.search
+                .draw(ctx, ((13, 0), (size.width.saturating_sub(13), 1)));
+
+            let mut row = 2;
+            for &idx in visible.iter().skip(self.offset.load(Ordering::SeqCst)) {
+                if row >= size.height {
+                    break;
+                }
+                row += self.draw_record(ctx, row, &records[idx], &theme);
+            }
         }
     }
 }
 
-fn level(level: Level) -> impl Fn(&mut ViewContext) {
+fn level(level: Level, theme: &Theme) -> impl Fn(&mut ViewContext) {
     let bg = match level {
-        Level::Error => Color::Rgb { r: 110, g: 0, b: 0 },
-        Level::Warn => Color::Rgb {
-            r: 110,
-            g: 105,
-            b: 24,
-        },
-        Level::Info => Color::Rgb {
-            r: 255,
-            g: 255,
-            b: 255,
-        },
-        Level::Debug => Color::Rgb { r: 0, g: 0, b: 0 },
-        Level::Trace => Color::Rgb { r: 0, g: 0, b: 0 },
-    };
-
-    let fg = match level {
-        Level::Info => Color::Rgb { r: 0, g: 0, b: 0 },
-        _ => Color::Rgb {
-            r: 255,
-            g: 255,
-            b: 255,
-        },
+        Level::Error => theme.error,
+        Level::Warn => theme.warning,
+        Level::Info => theme.info,
+        Level::Debug | Level::Trace => theme.muted,
     };
+    let fg = theme.bg_secondary;
     move |ctx| {
         ctx.fill_all(bg);
         ctx.insert(0, level.to_string().to_runes().fg(fg).bold())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use log::{Level, LevelFilter, Log, Record};
+
+    use super::{glob_match, ArkhamLogger, LogPlugin, LoggerConfig};
+
+    fn log(logger: &ArkhamLogger, level: Level, target: &str, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target(target)
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    fn messages(logger: &ArkhamLogger) -> Vec<(String, usize)> {
+        logger
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| (r.message.clone(), r.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_coalesce_duplicates() {
+        let logger = ArkhamLogger::default();
+        log(&logger, Level::Info, "app", "retrying");
+        log(&logger, Level::Info, "app", "retrying");
+        log(&logger, Level::Warn, "app", "retrying");
+        log(&logger, Level::Info, "app", "retrying");
+        assert_eq!(
+            messages(&logger),
+            vec![
+                ("retrying".to_string(), 2),
+                ("retrying".to_string(), 1),
+                ("retrying".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_levels() {
+        let logger = ArkhamLogger::default();
+        logger.config.lock().unwrap().modules = vec![
+            ("app".to_string(), LevelFilter::Warn),
+            ("app::net".to_string(), LevelFilter::Debug),
+        ];
+        log(&logger, Level::Info, "app", "hidden");
+        log(&logger, Level::Debug, "app::net::tcp", "shown");
+        log(&logger, Level::Debug, "app::network", "hidden");
+        log(&logger, Level::Info, "other", "shown");
+        assert_eq!(
+            messages(&logger),
+            vec![("shown".to_string(), 1), ("shown".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let mut config = LoggerConfig {
+            rate_limit: Some((2, Duration::from_secs(1))),
+            ..LoggerConfig::default()
+        };
+        let now = Instant::now();
+        assert_eq!(config.admit("noisy", now), (true, 0));
+        assert_eq!(config.admit("noisy", now), (true, 0));
+        assert_eq!(config.admit("noisy", now), (false, 0));
+        assert_eq!(config.admit("quiet", now), (true, 0));
+        assert_eq!(config.admit("noisy", now), (false, 0));
+        let later = now + Duration::from_secs(1);
+        assert_eq!(config.admit("noisy", later), (true, 2));
+    }
+
+    #[test]
+    fn test_fields() {
+        let logger = ArkhamLogger::default();
+        let fields: &[(&str, &str)] = &[("user", "ada"), ("attempt", "2")];
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .target("app::auth")
+                .args(format_args!("logged in"))
+                .key_values(&fields)
+                .build(),
+        );
+        let records = logger.records.lock().unwrap();
+        assert_eq!(records[0].target, "app::auth");
+        assert_eq!(
+            records[0].fields,
+            vec![
+                ("user".to_string(), "ada".to_string()),
+                ("attempt".to_string(), "2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("app::*", "app::net::tcp"));
+        assert!(glob_match("*net*", "app::net::tcp"));
+        assert!(glob_match("app::n?t", "app::net"));
+        assert!(!glob_match("app::*", "other::app"));
+        assert!(!glob_match("app", "app::net"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn test_search_filters_records() {
+        let logger: &'static ArkhamLogger = Box::leak(Box::default());
+        log(logger, Level::Info, "app::disk", "mounted");
+        log(logger, Level::Info, "app::net", "disk quota reached");
+        log(logger, Level::Info, "app::net", "connected");
+        log(logger, Level::Info, "other", "disk full");
+        let plugin = LogPlugin {
+            logger,
+            ..LogPlugin::default()
+        }
+        .target_filter("app::*");
+        let visible = |plugin: &LogPlugin| plugin.visible(&logger.records.lock().unwrap());
+        assert_eq!(visible(&plugin), vec![0, 1, 2]);
+        plugin.search.set_query("disk");
+        assert_eq!(visible(&plugin), vec![0, 1]);
+    }
+}
@@ -1,4 +1,18 @@
-use crate::{container::ContainerRef, context::ViewContext};
+use crate::{container::ContainerRef, context::ViewContext, view::View};
+mod cell_inspector;
+pub use cell_inspector::CellInspector;
+mod crash_report;
+pub use crash_report::CrashReport;
+mod link_hints;
+pub use link_hints::LinkHints;
+#[cfg(feature = "debug")]
+mod provenance_overlay;
+#[cfg(feature = "debug")]
+pub use provenance_overlay::ProvenanceOverlay;
+mod ruler;
+pub use ruler::{Guide, Ruler};
+mod screenshot;
+pub use screenshot::{Screenshot, ScreenshotFormat};
 #[cfg(feature = "log")]
 mod logview;
 #[cfg(feature = "log")]
@@ -8,4 +22,14 @@ pub trait Plugin {
     fn build(&mut self, _container: ContainerRef) {}
     fn before_render(&self, _ctx: &mut ViewContext, _container: ContainerRef) {}
     fn after_render(&self, _ctx: &mut ViewContext, _container: ContainerRef) {}
+    /// Called with the final composed view of each frame, after every
+    /// plugin has rendered and before it is written to the terminal.
+    /// Post-processors transform the frame as a whole, such as dimming it
+    /// when the terminal loses focus, and run in the order the plugins were
+    /// added.
+    fn post_process(&self, _view: &mut View, _container: ContainerRef) {}
+    /// Called when the application becomes idle. See App::idle_timeout.
+    fn on_idle(&self, _container: ContainerRef) {}
+    /// Called when input is received after the application was idle.
+    fn on_active(&self, _container: ContainerRef) {}
 }
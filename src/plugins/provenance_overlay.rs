@@ -0,0 +1,210 @@
+use std::cell::Cell;
+
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Color,
+};
+
+use crate::{
+    color::contrast,
+    container::{ContainerRef, Res},
+    context::ViewContext,
+    input::Keyboard,
+    plugins::Plugin,
+    provenance,
+    runes::{Rune, Runes},
+};
+
+/// The colors components are shown in, assigned in the order the
+/// components first appear on screen.
+const PALETTE: [Color; 8] = [
+    Color::Rgb {
+        r: 230,
+        g: 97,
+        b: 92,
+    },
+    Color::Rgb {
+        r: 92,
+        g: 184,
+        b: 230,
+    },
+    Color::Rgb {
+        r: 130,
+        g: 200,
+        b: 100,
+    },
+    Color::Rgb {
+        r: 230,
+        g: 180,
+        b: 80,
+    },
+    Color::Rgb {
+        r: 180,
+        g: 120,
+        b: 220,
+    },
+    Color::Rgb {
+        r: 80,
+        g: 200,
+        b: 180,
+    },
+    Color::Rgb {
+        r: 230,
+        g: 130,
+        b: 180,
+    },
+    Color::Rgb {
+        r: 160,
+        g: 160,
+        b: 160,
+    },
+];
+
+/// ProvenanceOverlay colors every cell on screen by the component that drew
+/// it, with a legend naming the components, to diagnose components that
+/// overlap or are drawn in the wrong order. Pressing the trigger key, F10
+/// by default, toggles the overlay.
+///
+/// This requires the `debug` feature. See Frame::writer_at for querying
+/// the same information from a component.
+///
+/// ```no_run
+/// use arkham::{plugins::ProvenanceOverlay, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(ProvenanceOverlay::new())
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.component(((0, 0), (20, 1)), header);
+///     ctx.insert((0, 1), "Press F10 to show who drew what");
+/// }
+///
+/// fn header(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Header");
+/// }
+/// ```
+pub struct ProvenanceOverlay {
+    key: (KeyCode, KeyModifiers),
+    active: Cell<bool>,
+}
+
+impl Default for ProvenanceOverlay {
+    fn default() -> Self {
+        Self {
+            key: (KeyCode::F(10), KeyModifiers::NONE),
+            active: Cell::new(false),
+        }
+    }
+}
+
+impl ProvenanceOverlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key which toggles the overlay.
+    pub fn key(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.key = (code, modifiers);
+        self
+    }
+}
+
+/// The name of a component without the path of the module it is in.
+fn short_name(name: &'static str) -> &'static str {
+    let path = name.split('<').next().unwrap_or(name);
+    let start = path
+        .match_indices("::")
+        .map(|(idx, _)| idx + 2)
+        .filter(|&idx| !path[idx..].starts_with("{{"))
+        .last()
+        .unwrap_or(0);
+    &name[start..]
+}
+
+impl Plugin for ProvenanceOverlay {
+    fn before_render(&self, _ctx: &mut ViewContext, container: ContainerRef) {
+        let container = container.borrow();
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        if kb.code() == Some(self.key.0) && kb.modifiers() == self.key.1 {
+            self.active.set(!self.active.get());
+            kb.reset();
+        }
+    }
+
+    fn after_render(&self, ctx: &mut ViewContext, _container: ContainerRef) {
+        if !self.active.get() {
+            return;
+        }
+        let regions = provenance::regions(&ctx.view);
+        let color = |name: &str| {
+            let idx = regions.iter().position(|(n, _)| *n == name).unwrap_or(0);
+            PALETTE[idx % PALETTE.len()]
+        };
+        for rune in ctx.view.iter_mut().flatten() {
+            if let Some(name) = provenance::writer(rune) {
+                let bg = color(name);
+                rune.bg = Some(bg);
+                rune.fg = contrast(bg);
+            }
+        }
+
+        let size = ctx.size();
+        let width = regions
+            .iter()
+            .map(|(name, _)| short_name(name).chars().count() + 3)
+            .max()
+            .unwrap_or(0)
+            .min(size.width);
+        let height = regions.len().min(size.height);
+        let (x, y) = (size.width - width, size.height - height);
+        let theme = ctx.theme();
+        for (row, (name, _)) in regions.iter().take(height).enumerate() {
+            let mut runes = Runes::from(Rune::new().content(' ').bg(color(name)));
+            runes.add(Runes::from(format!(" {} ", short_name(name))).bg(theme.bg_secondary));
+            let line: Vec<Rune> = runes.iter().take(width).copied().collect();
+            ctx.insert((x, y + row), Runes::new(line).fg(theme.fg));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{context::ViewContext, testing::TestApp};
+
+    use super::{short_name, ProvenanceOverlay, PALETTE};
+
+    fn header(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "Header");
+    }
+
+    #[test]
+    fn test_short_name() {
+        assert_eq!(short_name("app::views::header"), "header");
+        assert_eq!(short_name("app::root::{{closure}}"), "root::{{closure}}");
+        assert_eq!(short_name("app::List<app::Item>"), "List<app::Item>");
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| {
+                ctx.component(((0, 0), (10, 1)), header);
+                ctx.insert((0, 1), "Body");
+            },
+            (30, 4),
+        )
+        .insert_plugin(ProvenanceOverlay::new());
+        app.render();
+        let view = app.press(KeyCode::F(10));
+        assert_eq!(view[0][0].bg, Some(PALETTE[0]));
+        assert_eq!(view[1][0].bg, Some(PALETTE[1]));
+        let text = crate::testing::frame_text(view);
+        assert!(text.contains("header"));
+    }
+}
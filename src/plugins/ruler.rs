@@ -0,0 +1,380 @@
+use std::cell::RefCell;
+
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton};
+
+use crate::{
+    color::lerp,
+    container::{ContainerRef, Res},
+    context::ViewContext,
+    geometry::{Pos, Rect, Size},
+    input::{Keyboard, Mouse},
+    plugins::Plugin,
+    runes::{Rune, Runes},
+};
+
+/// How strongly the crosshair and the measured region are tinted with the
+/// theme's focus color.
+const TINT: f32 = 0.35;
+
+/// A guide line drawn by the Ruler to check the alignment of a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guide {
+    /// Lines dividing the screen into thirds in both directions.
+    Thirds,
+    /// Lines dividing the screen by the golden ratio in both directions,
+    /// at about 38% and 62% of its width and height.
+    GoldenRatio,
+    /// Vertical lines every given number of columns.
+    Columns(usize),
+    /// Horizontal lines every given number of rows.
+    Rows(usize),
+    /// A vertical line at a column.
+    Column(usize),
+    /// A horizontal line at a row.
+    Row(usize),
+}
+
+impl Guide {
+    /// The columns of the vertical lines and rows of the horizontal lines
+    /// the guide draws on a screen of a given size.
+    fn lines(&self, size: Size) -> (Vec<usize>, Vec<usize>) {
+        let fractions = |length: usize, at: &[f64]| -> Vec<usize> {
+            at.iter()
+                .map(|f| (length as f64 * f).round() as usize)
+                .collect()
+        };
+        let every = |length: usize, step: usize| -> Vec<usize> {
+            (step.max(1)..length).step_by(step.max(1)).collect()
+        };
+        let golden = 1.0 - 1.0 / 1.618_034;
+        match *self {
+            Guide::Thirds => (
+                fractions(size.width, &[1.0 / 3.0, 2.0 / 3.0]),
+                fractions(size.height, &[1.0 / 3.0, 2.0 / 3.0]),
+            ),
+            Guide::GoldenRatio => (
+                fractions(size.width, &[golden, 1.0 - golden]),
+                fractions(size.height, &[golden, 1.0 - golden]),
+            ),
+            Guide::Columns(step) => (every(size.width, step), vec![]),
+            Guide::Rows(step) => (vec![], every(size.height, step)),
+            Guide::Column(x) => (vec![x], vec![]),
+            Guide::Row(y) => (vec![], vec![y]),
+        }
+    }
+}
+
+struct RulerState {
+    active: bool,
+    pos: Pos,
+    anchor: Option<Pos>,
+}
+
+/// Ruler is a debugging aid for placing components by hand. It draws rulers
+/// numbering the columns along the top of the screen and the rows down its
+/// left side, and a crosshair labeled with its coordinates.
+///
+/// Pressing the trigger key, F11 by default, toggles the ruler. While it is
+/// shown the crosshair follows the mouse, and can be moved with the arrow
+/// keys. Pressing Enter, or clicking, anchors a measurement at the
+/// crosshair: the region between the anchor and the crosshair is
+/// highlighted and labeled with its position and size, ready to be copied
+/// into a Rect. Escape clears the anchor. These keys are not passed on to
+/// the application while the ruler is shown.
+///
+/// Guide lines, such as lines dividing the screen into thirds, can be added
+/// with Ruler::guide to check that a layout lines up, and Ruler::snap makes
+/// the crosshair snap to a grid.
+///
+/// ```no_run
+/// use arkham::{
+///     plugins::{Guide, Ruler},
+///     prelude::*,
+/// };
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(Ruler::new().guide(Guide::Thirds).snap(2))
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.insert((4, 2), "Press F11 to measure");
+/// }
+/// ```
+pub struct Ruler {
+    key: (KeyCode, KeyModifiers),
+    guides: Vec<Guide>,
+    snap: usize,
+    state: RefCell<RulerState>,
+}
+
+impl Default for Ruler {
+    fn default() -> Self {
+        Self {
+            key: (KeyCode::F(11), KeyModifiers::NONE),
+            guides: vec![],
+            snap: 1,
+            state: RefCell::new(RulerState {
+                active: false,
+                pos: Pos::new(0, 0),
+                anchor: None,
+            }),
+        }
+    }
+}
+
+impl Ruler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key which toggles the ruler.
+    pub fn key(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.key = (code, modifiers);
+        self
+    }
+
+    /// Add a guide line drawn while the ruler is shown. Several guides can
+    /// be added.
+    pub fn guide(mut self, guide: Guide) -> Self {
+        self.guides.push(guide);
+        self
+    }
+
+    /// Snap the crosshair to a grid of cells of the given size. The arrow
+    /// keys move the crosshair to the next grid line and the mouse moves it
+    /// to the nearest one. Defaults to 1, which does not snap.
+    pub fn snap(mut self, cells: usize) -> Self {
+        self.snap = cells.max(1);
+        self
+    }
+
+    /// The region between the anchor and the crosshair, including both.
+    fn measured(state: &RulerState) -> Option<Rect> {
+        let anchor = state.anchor?;
+        let (x, y) = (anchor.x.min(state.pos.x), anchor.y.min(state.pos.y));
+        let width = anchor.x.max(state.pos.x) - x + 1;
+        let height = anchor.y.max(state.pos.y) - y + 1;
+        Some(Rect::new((x, y), (width, height)))
+    }
+
+    fn draw(&self, ctx: &mut ViewContext, state: &RulerState) {
+        let size = ctx.size();
+        let theme = ctx.theme();
+        let symbols = ctx.symbols();
+        let pos = state.pos;
+        let measured = Self::measured(state);
+
+        // Tint the crosshair and the measured region.
+        for (y, row) in ctx.view.iter_mut().enumerate() {
+            for (x, rune) in row.iter_mut().enumerate() {
+                let crosshair = x == pos.x || y == pos.y;
+                let inside = measured.is_some_and(|rect| rect.contains(Pos::new(x, y)));
+                if crosshair || inside {
+                    let bg = rune.bg.unwrap_or(theme.bg_primary);
+                    rune.bg = Some(lerp(bg, theme.focus, TINT));
+                }
+            }
+        }
+
+        // Guide lines are drawn in the empty cells they cross, and tint the
+        // cells which have content.
+        for guide in self.guides.iter() {
+            let (columns, rows) = guide.lines(size);
+            let lines = columns
+                .iter()
+                .flat_map(|&x| (0..size.height).map(move |y| (x, y, symbols.border.vertical)))
+                .chain(rows.iter().flat_map(|&y| {
+                    (0..size.width).map(move |x| (x, y, symbols.border.horizontal))
+                }));
+            for (x, y, line) in lines {
+                let Some(rune) = ctx.view.get_mut(y).and_then(|row| row.get_mut(x)) else {
+                    continue;
+                };
+                if rune.continuation || !matches!(rune.content, None | Some(' ')) {
+                    let bg = rune.bg.unwrap_or(theme.bg_primary);
+                    rune.bg = Some(lerp(bg, theme.accent, TINT));
+                } else {
+                    *rune = Rune {
+                        content: Some(line),
+                        fg: Some(theme.accent),
+                        ..*rune
+                    };
+                }
+            }
+        }
+
+        // The column ruler along the top and the row ruler down the side.
+        for x in 0..size.width {
+            let rune = if x == pos.x {
+                Runes::from(tick(x)).fg(theme.bg_primary).bg(theme.focus)
+            } else {
+                Runes::from(tick(x)).fg(theme.muted).bg(theme.bg_secondary)
+            };
+            ctx.insert((x, 0), rune);
+        }
+        for y in 1..size.height {
+            let rune = if y == pos.y {
+                Runes::from(tick(y)).fg(theme.bg_primary).bg(theme.focus)
+            } else {
+                Runes::from(tick(y)).fg(theme.muted).bg(theme.bg_secondary)
+            };
+            ctx.insert((0, y), rune);
+        }
+
+        let label = match measured {
+            Some(rect) => format!(
+                " ({}, {}) ({}, {}) ",
+                rect.pos.x, rect.pos.y, rect.size.width, rect.size.height
+            ),
+            None => format!(" {},{} ", pos.x, pos.y),
+        };
+        let width = label.chars().count();
+        // Keep the label beside the crosshair and on screen.
+        let x = if pos.x + 1 + width <= size.width {
+            pos.x + 1
+        } else {
+            pos.x.saturating_sub(width)
+        };
+        let y = if pos.y + 1 < size.height {
+            pos.y + 1
+        } else {
+            pos.y.saturating_sub(1)
+        };
+        ctx.insert(
+            (x, y),
+            Runes::from(label).fg(theme.fg).bg(theme.bg_secondary),
+        );
+    }
+}
+
+/// The ruler character for a column or row: the tens digit at multiples of
+/// ten, a mark halfway between, and a dot elsewhere.
+fn tick(idx: usize) -> char {
+    if idx.is_multiple_of(10) {
+        char::from_digit((idx / 10 % 10) as u32, 10).unwrap_or('0')
+    } else if idx.is_multiple_of(5) {
+        '+'
+    } else {
+        '.'
+    }
+}
+
+impl Plugin for Ruler {
+    fn before_render(&self, _ctx: &mut ViewContext, container: ContainerRef) {
+        let container = container.borrow();
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        let mut state = self.state.borrow_mut();
+        if kb.code() == Some(self.key.0) && kb.modifiers() == self.key.1 {
+            state.active = !state.active;
+            kb.reset();
+            return;
+        }
+        if !state.active {
+            return;
+        }
+        let snap = self.snap;
+        let nearest = |v: usize| (v + snap / 2) / snap * snap;
+        if let Some(mouse) = container.get::<Res<Mouse>>() {
+            if let Some(pos) = mouse.position() {
+                state.pos = Pos::new(nearest(pos.x), nearest(pos.y));
+            }
+            if mouse.clicked(MouseButton::Left) {
+                state.anchor = Some(state.pos);
+                mouse.reset();
+            }
+        }
+        let pos = state.pos;
+        match kb.code() {
+            Some(KeyCode::Left) => state.pos.x = pos.x.saturating_sub(1) / snap * snap,
+            Some(KeyCode::Right) => state.pos.x = (pos.x / snap + 1) * snap,
+            Some(KeyCode::Up) => state.pos.y = pos.y.saturating_sub(1) / snap * snap,
+            Some(KeyCode::Down) => state.pos.y = (pos.y / snap + 1) * snap,
+            Some(KeyCode::Enter) => state.anchor = Some(pos),
+            Some(KeyCode::Esc) => state.anchor = None,
+            _ => return,
+        }
+        kb.reset();
+    }
+
+    fn after_render(&self, ctx: &mut ViewContext, _container: ContainerRef) {
+        let mut state = self.state.borrow_mut();
+        if !state.active {
+            return;
+        }
+        let size = ctx.size();
+        state.pos.x = state.pos.x.min(size.width.saturating_sub(1));
+        state.pos.y = state.pos.y.min(size.height.saturating_sub(1));
+        self.draw(ctx, &state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{context::ViewContext, testing::TestApp};
+
+    use super::{Guide, Ruler};
+
+    #[test]
+    fn test_measure() {
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| {
+                ctx.insert((2, 2), "Body");
+            },
+            (24, 6),
+        )
+        .insert_plugin(Ruler::new());
+        app.render();
+        let view = app.press(KeyCode::F(11));
+        let text = crate::testing::frame_text(view);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "0....+....1....+....2...");
+        assert!(lines[1].starts_with(". 0,0"));
+
+        app.press(KeyCode::Right);
+        app.press(KeyCode::Down);
+        app.press(KeyCode::Enter);
+        app.press(KeyCode::Right);
+        app.press(KeyCode::Right);
+        let view = app.press(KeyCode::Down);
+        let text = crate::testing::frame_text(view);
+        assert!(text.contains("(1, 1) (3, 2)"));
+        assert_ne!(view[1][2].bg, view[4][10].bg);
+
+        let view = app.press(KeyCode::F(11));
+        assert!(crate::testing::frame_text(view).contains("Body"));
+        assert_eq!(view[0][0].content, None);
+    }
+
+    #[test]
+    fn test_guide_lines() {
+        let size = (30, 9).into();
+        assert_eq!(Guide::Thirds.lines(size), (vec![10, 20], vec![3, 6]));
+        assert_eq!(Guide::GoldenRatio.lines(size).0, vec![11, 19]);
+        assert_eq!(Guide::Columns(8).lines(size).0, vec![8, 16, 24]);
+        assert_eq!(Guide::Rows(0).lines(size).1.len(), 8);
+    }
+
+    #[test]
+    fn test_guides_and_snap() {
+        let mut app = TestApp::new(
+            |ctx: &mut ViewContext| {
+                ctx.insert((10, 3), "Body");
+            },
+            (24, 6),
+        )
+        .insert_plugin(Ruler::new().snap(4).guide(Guide::Column(12)));
+        app.render();
+        app.press(KeyCode::F(11));
+        app.press(KeyCode::Right);
+        let view = app.press(KeyCode::Right);
+        assert!(crate::testing::frame_text(view).contains(" 8,0"));
+        assert_eq!(view[4][12].content, Some('│'));
+        assert_eq!(view[3][12].content, Some('d'));
+        assert_ne!(view[3][12].bg, view[3][13].bg);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Color,
+};
+
+use crate::{
+    app::Renderer,
+    container::{ContainerRef, Res},
+    context::ViewContext,
+    input::Keyboard,
+    plugins::Plugin,
+    runes::Runes,
+    view::View,
+};
+
+/// The file format screenshots are saved in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// Text with ANSI escape sequences, which can be displayed with `cat`.
+    #[default]
+    Ansi,
+    /// A standalone HTML page.
+    Html,
+}
+
+impl ScreenshotFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ScreenshotFormat::Ansi => "ans",
+            ScreenshotFormat::Html => "html",
+        }
+    }
+
+    fn export(&self, view: &View) -> String {
+        match self {
+            ScreenshotFormat::Ansi => view.to_ansi(),
+            ScreenshotFormat::Html => view.to_html(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ScreenshotState {
+    requested: bool,
+    /// The confirmation message, its color, and the time it stops being
+    /// displayed.
+    toast: Option<(String, Color, Instant)>,
+}
+
+/// Screenshot saves the current frame to a timestamped file when a key is
+/// pressed, Ctrl+P by default, and briefly displays a message with the path
+/// the file was saved to. This makes it easy for the users of an
+/// application to attach the screen they were looking at to a bug report.
+///
+/// Files are named `screenshot-YYYYMMDD-HHMMSS` with an extension for the
+/// format, and are saved to the working directory unless another directory
+/// is set. See View::to_ansi and View::to_html for the formats.
+///
+/// ```no_run
+/// use arkham::{plugins::{Screenshot, ScreenshotFormat}, prelude::*};
+///
+/// fn main() {
+///     App::new(root)
+///         .insert_plugin(
+///             Screenshot::new()
+///                 .key(KeyCode::F(12), KeyModifiers::NONE)
+///                 .format(ScreenshotFormat::Html),
+///         )
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Press F12 to save a screenshot");
+/// }
+/// ```
+pub struct Screenshot {
+    key: (KeyCode, KeyModifiers),
+    format: ScreenshotFormat,
+    directory: PathBuf,
+    duration: Duration,
+    renderer: Option<Renderer>,
+    state: RefCell<ScreenshotState>,
+}
+
+impl Default for Screenshot {
+    fn default() -> Self {
+        Self {
+            key: (KeyCode::Char('p'), KeyModifiers::CONTROL),
+            format: ScreenshotFormat::default(),
+            directory: PathBuf::from("."),
+            duration: Duration::from_secs(3),
+            renderer: None,
+            state: RefCell::new(ScreenshotState::default()),
+        }
+    }
+}
+
+impl Screenshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key which saves a screenshot.
+    pub fn key(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.key = (code, modifiers);
+        self
+    }
+
+    /// Set the format screenshots are saved in.
+    pub fn format(mut self, format: ScreenshotFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the directory screenshots are saved to.
+    pub fn directory<P: AsRef<Path>>(mut self, directory: P) -> Self {
+        self.directory = directory.as_ref().to_path_buf();
+        self
+    }
+
+    /// Set how long the confirmation message is displayed for. Defaults to
+    /// three seconds.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Write a view to a new file, returning its path.
+    fn save(&self, view: &View) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.directory)?;
+        let stamp = timestamp(SystemTime::now());
+        let ext = self.format.extension();
+        let mut path = self.directory.join(format!("screenshot-{stamp}.{ext}"));
+        let mut n = 1;
+        while path.exists() {
+            path = self.directory.join(format!("screenshot-{stamp}-{n}.{ext}"));
+            n += 1;
+        }
+        std::fs::write(&path, self.format.export(view))?;
+        Ok(path)
+    }
+
+    /// Draw the confirmation message in the bottom right corner.
+    fn draw_toast(&self, ctx: &mut ViewContext, state: &ScreenshotState) {
+        let Some((message, color, _)) = &state.toast else {
+            return;
+        };
+        let size = ctx.size();
+        let text = format!(" {message} ");
+        let width = text.chars().count().min(size.width);
+        let runes = Runes::from(text.chars().take(width).collect::<String>())
+            .fg(Color::Black)
+            .bg(*color);
+        ctx.insert((size.width - width, size.height.saturating_sub(1)), runes);
+    }
+}
+
+/// Format a time as `YYYYMMDD-HHMMSS` in UTC.
+pub(super) fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (days, rem) = (secs / 86400, secs % 86400);
+
+    // Convert days since the epoch to a civil date.
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+impl Plugin for Screenshot {
+    fn build(&mut self, container: ContainerRef) {
+        self.renderer = container
+            .borrow()
+            .get::<Res<Renderer>>()
+            .map(|renderer| renderer.get().clone());
+    }
+
+    fn before_render(&self, _ctx: &mut ViewContext, container: ContainerRef) {
+        let container = container.borrow();
+        let kb = container.get::<Res<Keyboard>>().unwrap();
+        if kb.code() == Some(self.key.0) && kb.modifiers() == self.key.1 {
+            self.state.borrow_mut().requested = true;
+            kb.reset();
+        }
+    }
+
+    fn after_render(&self, ctx: &mut ViewContext, _container: ContainerRef) {
+        let mut state = self.state.borrow_mut();
+        let theme = ctx.theme();
+        if state.requested {
+            state.requested = false;
+            let (message, color) = match self.save(&ctx.view) {
+                Ok(path) => (format!("Saved {}", path.display()), theme.success),
+                Err(e) => (format!("Screenshot failed: {e}"), theme.error),
+            };
+            state.toast = Some((message, color, Instant::now() + self.duration));
+            // Render once the message expires so that it is removed even if
+            // nothing else changes.
+            if let Some(renderer) = self.renderer.clone() {
+                let duration = self.duration;
+                std::thread::spawn(move || {
+                    std::thread::sleep(duration);
+                    renderer.render();
+                });
+            }
+            self.draw_toast(ctx, &state);
+        } else if let Some((_, _, until)) = &state.toast {
+            if Instant::now() >= *until {
+                state.toast = None;
+            } else {
+                self.draw_toast(ctx, &state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::{context::ViewContext, testing::TestApp};
+
+    use super::{timestamp, Screenshot, ScreenshotFormat};
+
+    #[test]
+    fn test_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        assert_eq!(timestamp(time), "20240229-123456");
+    }
+
+    #[test]
+    fn test_save_screenshot() {
+        let dir = std::env::temp_dir().join(format!("arkham-screenshot-{}", std::process::id()));
+        let mut app = TestApp::new(|ctx: &mut ViewContext| ctx.insert((0, 0), "hello"), (40, 2))
+            .insert_plugin(
+                Screenshot::new()
+                    .directory(&dir)
+                    .format(ScreenshotFormat::Html),
+            );
+        app.render();
+        let view = app.press_with(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let text = crate::testing::frame_text(view);
+        assert!(text.contains("Saved"));
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "html");
+        let html = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(html.contains("hello"));
+        assert!(!html.contains("Saved"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use crate::geometry::{Pos, Rect};
+
+/// The shape of the mouse pointer. Shapes are requested from the terminal
+/// with the OSC 22 escape sequence, terminals that do not support it keep
+/// their default pointer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CursorShape {
+    #[default]
+    Default,
+    /// A hand, used for links and other clickable elements.
+    Pointer,
+    /// An I-beam, used for editable text.
+    Text,
+    /// Used for dividers that can be dragged horizontally.
+    ColResize,
+    /// Used for dividers that can be dragged vertically.
+    RowResize,
+    Move,
+    NotAllowed,
+    Wait,
+    Crosshair,
+}
+
+impl CursorShape {
+    /// The name of the shape used in the escape sequence.
+    fn name(&self) -> &'static str {
+        match self {
+            CursorShape::Default => "default",
+            CursorShape::Pointer => "pointer",
+            CursorShape::Text => "text",
+            CursorShape::ColResize => "col-resize",
+            CursorShape::RowResize => "row-resize",
+            CursorShape::Move => "move",
+            CursorShape::NotAllowed => "not-allowed",
+            CursorShape::Wait => "wait",
+            CursorShape::Crosshair => "crosshair",
+        }
+    }
+
+    /// Write the escape sequence which sets the pointer to this shape.
+    pub(crate) fn write<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        write!(out, "\x1b]22;{}\x1b\\", self.name())?;
+        out.flush()
+    }
+}
+
+#[derive(Debug, Default)]
+struct CursorHintsState {
+    regions: Vec<(Rect, CursorShape)>,
+    current: CursorShape,
+}
+
+/// The pointer shapes requested with ViewContext::cursor_hint during the
+/// current frame, in screen coordinates.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CursorHints {
+    state: Rc<RefCell<CursorHintsState>>,
+}
+
+impl CursorHints {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discard the hints from the previous frame.
+    pub(crate) fn begin_frame(&self) {
+        self.state.borrow_mut().regions.clear();
+    }
+
+    pub(crate) fn add(&self, rect: Rect, shape: CursorShape) {
+        self.state.borrow_mut().regions.push((rect, shape));
+    }
+
+    /// The shape for a position. Hints added later take precedence, so the
+    /// hint of a component drawn above another is used.
+    pub(crate) fn shape_at(&self, pos: Pos) -> CursorShape {
+        self.state
+            .borrow()
+            .regions
+            .iter()
+            .rev()
+            .find(|(rect, _)| rect.contains(pos))
+            .map(|(_, shape)| *shape)
+            .unwrap_or_default()
+    }
+
+    /// Returns the shape for the mouse position if it differs from the shape
+    /// last returned.
+    pub(crate) fn update(&self, pos: Option<Pos>) -> Option<CursorShape> {
+        let shape = pos.map(|pos| self.shape_at(pos)).unwrap_or_default();
+        let mut state = self.state.borrow_mut();
+        if shape == state.current {
+            return None;
+        }
+        state.current = shape;
+        Some(shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        container::Res,
+        context::ViewContext,
+        geometry::{Pos, Rect},
+    };
+
+    use super::{CursorHints, CursorShape};
+
+    #[test]
+    fn test_hints_in_screen_coordinates() {
+        let mut ctx = crate::context::tests::context_fixture();
+        let hints = CursorHints::new();
+        ctx.container.borrow_mut().bind(Res::new(hints.clone()));
+        ctx.cursor_hint(((0, 0), (20, 20)), CursorShape::Text);
+        ctx.component(((5, 2), (10, 4)), |ctx: &mut ViewContext| {
+            ctx.cursor_hint(((1, 1), (1, 3)), CursorShape::ColResize);
+        });
+        assert_eq!(hints.shape_at(Pos::new(6, 3)), CursorShape::ColResize);
+        assert_eq!(hints.shape_at(Pos::new(1, 3)), CursorShape::Text);
+
+        assert_eq!(
+            hints.update(Some(Pos::new(6, 4))),
+            Some(CursorShape::ColResize)
+        );
+        assert_eq!(hints.update(Some(Pos::new(6, 5))), None);
+        hints.begin_frame();
+        assert_eq!(
+            hints.update(Some(Pos::new(6, 5))),
+            Some(CursorShape::Default)
+        );
+        hints.add(Rect::new((0, 0), (1, 1)), CursorShape::Pointer);
+        assert_eq!(hints.update(None), None);
+    }
+
+    #[test]
+    fn test_escape_sequence() {
+        let mut out = vec![];
+        CursorShape::Pointer.write(&mut out).unwrap();
+        assert_eq!(out, b"\x1b]22;pointer\x1b\\");
+    }
+}
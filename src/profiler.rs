@@ -0,0 +1,141 @@
+//! Component render timing, enabled with the `profiling` feature.
+//!
+//! While enabled, the time spent rendering every component is recorded
+//! during a frame, excluding the time spent in the components it renders.
+//! The slowest component of each frame is reported through FrameStats.
+//!
+//! When the feature is disabled these functions do nothing.
+
+use std::time::Duration;
+
+#[cfg(feature = "profiling")]
+mod timings {
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    struct Entry {
+        name: &'static str,
+        start: Instant,
+        /// The time spent rendering child components.
+        children: Duration,
+    }
+
+    thread_local! {
+        static STACK: RefCell<Vec<Entry>> = const { RefCell::new(vec![]) };
+        static TOTALS: RefCell<Option<HashMap<&'static str, Duration>>> =
+            const { RefCell::new(None) };
+    }
+
+    pub fn enter(name: &'static str) {
+        STACK.with(|s| {
+            s.borrow_mut().push(Entry {
+                name,
+                start: Instant::now(),
+                children: Duration::ZERO,
+            })
+        });
+    }
+
+    pub fn exit() {
+        let Some(entry) = STACK.with(|s| s.borrow_mut().pop()) else {
+            return;
+        };
+        let elapsed = entry.start.elapsed();
+        STACK.with(|s| {
+            if let Some(parent) = s.borrow_mut().last_mut() {
+                parent.children += elapsed;
+            }
+        });
+        TOTALS.with(|t| {
+            *t.borrow_mut()
+                .get_or_insert_with(HashMap::new)
+                .entry(entry.name)
+                .or_default() += elapsed.saturating_sub(entry.children);
+        });
+    }
+
+    pub fn depth() -> usize {
+        STACK.with(|s| s.borrow().len())
+    }
+
+    pub fn unwind(depth: usize) {
+        while STACK.with(|s| s.borrow().len()) > depth {
+            exit();
+        }
+    }
+
+    pub fn take() -> Option<(&'static str, Duration)> {
+        TOTALS.with(|t| {
+            t.borrow_mut()
+                .take()
+                .unwrap_or_default()
+                .into_iter()
+                .max_by_key(|(_, time)| *time)
+        })
+    }
+}
+
+/// Record that a component has started rendering.
+#[inline]
+pub(crate) fn enter<F>() {
+    #[cfg(feature = "profiling")]
+    timings::enter(std::any::type_name::<F>());
+}
+
+/// Record that the most recently entered component has finished rendering.
+#[inline]
+pub(crate) fn exit() {
+    #[cfg(feature = "profiling")]
+    timings::exit();
+}
+
+/// The number of components currently rendering.
+#[inline]
+pub(crate) fn depth() -> usize {
+    #[cfg(feature = "profiling")]
+    return timings::depth();
+    #[cfg(not(feature = "profiling"))]
+    0
+}
+
+/// Finish timing components entered after a given depth which did not exit
+/// because they panicked.
+#[inline]
+pub(crate) fn unwind(_depth: usize) {
+    #[cfg(feature = "profiling")]
+    timings::unwind(_depth);
+}
+
+/// Take the component with the longest render time during the frame, along
+/// with that time.
+#[inline]
+pub(crate) fn take_slowest() -> Option<(&'static str, Duration)> {
+    #[cfg(feature = "profiling")]
+    return timings::take();
+    #[cfg(not(feature = "profiling"))]
+    None
+}
+
+#[cfg(all(test, feature = "profiling"))]
+mod tests {
+    use std::time::Duration;
+
+    #[test]
+    fn test_self_time() {
+        struct Outer;
+        struct Inner;
+        super::take_slowest();
+        super::enter::<Outer>();
+        super::enter::<Inner>();
+        std::thread::sleep(Duration::from_millis(20));
+        super::exit();
+        super::exit();
+        let (name, time) = super::take_slowest().unwrap();
+        assert!(name.ends_with("Inner"));
+        assert!(time >= Duration::from_millis(20));
+        assert!(super::take_slowest().is_none());
+    }
+}
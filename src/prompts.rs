@@ -0,0 +1,355 @@
+//! Blocking prompts for quick interactive scripts.
+//!
+//! Each prompt is drawn inline below the cursor, rather than taking over the
+//! screen like App does, and returns once the user answers it. The prompt is
+//! then replaced by a single line summarizing the answer.
+//!
+//! Pressing Esc or Ctrl+C cancels a prompt, which returns a Cancelled error.
+//!
+//! ```no_run
+//! use arkham::prompts;
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let name = prompts::input("Name?")?;
+//!     let colors = ["Red", "Green", "Blue"];
+//!     let color = prompts::select(colors)?;
+//!     if prompts::confirm(&format!("Save {} for {}?", colors[color], name))? {
+//!         println!("Saved");
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    rc::Rc,
+};
+
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Attribute, Color, SetAttribute},
+    terminal::{self, Clear, ClearType},
+};
+
+use crate::{
+    components::{List, TextInput},
+    container::{Container, Res},
+    context::ViewContext,
+    input::Keyboard,
+    runes::{RenderState, Runes},
+    style::Style,
+};
+
+/// The most rows a select prompt displays before it scrolls.
+const MAX_ROWS: usize = 10;
+
+/// The error returned when a prompt is cancelled with Esc or Ctrl+C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "prompt cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Ask the user to choose one of a list of items with the arrow keys and
+/// Enter. Returns the index of the chosen item.
+pub fn select<I, R>(items: I) -> anyhow::Result<usize>
+where
+    I: IntoIterator<Item = R>,
+    R: Into<Runes>,
+{
+    let list = List::new(items);
+    if list.is_empty() {
+        anyhow::bail!("no items to select from");
+    }
+    let idx = prompt(list.len().min(MAX_ROWS), select_frame(&list))?;
+    let item: String = list
+        .selected_item()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|rune| rune.content)
+        .collect();
+    println!("{}", item);
+    Ok(idx)
+}
+
+/// Ask the user to type a line of text, confirmed with Enter.
+pub fn input(prompt_text: &str) -> anyhow::Result<String> {
+    let input = TextInput::new();
+    let value = prompt(1, input_frame(prompt_text, &input))?;
+    println!("{} {}", prompt_text, value);
+    Ok(value)
+}
+
+/// Ask the user a yes or no question, answered with Y or N.
+pub fn confirm(prompt_text: &str) -> anyhow::Result<bool> {
+    let answer = prompt(1, confirm_frame(prompt_text))?;
+    println!("{} {}", prompt_text, if answer { "yes" } else { "no" });
+    Ok(answer)
+}
+
+/// Run a prompt on the terminal in raw mode.
+fn prompt<T, F>(height: usize, frame: F) -> anyhow::Result<T>
+where
+    F: FnMut(&mut ViewContext, &Keyboard) -> Option<T>,
+{
+    let (width, _) = terminal::size()?;
+    terminal::enable_raw_mode()?;
+    let _raw_mode = RawMode;
+    run(
+        &mut io::stdout(),
+        || Ok(crossterm::event::read()?),
+        width as usize,
+        height,
+        frame,
+    )
+}
+
+/// Leaves raw mode when dropped, so the terminal is restored however a
+/// prompt exits, including when a frame function panics.
+struct RawMode;
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Clears a prompt and shows the cursor again when dropped, so the cursor
+/// is not left hidden when reading an event or drawing fails.
+struct Restore<'a, W: Write>(&'a mut W);
+
+impl<W: Write> Drop for Restore<'_, W> {
+    fn drop(&mut self) {
+        let _ = queue!(
+            self.0,
+            cursor::MoveToColumn(0),
+            Clear(ClearType::FromCursorDown),
+            cursor::Show
+        );
+        let _ = self.0.flush();
+    }
+}
+
+/// Draw a prompt inline, reading events until the frame function returns a
+/// value. The frame function is called once before any events are read, and
+/// again after each key press or paste.
+fn run<T, W, E, F>(
+    out: &mut W,
+    mut events: E,
+    mut width: usize,
+    height: usize,
+    mut frame: F,
+) -> anyhow::Result<T>
+where
+    W: Write,
+    E: FnMut() -> anyhow::Result<Event>,
+    F: FnMut(&mut ViewContext, &Keyboard) -> Option<T>,
+{
+    let kb = Res::new(Keyboard::new());
+    let mut container = Container::default();
+    container.bind(kb.clone());
+    let container = Rc::new(RefCell::new(container));
+
+    // Reserve the rows the prompt is drawn in, scrolling the terminal if the
+    // cursor is near the bottom.
+    let restore = Restore(out);
+    let out = &mut *restore.0;
+    queue!(out, cursor::Hide)?;
+    for _ in 1..height {
+        write!(out, "\r\n")?;
+    }
+    if height > 1 {
+        queue!(out, cursor::MoveUp(height as u16 - 1))?;
+    }
+
+    let result = loop {
+        let mut ctx = ViewContext::new(container.clone(), (width, height).into());
+        // Text without a color uses the terminal's default color, as the
+        // prompt is drawn among the script's other output.
+        ctx.set_default_style(Style::new().fg(Color::Reset));
+        let value = frame(&mut ctx, &kb);
+        kb.reset();
+        draw(out, &ctx)?;
+        if let Some(value) = value {
+            break Ok(value);
+        }
+
+        match events()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.code == KeyCode::Esc || ctrl_c {
+                    break Err(Cancelled.into());
+                }
+                kb.set_key(key.code);
+                kb.set_modifiers(key.modifiers);
+            }
+            Event::Paste(text) => kb.set_paste(text),
+            Event::Resize(columns, _) => width = columns as usize,
+            _ => {}
+        }
+    };
+
+    drop(restore);
+    result
+}
+
+/// Write the rows of a prompt, leaving the cursor at the start of the first
+/// row.
+fn draw<W: Write>(out: &mut W, ctx: &ViewContext) -> anyhow::Result<()> {
+    let height = ctx.view.len();
+    for (row, line) in ctx.view.iter().enumerate() {
+        queue!(out, cursor::MoveToColumn(0))?;
+        let mut state = RenderState::default();
+        for rune in line.iter() {
+            rune.render(out, &mut state)?;
+        }
+        queue!(
+            out,
+            SetAttribute(Attribute::Reset),
+            Clear(ClearType::UntilNewLine)
+        )?;
+        if row + 1 < height {
+            queue!(out, cursor::MoveDown(1))?;
+        }
+    }
+    if height > 1 {
+        queue!(out, cursor::MoveUp(height as u16 - 1))?;
+    }
+    queue!(out, cursor::MoveToColumn(0))?;
+    out.flush()?;
+    Ok(())
+}
+
+fn select_frame(list: &List) -> impl FnMut(&mut ViewContext, &Keyboard) -> Option<usize> + '_ {
+    move |ctx, kb| {
+        if kb.code() == Some(KeyCode::Enter) {
+            return list.selected();
+        }
+        list.handle_keys(kb);
+        let size = ctx.size();
+        list.draw(ctx, size);
+        None
+    }
+}
+
+fn input_frame<'a>(
+    prompt_text: &'a str,
+    input: &'a TextInput,
+) -> impl FnMut(&mut ViewContext, &Keyboard) -> Option<String> + 'a {
+    move |ctx, kb| {
+        if kb.code() == Some(KeyCode::Enter) {
+            return Some(input.value());
+        }
+        input.handle_keys(kb);
+        let theme = ctx.theme();
+        let label = Runes::from(prompt_text.to_string()).fg(theme.accent);
+        let x = label.len() + 1;
+        let width = ctx.size().width.saturating_sub(x);
+        ctx.insert((0, 0), label);
+        input.draw(ctx, ((x, 0), (width, 1)));
+        None
+    }
+}
+
+fn confirm_frame(
+    prompt_text: &str,
+) -> impl FnMut(&mut ViewContext, &Keyboard) -> Option<bool> + '_ {
+    move |ctx, kb| {
+        match kb.code() {
+            Some(KeyCode::Char('y' | 'Y')) => return Some(true),
+            Some(KeyCode::Char('n' | 'N')) => return Some(false),
+            _ => {}
+        }
+        let theme = ctx.theme();
+        let label = Runes::from(prompt_text.to_string()).fg(theme.accent);
+        let x = label.len() + 1;
+        ctx.insert((0, 0), label);
+        ctx.insert((x, 0), Runes::from("(y/n)").fg(theme.muted));
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::components::{List, TextInput};
+
+    use super::{confirm_frame, input_frame, run, select_frame, Cancelled};
+
+    /// Returns an event source which presses keys in order.
+    fn keys(codes: Vec<KeyCode>) -> impl FnMut() -> anyhow::Result<Event> {
+        let mut codes = codes.into_iter();
+        move || {
+            let code = codes.next().expect("prompt read past the last key");
+            Ok(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let list = List::new(["one", "two", "three"]);
+        let mut out = vec![];
+        let events = keys(vec![
+            KeyCode::Down,
+            KeyCode::Down,
+            KeyCode::Up,
+            KeyCode::Enter,
+        ]);
+        let idx = run(&mut out, events, 20, 3, select_frame(&list)).unwrap();
+        assert_eq!(idx, 1);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("three"));
+    }
+
+    #[test]
+    fn test_input() {
+        let input = TextInput::new();
+        let mut out = vec![];
+        let events = keys(vec![
+            KeyCode::Char('B'),
+            KeyCode::Char('o'),
+            KeyCode::Char('b'),
+            KeyCode::Enter,
+        ]);
+        let value = run(&mut out, events, 20, 1, input_frame("Name?", &input)).unwrap();
+        assert_eq!(value, "Bob");
+        assert!(String::from_utf8(out).unwrap().contains("Name?"));
+    }
+
+    #[test]
+    fn test_confirm_and_cancel() {
+        let events = keys(vec![KeyCode::Char('x'), KeyCode::Char('y')]);
+        assert!(run(&mut vec![], events, 20, 1, confirm_frame("Ok?")).unwrap());
+
+        let events = keys(vec![KeyCode::Esc]);
+        let err = run(&mut vec![], events, 20, 1, confirm_frame("Ok?")).unwrap_err();
+        assert!(err.is::<Cancelled>());
+    }
+
+    #[test]
+    fn test_cursor_restored_on_error() {
+        let mut out = vec![];
+        let events = || Err(anyhow::anyhow!("end of input"));
+        assert!(run(&mut out, events, 20, 1, confirm_frame("Ok?")).is_err());
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("\x1b[?25h"));
+
+        let mut out = vec![];
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let frame = |_: &mut _, _: &_| -> Option<()> { panic!("frame failed") };
+            run(&mut out, keys(vec![]), 20, 1, frame)
+        }));
+        assert!(panicked.is_err());
+        assert!(String::from_utf8(out).unwrap().ends_with("\x1b[?25h"));
+    }
+}
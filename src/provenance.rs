@@ -0,0 +1,126 @@
+//! Write provenance, enabled with the `debug` feature.
+//!
+//! While enabled, every rune records the component that last drew it. When
+//! a component finishes rendering, the runes it drew are stamped with its
+//! name before its view is applied to its parent. Runes drawn by the
+//! components it rendered keep their own stamps, so each rune names the
+//! innermost component responsible for it.
+//!
+//! The Frame resource can be queried for the component that drew each
+//! cell of the last frame, and the ProvenanceOverlay plugin colors the
+//! screen by component.
+//!
+//! When the feature is disabled these functions do nothing.
+
+use crate::{runes::Rune, view::View};
+
+#[cfg(feature = "debug")]
+mod names {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static NAMES: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+    }
+
+    /// The index of a component name, adding it if it has not been seen.
+    pub fn intern(name: &'static str) -> u16 {
+        NAMES.with(|n| {
+            let mut names = n.borrow_mut();
+            match names.iter().position(|&n| n == name) {
+                Some(idx) => idx as u16,
+                None => {
+                    names.push(name);
+                    (names.len() - 1) as u16
+                }
+            }
+        })
+    }
+
+    pub fn get(idx: u16) -> Option<&'static str> {
+        NAMES.with(|n| n.borrow().get(idx as usize).copied())
+    }
+}
+
+/// Stamp the runes a component drew, which have not already been stamped
+/// by the components it rendered, with its name.
+#[inline]
+pub(crate) fn stamp<F>(_view: &mut View) {
+    #[cfg(feature = "debug")]
+    {
+        let writer = names::intern(std::any::type_name::<F>());
+        for rune in _view.iter_mut().flatten() {
+            if rune.writer.is_none() && *rune != Rune::default() {
+                rune.writer = Some(writer);
+            }
+        }
+    }
+}
+
+/// The name of the component which drew a rune, if it is known.
+#[inline]
+pub(crate) fn writer(_rune: &Rune) -> Option<&'static str> {
+    #[cfg(feature = "debug")]
+    return _rune.writer.and_then(names::get);
+    #[cfg(not(feature = "debug"))]
+    None
+}
+
+/// The components which drew a view, in the order they first appear
+/// reading from the top left, with the smallest region containing every
+/// rune each drew.
+#[cfg(feature = "debug")]
+pub(crate) fn regions(view: &View) -> Vec<(&'static str, crate::geometry::Rect)> {
+    use crate::geometry::{Pos, Rect};
+
+    let mut regions: Vec<(&'static str, Pos, Pos)> = vec![];
+    for (y, row) in view.iter().enumerate() {
+        for (x, rune) in row.iter().enumerate() {
+            let Some(name) = writer(rune) else {
+                continue;
+            };
+            match regions.iter_mut().find(|(n, _, _)| *n == name) {
+                Some((_, min, max)) => {
+                    min.x = min.x.min(x);
+                    max.x = max.x.max(x);
+                    max.y = y;
+                }
+                None => regions.push((name, Pos::new(x, y), Pos::new(x, y))),
+            }
+        }
+    }
+    regions
+        .into_iter()
+        .map(|(name, min, max)| {
+            let size = (max.x - min.x + 1, max.y - min.y + 1);
+            (name, Rect::new(min, size))
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "debug"))]
+mod tests {
+    use crate::context::ViewContext;
+
+    use super::writer;
+
+    fn header(ctx: &mut ViewContext) {
+        ctx.insert((0, 0), "Header");
+    }
+
+    #[test]
+    fn test_innermost_writer() {
+        let mut ctx = crate::context::tests::context_fixture();
+        ctx.component(((0, 0), (10, 2)), |ctx: &mut ViewContext| {
+            ctx.component(((0, 0), (10, 1)), header);
+            ctx.insert((0, 1), "Body");
+        });
+        assert!(writer(&ctx.view[0][0]).unwrap().ends_with("header"));
+        assert!(writer(&ctx.view[1][0]).unwrap().contains("{{closure}}"));
+        assert_eq!(writer(&ctx.view[5][0]), None);
+
+        let regions = super::regions(&ctx.view);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].1, crate::geometry::Rect::new((0, 0), (6, 1)));
+        assert_eq!(regions[1].1, crate::geometry::Rect::new((0, 1), (4, 1)));
+    }
+}
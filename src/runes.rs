@@ -1,19 +1,60 @@
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
 use crossterm::{
     queue,
     style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
 };
 
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::theme::Theme;
+
 /// Rune repesents the state of the screen at a specific position. It stores
 /// the character content and styling information that will be rendered.
+///
+/// So that runes remain Copy, grapheme clusters, hyperlink URLs and the
+/// names of theme colors are stored in tables shared by the whole process,
+/// and a rune holds an index into them. Each table holds at most 65,536
+/// distinct strings, which are kept for the life of the process. Once a
+/// table is full new strings are dropped, and a warning is logged with the
+/// log feature: a link is not made, a grapheme cluster displays its first character and a theme
+/// color falls back to the plain color.
 #[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub struct Rune {
     pub content: Option<char>,
+    /// The full grapheme cluster when the rune displays more than one
+    /// character, such as a letter followed by a combining accent. The
+    /// content is then the first character of the cluster. See
+    /// Rune::grapheme.
+    pub(crate) cluster: Option<u32>,
     pub fg: Option<Color>,
     pub bg: Option<Color>,
     pub bold: bool,
     pub italic: bool,
     pub underline: bool,
     pub undercurl: bool,
+    pub dim: bool,
+    pub reverse: bool,
+    pub crossed_out: bool,
+    /// True for the cell following a wide character, such as a CJK
+    /// character or an emoji, which the character extends into.
+    /// Continuation runes have no content and are not rendered.
+    pub continuation: bool,
+    pub id: Option<u32>,
+    /// The hyperlink the rune belongs to. See Rune::link.
+    pub link: Option<u32>,
+    /// The names of the theme colors the text and background colors are
+    /// taken from when rendered. See Rune::fg_token.
+    pub(crate) fg_token: Option<u32>,
+    pub(crate) bg_token: Option<u32>,
+    /// The component which last drew the rune. See the provenance module.
+    #[cfg(feature = "debug")]
+    pub(crate) writer: Option<u16>,
 }
 
 impl std::fmt::Debug for Rune {
@@ -27,8 +68,15 @@ impl std::ops::Add<Rune> for Rune {
     type Output = Rune;
 
     fn add(self, mut rhs: Rune) -> Self::Output {
+        if rhs.fg.is_none() && rhs.fg_token.is_none() {
+            rhs.fg_token = self.fg_token;
+        }
+        if rhs.bg.is_none() && rhs.bg_token.is_none() {
+            rhs.bg_token = self.bg_token;
+        }
         rhs.fg = rhs.fg.or(self.fg);
         rhs.bg = rhs.bg.or(self.bg);
+        rhs.id = rhs.id.or(self.id);
         rhs
     }
 }
@@ -60,8 +108,27 @@ impl Rune {
     /// use arkham::prelude::*;
     /// let rune:Rune = Rune::new().bg(Color::Blue).fg(Color::White).bold();
     /// ```
-    pub fn new() -> Self {
-        Self::default()
+    pub const fn new() -> Self {
+        Self {
+            content: None,
+            cluster: None,
+            fg: None,
+            bg: None,
+            bold: false,
+            italic: false,
+            underline: false,
+            undercurl: false,
+            dim: false,
+            reverse: false,
+            crossed_out: false,
+            continuation: false,
+            id: None,
+            link: None,
+            fg_token: None,
+            bg_token: None,
+            #[cfg(feature = "debug")]
+            writer: None,
+        }
     }
 
     /// Set the content of the rune. The rune's content is a single character.
@@ -72,11 +139,56 @@ impl Rune {
     /// let rune = Rune::new().content('A');
     /// assert_eq!(rune.content, Some('A'));
     /// ```
-    pub fn content(mut self, content: char) -> Self {
+    pub const fn content(mut self, content: char) -> Self {
         self.content = Some(content);
+        self.cluster = None;
         self
     }
 
+    /// Set the content of the rune to a grapheme cluster: a sequence of
+    /// characters displayed as one glyph, such as a letter followed by a
+    /// combining accent, an emoji joined with zero width joiners, or a
+    /// flag. The rune's content is set to the first character of the
+    /// cluster.
+    ///
+    /// Strings converted to Runes are split into grapheme clusters, so this
+    /// is only needed when building runes individually.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().grapheme("e\u{301}");
+    /// assert_eq!(rune.content, Some('e'));
+    /// assert_eq!(rune.text().as_deref(), Some("e\u{301}"));
+    /// ```
+    pub fn grapheme(mut self, grapheme: &str) -> Self {
+        let mut chars = grapheme.chars();
+        self.content = chars.next();
+        self.cluster = if chars.next().is_some() {
+            GRAPHEMES.intern(grapheme)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// The text the rune displays: its grapheme cluster, or its content.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// assert_eq!(Rune::new().content('a').text().as_deref(), Some("a"));
+    /// assert_eq!(Rune::new().text(), None);
+    /// ```
+    pub fn text(&self) -> Option<String> {
+        match self.cluster {
+            Some(id) => GRAPHEMES
+                .with(id, str::to_string)
+                .or_else(|| self.content.map(String::from)),
+            None => self.content.map(String::from),
+        }
+    }
+
     /// Set the background color of the rune.
     ///
     /// Example:
@@ -85,8 +197,9 @@ impl Rune {
     /// let rune = Rune::new().bg(Color::Green);
     /// assert_eq!(rune.bg, Some(Color::Green));
     /// ```
-    pub fn bg(mut self, bg: Color) -> Self {
+    pub const fn bg(mut self, bg: Color) -> Self {
         self.bg = Some(bg);
+        self.bg_token = None;
         self
     }
 
@@ -98,8 +211,9 @@ impl Rune {
     /// let rune = Rune::new().fg(Color::Green);
     /// assert_eq!(rune.fg, Some(Color::Green));
     /// ```
-    pub fn fg(mut self, fg: Color) -> Self {
+    pub const fn fg(mut self, fg: Color) -> Self {
         self.fg = Some(fg);
+        self.fg_token = None;
         self
     }
 
@@ -110,7 +224,7 @@ impl Rune {
     /// use arkham::prelude::*;
     /// let rune = Rune::new().bold();
     /// ```
-    pub fn bold(mut self) -> Self {
+    pub const fn bold(mut self) -> Self {
         self.bold = true;
         self
     }
@@ -122,7 +236,7 @@ impl Rune {
     /// use arkham::prelude::*;
     /// let rune = Rune::new().italic();
     /// ```
-    pub fn italic(mut self) -> Self {
+    pub const fn italic(mut self) -> Self {
         self.italic = true;
         self
     }
@@ -134,7 +248,7 @@ impl Rune {
     /// use arkham::prelude::*;
     /// let rune = Rune::new().underline();
     /// ```
-    pub fn underline(mut self) -> Self {
+    pub const fn underline(mut self) -> Self {
         self.underline = true;
         self
     }
@@ -146,52 +260,344 @@ impl Rune {
     /// use arkham::prelude::*;
     /// let rune = Rune::new().undercurl();
     /// ```
-    pub fn undercurl(mut self) -> Self {
+    pub const fn undercurl(mut self) -> Self {
         self.undercurl = true;
         self
     }
 
-    /// Renders a Print command into the terminal's output queue
-    pub(crate) fn render<W>(self, out: &mut W) -> anyhow::Result<()>
+    /// Set the text to a dimmed, faint intensity
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().dim();
+    /// ```
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Swap the text and background colors
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().reverse();
+    /// ```
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Set the text to strikethrough style
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().crossed_out();
+    /// ```
+    pub const fn crossed_out(mut self) -> Self {
+        self.crossed_out = true;
+        self
+    }
+
+    /// The number of terminal columns the rune's content occupies: two for
+    /// wide characters, such as CJK characters and most emoji, zero for the
+    /// continuation cell that follows them, and one otherwise.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// assert_eq!(Rune::new().content('a').width(), 1);
+    /// assert_eq!(Rune::new().content('中').width(), 2);
+    /// let runes = "中a".to_runes();
+    /// assert_eq!(runes.len(), 3);
+    /// assert_eq!(runes[1].width(), 0);
+    /// ```
+    pub fn width(&self) -> usize {
+        if self.continuation {
+            0
+        } else if let Some(id) = self.cluster {
+            GRAPHEMES.with(id, grapheme_width).unwrap_or(1)
+        } else {
+            self.content.map_or(1, char_width)
+        }
+    }
+
+    /// The continuation rune following this rune when it holds a wide
+    /// character. It shares the rune's styling so that both cells are drawn
+    /// alike.
+    pub(crate) fn continued(self) -> Rune {
+        Rune {
+            content: None,
+            cluster: None,
+            continuation: true,
+            ..self
+        }
+    }
+
+    /// Attach an identifier to the rune. Identifiers are not rendered, but
+    /// can be looked up by position after a frame is rendered using the
+    /// Frame resource. This allows mapping a screen position back to the
+    /// item that was drawn there.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().content('A').id(7);
+    /// assert_eq!(rune.id, Some(7));
+    /// ```
+    pub const fn id(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Make the rune part of a hyperlink to a URL. Links are written with
+    /// the OSC 8 escape sequence, so they can be clicked in terminals that
+    /// support it and are displayed as plain text in those that do not.
+    /// They can also be followed from the keyboard with the LinkHints
+    /// plugin. See App::hyperlinks.
+    ///
+    /// URLs containing control characters, such as an escape, are not
+    /// linked, since they would be interpreted by the terminal.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().content('A').link("https://example.com");
+    /// assert_eq!(rune.url().as_deref(), Some("https://example.com"));
+    /// ```
+    pub fn link(mut self, url: &str) -> Self {
+        self.link = intern_link(url);
+        self
+    }
+
+    /// The URL of the hyperlink the rune belongs to.
+    pub fn url(&self) -> Option<String> {
+        LINKS.with(self.link?, str::to_string)
+    }
+
+    /// Take the text color from a named color of the active Theme, such as
+    /// `accent` or a color added with Theme::with_color. The color is looked
+    /// up when the rune is drawn rather than when it is built, so runes that
+    /// are cached between frames follow changes to the theme.
+    ///
+    /// If the theme has no color with the name, the text color set with
+    /// Rune::fg is used instead, if there is one. Setting the text color
+    /// with Rune::fg afterwards replaces the token.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let rune = Rune::new().content('!').fg_token("warning");
+    /// assert_eq!(rune.fg_token_name().as_deref(), Some("warning"));
+    /// ```
+    pub fn fg_token(mut self, name: &str) -> Self {
+        self.fg_token = TOKENS.intern(name);
+        self
+    }
+
+    /// Take the background color from a named color of the active Theme.
+    /// See Rune::fg_token.
+    pub fn bg_token(mut self, name: &str) -> Self {
+        self.bg_token = TOKENS.intern(name);
+        self
+    }
+
+    /// The name of the theme color the text color is taken from.
+    pub fn fg_token_name(&self) -> Option<String> {
+        TOKENS.with(self.fg_token?, str::to_string)
+    }
+
+    /// The name of the theme color the background color is taken from.
+    pub fn bg_token_name(&self) -> Option<String> {
+        TOKENS.with(self.bg_token?, str::to_string)
+    }
+
+    /// Returns true if either of the rune's colors is taken from the theme.
+    pub(crate) fn has_tokens(&self) -> bool {
+        self.fg_token.is_some() || self.bg_token.is_some()
+    }
+
+    /// Replace the rune's color tokens with the colors they name in a
+    /// theme. Tokens the theme has no color for leave the color as it was.
+    pub(crate) fn resolve_tokens(mut self, theme: &Theme) -> Rune {
+        if let Some(color) = self.fg_token_name().and_then(|name| theme.color(&name)) {
+            self.fg = Some(color);
+        }
+        if let Some(color) = self.bg_token_name().and_then(|name| theme.color(&name)) {
+            self.bg = Some(color);
+        }
+        self.fg_token = None;
+        self.bg_token = None;
+        self
+    }
+
+    /// Renders a Print command into the terminal's output queue. Styling
+    /// commands are only queued when they differ from the styling last
+    /// emitted, as tracked by the given RenderState.
+    pub(crate) fn render<W>(self, out: &mut W, state: &mut RenderState) -> anyhow::Result<()>
     where
         W: std::io::Write,
     {
-        if let Some(c) = self.fg {
-            queue!(out, SetForegroundColor(c))?;
-        } else {
-            queue!(out, SetForegroundColor(Color::Red))?;
+        // The wide character before a continuation already covers its cell.
+        if self.continuation {
+            return Ok(());
         }
+        let style = RuneStyle::from(&self);
+        let last = match state.style {
+            Some(last) if last.same_attributes(&style) => Some(last),
+            Some(_) => {
+                queue!(out, SetAttribute(Attribute::Reset))?;
+                None
+            }
+            None => None,
+        };
 
-        if let Some(c) = self.bg {
-            queue!(out, SetBackgroundColor(c))?;
-        } else {
-            queue!(out, SetBackgroundColor(Color::Reset))?;
+        if last.map(|s| s.fg) != Some(style.fg) {
+            queue!(out, SetForegroundColor(style.fg))?;
         }
 
-        if self.bold {
-            queue!(out, SetAttribute(Attribute::Bold))?;
+        if last.map(|s| s.bg) != Some(style.bg) {
+            queue!(out, SetBackgroundColor(style.bg))?;
         }
 
-        if self.italic {
-            queue!(out, SetAttribute(Attribute::Italic))?;
-        }
+        if last.is_none() {
+            if style.bold {
+                queue!(out, SetAttribute(Attribute::Bold))?;
+            }
+
+            if style.italic {
+                queue!(out, SetAttribute(Attribute::Italic))?;
+            }
+
+            if style.underline {
+                queue!(out, SetAttribute(Attribute::Underlined))?;
+            }
 
-        if self.underline {
-            queue!(out, SetAttribute(Attribute::Underlined))?;
+            if style.undercurl {
+                queue!(out, SetAttribute(Attribute::Undercurled))?;
+            }
+
+            if style.dim {
+                queue!(out, SetAttribute(Attribute::Dim))?;
+            }
+
+            if style.reverse {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+
+            if style.crossed_out {
+                queue!(out, SetAttribute(Attribute::CrossedOut))?;
+            }
         }
 
-        if self.undercurl {
-            queue!(out, SetAttribute(Attribute::Undercurled))?;
+        state.style = Some(style);
+
+        if state.hyperlinks && state.link != self.link {
+            if state.link.is_some() {
+                queue!(out, Print("\x1b]8;;\x1b\\"))?;
+            }
+            if let Some(url) = self.url() {
+                queue!(out, Print(format!("\x1b]8;;{}\x1b\\", url)))?;
+            }
+            state.link = self.link;
         }
-        if let Some(content) = self.content {
+
+        let cluster = self
+            .cluster
+            .and_then(|id| GRAPHEMES.with(id, |g| queue!(out, Print(g))));
+        if let Some(result) = cluster {
+            result?;
+        } else if let Some(content) = self.content.filter(|c| !c.is_control()) {
             queue!(out, Print(content))?;
         } else {
+            // Control characters, such as the line breaks and unexpanded
+            // tabs kept in runes, would move the terminal's cursor, so they
+            // are drawn as spaces.
             queue!(out, Print(' '))?;
         }
         Ok(())
     }
 }
 
+/// The resolved styling of a rune as it is written to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RuneStyle {
+    fg: Color,
+    bg: Color,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    undercurl: bool,
+    dim: bool,
+    reverse: bool,
+    crossed_out: bool,
+}
+
+impl RuneStyle {
+    fn same_attributes(&self, other: &RuneStyle) -> bool {
+        self.bold == other.bold
+            && self.italic == other.italic
+            && self.underline == other.underline
+            && self.undercurl == other.undercurl
+            && self.dim == other.dim
+            && self.reverse == other.reverse
+            && self.crossed_out == other.crossed_out
+    }
+}
+
+impl From<&Rune> for RuneStyle {
+    fn from(rune: &Rune) -> Self {
+        Self {
+            fg: rune.fg.unwrap_or(Color::Red),
+            bg: rune.bg.unwrap_or(Color::Reset),
+            bold: rune.bold,
+            italic: rune.italic,
+            underline: rune.underline,
+            undercurl: rune.undercurl,
+            dim: rune.dim,
+            reverse: rune.reverse,
+            crossed_out: rune.crossed_out,
+        }
+    }
+}
+
+/// RenderState tracks the styling that was last written to the terminal
+/// during a flush. It allows runes that share styling with the previously
+/// rendered rune to skip emitting redundant SGR sequences.
+///
+/// A fresh state assumes nothing about the terminal and will emit the full
+/// styling for the first rune rendered.
+///
+/// Hyperlinks are only written when enabled, see RenderState::new. Once
+/// everything has been rendered RenderState::finish must be called to end
+/// any hyperlink left open.
+#[derive(Debug, Default)]
+pub(crate) struct RenderState {
+    style: Option<RuneStyle>,
+    hyperlinks: bool,
+    link: Option<u32>,
+}
+
+impl RenderState {
+    /// A fresh state, writing runes' hyperlinks if enabled.
+    pub(crate) fn new(hyperlinks: bool) -> Self {
+        Self {
+            hyperlinks,
+            ..Self::default()
+        }
+    }
+
+    /// End the hyperlink the last rune rendered belonged to, if any.
+    pub(crate) fn finish<W: std::io::Write>(&mut self, out: &mut W) -> anyhow::Result<()> {
+        if self.link.take().is_some() {
+            queue!(out, Print("\x1b]8;;\x1b\\"))?;
+        }
+        Ok(())
+    }
+}
+
 /// Runes represents a series of runes. This is generally used to convert
 /// strings into Runes and apply styling information to them.
 ///
@@ -201,11 +607,131 @@ impl Rune {
 /// use arkham::prelude::*;
 /// let runes = "This is a test string".to_runes().fg(Color::White);
 /// ```
+///
+/// Labels that never change can be built with `Runes::from_static`, which
+/// caches the converted runes so they are not rebuilt every frame:
+///
+/// ```
+/// use arkham::prelude::*;
+/// let runes = Runes::from_static("Status");
+/// assert_eq!(runes.len(), 6);
+/// ```
 #[derive(Clone, Debug, Default)]
-pub struct Runes(pub(crate) Vec<Rune>);
+pub struct Runes(pub(crate) Cow<'static, [Rune]>);
+
+thread_local! {
+    /// Runes converted from static strings, keyed by the string and the tab
+    /// width it was converted with.
+    static STATIC_RUNES: RefCell<HashMap<(&'static str, usize), &'static [Rune]>> =
+        RefCell::new(HashMap::new());
+    static CACHED_RUNES: RefCell<HashMap<&'static str, &'static [Rune]>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The most runes each of the static and cached runes caches holds on a
+/// thread. Cached runes are leaked so they can be borrowed for 'static, so
+/// once a cache is full further runes are built on each call instead.
+const MAX_CACHED_RUNES: usize = 1024;
+
+static LINKS: Interner = Interner::new("hyperlink");
+
+/// The identifier for a hyperlink's URL. URLs containing control characters
+/// are rejected, as an escape sequence within one would end the OSC 8
+/// sequence it is written in and be run by the terminal.
+fn intern_link(url: &str) -> Option<u32> {
+    if url.chars().any(char::is_control) {
+        return None;
+    }
+    LINKS.intern(url)
+}
+static TOKENS: Interner = Interner::new("theme color");
+static GRAPHEMES: Interner = Interner::new("grapheme cluster");
+
+/// The most strings an Interner holds.
+const MAX_INTERNED: usize = 1 << 16;
+
+/// A table of strings, such as the URLs of hyperlinks, the names of theme
+/// colors and grapheme clusters. Runes store an index into a table so that
+/// they remain Copy.
+///
+/// Tables are shared by every thread, as runes built on one thread may be
+/// drawn on another. Strings are never removed, since any rune may still
+/// refer to them, so each table is limited to MAX_INTERNED strings. Once a
+/// table is full new strings are not interned, and runes fall back to
+/// displaying without them: a link is dropped, a grapheme cluster displays
+/// its first character and a token keeps the plain color. A warning is
+/// logged the first time a string is dropped.
+struct Interner {
+    /// What the table holds, for the warning logged when it is full.
+    #[cfg_attr(not(feature = "log"), allow(dead_code))]
+    kind: &'static str,
+    table: OnceLock<RwLock<Interned>>,
+}
+
+#[derive(Default)]
+struct Interned {
+    ids: HashMap<String, u32>,
+    names: Vec<String>,
+    warned: bool,
+}
+
+impl Interner {
+    const fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            table: OnceLock::new(),
+        }
+    }
+
+    fn table(&self) -> &RwLock<Interned> {
+        self.table.get_or_init(RwLock::default)
+    }
+
+    /// The identifier for a string, registering it if it has not been
+    /// seen. Returns None if the table is full.
+    fn intern(&self, name: &str) -> Option<u32> {
+        let table = self.table();
+        if let Some(id) = table
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .ids
+            .get(name)
+        {
+            return Some(*id);
+        }
+        let mut table = table.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(id) = table.ids.get(name) {
+            return Some(*id);
+        }
+        if table.names.len() >= MAX_INTERNED {
+            if !table.warned {
+                table.warned = true;
+                #[cfg(feature = "log")]
+                log::warn!(
+                    target: "arkham",
+                    "the {} table is full with {} entries, new {}s are dropped",
+                    self.kind,
+                    MAX_INTERNED,
+                    self.kind
+                );
+            }
+            return None;
+        }
+        let id = table.names.len() as u32;
+        table.names.push(name.to_string());
+        table.ids.insert(name.to_string(), id);
+        Some(id)
+    }
+
+    /// Call a function with the string for an identifier, if there is one.
+    fn with<T>(&self, id: u32, f: impl FnOnce(&str) -> T) -> Option<T> {
+        let table = self.table().read().unwrap_or_else(PoisonError::into_inner);
+        table.names.get(id as usize).map(|name| f(name))
+    }
+}
 
 impl std::ops::Deref for Runes {
-    type Target = Vec<Rune>;
+    type Target = [Rune];
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -220,7 +746,260 @@ impl From<Rune> for Runes {
 
 impl<T: ToString> From<T> for Runes {
     fn from(value: T) -> Self {
-        Runes(
+        Runes::new(runes_from_str(&value.to_string()))
+    }
+}
+
+thread_local! {
+    /// The number of columns between tab stops used when converting strings
+    /// to runes. A width of zero disables tab expansion.
+    static TAB_WIDTH: Cell<usize> = const { Cell::new(4) };
+}
+
+/// The number of columns between tab stops. See Runes::set_tab_width.
+pub(crate) fn tab_width() -> usize {
+    TAB_WIDTH.with(Cell::get)
+}
+
+/// Convert a string into unstyled runes, escaping control characters and
+/// applying tab expansion.
+fn runes_from_str(value: &str) -> Vec<Rune> {
+    styled_runes(value, |_| Rune::new())
+}
+
+/// Convert a string into runes in the same way as Runes::from, styling each
+/// grapheme cluster with the rune returned for its byte offset.
+pub(crate) fn styled_runes<F>(value: &str, style: F) -> Vec<Rune>
+where
+    F: Fn(usize) -> Rune,
+{
+    let mut runes = Vec::with_capacity(value.len());
+    for (idx, grapheme) in value.grapheme_indices(true) {
+        let mut chars = grapheme.chars();
+        let c = chars.next().unwrap_or_default();
+        if chars.next().is_some() && !c.is_control() {
+            let rune = style(idx).grapheme(grapheme);
+            runes.push(rune);
+            if rune.width() == 2 {
+                runes.push(rune.continued());
+            }
+            continue;
+        }
+        // Single characters, and clusters such as CRLF which begin with a
+        // control character, are converted one character at a time.
+        for (offset, c) in grapheme.char_indices() {
+            push_char(&mut runes, style(idx + offset), c);
+        }
+    }
+    expand_tabs(runes, tab_width())
+}
+
+/// Convert a single character into runes, escaping control characters.
+/// Line breaks and tabs are kept so that lines can be split and tabs
+/// expanded, and are drawn as spaces.
+fn push_char(runes: &mut Vec<Rune>, rune: Rune, c: char) {
+    match c {
+        '\n' | '\t' => runes.push(rune.content(c)),
+        '\x00'..='\x1f' => {
+            runes.push(rune.content('^'));
+            runes.push(rune.content((c as u8 + b'@') as char));
+        }
+        '\x7f' => {
+            runes.push(rune.content('^'));
+            runes.push(rune.content('?'));
+        }
+        '\u{80}'..='\u{9f}' => runes.push(rune.content(char::REPLACEMENT_CHARACTER)),
+        _ => {
+            runes.push(rune.content(c));
+            if char_width(c) == 2 {
+                runes.push(rune.content(c).continued());
+            }
+        }
+    }
+}
+
+/// The number of columns a character occupies in the terminal. Characters
+/// without a width of their own, such as combining marks, are given a cell
+/// so that they remain visible.
+pub(crate) fn char_width(c: char) -> usize {
+    match unicode_width::UnicodeWidthChar::width(c) {
+        Some(2) => 2,
+        _ => 1,
+    }
+}
+
+/// The number of columns a grapheme cluster occupies in the terminal.
+/// Clusters are displayed as a single glyph, so they occupy one or two
+/// columns. Emoji presentation selectors make the cluster wide.
+pub(crate) fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.contains('\u{fe0f}') {
+        return 2;
+    }
+    unicode_width::UnicodeWidthStr::width(grapheme).clamp(1, 2)
+}
+
+/// Replace tab runes with enough spaces to reach the next tab stop. The
+/// spaces keep the styling of the tab they replace. Columns are counted from
+/// the start of the runes and reset after each newline.
+fn expand_tabs(runes: Vec<Rune>, width: usize) -> Vec<Rune> {
+    if width == 0 || !runes.iter().any(|r| r.content == Some('\t')) {
+        return runes;
+    }
+    let mut expanded = Vec::with_capacity(runes.len());
+    let mut col = 0;
+    for rune in runes {
+        match rune.content {
+            Some('\t') => {
+                let n = width - col % width;
+                expanded.extend(std::iter::repeat_n(rune.content(' '), n));
+                col += n;
+            }
+            Some('\n') => {
+                expanded.push(rune);
+                col = 0;
+            }
+            _ => {
+                expanded.push(rune);
+                col += 1;
+            }
+        }
+    }
+    expanded
+}
+
+impl Runes {
+    /// Create a new runes collection from a vector of Rune.
+    pub fn new(runes: Vec<Rune>) -> Self {
+        Self(Cow::Owned(runes))
+    }
+
+    /// The number of terminal columns the runes occupy. Wide characters are
+    /// followed by a continuation rune, and grapheme clusters are held in a
+    /// single rune, so this is the number of runes when they are built from
+    /// a string. It should be used rather than the length of the string
+    /// they were built from when laying out text.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "cafe\u{301} 中".to_runes();
+    /// assert_eq!(runes.width(), 7);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.iter().map(Rune::width).sum()
+    }
+
+    /// Create a runes collection from text containing ANSI escape
+    /// sequences, such as the output of a command run with `--color`. The
+    /// colors and attributes set by SGR sequences and hyperlinks set by OSC 8
+    /// sequences are applied to the runes, and other sequences are dropped.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = Runes::from_ansi("\x1b[1;32m+added\x1b[0m");
+    /// assert_eq!(runes.len(), 6);
+    /// assert_eq!(runes[0].fg, Some(Color::DarkGreen));
+    /// assert!(runes[0].bold);
+    /// ```
+    pub fn from_ansi(value: &str) -> Self {
+        Self::new(crate::ansi::parse(value))
+    }
+
+    /// Create a runes collection that borrows a static slice of runes. No
+    /// allocation takes place unless the runes are later modified.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// const DOTS: &[Rune] = &[Rune::new().content('.'), Rune::new().content('.')];
+    /// let runes = Runes::from_slice(DOTS);
+    /// assert_eq!(runes.len(), 2);
+    /// ```
+    pub const fn from_slice(runes: &'static [Rune]) -> Self {
+        Self(Cow::Borrowed(runes))
+    }
+
+    /// Create a runes collection from a static string. The conversion is
+    /// performed once per string and tab width and cached, subsequent calls
+    /// borrow the cached runes without allocating.
+    ///
+    /// Up to 1,024 strings are cached on each thread. Strings beyond that
+    /// are converted on every call.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = Runes::from_static("Help");
+    /// assert_eq!(runes[0].content, Some('H'));
+    /// ```
+    pub fn from_static(value: &'static str) -> Self {
+        STATIC_RUNES.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let key = (value, tab_width());
+            if let Some(runes) = cache.get(&key) {
+                return Self::from_slice(runes);
+            }
+            let runes = runes_from_str(value);
+            if cache.len() >= MAX_CACHED_RUNES {
+                return Self::new(runes);
+            }
+            let runes = Vec::leak(runes);
+            cache.insert(key, runes);
+            Self::from_slice(runes)
+        })
+    }
+
+    /// Build a styled runes collection once and cache it under a static key.
+    /// The builder function is only called the first time a given key is
+    /// used; subsequent calls borrow the cached runes without allocating.
+    ///
+    /// This is intended for static labels that carry styling, such as status
+    /// bar text. The key should uniquely identify the label, as the cached
+    /// value is never rebuilt. As with Runes::from_static, up to 1,024 keys
+    /// are cached on each thread.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = Runes::cached("title", || "Title".to_runes().bold());
+    /// assert!(runes.iter().all(|r| r.bold));
+    /// ```
+    pub fn cached<F>(key: &'static str, f: F) -> Self
+    where
+        F: FnOnce() -> Runes,
+    {
+        let cached = CACHED_RUNES.with(|cache| cache.borrow().get(key).copied());
+        if let Some(runes) = cached {
+            return Self::from_slice(runes);
+        }
+        let runes = f();
+        if CACHED_RUNES.with(|cache| cache.borrow().len()) >= MAX_CACHED_RUNES {
+            return runes;
+        }
+        let runes: &'static [Rune] = match runes.0 {
+            Cow::Borrowed(runes) => runes,
+            Cow::Owned(runes) => Vec::leak(runes),
+        };
+        CACHED_RUNES.with(|cache| cache.borrow_mut().insert(key, runes));
+        Self::from_slice(runes)
+    }
+
+    /// Create runes from a string without escaping control characters or
+    /// expanding tabs. Every character is mapped directly to a rune.
+    ///
+    /// Strings are normally sanitized when converted to runes, so that
+    /// untrusted text can not write escape sequences to the terminal. Only
+    /// use this for text that is known to be safe.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// assert_eq!("\x07".to_runes().len(), 2);
+    /// assert_eq!(Runes::raw("\x07").len(), 1);
+    /// ```
+    pub fn raw<T: ToString>(value: T) -> Self {
+        Runes::new(
             value
                 .to_string()
                 .chars()
@@ -228,13 +1007,33 @@ impl<T: ToString> From<T> for Runes {
                 .collect(),
         )
     }
-}
 
-impl Runes {
-    /// Create a new runes collection from a vector of Rune.
-    pub fn new(runes: Vec<Rune>) -> Self {
-        Self(runes)
+    /// Set the number of columns between tab stops used when strings are
+    /// converted to runes. Tabs are expanded into spaces, aligned to the next
+    /// tab stop. The default width is 4, and a width of zero disables tab
+    /// expansion.
+    ///
+    /// The setting applies to conversions made on the thread it is set on,
+    /// which for App::tab_width is the thread the application runs on, so
+    /// applications and tests running on other threads are not affected.
+    /// Strings converted on other threads use their own setting.
+    pub fn set_tab_width(width: usize) {
+        TAB_WIDTH.with(|tab_width| tab_width.set(width));
+    }
+
+    /// Expand tab characters into spaces using a specific tab width,
+    /// regardless of the global setting.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = Runes::new(vec![Rune::new().content('a'), Rune::new().content('\t')]);
+    /// assert_eq!(runes.expand_tabs(8).len(), 8);
+    /// ```
+    pub fn expand_tabs(self, width: usize) -> Self {
+        Runes::new(expand_tabs(self.0.into_owned(), width))
     }
+
     /// Set the text color of the rune.
     ///
     /// Example:
@@ -244,8 +1043,9 @@ impl Runes {
     /// assert!(runes.iter().all(|r| r.fg == Some(Color::Blue)))
     /// ```
     pub fn fg(mut self, color: Color) -> Self {
-        for r in self.0.iter_mut() {
+        for r in self.0.to_mut().iter_mut() {
             r.fg = Some(color);
+            r.fg_token = None;
         }
         self
     }
@@ -259,8 +1059,9 @@ impl Runes {
     /// let runes = runes.clear_fg();
     /// assert!(runes.iter().all(|r| r.fg == None))
     pub fn clear_fg(mut self) -> Self {
-        for r in self.0.iter_mut() {
+        for r in self.0.to_mut().iter_mut() {
             r.fg = None;
+            r.fg_token = None;
         }
         self
     }
@@ -274,8 +1075,9 @@ impl Runes {
     /// assert!(runes.iter().all(|r| r.bg == Some(Color::Blue)))
     /// ```
     pub fn bg(mut self, color: Color) -> Self {
-        for r in self.0.iter_mut() {
+        for r in self.0.to_mut().iter_mut() {
             r.bg = Some(color);
+            r.bg_token = None;
         }
         self
     }
@@ -289,19 +1091,242 @@ impl Runes {
     /// let runes = runes.clear_bg();
     /// assert!(runes.iter().all(|r| r.bg == None))
     pub fn clear_bg(mut self) -> Self {
-        for r in self.0.iter_mut() {
+        for r in self.0.to_mut().iter_mut() {
             r.bg = None;
+            r.bg_token = None;
+        }
+        self
+    }
+
+    /// Set the text color of the runes to a gradient, blending from one
+    /// color at the first character to another at the last. Colors without a
+    /// fixed RGB value, such as Color::Reset, cannot be blended and switch
+    /// over halfway.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "abc".to_runes().gradient(Color::Black, Color::White);
+    /// assert_eq!(runes[0].fg, Some(Color::Rgb { r: 0, g: 0, b: 0 }));
+    /// assert_eq!(runes[2].fg, Some(Color::Rgb { r: 255, g: 255, b: 255 }));
+    /// ```
+    pub fn gradient(self, from: Color, to: Color) -> Self {
+        self.blend(
+            |t| crate::color::lerp(from, to, t),
+            |r, color| {
+                r.fg = Some(color);
+                r.fg_token = None;
+            },
+        )
+    }
+
+    /// Set the background color of the runes to a gradient. See
+    /// Runes::gradient.
+    pub fn bg_gradient(self, from: Color, to: Color) -> Self {
+        self.blend(
+            |t| crate::color::lerp(from, to, t),
+            |r, color| {
+                r.bg = Some(color);
+                r.bg_token = None;
+            },
+        )
+    }
+
+    /// Set the text color of the runes to a rainbow, sweeping through the
+    /// hues from red to violet.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "Arkham".to_runes().rainbow();
+    /// assert_eq!(runes[0].fg, Some(Color::Rgb { r: 255, g: 0, b: 0 }));
+    /// ```
+    pub fn rainbow(self) -> Self {
+        self.blend(
+            |t| crate::color::hue(t * 300.0),
+            |r, color| {
+                r.fg = Some(color);
+                r.fg_token = None;
+            },
+        )
+    }
+
+    /// Apply a color to each rune, picked by its position between 0.0 at the
+    /// first character and 1.0 at the last. Continuation runes share the
+    /// color of the wide character before them.
+    fn blend<C, S>(mut self, color: C, mut set: S) -> Self
+    where
+        C: Fn(f32) -> Color,
+        S: FnMut(&mut Rune, Color),
+    {
+        let count = self.iter().filter(|r| !r.continuation).count();
+        let step = 1.0 / count.saturating_sub(1).max(1) as f32;
+        let mut idx = 0;
+        let mut last = color(0.0);
+        for r in self.0.to_mut().iter_mut() {
+            if !r.continuation {
+                last = color(idx as f32 * step);
+                idx += 1;
+            }
+            set(r, last);
         }
         self
     }
 
     pub fn bold(mut self) -> Self {
-        for r in self.0.iter_mut() {
+        for r in self.0.to_mut().iter_mut() {
             r.bold = true;
         }
         self
     }
 
+    /// Set all runes to italic styling. See Rune::italic.
+    pub fn italic(mut self) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.italic = true;
+        }
+        self
+    }
+
+    /// Set all runes to underline style. See Rune::underline.
+    pub fn underline(mut self) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.underline = true;
+        }
+        self
+    }
+
+    /// Set all runes to undercurl style. See Rune::undercurl.
+    pub fn undercurl(mut self) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.undercurl = true;
+        }
+        self
+    }
+
+    /// Set all runes to a dimmed intensity. See Rune::dim.
+    pub fn dim(mut self) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.dim = true;
+        }
+        self
+    }
+
+    /// Swap the text and background colors of all runes. See Rune::reverse.
+    pub fn reverse(mut self) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.reverse = true;
+        }
+        self
+    }
+
+    /// Set all runes to strikethrough style. See Rune::crossed_out.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "done".to_runes().crossed_out().dim();
+    /// assert!(runes.iter().all(|r| r.crossed_out && r.dim));
+    /// ```
+    pub fn crossed_out(mut self) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.crossed_out = true;
+        }
+        self
+    }
+
+    /// Attach an identifier to all runes. See Rune::id.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "item".to_runes().id(3);
+    /// assert!(runes.iter().all(|r| r.id == Some(3)))
+    /// ```
+    pub fn id(mut self, id: u32) -> Self {
+        for r in self.0.to_mut().iter_mut() {
+            r.id = Some(id);
+        }
+        self
+    }
+
+    /// Take the text color of all runes from a named color of the active
+    /// Theme. See Rune::fg_token.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "Saved".to_runes().fg_token("success");
+    /// assert!(runes.iter().all(|r| r.fg_token_name().as_deref() == Some("success")))
+    /// ```
+    pub fn fg_token(mut self, name: &str) -> Self {
+        let rune = Rune::new().fg_token(name);
+        for r in self.0.to_mut().iter_mut() {
+            r.fg_token = rune.fg_token;
+        }
+        self
+    }
+
+    /// Take the background color of all runes from a named color of the
+    /// active Theme. See Rune::fg_token.
+    pub fn bg_token(mut self, name: &str) -> Self {
+        let rune = Rune::new().bg_token(name);
+        for r in self.0.to_mut().iter_mut() {
+            r.bg_token = rune.bg_token;
+        }
+        self
+    }
+
+    /// Make all runes a hyperlink to a URL. See Rune::link.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "docs".to_runes().link("https://docs.rs");
+    /// assert!(runes.iter().all(|r| r.url().as_deref() == Some("https://docs.rs")))
+    /// ```
+    pub fn link(mut self, url: &str) -> Self {
+        let id = intern_link(url);
+        for r in self.0.to_mut().iter_mut() {
+            r.link = id;
+        }
+        self
+    }
+
+    /// Make the URLs and file paths within the runes hyperlinks, so that
+    /// they can be followed with the LinkHints plugin. Runes that are
+    /// already part of a link are left as they are.
+    ///
+    /// File paths, optionally followed by a line number as in
+    /// `src/main.rs:12`, link to a `file://` URL of the absolute path. See
+    /// LinkHints::file_location.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let runes = "see https://docs.rs".to_runes().detect_links();
+    /// assert_eq!(runes[0].link, None);
+    /// assert_eq!(runes[4].url().as_deref(), Some("https://docs.rs"));
+    /// ```
+    pub fn detect_links(mut self) -> Self {
+        let text: Vec<char> = self.iter().map(|r| r.content.unwrap_or(' ')).collect();
+        let links = crate::links::detect(&text);
+        if links.is_empty() {
+            return self;
+        }
+        let runes = self.0.to_mut();
+        for (range, url) in links {
+            if runes[range.clone()].iter().any(|r| r.link.is_some()) {
+                continue;
+            }
+            let id = intern_link(&url);
+            for rune in runes[range].iter_mut() {
+                rune.link = id;
+            }
+        }
+        self
+    }
+
     /// Append runes or a string displayable object to the Runes
     ///
     /// Example:
@@ -314,10 +1339,102 @@ impl Runes {
     where
         R: Into<Runes>,
     {
-        self.0.append(&mut runes.into().0);
+        self.0.to_mut().extend_from_slice(&runes.into());
     }
 }
 
+/// RuneSlice is a borrowed or owned view of a series of runes. It is
+/// accepted by functions which only need to read runes, such as
+/// View::insert, so that existing Runes can be rendered by reference
+/// without being cloned each frame.
+///
+/// Anything that can be converted into Runes can also be converted into an
+/// owned RuneSlice.
+///
+/// ```
+/// use arkham::{internal::View, prelude::*};
+/// let label = "Shared label".to_runes().fg(Color::Blue);
+/// let mut view = View::new((20, 2));
+/// view.insert((0, 0), &label);
+/// view.insert((0, 1), &label);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RuneSlice<'a>(Cow<'a, [Rune]>);
+
+impl<'a> RuneSlice<'a> {
+    /// Create a RuneSlice that borrows an existing slice of runes.
+    pub fn new(runes: &'a [Rune]) -> Self {
+        Self(Cow::Borrowed(runes))
+    }
+
+    /// Convert the slice into an owned Runes collection. Borrowed runes
+    /// are cloned.
+    pub fn into_runes(self) -> Runes {
+        match self.0 {
+            Cow::Borrowed(runes) => Runes::new(runes.to_vec()),
+            Cow::Owned(runes) => Runes::new(runes),
+        }
+    }
+}
+
+impl std::ops::Deref for RuneSlice<'_> {
+    type Target = [Rune];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> From<&'a Runes> for RuneSlice<'a> {
+    fn from(value: &'a Runes) -> Self {
+        RuneSlice::new(&value.0)
+    }
+}
+
+impl<T: Into<Runes>> From<T> for RuneSlice<'static> {
+    fn from(value: T) -> Self {
+        RuneSlice(value.into().0)
+    }
+}
+
+/// Split runes into lines no wider than `width`. Lines are broken at
+/// newlines and, where possible, at the last space that fits on the line.
+/// Words longer than the width are broken mid-word. The space a line is
+/// broken at is not included in either line.
+pub(crate) fn wrap(runes: &[Rune], width: usize) -> Vec<&[Rune]> {
+    let mut lines = vec![];
+    if width == 0 {
+        return lines;
+    }
+    for paragraph in runes.split(|r| r.content == Some('\n')) {
+        let mut rest = paragraph;
+        while rest.len() > width {
+            let split = rest[..=width]
+                .iter()
+                .rposition(|r| r.content == Some(' '))
+                .filter(|idx| *idx > 0);
+            match split {
+                Some(idx) => {
+                    lines.push(&rest[..idx]);
+                    rest = &rest[idx + 1..];
+                }
+                None => {
+                    // Keep wide characters together with their continuation.
+                    let split = if width > 1 && rest[width].continuation {
+                        width - 1
+                    } else {
+                        width
+                    };
+                    lines.push(&rest[..split]);
+                    rest = &rest[split..];
+                }
+            }
+        }
+        lines.push(rest);
+    }
+    lines
+}
+
 pub trait ToRuneExt {
     fn to_runes(&self) -> Runes;
 }
@@ -327,3 +1444,322 @@ impl<T: ToString> ToRuneExt for T {
         Runes::from(self.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use std::borrow::Cow;
+
+    use super::{
+        expand_tabs, wrap, Interner, RenderState, Rune, Runes, ToRuneExt, MAX_CACHED_RUNES,
+        MAX_INTERNED,
+    };
+
+    fn render_all(runes: &[Rune]) -> String {
+        let mut out = Vec::new();
+        let mut state = RenderState::default();
+        for rune in runes {
+            rune.render(&mut out, &mut state).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_render_skips_unchanged_style() {
+        let rune = Rune::new().content('a').fg(Color::Blue).bg(Color::Black);
+        let single = render_all(&[rune]);
+        let double = render_all(&[rune, rune]);
+        assert_eq!(double, format!("{}a", single));
+    }
+
+    #[test]
+    fn test_render_emits_changed_color() {
+        let a = Rune::new().content('a').fg(Color::Blue);
+        let b = Rune::new().content('b').fg(Color::Green);
+        let out = render_all(&[a, b]);
+        assert!(out.ends_with("a\x1b[38;5;10mb"));
+    }
+
+    #[test]
+    fn test_render_resets_removed_attributes() {
+        let a = Rune::new().content('a').bold();
+        let b = Rune::new().content('b');
+        let out = render_all(&[a, b]);
+        assert!(out.contains("a\x1b[0m"));
+    }
+
+    #[test]
+    fn test_render_extended_attributes() {
+        let a = Rune::new().content('a').dim().reverse().crossed_out();
+        let b = Rune::new().content('b').dim().reverse().crossed_out();
+        let out = render_all(&[a, b]);
+        assert!(out.contains("\x1b[2m"));
+        assert!(out.contains("\x1b[7m"));
+        assert!(out.ends_with("\x1b[9mab"));
+    }
+
+    #[test]
+    fn test_render_hyperlinks() {
+        let a = Rune::new().content('a').link("https://a.io");
+        let b = Rune::new().content('b');
+        let mut out = Vec::new();
+        let mut state = RenderState::new(true);
+        for rune in [a, a, b] {
+            rune.render(&mut out, &mut state).unwrap();
+        }
+        a.render(&mut out, &mut state).unwrap();
+        state.finish(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b]8;;https://a.io\x1b\\aa\x1b]8;;\x1b\\b"));
+        assert!(out.ends_with("a\x1b]8;;\x1b\\"));
+
+        // Without hyperlinks only the text is written.
+        assert!(!render_all(&[a]).contains("\x1b]8"));
+    }
+
+    #[test]
+    fn test_link_with_control_characters() {
+        for url in [
+            "https://a.io/\x1b[2J",
+            "https://a.io/\x07",
+            "https://a.io/\u{9c}",
+        ] {
+            let rune = Rune::new().content('a').link(url);
+            assert_eq!(rune.url(), None);
+            let runes = "a".to_runes().link(url);
+            assert_eq!(runes[0].link, None);
+            let mut out = Vec::new();
+            let mut state = RenderState::new(true);
+            rune.render(&mut out, &mut state).unwrap();
+            state.finish(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+            assert!(!out.contains("\x1b]8") && !out.contains("a.io"));
+        }
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let runes = "a中🙂".to_runes();
+        assert_eq!(runes.len(), 5);
+        assert!(runes[2].continuation && runes[4].continuation);
+        assert_eq!(runes[2].content, None);
+        assert!(render_all(&runes).ends_with("a中🙂"));
+
+        let runes = "ab中文".to_runes();
+        let wrapped = wrap(&runes, 3);
+        let widths: Vec<usize> = wrapped.iter().map(|line| line.len()).collect();
+        assert_eq!(widths, vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn test_from_static_is_cached() {
+        let a = Runes::from_static("cached label");
+        let b = Runes::from_static("cached label");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a[0].content, Some('c'));
+    }
+
+    #[test]
+    fn test_cached_builds_once() {
+        let a = Runes::cached("test.cached", || "one".to_runes().bold());
+        let b = Runes::cached("test.cached", || "two".to_runes());
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(b[0].content, Some('o'));
+        assert!(b.iter().all(|r| r.bold));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let runes = "the quick brown fox\njumps".to_runes();
+        let lines: Vec<String> = wrap(&runes, 10)
+            .iter()
+            .map(|line| line.iter().filter_map(|r| r.content).collect())
+            .collect();
+        assert_eq!(lines, vec!["the quick", "brown fox", "jumps"]);
+    }
+
+    #[test]
+    fn test_wrap_long_word() {
+        let runes = "abcdefgh ij".to_runes();
+        let lines: Vec<String> = wrap(&runes, 3)
+            .iter()
+            .map(|line| line.iter().filter_map(|r| r.content).collect())
+            .collect();
+        assert_eq!(lines, vec!["abc", "def", "gh", "ij"]);
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        let runes = "a\tbc\td\n\te".chars().map(|c| Rune::new().content(c));
+        let text: String = expand_tabs(runes.collect(), 4)
+            .iter()
+            .filter_map(|r| r.content)
+            .collect();
+        assert_eq!(text, "a   bc  d\n    e");
+    }
+
+    #[test]
+    fn test_runes_caches_bounded() {
+        for idx in 0..MAX_CACHED_RUNES {
+            let key: &'static str = Box::leak(idx.to_string().into_boxed_str());
+            assert!(matches!(Runes::from_static(key).0, Cow::Borrowed(_)));
+            assert!(matches!(
+                Runes::cached(key, || key.into()).0,
+                Cow::Borrowed(_)
+            ));
+        }
+        assert!(matches!(Runes::from_static("full").0, Cow::Owned(_)));
+        assert!(matches!(
+            Runes::cached("full", || "full".into()).0,
+            Cow::Owned(_)
+        ));
+        assert!(matches!(Runes::from_static("0").0, Cow::Borrowed(_)));
+        assert_eq!(
+            Runes::cached("full", || "again".into())[0].content,
+            Some('a')
+        );
+    }
+
+    #[test]
+    fn test_tab_width_per_thread() {
+        let text = |runes: &Runes| -> String { runes.iter().filter_map(|r| r.content).collect() };
+        assert_eq!(text(&Runes::from_static("a\tb")), "a   b");
+        Runes::set_tab_width(2);
+        assert_eq!(text(&Runes::from_static("a\tb")), "a b");
+        let other = std::thread::spawn(|| "a\tb".to_runes()).join().unwrap();
+        assert_eq!(text(&other), "a   b");
+        Runes::set_tab_width(4);
+        assert_eq!(text(&Runes::from_static("a\tb")), "a   b");
+    }
+
+    #[test]
+    fn test_control_characters_escaped() {
+        let text: String = "a\x1b[31mb\x7f\u{9b}"
+            .to_runes()
+            .iter()
+            .filter_map(|r| r.content)
+            .collect();
+        assert_eq!(text, "a^[[31mb^?\u{fffd}");
+    }
+
+    #[test]
+    fn test_line_breaks_and_tabs_drawn_as_spaces() {
+        Runes::set_tab_width(0);
+        let runes = "a\nb\tc".to_runes();
+        Runes::set_tab_width(4);
+        assert_eq!(runes[1].content, Some('\n'));
+        assert_eq!(runes[3].content, Some('\t'));
+        let out = render_all(&runes);
+        assert!(out.ends_with("a b c"));
+        assert!(!out.contains(['\n', '\t']));
+        assert!(render_all(&[Rune::new().content('\x1b')]).ends_with(' '));
+    }
+    #[test]
+    fn test_grapheme_clusters() {
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let flag = "\u{1f1f3}\u{1f1f4}";
+        let runes = format!("e\u{301}{}{}\r\n", family, flag).to_runes();
+        let text: Vec<String> = runes.iter().map(|r| r.text().unwrap_or_default()).collect();
+        assert_eq!(text, vec!["e\u{301}", family, "", flag, "", "^", "M", "\n"]);
+        assert_eq!(runes.width(), 8);
+        assert_eq!(runes[0].width(), 1);
+        assert!(runes[2].continuation);
+        assert!(render_all(&runes).contains(family));
+
+        // Setting the content replaces the cluster.
+        assert_eq!(runes[0].content('x').text().as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_interner_limit() {
+        let table = Interner::new("test");
+        for idx in 0..MAX_INTERNED {
+            assert_eq!(table.intern(&idx.to_string()), Some(idx as u32));
+        }
+        assert_eq!(table.intern("full"), None);
+        assert_eq!(table.intern("7"), Some(7));
+        assert_eq!(table.with(7, str::to_string).as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn test_runes_from_other_threads() {
+        let runes = std::thread::spawn(|| {
+            "e\u{301}"
+                .to_runes()
+                .link("https://example.com/thread")
+                .fg_token("accent")
+        })
+        .join()
+        .unwrap();
+        assert!(render_all(&runes).contains("e\u{301}"));
+        assert_eq!(
+            runes[0].url().as_deref(),
+            Some("https://example.com/thread")
+        );
+        assert_eq!(runes[0].fg_token_name().as_deref(), Some("accent"));
+
+        // Unknown identifiers fall back to the rune's content.
+        let rune = Rune {
+            cluster: Some(u32::MAX),
+            ..Rune::new().content('x')
+        };
+        assert_eq!(rune.text().as_deref(), Some("x"));
+        assert!(render_all(&[rune]).contains('x'));
+    }
+
+    #[test]
+    fn test_gradient() {
+        let runes = "a中b".to_runes().bg_gradient(Color::Black, Color::White);
+        let grey = Color::Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        assert_eq!(runes[1].bg, Some(grey));
+        assert_eq!(runes[2].bg, Some(grey));
+        assert_eq!(
+            runes[3].bg,
+            Some(Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            })
+        );
+
+        // A single character takes the first color.
+        let runes = "a"
+            .to_runes()
+            .fg_token("accent")
+            .gradient(Color::Reset, Color::Blue);
+        assert_eq!(runes[0].fg, Some(Color::Reset));
+        assert!(!runes[0].has_tokens());
+    }
+
+    #[test]
+    fn test_color_tokens() {
+        use crate::theme::Theme;
+
+        let theme = Theme::dark().with_color("brand", Color::Magenta);
+        let rune = Rune::new()
+            .fg(Color::Red)
+            .fg_token("brand")
+            .bg_token("missing");
+        let resolved = rune.bg(Color::Blue).resolve_tokens(&theme);
+        assert_eq!(resolved.fg, Some(Color::Magenta));
+        assert_eq!(resolved.bg, Some(Color::Blue));
+        assert!(!resolved.has_tokens());
+
+        // Unknown tokens fall back to the plain color.
+        let resolved = Rune::new().fg(Color::Red).fg_token("missing");
+        assert_eq!(resolved.resolve_tokens(&theme).fg, Some(Color::Red));
+
+        // A token on the rune below is only inherited without a color.
+        let below = Rune::new().fg_token("brand");
+        assert_eq!(
+            (below + Rune::new()).fg_token_name().as_deref(),
+            Some("brand")
+        );
+        assert_eq!((below + Rune::new().fg(Color::Red)).fg_token, None);
+    }
+}
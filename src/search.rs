@@ -0,0 +1,427 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    components::TextInput,
+    container::Callable,
+    context::ViewContext,
+    geometry::Rect,
+    input::Keyboard,
+    runes::{Rune, Runes},
+    theme::Theme,
+};
+
+/// A match found by SearchState::update, within one of the items searched.
+/// The range is measured in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub item: usize,
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Default)]
+struct SearchInner {
+    editing: bool,
+    matches: Vec<SearchMatch>,
+    current: usize,
+    /// Incremented each time the query changes.
+    revision: u64,
+}
+
+/// SearchState is a shared resource holding a search query and the matches
+/// found for it, so that searching behaves the same in every component that
+/// supports it. Pager, List, and Table jump between matches and highlight
+/// them, and the log view filters its messages by the query.
+///
+/// SearchState::handle_keys provides the key bindings: `/` starts editing
+/// the query, Enter finishes editing, and Esc clears the search. Once a
+/// query is entered, `n` and `N` move to the next and previous match.
+///
+/// Queries match case insensitively unless they contain an uppercase
+/// character. The matches belong to the component that last searched with
+/// the query, so a view should attach the state to one component at a time.
+///
+/// ```no_run
+/// use arkham::{components::Pager, prelude::*};
+///
+/// fn main() {
+///     let search = SearchState::new();
+///     let pager = Pager::new(std::fs::read_to_string("server.log").unwrap()).search(&search);
+///     App::new(root)
+///         .insert_resource(search)
+///         .insert_resource(pager)
+///         .run()
+///         .unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, pager: Res<Pager>, search: Res<SearchState>, kb: Res<Keyboard>) {
+///     let size = ctx.size();
+///     pager.handle_keys(&kb);
+///     ctx.component(((0, 0), (size.width, size.height - 1)), pager.get().clone());
+///     ctx.component(((0, size.height - 1), (size.width, 1)), search.get().clone());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchState {
+    state: Rc<RefCell<SearchInner>>,
+    input: TextInput,
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        let input = TextInput::new();
+        input.set_focused(false);
+        Self {
+            state: Rc::default(),
+            input,
+        }
+    }
+}
+
+impl SearchState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current query.
+    pub fn query(&self) -> String {
+        self.input.value()
+    }
+
+    /// Replace the query, discarding the matches found for the previous
+    /// one.
+    pub fn set_query<S: AsRef<str>>(&self, query: S) {
+        self.input.set_value(query);
+        self.changed();
+    }
+
+    /// Clear the query and stop editing it.
+    pub fn clear(&self) {
+        self.set_query("");
+        self.set_editing(false);
+    }
+
+    /// Returns true if there is a query to search for.
+    pub fn is_active(&self) -> bool {
+        !self.query().is_empty()
+    }
+
+    /// Returns true if the query is being edited.
+    pub fn is_editing(&self) -> bool {
+        self.state.borrow().editing
+    }
+
+    /// A number that changes each time the query changes. Components
+    /// compare it with the revision they last searched with to know when
+    /// to search again.
+    pub fn revision(&self) -> u64 {
+        self.state.borrow().revision
+    }
+
+    fn set_editing(&self, editing: bool) {
+        self.state.borrow_mut().editing = editing;
+        self.input.set_focused(editing);
+    }
+
+    fn changed(&self) {
+        let mut state = self.state.borrow_mut();
+        state.revision += 1;
+        state.matches.clear();
+        state.current = 0;
+    }
+
+    /// The character ranges where the query occurs in some text. Matches do
+    /// not overlap.
+    pub fn find(&self, text: &str) -> Vec<Range<usize>> {
+        find(&self.query(), text)
+    }
+
+    /// Returns true if the text contains the query. Every text matches an
+    /// empty query, so this can be used to filter items.
+    pub fn is_match(&self, text: &str) -> bool {
+        let query = self.query();
+        query.is_empty() || !find(&query, text).is_empty()
+    }
+
+    /// Search a list of items, such as the lines of a document, replacing
+    /// the matches. The first match becomes the current match.
+    pub fn update<I, S>(&self, items: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let query = self.query();
+        let matches = if query.is_empty() {
+            vec![]
+        } else {
+            items
+                .into_iter()
+                .enumerate()
+                .flat_map(|(item, text)| {
+                    find(&query, text.as_ref())
+                        .into_iter()
+                        .map(move |range| SearchMatch { item, range })
+                })
+                .collect()
+        };
+        let mut state = self.state.borrow_mut();
+        state.matches = matches;
+        state.current = 0;
+    }
+
+    /// The number of matches found by the last search.
+    pub fn match_count(&self) -> usize {
+        self.state.borrow().matches.len()
+    }
+
+    /// The current match, or None if there are no matches.
+    pub fn current(&self) -> Option<SearchMatch> {
+        let state = self.state.borrow();
+        state.matches.get(state.current).cloned()
+    }
+
+    /// Move to the next match, wrapping around to the first.
+    pub fn next(&self) {
+        let mut state = self.state.borrow_mut();
+        if !state.matches.is_empty() {
+            state.current = (state.current + 1) % state.matches.len();
+        }
+    }
+
+    /// Move to the previous match, wrapping around to the last.
+    pub fn previous(&self) {
+        let mut state = self.state.borrow_mut();
+        if !state.matches.is_empty() {
+            let len = state.matches.len();
+            state.current = (state.current + len - 1) % len;
+        }
+    }
+
+    /// Apply the key bindings. Returns true if the key was handled, in which
+    /// case the keyboard is reset so that other components do not respond to
+    /// it. While the query is being edited every key is handled.
+    pub fn handle_keys(&self, kb: &Keyboard) -> bool {
+        if self.is_editing() {
+            if kb.code().is_none() && kb.pasted().is_none() {
+                return false;
+            }
+            match kb.code() {
+                Some(KeyCode::Enter) => self.set_editing(false),
+                Some(KeyCode::Esc) => self.clear(),
+                _ => {
+                    let query = self.query();
+                    self.input.handle_keys(kb);
+                    if self.query() != query {
+                        self.changed();
+                    }
+                }
+            }
+            kb.reset();
+            return true;
+        }
+        match kb.char() {
+            Some('/') => self.set_editing(true),
+            Some('n') if self.is_active() => self.next(),
+            Some('N') if self.is_active() => self.previous(),
+            _ if kb.code() == Some(KeyCode::Esc) && self.is_active() => self.clear(),
+            _ => return false,
+        }
+        kb.reset();
+        true
+    }
+
+    /// Highlight the places the query occurs within runes.
+    pub fn highlight(&self, runes: Runes, theme: &Theme) -> Runes {
+        let text: String = runes.iter().map(|r| r.content.unwrap_or(' ')).collect();
+        let ranges = self.find(&text);
+        highlight(runes, &ranges, theme)
+    }
+
+    /// Draw the query on the first row of a region of a context, along with
+    /// the position of the current match.
+    pub fn draw<R: Into<Rect>>(&self, ctx: &mut ViewContext, rect: R) {
+        let rect = rect.into();
+        if rect.size.width < 2 || rect.size.height == 0 {
+            return;
+        }
+        let theme = ctx.theme();
+        if !self.is_editing() && !self.is_active() {
+            return;
+        }
+        let status = match self.match_count() {
+            _ if self.is_editing() => String::new(),
+            0 => "no matches".to_string(),
+            count => format!("{}/{}", self.state.borrow().current + 1, count),
+        };
+        let status_width = status.chars().count();
+        ctx.insert(rect.pos, Runes::from("/").fg(theme.muted));
+        let input_width = (rect.size.width - 1).saturating_sub(status_width + 1);
+        self.input
+            .draw(ctx, ((rect.pos.x + 1, rect.pos.y), (input_width, 1)));
+        if status_width > 0 && status_width < rect.size.width - 1 {
+            let x = rect.pos.x + rect.size.width - status_width;
+            ctx.insert((x, rect.pos.y), Runes::from(status).fg(theme.muted));
+        }
+    }
+}
+
+impl Callable<()> for SearchState {
+    fn call(&self, ctx: &mut ViewContext, _args: ()) {
+        let size = ctx.size();
+        self.draw(ctx, size);
+    }
+}
+
+/// The SearchState attached to a component, with what the component last
+/// did with it.
+#[derive(Debug, Default)]
+pub(crate) struct SearchFollower {
+    pub(crate) search: Option<SearchState>,
+    /// The revision the component's items were last searched with.
+    searched: Option<u64>,
+    /// The match the component last moved to.
+    shown: Option<SearchMatch>,
+}
+
+impl SearchFollower {
+    /// Search the items again on the next call to follow, such as after
+    /// they have changed.
+    pub(crate) fn invalidate(&mut self) {
+        self.searched = None;
+    }
+
+    /// The attached search if it has a query, used to highlight matches.
+    pub(crate) fn active(&self) -> Option<SearchState> {
+        self.search.clone().filter(SearchState::is_active)
+    }
+
+    /// Search the items if the query has changed since they were last
+    /// searched. Returns the item the current match is in when the current
+    /// match has changed, so the component can move to it.
+    pub(crate) fn follow<I, S>(&mut self, items: impl FnOnce() -> I) -> Option<usize>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let search = self.search.as_ref()?;
+        if self.searched != Some(search.revision()) {
+            search.update(items());
+            self.searched = Some(search.revision());
+            self.shown = None;
+        }
+        let current = search.current();
+        if current == self.shown {
+            return None;
+        }
+        self.shown = current;
+        self.shown.as_ref().map(|found| found.item)
+    }
+}
+
+/// The character ranges where a query occurs in some text, ignoring case
+/// unless the query contains an uppercase character.
+pub(crate) fn find(query: &str, text: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return vec![];
+    }
+    let ignore_case = !query.chars().any(char::is_uppercase);
+    let fold = |c: char| {
+        if ignore_case {
+            c.to_lowercase().next().unwrap_or(c)
+        } else {
+            c
+        }
+    };
+    let query: Vec<char> = query.chars().map(fold).collect();
+    let text: Vec<char> = text.chars().map(fold).collect();
+    let mut ranges = vec![];
+    let mut start = 0;
+    while start + query.len() <= text.len() {
+        if text[start..start + query.len()] == query[..] {
+            ranges.push(start..start + query.len());
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// Highlight ranges of runes using the theme's warning color.
+pub(crate) fn highlight(runes: Runes, ranges: &[Range<usize>], theme: &Theme) -> Runes {
+    if ranges.is_empty() {
+        return runes;
+    }
+    let fg = crate::color::contrast(theme.warning).unwrap_or(theme.bg_primary);
+    let mut highlighted: Vec<Rune> = runes.to_vec();
+    for range in ranges {
+        for rune in highlighted.iter_mut().take(range.end).skip(range.start) {
+            rune.fg = Some(fg);
+            rune.bg = Some(theme.warning);
+        }
+    }
+    Runes::new(highlighted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{input::Keyboard, theme::Theme};
+
+    use super::{find, SearchMatch, SearchState};
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("ab", "Ab cab abab"), vec![0..2, 4..6, 7..9, 9..11]);
+        assert_eq!(find("Ab", "Ab cab"), vec![0..2]);
+        assert_eq!(find("aa", "aaa"), vec![0..2]);
+        assert!(find("", "text").is_empty());
+        assert_eq!(find("é", "café É"), vec![3..4, 5..6]);
+    }
+
+    #[test]
+    fn test_keys_and_matches() {
+        let search = SearchState::new();
+        assert!(!search.handle_keys(&Keyboard::with_key(KeyCode::Char('n'))));
+        assert!(search.handle_keys(&Keyboard::with_key(KeyCode::Char('/'))));
+        let revision = search.revision();
+        for c in "to".chars() {
+            search.handle_keys(&Keyboard::with_key(KeyCode::Char(c)));
+        }
+        assert_eq!(search.query(), "to");
+        assert!(search.revision() > revision);
+        assert!(search.handle_keys(&Keyboard::with_key(KeyCode::Enter)));
+        assert!(!search.is_editing());
+
+        search.update(["one", "top", "photo tomato"]);
+        assert_eq!(search.match_count(), 4);
+        assert_eq!(
+            search.current(),
+            Some(SearchMatch {
+                item: 1,
+                range: 0..2
+            })
+        );
+        search.handle_keys(&Keyboard::with_key(KeyCode::Char('N')));
+        assert_eq!(search.current().unwrap().range, 10..12);
+        search.handle_keys(&Keyboard::with_key(KeyCode::Char('n')));
+        assert_eq!(search.current().unwrap().item, 1);
+
+        assert!(search.handle_keys(&Keyboard::with_key(KeyCode::Esc)));
+        assert!(!search.is_active());
+        assert_eq!(search.match_count(), 0);
+        assert!(search.is_match("anything"));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let theme = Theme::default();
+        let search = SearchState::new();
+        search.set_query("b");
+        let runes = search.highlight("abcb".into(), &theme);
+        let bgs: Vec<bool> = runes.iter().map(|r| r.bg == Some(theme.warning)).collect();
+        assert_eq!(bgs, vec![false, true, false, true]);
+    }
+}
@@ -2,7 +2,12 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::{
     container::Container,
-    prelude::{Callable, Pos, Runes, Size, ViewContext},
+    geometry::Rect,
+    layout_checks,
+    overlay::Overlay,
+    prelude::{Callable, Color, Pos, RuneSlice, Runes, Size, ViewContext},
+    profiler, provenance,
+    style::Style,
     view::View,
 };
 
@@ -29,6 +34,10 @@ pub struct Stack {
     pub(crate) view: View,
     pub(crate) position: Pos,
     pub(crate) alignment: StackAlignment,
+    pub(crate) style: Style,
+    pub(crate) background: Option<Color>,
+    pub(crate) overlays: Vec<Overlay>,
+    pub(crate) origin: Pos,
 }
 
 impl Stack {
@@ -93,9 +102,24 @@ impl Stack {
             }
         };
 
+        layout_checks::enter::<F>();
+
+        profiler::enter::<F>();
+        layout_checks::check_rect(Rect::new(pos, size), self.view.size());
         let mut context = ViewContext::new(self.container.clone(), size);
-        f.call(&mut context, Args::from_container(&self.container.borrow()));
+        context.style = self.style;
+        context.background = self.background;
+        context.origin = self.origin + pos;
+        match context.inject::<Args>() {
+            Ok(args) => f.call(&mut context, args),
+            Err(error) => context.diagnostic(&error),
+        }
+        layout_checks::exit();
+        profiler::exit();
+        provenance::stamp::<F>(&mut context.view);
         self.view.apply(pos, &context.view);
+        self.overlays
+            .extend(context.overlays.into_iter().map(|o| o.offset(pos)));
         self.position += match self.direction {
             StackDirection::Vertical => Pos::new(0, size.height),
             StackDirection::Horizontal => Pos::new(size.width, 0),
@@ -103,8 +127,14 @@ impl Stack {
     }
 
     /// Insert a set a runes, such as a string, into the stack.
-    pub fn insert<R: Into<Runes>>(&mut self, value: R) {
-        let runes: Runes = value.into();
+    pub fn insert<'a, R: Into<RuneSlice<'a>>>(&mut self, value: R) {
+        let runes: RuneSlice = value.into();
+        let runes = Runes::new(
+            runes
+                .iter()
+                .map(|rune| self.style.resolve(*rune, self.background))
+                .collect(),
+        );
         let size = Size::new(runes.len(), 1);
 
         let pos = match self.direction {
@@ -168,6 +198,7 @@ impl Stack {
 impl Callable<()> for Stack {
     fn call(&self, ctx: &mut ViewContext, _args: ()) {
         ctx.apply((0, 0), &self.view);
+        ctx.overlays.extend(self.overlays.iter().cloned());
     }
 }
 
@@ -0,0 +1,88 @@
+use crossterm::style::Color;
+
+use crate::runes::Rune;
+
+/// Style holds styling overrides that apply to a region of the view tree.
+/// Any value that is not set is inherited from the enclosing style.
+///
+/// Styles are applied with ViewContext::with_style and are available to
+/// every component rendered within it through ViewContext::style.
+///
+/// ```
+/// use arkham::prelude::*;
+/// let style = Style::new().fg(Color::Red).border(Color::DarkRed);
+/// assert_eq!(style.fg, Some(Color::Red));
+/// assert_eq!(style.bg, None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub border: Option<Color>,
+}
+
+impl Style {
+    /// Create a new empty style. This can be used with the settings
+    /// functions as a _builder_ pattern.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            border: None,
+        }
+    }
+
+    /// Set the default text color.
+    pub const fn fg(mut self, fg: Color) -> Self {
+        self.fg = Some(fg);
+        self
+    }
+
+    /// Set the default background color.
+    pub const fn bg(mut self, bg: Color) -> Self {
+        self.bg = Some(bg);
+        self
+    }
+
+    /// Set the color used for borders and dividers.
+    pub const fn border(mut self, border: Color) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Layer another style on top of this one. Values set in the other
+    /// style take precedence.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let base = Style::new().fg(Color::White).bg(Color::Black);
+    /// let style = base.merge(Style::new().fg(Color::Red));
+    /// assert_eq!(style.fg, Some(Color::Red));
+    /// assert_eq!(style.bg, Some(Color::Black));
+    /// ```
+    pub fn merge(self, other: Style) -> Self {
+        Self {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            border: other.border.or(self.border),
+        }
+    }
+
+    /// Fill in the unset colors of a rune using this style. The background
+    /// is the color the rune will be drawn over, if known. When neither the
+    /// rune nor the style specifies a text color, one is chosen that
+    /// contrasts with the background.
+    pub(crate) fn resolve(&self, mut rune: Rune, background: Option<Color>) -> Rune {
+        if background.is_none() {
+            rune.bg = rune.bg.or(self.bg);
+        }
+        rune.fg = rune.fg.or(self.fg).or_else(|| {
+            rune.bg
+                .or(background)
+                .or(self.bg)
+                .and_then(crate::color::contrast)
+        });
+        rune
+    }
+}
@@ -1,4 +1,13 @@
 #![allow(dead_code)]
+//! Unicode symbols used when drawing interfaces.
+//!
+//! Common symbols are available at the top level of this module. Related
+//! symbols are also grouped into namespaced sets such as `arrows`, `boxes`,
+//! `blocks`, `braille`, and `weather`, and the `legacy` set provides plain
+//! ASCII fallbacks for terminals with limited character support.
+//!
+//! Components that need to adapt to the capabilities of the terminal should
+//! use a SymbolSet, which selects the appropriate glyphs for a SymbolLevel.
 
 mod universal {
     pub const TICK: char = '✔';
@@ -86,3 +95,305 @@ pub mod win {
     pub const QUESTION_MARK_PREFIX: char = '？';
     pub const ONE_HALF: char = ' ';
 }
+
+pub mod arrows {
+    pub const UP: char = '↑';
+    pub const DOWN: char = '↓';
+    pub const LEFT: char = '←';
+    pub const RIGHT: char = '→';
+    pub const UP_DOWN: char = '↕';
+    pub const LEFT_RIGHT: char = '↔';
+    pub const TRIANGLE_UP: char = '▲';
+    pub const TRIANGLE_DOWN: char = '▼';
+    pub const TRIANGLE_LEFT: char = '◀';
+    pub const TRIANGLE_RIGHT: char = '▶';
+    pub const RETURN: char = '↵';
+}
+
+/// The characters used to draw a rectangular border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderSet {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+pub mod boxes {
+    use super::BorderSet;
+
+    pub const PLAIN: BorderSet = BorderSet {
+        top_left: '┌',
+        top_right: '┐',
+        bottom_left: '└',
+        bottom_right: '┘',
+        horizontal: '─',
+        vertical: '│',
+    };
+    pub const ROUNDED: BorderSet = BorderSet {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..PLAIN
+    };
+    pub const THICK: BorderSet = BorderSet {
+        top_left: '┏',
+        top_right: '┓',
+        bottom_left: '┗',
+        bottom_right: '┛',
+        horizontal: '━',
+        vertical: '┃',
+    };
+    pub const DOUBLE: BorderSet = BorderSet {
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        horizontal: '═',
+        vertical: '║',
+    };
+}
+
+pub mod blocks {
+    pub const FULL: char = '█';
+    pub const UPPER_HALF: char = '▀';
+    pub const LOWER_HALF: char = '▄';
+    pub const LEFT_HALF: char = '▌';
+    pub const RIGHT_HALF: char = '▐';
+    pub const LIGHT_SHADE: char = '░';
+    pub const MEDIUM_SHADE: char = '▒';
+    pub const DARK_SHADE: char = '▓';
+    /// Blocks filling a cell from the left in eighths, from empty to full.
+    pub const HORIZONTAL: [char; 9] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+    /// Blocks filling a cell from the bottom in eighths, from empty to full.
+    pub const VERTICAL: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+}
+
+pub mod braille {
+    pub const BLANK: char = '⠀';
+    pub const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    /// Braille filling a cell from the bottom in quarters, from empty to
+    /// full.
+    pub const VERTICAL: [char; 5] = [BLANK, '⣀', '⣤', '⣶', '⣿'];
+
+    /// Returns the braille character with a set of dots raised. Each bit
+    /// corresponds to a dot using the standard Unicode braille numbering.
+    pub fn from_dots(dots: u8) -> char {
+        char::from_u32(0x2800 + dots as u32).unwrap_or(BLANK)
+    }
+}
+
+pub mod weather {
+    pub const SUN: char = '☀';
+    pub const CLOUD: char = '☁';
+    pub const RAIN: char = '☂';
+    pub const SNOW: char = '❄';
+    pub const SNOWMAN: char = '☃';
+    pub const LIGHTNING: char = 'ϟ';
+    pub const MOON: char = '☾';
+    pub const FOG: char = '≋';
+    pub const WIND: char = '≈';
+    pub const THERMOMETER: char = '°';
+}
+
+/// ASCII replacements for terminals and fonts without Unicode support.
+pub mod legacy {
+    use super::BorderSet;
+
+    pub const TICK: char = 'v';
+    pub const CROSS: char = 'x';
+    pub const BULLET: char = '*';
+    pub const POINTER: char = '>';
+    pub const ARROW_UP: char = '^';
+    pub const ARROW_DOWN: char = 'v';
+    pub const ARROW_LEFT: char = '<';
+    pub const ARROW_RIGHT: char = '>';
+    pub const BLOCK_FULL: char = '#';
+    pub const BLOCK_LIGHT: char = '.';
+    pub const CHECKBOX_ON: char = 'x';
+    pub const CHECKBOX_OFF: char = '-';
+    pub const RADIO_ON: char = '*';
+    pub const RADIO_OFF: char = 'o';
+    pub const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+    /// Characters filling a cell from the bottom, from empty to full.
+    pub const VERTICAL: [char; 4] = [' ', '.', ':', '|'];
+    /// Characters filling a cell from the left, from empty to full.
+    pub const HORIZONTAL: [char; 2] = [' ', '#'];
+    pub const BORDER: BorderSet = BorderSet {
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        horizontal: '-',
+        vertical: '|',
+    };
+}
+
+/// The range of characters a terminal is able to display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymbolLevel {
+    /// Box drawing, block, braille, and other Unicode symbols are available.
+    #[default]
+    Unicode,
+    /// Only ASCII characters can be relied upon.
+    Legacy,
+}
+
+impl SymbolLevel {
+    /// Guess the level supported by the terminal from the environment. The
+    /// Linux console and non UTF-8 locales are treated as legacy terminals.
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        if var("TERM") == "linux" {
+            return SymbolLevel::Legacy;
+        }
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .map(var)
+            .find(|v| !v.is_empty());
+        match locale {
+            Some(locale) if !locale.to_lowercase().replace('-', "").contains("utf8") => {
+                SymbolLevel::Legacy
+            }
+            _ => SymbolLevel::Unicode,
+        }
+    }
+}
+
+/// A set of symbols appropriate for a SymbolLevel. SymbolSet can be
+/// inserted as a resource to control the glyphs used by built-in components,
+/// which otherwise use the Unicode set.
+///
+/// Example:
+/// ```
+/// use arkham::symbols::{SymbolLevel, SymbolSet};
+/// let symbols = SymbolSet::for_level(SymbolLevel::Legacy);
+/// assert_eq!(symbols.border.top_left, '+');
+/// ```
+///
+/// ```no_run
+/// use arkham::{prelude::*, symbols::SymbolSet};
+///
+/// fn main() {
+///     App::new(root).insert_resource(SymbolSet::detect()).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext) {}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolSet {
+    pub level: SymbolLevel,
+    pub tick: char,
+    pub cross: char,
+    pub bullet: char,
+    pub pointer: char,
+    pub arrow_up: char,
+    pub arrow_down: char,
+    pub arrow_left: char,
+    pub arrow_right: char,
+    pub block_full: char,
+    pub block_light: char,
+    pub checkbox_on: char,
+    pub checkbox_off: char,
+    pub radio_on: char,
+    pub radio_off: char,
+    pub border: BorderSet,
+    pub spinner: &'static [char],
+    /// Characters filling a cell from the bottom, from empty to full, used
+    /// for vertical bars such as sparklines.
+    pub bars_vertical: &'static [char],
+    /// Characters filling a cell from the left, from empty to full, used
+    /// for horizontal bars such as gauges.
+    pub bars_horizontal: &'static [char],
+}
+
+impl SymbolSet {
+    pub const UNICODE: SymbolSet = SymbolSet {
+        level: SymbolLevel::Unicode,
+        tick: TICK,
+        cross: CROSS,
+        bullet: BULLET,
+        pointer: POINTER,
+        arrow_up: arrows::UP,
+        arrow_down: arrows::DOWN,
+        arrow_left: arrows::LEFT,
+        arrow_right: arrows::RIGHT,
+        block_full: blocks::FULL,
+        block_light: blocks::LIGHT_SHADE,
+        checkbox_on: CHECKBOX_ON,
+        checkbox_off: CHECKBOX_OFF,
+        radio_on: RADIO_ON,
+        radio_off: RADIO_OFF,
+        border: boxes::PLAIN,
+        spinner: &braille::SPINNER,
+        bars_vertical: &blocks::VERTICAL,
+        bars_horizontal: &blocks::HORIZONTAL,
+    };
+
+    pub const LEGACY: SymbolSet = SymbolSet {
+        level: SymbolLevel::Legacy,
+        tick: legacy::TICK,
+        cross: legacy::CROSS,
+        bullet: legacy::BULLET,
+        pointer: legacy::POINTER,
+        arrow_up: legacy::ARROW_UP,
+        arrow_down: legacy::ARROW_DOWN,
+        arrow_left: legacy::ARROW_LEFT,
+        arrow_right: legacy::ARROW_RIGHT,
+        block_full: legacy::BLOCK_FULL,
+        block_light: legacy::BLOCK_LIGHT,
+        checkbox_on: legacy::CHECKBOX_ON,
+        checkbox_off: legacy::CHECKBOX_OFF,
+        radio_on: legacy::RADIO_ON,
+        radio_off: legacy::RADIO_OFF,
+        border: legacy::BORDER,
+        spinner: &legacy::SPINNER,
+        bars_vertical: &legacy::VERTICAL,
+        bars_horizontal: &legacy::HORIZONTAL,
+    };
+
+    /// The symbol set for a capability level.
+    pub fn for_level(level: SymbolLevel) -> Self {
+        match level {
+            SymbolLevel::Unicode => Self::UNICODE,
+            SymbolLevel::Legacy => Self::LEGACY,
+        }
+    }
+
+    /// The symbol set for the level detected from the environment. See
+    /// SymbolLevel::detect.
+    pub fn detect() -> Self {
+        Self::for_level(SymbolLevel::detect())
+    }
+}
+
+impl Default for SymbolSet {
+    fn default() -> Self {
+        Self::UNICODE
+    }
+}
+
+/// Split a fill between 0.0 and 1.0 across a number of cells, returning the
+/// symbol for each cell from the start of the fill. The symbols for a cell
+/// filled from empty to full are given in order, such as blocks::HORIZONTAL,
+/// so that the end of the fill is drawn with a partial symbol and moves
+/// smoothly rather than a whole cell at a time.
+///
+/// Example:
+/// ```
+/// use arkham::symbols::{blocks, partial_fill};
+/// let bar: String = partial_fill(0.3, 4, &blocks::HORIZONTAL).into_iter().collect();
+/// assert_eq!(bar, "█▎  ");
+/// ```
+pub fn partial_fill(ratio: f64, cells: usize, steps: &[char]) -> Vec<char> {
+    let Some(top) = steps.len().checked_sub(1).filter(|&top| top > 0) else {
+        return vec![steps.first().copied().unwrap_or(' '); cells];
+    };
+    let levels = (ratio.clamp(0.0, 1.0) * (cells * top) as f64).round() as usize;
+    (0..cells)
+        .map(|cell| steps[levels.saturating_sub(cell * top).min(top)])
+        .collect()
+}
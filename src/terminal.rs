@@ -0,0 +1,368 @@
+use std::{
+    sync::{Mutex, OnceLock, PoisonError},
+    time::Duration,
+};
+
+use crossterm::{
+    event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    style::Color,
+    terminal,
+};
+
+use crate::theme::ThemeVariant;
+
+/// TerminalBackground holds the terminal's default background color, as
+/// reported by the terminal at startup. It is available as a resource to
+/// component functions and can be used to adapt styling to the user's
+/// terminal.
+///
+/// Not all terminals respond to the background color query, in which case
+/// the color will be None.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn root(ctx: &mut ViewContext, background: Res<TerminalBackground>) {
+///     if background.is_light() {
+///         ctx.insert(0, "Light terminal".to_runes().fg(Color::Black));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TerminalBackground {
+    pub color: Option<Color>,
+}
+
+impl TerminalBackground {
+    /// Query the terminal for its default background color using OSC 11.
+    /// This will briefly enable raw mode if it is not already enabled and
+    /// will wait up to 100ms for the terminal to respond, delaying startup
+    /// by as much for terminals that do not.
+    ///
+    /// Input that arrives before the response, such as keys typed while
+    /// the application starts, is kept rather than discarded, and App::run
+    /// handles it before any further input.
+    ///
+    /// The terminal is only queried once, subsequent calls return the
+    /// previously detected value.
+    pub fn detect() -> Self {
+        static DETECTED: OnceLock<TerminalBackground> = OnceLock::new();
+        *DETECTED.get_or_init(|| Self {
+            color: query_background(Duration::from_millis(100)),
+        })
+    }
+
+    /// Returns true if the background was detected and is light.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let background = TerminalBackground { color: Some(Color::White) };
+    /// assert!(background.is_light());
+    /// ```
+    pub fn is_light(&self) -> bool {
+        self.color.and_then(crate::color::contrast) == Some(Color::Black)
+    }
+
+    /// A theme variant suitable for the background. Dark is assumed when
+    /// the background could not be detected.
+    pub fn variant(&self) -> ThemeVariant {
+        if self.is_light() {
+            ThemeVariant::Light
+        } else {
+            ThemeVariant::Dark
+        }
+    }
+}
+
+/// Returns true if a terminal identifying itself with the given TERM
+/// value is likely to handle OSC 8 hyperlinks. Terminals that do not
+/// support them usually ignore the sequence, but the Linux console and dumb
+/// terminals print it.
+pub(crate) fn supports_hyperlinks(term: Option<&str>) -> bool {
+    !matches!(term, Some("dumb") | Some("linux"))
+}
+
+#[cfg(unix)]
+fn query_background(timeout: Duration) -> Option<Color> {
+    use std::{
+        io::{IsTerminal, Write},
+        os::fd::AsRawFd,
+        time::Instant,
+    };
+
+    let stdin = std::io::stdin();
+    let mut out = std::io::stdout();
+    if !stdin.is_terminal() || !out.is_terminal() {
+        return None;
+    }
+
+    let was_raw = terminal::is_raw_mode_enabled().unwrap_or(false);
+    if !was_raw {
+        terminal::enable_raw_mode().ok()?;
+    }
+
+    let mut response = Vec::new();
+    if out.write_all(b"\x1b]11;?\x07").is_ok() && out.flush().is_ok() {
+        let fd = stdin.as_raw_fd();
+        let deadline = Instant::now() + timeout;
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            let mut pollfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: pollfd is a valid pollfd structure for the duration of the call.
+            let ready = unsafe { libc::poll(&mut pollfd, 1, remaining.as_millis() as i32) };
+            if ready <= 0 {
+                break;
+            }
+            let mut buf = [0u8; 64];
+            // SAFETY: buf is valid for writes of its full length.
+            let n = unsafe { libc::read(fd, buf.as_mut_ptr().cast(), buf.len()) };
+            if n <= 0 {
+                break;
+            }
+            response.extend_from_slice(&buf[..n as usize]);
+            if split_reply(&response).0.is_some() {
+                break;
+            }
+        }
+    }
+
+    if !was_raw {
+        let _ = terminal::disable_raw_mode();
+    }
+
+    let (reply, typed) = split_reply(&response);
+    TYPED_AHEAD
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .extend_from_slice(&typed);
+    parse_osc11(reply?)
+}
+
+#[cfg(not(unix))]
+fn query_background(_timeout: Duration) -> Option<Color> {
+    None
+}
+
+/// Input read from stdin while waiting for the OSC 11 response that was not
+/// part of it. See take_typed_ahead.
+static TYPED_AHEAD: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+/// Split input read while waiting for an OSC 11 response into the complete
+/// response, if it has arrived, and the other input around it.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn split_reply(input: &[u8]) -> (Option<&[u8]>, Vec<u8>) {
+    let Some(start) = input.windows(5).position(|w| w == b"\x1b]11;") else {
+        return (None, input.to_vec());
+    };
+    let body = &input[start..];
+    let end = body.iter().enumerate().find_map(|(idx, &b)| match b {
+        b'\x07' => Some(idx + 1),
+        b'\\' if idx > 0 && body[idx - 1] == b'\x1b' => Some(idx + 1),
+        _ => None,
+    });
+    let Some(end) = end.map(|end| start + end) else {
+        return (None, input.to_vec());
+    };
+    let mut typed = input[..start].to_vec();
+    typed.extend_from_slice(&input[end..]);
+    (Some(&input[start..end]), typed)
+}
+
+/// Take the input that arrived while the terminal background was being
+/// detected, decoded into key events. Common keys are decoded: text,
+/// Enter, Tab, Backspace, Esc, Ctrl with a letter, the arrow keys, Home,
+/// End and Delete. Other escape sequences are dropped.
+pub(crate) fn take_typed_ahead() -> Vec<Event> {
+    let bytes = std::mem::take(&mut *TYPED_AHEAD.lock().unwrap_or_else(PoisonError::into_inner));
+    decode_keys(&bytes)
+}
+
+fn decode_keys(bytes: &[u8]) -> Vec<Event> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut chars = text.chars().peekable();
+    let mut events = vec![];
+    while let Some(c) = chars.next() {
+        let (code, modifiers) = match c {
+            '\x1b' if matches!(chars.peek(), Some('[' | 'O')) => {
+                chars.next();
+                let mut params = String::new();
+                let mut last = None;
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        last = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                let code = match (last, params.as_str()) {
+                    (Some('A'), _) => KeyCode::Up,
+                    (Some('B'), _) => KeyCode::Down,
+                    (Some('C'), _) => KeyCode::Right,
+                    (Some('D'), _) => KeyCode::Left,
+                    (Some('H'), _) | (Some('~'), "1" | "7") => KeyCode::Home,
+                    (Some('F'), _) | (Some('~'), "4" | "8") => KeyCode::End,
+                    (Some('~'), "3") => KeyCode::Delete,
+                    _ => continue,
+                };
+                (code, KeyModifiers::NONE)
+            }
+            '\x1b' => (KeyCode::Esc, KeyModifiers::NONE),
+            '\r' | '\n' => (KeyCode::Enter, KeyModifiers::NONE),
+            '\t' => (KeyCode::Tab, KeyModifiers::NONE),
+            '\x7f' | '\x08' => (KeyCode::Backspace, KeyModifiers::NONE),
+            '\x01'..='\x1a' => (
+                KeyCode::Char((c as u8 - 1 + b'a') as char),
+                KeyModifiers::CONTROL,
+            ),
+            c if c.is_control() => continue,
+            c if c.is_uppercase() => (KeyCode::Char(c), KeyModifiers::SHIFT),
+            c => (KeyCode::Char(c), KeyModifiers::NONE),
+        };
+        events.push(Event::Key(KeyEvent::new(code, modifiers)));
+    }
+    events
+}
+
+/// Parse an OSC 11 response in the form `ESC ] 11 ; rgb:RRRR/GGGG/BBBB BEL`.
+/// Each component usually contains one to four hex digits, longer
+/// components are scaled the same way up to the precision of a u64.
+fn parse_osc11(response: &[u8]) -> Option<Color> {
+    let response = std::str::from_utf8(response).ok()?;
+    let start = response.find("rgb:")? + 4;
+    let body = response[start..].trim_end_matches(['\x07', '\\', '\x1b']);
+    let mut parts = body.split('/').map(|part| {
+        let value = u64::from_str_radix(part, 16).ok()?;
+        let max = 16u64.checked_pow(part.len() as u32)? - 1;
+        Some((u128::from(value) * 255 / u128::from(max)) as u8)
+    });
+    let r = parts.next()??;
+    let g = parts.next()??;
+    let b = parts.next()??;
+    Some(Color::Rgb { r, g, b })
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{decode_keys, parse_osc11, split_reply, supports_hyperlinks, TerminalBackground};
+    use crate::theme::ThemeVariant;
+
+    #[test]
+    fn test_parse_osc11() {
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:ffff/8080/0000\x07"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:ff/80/00\x1b\\"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:fffffff/8000000/0\x07"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 127,
+                b: 0
+            })
+        );
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:ffffffffffffffffffff/0/0\x07"),
+            None
+        );
+        assert_eq!(parse_osc11(b""), None);
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:zz/00/00\x07"), None);
+    }
+
+    #[test]
+    fn test_typed_ahead() {
+        let input = b"ab\x1b]11;rgb:ff/ff/ff\x1b\\\x1b[A\r";
+        let (reply, typed) = split_reply(input);
+        assert_eq!(reply, Some(&b"\x1b]11;rgb:ff/ff/ff\x1b\\"[..]));
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            decode_keys(&typed),
+            vec![
+                key(KeyCode::Char('a'), KeyModifiers::NONE),
+                key(KeyCode::Char('b'), KeyModifiers::NONE),
+                key(KeyCode::Up, KeyModifiers::NONE),
+                key(KeyCode::Enter, KeyModifiers::NONE),
+            ]
+        );
+
+        // Without a complete response all of the input is kept.
+        assert_eq!(
+            split_reply(b"Q\x1b]11;rgb:ff"),
+            (None, b"Q\x1b]11;rgb:ff".to_vec())
+        );
+        assert_eq!(
+            decode_keys(b"Q\x03\x1b\x1b[3~\x1b[15~"),
+            vec![
+                key(KeyCode::Char('Q'), KeyModifiers::SHIFT),
+                key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                key(KeyCode::Esc, KeyModifiers::NONE),
+                key(KeyCode::Delete, KeyModifiers::NONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_theme_follows_background() {
+        use crate::{context::ViewContext, testing::TestApp, theme::Theme};
+
+        fn root(ctx: &mut ViewContext) {
+            let theme = ctx.theme();
+            ctx.fill_all(theme.bg_primary);
+        }
+
+        let light = TerminalBackground {
+            color: Some(Color::White),
+        };
+        let mut app = TestApp::new(root, (2, 1)).insert_resource(light);
+        assert_eq!(app.render()[0][0].bg, Some(Theme::light().bg_primary));
+        let mut app = TestApp::new(root, (2, 1));
+        assert_eq!(app.render()[0][0].bg, Some(Theme::dark().bg_primary));
+    }
+
+    #[test]
+    fn test_variant() {
+        let light = TerminalBackground {
+            color: Some(Color::Rgb {
+                r: 250,
+                g: 250,
+                b: 240,
+            }),
+        };
+        let dark = TerminalBackground {
+            color: Some(Color::Rgb {
+                r: 20,
+                g: 20,
+                b: 30,
+            }),
+        };
+        assert_eq!(light.variant(), ThemeVariant::Light);
+        assert_eq!(dark.variant(), ThemeVariant::Dark);
+        assert_eq!(TerminalBackground::default().variant(), ThemeVariant::Dark);
+    }
+
+    #[test]
+    fn test_supports_hyperlinks() {
+        assert!(supports_hyperlinks(Some("xterm-256color")));
+        assert!(supports_hyperlinks(None));
+        assert!(!supports_hyperlinks(Some("linux")));
+    }
+}
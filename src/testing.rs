@@ -0,0 +1,709 @@
+//! Helpers for testing components by comparing rendered frames against
+//! expected text, and for driving an application through a sequence of
+//! events with TestApp and Scenario.
+//!
+//! Frames are compared as plain text. Empty cells are rendered as spaces,
+//! trailing whitespace is ignored on every line, and trailing empty lines
+//! are ignored, so expected frames can be written as ordinary string
+//! literals. When a frame does not match, the assertion panics with a
+//! unified diff between the expected and rendered text.
+//!
+//! ```
+//! use arkham::{assert_view_matches, internal::View, prelude::*};
+//!
+//! let mut view = View::new((10, 3));
+//! view.insert((1, 1), "Hello".to_runes().fg(Color::Red));
+//!
+//! assert_view_matches!(view, "
+//!
+//!  Hello
+//! ");
+//!
+//! assert_view_matches!(view, "\n\n Hello", (1, 1) => Style::new().fg(Color::Red));
+//! ```
+
+use std::{cell::RefCell, fmt::Write, io, marker::PhantomData, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyModifiers, MouseEvent};
+
+use crate::{
+    animation::Animation,
+    app::{flush, handle_global_keys, macro_keys, render_frame, KeyHandler, Terminal},
+    color::{ColorBlindness, ColorFilter},
+    container::{Callable, Container, ContainerRef, FromContainer, Res, State},
+    dock::Docks,
+    features::Features,
+    frame::{Frame, FrameStats},
+    geometry::{Pos, Size},
+    idle::Idle,
+    input::{Keyboard, Macros, Mouse},
+    keymap::KeyBinding,
+    plugins::Plugin,
+    pointer::CursorHints,
+    runes::Rune,
+    style::Style,
+    terminal::TerminalBackground,
+    view::View,
+    zoom::Zoom,
+};
+
+pub use crate::assert_view_matches;
+
+/// The number of unchanged lines displayed around each change in a diff.
+const CONTEXT: usize = 2;
+
+/// Render a view as plain text, with empty cells rendered as spaces and
+/// trailing whitespace removed.
+pub fn frame_text(view: &View) -> String {
+    let lines: Vec<String> = view
+        .iter()
+        .map(|line| {
+            line.iter()
+                .filter(|r| !r.continuation)
+                .map(|r| r.text().unwrap_or(' '.into()))
+                .collect()
+        })
+        .collect();
+    normalize(&lines.join("\n")).join("\n")
+}
+
+/// Split text into lines for comparison, ignoring trailing whitespace.
+fn normalize(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text.lines().map(|l| l.trim_end().to_string()).collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Produce a unified diff between two texts, or None if they are equal.
+/// Trailing whitespace and trailing empty lines are not considered.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    let expected = normalize(expected);
+    let actual = normalize(actual);
+    if expected == actual {
+        return None;
+    }
+
+    // Longest common subsequence table, used to walk the two texts and
+    // classify every line as unchanged, removed, or added.
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            ops.push((' ', i, j));
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', i, j));
+            i += 1;
+        } else {
+            ops.push(('+', i, j));
+            j += 1;
+        }
+    }
+
+    let mut out = String::from("--- expected\n+++ actual\n");
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(CONTEXT);
+        let mut end = changes[k];
+        while k < changes.len() && changes[k] <= end + CONTEXT * 2 + 1 {
+            end = changes[k];
+            k += 1;
+        }
+        let end = (end + CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+        let removed = hunk.iter().filter(|op| op.0 != '+').count();
+        let added = hunk.iter().filter(|op| op.0 != '-').count();
+        let _ = writeln!(
+            out,
+            "@@ -{},{} +{},{} @@",
+            hunk[0].1 + 1,
+            removed,
+            hunk[0].2 + 1,
+            added
+        );
+        for (op, i, j) in hunk {
+            let line = if *op == '+' {
+                &actual[*j]
+            } else {
+                &expected[*i]
+            };
+            let _ = writeln!(out, "{}{}", op, line);
+        }
+    }
+    Some(out)
+}
+
+/// Assert that a view renders as the expected text and that the runes at
+/// the given positions use the given styles. Only the colors set in each
+/// style are compared.
+///
+/// This is the function behind assert_view_matches and is usually not
+/// called directly.
+#[track_caller]
+pub fn assert_frame(view: &View, expected: &str, styles: &[(Pos, Style)]) {
+    // Expected frames may begin on the line after the opening quote.
+    let expected = expected.strip_prefix('\n').unwrap_or(expected);
+    if let Some(diff) = diff(expected, &frame_text(view)) {
+        panic!("frame does not match the expected text\n{}", diff);
+    }
+    for (pos, style) in styles {
+        let Some(rune) = view.get(pos.y).and_then(|line| line.get(pos.x)) else {
+            panic!("style position {},{} is outside of the frame", pos.x, pos.y);
+        };
+        if style.fg.is_some_and(|fg| rune.fg != Some(fg))
+            || style.bg.is_some_and(|bg| rune.bg != Some(bg))
+        {
+            panic!(
+                "style at {},{} does not match\n  expected: fg {:?}, bg {:?}\n  actual:   fg {:?}, bg {:?}",
+                pos.x, pos.y, style.fg, style.bg, rune.fg, rune.bg
+            );
+        }
+    }
+}
+
+/// TestApp renders a root component the way App does, but into an in
+/// memory view instead of the terminal. Key presses and resizes can be
+/// applied directly, and each returns the frame rendered in response.
+///
+/// The framework resources are bound as they are for a running application.
+/// The Terminal resource is headless and reports the size of the TestApp.
+///
+/// ```
+/// use arkham::{assert_view_matches, prelude::*, testing::TestApp};
+///
+/// fn counter(ctx: &mut ViewContext, kb: Res<Keyboard>, count: State<i32>) {
+///     if kb.char() == Some('+') {
+///         *count.get_mut() += 1;
+///     }
+///     ctx.insert(0, format!("Count: {}", count.get()));
+/// }
+///
+/// let mut app = TestApp::new(counter, (20, 1)).insert_state(0);
+/// assert_view_matches!(app.render(), "Count: 0");
+/// assert_view_matches!(app.press(KeyCode::Char('+')), "Count: 1");
+/// ```
+pub struct TestApp<F, Args>
+where
+    F: Callable<Args>,
+    Args: FromContainer,
+{
+    container: ContainerRef,
+    root: F,
+    plugins: Vec<Box<dyn Plugin>>,
+    key_handlers: Vec<KeyHandler>,
+    view: View,
+    displayed: Vec<Vec<Rune>>,
+    displayed_filter: Option<ColorBlindness>,
+    exited: bool,
+    args: PhantomData<Args>,
+}
+
+impl<F, Args> TestApp<F, Args>
+where
+    F: Callable<Args>,
+    Args: FromContainer,
+{
+    pub fn new<S: Into<Size>>(root: F, size: S) -> Self {
+        let size = size.into();
+        let mut container = Container::default();
+        container
+            .bind_default(|| Res::new(Terminal::headless(size.width as u16, size.height as u16)));
+        container.bind_default(|| Res::new(Keyboard::new()));
+        container.bind_default(|| Res::new(Mouse::new()));
+        container.bind_default(|| Res::new(CursorHints::new()));
+        container.bind_default(|| Res::new(Frame::new()));
+        container.bind_default(|| Res::new(FrameStats::default()));
+        container.bind_default(|| Res::new(ColorFilter::default()));
+        container.bind_default(|| Res::new(Zoom::new()));
+        container.bind_default(|| Res::new(Docks::new()));
+        container.bind_default(|| Res::new(Features::new()));
+        container.bind_default(|| Res::new(Macros::new()));
+        container.bind_default(|| Res::new(Idle::default()));
+        container.bind_default(|| Res::new(Animation::new()));
+        container.bind_default(|| Res::new(TerminalBackground::default()));
+        Self {
+            container: Rc::new(RefCell::new(container)),
+            root,
+            plugins: vec![],
+            key_handlers: vec![],
+            view: View::new(size),
+            displayed: vec![vec![Rune::default(); size.width]; size.height],
+            displayed_filter: None,
+            exited: false,
+            args: PhantomData,
+        }
+    }
+
+    /// Add a plugin to the application. The plugin is built immediately.
+    pub fn insert_plugin(mut self, mut plugin: impl Plugin + 'static) -> Self {
+        plugin.build(self.container.clone());
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    /// Set the frame budget. See App::frame_budget.
+    pub fn frame_budget(self, budget: std::time::Duration) -> Self {
+        self.container
+            .borrow_mut()
+            .bind_replace(Res::new(FrameStats::new(Some(budget))));
+        self
+    }
+
+    /// Enable a feature flag. See App::enable_feature.
+    pub fn enable_feature(self, name: &str) -> Self {
+        self.container
+            .borrow()
+            .get::<Res<Features>>()
+            .unwrap()
+            .set(name, true);
+        self
+    }
+
+    /// Add a handler for application wide shortcuts. See App::global_keys.
+    pub fn global_keys<H>(mut self, handler: H) -> Self
+    where
+        H: Fn(&Keyboard) -> bool + 'static,
+    {
+        self.key_handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Insert a resource which can be injected into component functions.
+    pub fn insert_resource<T: 'static>(self, v: T) -> Self {
+        self.container.borrow_mut().bind(Res::new(v));
+        self
+    }
+
+    /// Insert a state object which can be injected into component functions.
+    pub fn insert_state<T: 'static>(self, v: T) -> Self {
+        self.container.borrow_mut().bind(State::new(v));
+        self
+    }
+
+    /// Bind an existing state, so that it can be inspected by the test.
+    pub fn bind_state<T: 'static>(self, v: State<T>) -> Self {
+        self.container.borrow_mut().bind(v);
+        self
+    }
+
+    /// The container holding the application's resources.
+    pub fn container(&self) -> ContainerRef {
+        self.container.clone()
+    }
+
+    /// Returns true if a component has called ViewContext::exit.
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Render a frame and return it.
+    pub fn render(&mut self) -> &View {
+        if !self.exited {
+            self.exited = render_frame(&self.container, &self.root, &self.plugins, &mut self.view);
+            self.container
+                .borrow()
+                .get::<Res<Frame>>()
+                .unwrap()
+                .set(&self.view);
+        }
+        &self.view
+    }
+
+    /// Run a complete render cycle, rendering a frame and writing the
+    /// changes since the previous draw to an output as the application
+    /// would write them to the terminal. An in memory output, such as a
+    /// Vec<u8>, can be used to measure rendering without a terminal.
+    ///
+    /// ```
+    /// use arkham::{prelude::*, testing::TestApp};
+    ///
+    /// let mut app = TestApp::new(|ctx: &mut ViewContext| {
+    ///     ctx.insert(0, "Hello");
+    /// }, (10, 1));
+    /// let mut out = vec![];
+    /// app.draw(&mut out).unwrap();
+    /// assert!(!out.is_empty());
+    ///
+    /// out.clear();
+    /// app.draw(&mut out).unwrap();
+    /// assert!(out.is_empty());
+    /// ```
+    pub fn draw<W: io::Write>(&mut self, out: &mut W) -> anyhow::Result<()> {
+        self.render();
+        let filter = self
+            .container
+            .borrow()
+            .get::<Res<ColorFilter>>()
+            .unwrap()
+            .mode();
+        if filter != self.displayed_filter {
+            self.view.damage_all();
+            self.displayed_filter = filter;
+        }
+        flush(&mut self.view, &mut self.displayed, out, filter, true)
+    }
+
+    /// Press a key and return the frame rendered in response.
+    pub fn press(&mut self, code: KeyCode) -> &View {
+        self.press_with(code, KeyModifiers::NONE)
+    }
+
+    /// Press a key along with modifier keys and return the frame rendered
+    /// in response. When the key replays a macro, a frame is rendered for
+    /// each of the macro's keys and the last one is returned.
+    pub fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &View {
+        let keys = macro_keys(&self.container, KeyBinding::new(code, modifiers));
+        if keys.is_empty() {
+            self.render();
+        }
+        for key in keys {
+            {
+                let container = self.container.borrow();
+                let kb = container.get::<Res<Keyboard>>().unwrap();
+                kb.set_key(key.code);
+                kb.set_modifiers(key.modifiers);
+                handle_global_keys(&self.key_handlers, kb);
+            }
+            // As in the application, a second frame is rendered so that state
+            // changed in response to the key is displayed.
+            self.render();
+            self.render();
+        }
+        &self.view
+    }
+
+    /// Paste text and return the frame rendered in response.
+    pub fn paste(&mut self, text: &str) -> &View {
+        self.container
+            .borrow()
+            .get::<Res<Keyboard>>()
+            .unwrap()
+            .set_paste(text);
+        self.render();
+        self.render()
+    }
+
+    /// Send a mouse event and return the frame rendered in response.
+    pub fn mouse(&mut self, event: MouseEvent) -> &View {
+        self.container
+            .borrow()
+            .get::<Res<Mouse>>()
+            .unwrap()
+            .set_event(event);
+        self.render();
+        self.render()
+    }
+
+    /// Resize the application and return the frame rendered at the new size.
+    pub fn resize(&mut self, width: usize, height: usize) -> &View {
+        self.view = View::new((width, height));
+        self.displayed = vec![vec![Rune::default(); width]; height];
+        self.container
+            .borrow()
+            .get::<Res<Terminal>>()
+            .unwrap()
+            .resize(width as u16, height as u16);
+        self.render()
+    }
+}
+
+/// A single event applied by a Scenario.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Key(KeyCode, KeyModifiers),
+    Resize(usize, usize),
+    Tick,
+}
+
+/// Scenario scripts a sequence of events to apply to a TestApp, capturing
+/// the frame rendered after each one. Scenarios make it simple to write
+/// regression tests for interactions that span several frames.
+///
+/// ```
+/// use arkham::{assert_view_matches, prelude::*, testing::{Scenario, TestApp}};
+///
+/// fn echo(ctx: &mut ViewContext, kb: Res<Keyboard>, text: State<String>) {
+///     if let Some(c) = kb.char() {
+///         text.get_mut().push(c);
+///     }
+///     if kb.code() == Some(KeyCode::Backspace) {
+///         text.get_mut().pop();
+///     }
+///     ctx.insert(0, text.get().as_str());
+/// }
+///
+/// let mut app = TestApp::new(echo, (10, 1)).insert_state(String::new());
+/// let frames = Scenario::new()
+///     .type_text("hi")
+///     .key(KeyCode::Backspace)
+///     .resize(1, 1)
+///     .run(&mut app);
+/// assert_eq!(frames.len(), 4);
+/// assert_view_matches!(frames[1], "hi");
+/// assert_view_matches!(frames[2], "h");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scenario {
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Press a key.
+    pub fn key(self, code: KeyCode) -> Self {
+        self.step(Step::Key(code, KeyModifiers::NONE))
+    }
+
+    /// Press a key along with modifier keys.
+    pub fn key_with(self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.step(Step::Key(code, modifiers))
+    }
+
+    /// Press the key for each character of a string, capturing a frame
+    /// after each one.
+    pub fn type_text(self, text: &str) -> Self {
+        text.chars().fold(self, |s, c| s.key(KeyCode::Char(c)))
+    }
+
+    /// Resize the application.
+    pub fn resize(self, width: usize, height: usize) -> Self {
+        self.step(Step::Resize(width, height))
+    }
+
+    /// Render a frame without any input, as happens when a render is
+    /// requested through a Renderer.
+    pub fn tick(self) -> Self {
+        self.step(Step::Tick)
+    }
+
+    /// Add a step to the scenario.
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Apply each step to an application, returning the frame rendered
+    /// after each one. The application is rendered once before the first
+    /// step, as it is when an application starts.
+    pub fn run<F, Args>(&self, app: &mut TestApp<F, Args>) -> Vec<View>
+    where
+        F: Callable<Args>,
+        Args: FromContainer,
+    {
+        app.render();
+        self.steps
+            .iter()
+            .map(|step| {
+                match step {
+                    Step::Key(code, modifiers) => app.press_with(*code, *modifiers),
+                    Step::Resize(width, height) => app.resize(*width, *height),
+                    Step::Tick => app.render(),
+                }
+                .clone()
+            })
+            .collect()
+    }
+}
+
+/// Assert that a view, or a ViewContext, renders as the expected text.
+/// Styles can optionally be checked by listing positions and the style
+/// expected at each one.
+///
+/// See the testing module for details on how frames are compared.
+#[macro_export]
+macro_rules! assert_view_matches {
+    ($view:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_frame(&$view, $expected, &[])
+    };
+    ($view:expr, $expected:expr, $($pos:expr => $style:expr),+ $(,)?) => {
+        $crate::testing::assert_frame(
+            &$view,
+            $expected,
+            &[$(($crate::prelude::Pos::from($pos), $style)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crossterm::event::KeyCode;
+
+    use crate::{
+        app::Terminal,
+        container::{ContainerRef, Res, State},
+        context::ViewContext,
+        input::Keyboard,
+        plugins::Plugin,
+        runes::ToRuneExt,
+        style::Style,
+        view::View,
+    };
+
+    use super::{diff, frame_text, Scenario, TestApp};
+
+    #[test]
+    fn test_frame_text() {
+        let mut view = View::new((6, 3));
+        view.insert((1, 1), "ab");
+        assert_eq!(frame_text(&view), "\n ab");
+        assert_view_matches!(view, "\n\n ab\n");
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb", "a\nb  \n\n"), None);
+        let out = diff("a\nb\nc\nd\ne\nf\ng", "a\nb\nc\nX\ne\nf\ng").unwrap();
+        assert_eq!(
+            out,
+            "--- expected\n+++ actual\n@@ -2,5 +2,5 @@\n b\n c\n-d\n+X\n e\n f\n"
+        );
+    }
+
+    #[test]
+    fn test_style_assertion() {
+        let mut view = View::new((4, 1));
+        view.insert(0, "hi".to_runes().fg(Color::Red));
+        assert_view_matches!(view, "hi", (1, 0) => Style::new().fg(Color::Red));
+        let result = std::panic::catch_unwind(|| {
+            assert_view_matches!(view, "hi", (0, 0) => Style::new().bg(Color::Red));
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "-ho\n+hi")]
+    fn test_mismatch() {
+        let mut view = View::new((4, 1));
+        view.insert(0, "hi");
+        assert_view_matches!(view, "ho");
+    }
+
+    fn sized(ctx: &mut ViewContext, terminal: Res<Terminal>, kb: Res<Keyboard>) {
+        if kb.char() == Some('q') {
+            ctx.exit();
+        }
+        let (width, height) = terminal.size();
+        ctx.insert(0, format!("{}x{}", width, height));
+    }
+
+    #[test]
+    fn test_scenario() {
+        let mut app = TestApp::new(sized, (10, 2));
+        let frames = Scenario::new()
+            .tick()
+            .resize(5, 1)
+            .key(KeyCode::Char('q'))
+            .resize(8, 1)
+            .run(&mut app);
+        assert_view_matches!(frames[0], "10x2");
+        assert_view_matches!(frames[1], "5x1");
+        assert!(app.exited());
+        assert_view_matches!(frames[3], "");
+    }
+
+    #[test]
+    fn test_global_keys_swallow() {
+        let pressed = State::new(vec![]);
+        let mut app = TestApp::new(sized, (10, 2)).global_keys({
+            let pressed = pressed.clone();
+            move |kb| {
+                pressed.get_mut().extend(kb.char());
+                kb.char() == Some('q')
+            }
+        });
+        app.press(KeyCode::Char('q'));
+        assert!(!app.exited());
+        app.press(KeyCode::Char('x'));
+        assert_eq!(*pressed.get(), vec!['q', 'x']);
+    }
+
+    #[test]
+    fn test_macros() {
+        use crate::{input::Macros, keymap::Keymap};
+
+        fn typed(ctx: &mut ViewContext, kb: Res<Keyboard>, text: State<String>) {
+            text.get_mut().extend(kb.char());
+            ctx.insert(0, text.get().as_str());
+        }
+
+        let keymap = Keymap::new()
+            .bind(Macros::RECORD, KeyCode::Char('q'))
+            .bind(Macros::REPLAY, KeyCode::Char('@'));
+        let mut app = TestApp::new(typed, (10, 1))
+            .insert_resource(keymap)
+            .insert_state(String::new());
+        for c in "qaxyq".chars() {
+            app.press(KeyCode::Char(c));
+        }
+        assert_view_matches!(app.render(), "xy");
+        app.press(KeyCode::Char('@'));
+        assert_view_matches!(app.press(KeyCode::Char('a')), "xyxy");
+    }
+
+    #[test]
+    fn test_replace_default_resources() {
+        use crate::features::Features;
+
+        fn flagged(ctx: &mut ViewContext, features: Res<Features>) {
+            ctx.insert(0, features.names().join(","));
+        }
+
+        let mut app =
+            TestApp::new(flagged, (10, 1)).insert_resource(Features::new().enable("beta"));
+        assert_view_matches!(app.render(), "beta");
+    }
+
+    #[test]
+    fn test_post_process() {
+        struct Upper;
+
+        impl Plugin for Upper {
+            fn post_process(&self, view: &mut View, _container: ContainerRef) {
+                for rune in view.iter_mut().flatten() {
+                    rune.content = rune.content.map(|c| c.to_ascii_uppercase());
+                }
+            }
+        }
+
+        let mut app = TestApp::new(sized, (10, 2)).insert_plugin(Upper);
+        assert_view_matches!(app.render(), "10X2");
+
+        // Changes made by post processors are written to the terminal, even
+        // when nothing else changed.
+        struct Counter(std::cell::Cell<u8>);
+
+        impl Plugin for Counter {
+            fn post_process(&self, view: &mut View, _container: ContainerRef) {
+                self.0.set(self.0.get() + 1);
+                view[1][0].content = Some((b'0' + self.0.get()) as char);
+            }
+        }
+
+        let mut app = TestApp::new(sized, (10, 2)).insert_plugin(Counter(Default::default()));
+        let mut out = vec![];
+        app.draw(&mut out).unwrap();
+        out.clear();
+        app.draw(&mut out).unwrap();
+        assert!(String::from_utf8_lossy(&out).contains('2'));
+    }
+}
@@ -1,13 +1,48 @@
+use std::collections::HashMap;
+
 use crossterm::style::Color;
 
+use crate::terminal::TerminalBackground;
+
+/// The general appearance a Theme is built for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThemeVariant {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
 /// Theme is a simple theme provider. This structure is nothing special. It
 /// simply holds some general styling information and can be inserted as a
 /// resource into the application.
 ///
+/// Along with general background and foreground colors the theme provides
+/// semantic color roles such as `success` and `error`. Built-in components,
+/// like the LogPlugin, use these roles when a Theme resource is available.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root).insert_resource(Theme::light()).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, theme: Res<Theme>) {
+///     ctx.insert(0, "Saved".to_runes().fg(theme.success));
+/// }
+/// ```
+///
+/// Colors can also be looked up by name with Theme::color, which includes
+/// any application specific colors added with Theme::with_color. Runes can
+/// refer to these names with Rune::fg_token and Rune::bg_token, in which case
+/// the color is taken from the theme when they are rendered.
+///
 /// If you would like to use different style names just make your own structure
-/// which meets your needs and  add it as a resource with App::insert_resource.  
-#[derive(Debug)]
+/// which meets your needs and  add it as a resource with App::insert_resource.
+#[derive(Debug, Clone)]
 pub struct Theme {
+    pub variant: ThemeVariant,
     pub bg_primary: Color,
     pub bg_secondary: Color,
     pub bg_tertiary: Color,
@@ -15,11 +50,167 @@ pub struct Theme {
     pub fg_selection: Color,
     pub fg: Color,
     pub accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub info: Color,
+    pub muted: Color,
+    pub border: Color,
+    pub focus: Color,
+    colors: HashMap<String, Color>,
 }
 
 impl Default for Theme {
     fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Construct the theme for a given variant.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let theme = Theme::from_variant(ThemeVariant::Light);
+    /// assert_eq!(theme.variant, ThemeVariant::Light);
+    /// ```
+    pub fn from_variant(variant: ThemeVariant) -> Self {
+        match variant {
+            ThemeVariant::Dark => Self::dark(),
+            ThemeVariant::Light => Self::light(),
+            ThemeVariant::HighContrast => Self::high_contrast(),
+        }
+    }
+
+    /// Construct a theme matching the terminal's background color. The dark
+    /// theme is used if the background can not be detected.
+    ///
+    /// See TerminalBackground::detect for details on how the background is
+    /// determined.
+    pub fn auto() -> Self {
+        Self::from_variant(TerminalBackground::detect().variant())
+    }
+
+    /// Produce a theme interpolated between this theme and another. A `t` of
+    /// 0.0 or less returns a copy of this theme and 1.0 or more returns a
+    /// copy of `other`, keeping named terminal colors such as Color::Blue.
+    /// Colors in between are blended in RGB.
+    ///
+    /// Stepping `t` over several frames can be used to fade between themes
+    /// rather than switching abruptly.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let theme = Theme::dark().lerp(&Theme::light(), 0.25);
+    /// assert_eq!(theme.variant, ThemeVariant::Dark);
+    ///
+    /// let mut named = Theme::dark();
+    /// named.accent = Color::Blue;
+    /// assert_eq!(named.lerp(&Theme::light(), 0.0).accent, Color::Blue);
+    /// assert_ne!(named.lerp(&Theme::light(), 0.5).accent, Color::Blue);
+    /// ```
+    pub fn lerp(&self, other: &Theme, t: f32) -> Theme {
+        if t <= 0.0 {
+            return self.clone();
+        }
+        if t >= 1.0 {
+            return other.clone();
+        }
+        let mix = |a: Color, b: Color| crate::color::lerp(a, b, t);
+        Theme {
+            variant: if t < 0.5 { self.variant } else { other.variant },
+            bg_primary: mix(self.bg_primary, other.bg_primary),
+            bg_secondary: mix(self.bg_secondary, other.bg_secondary),
+            bg_tertiary: mix(self.bg_tertiary, other.bg_tertiary),
+            bg_selection: mix(self.bg_selection, other.bg_selection),
+            fg_selection: mix(self.fg_selection, other.fg_selection),
+            fg: mix(self.fg, other.fg),
+            accent: mix(self.accent, other.accent),
+            success: mix(self.success, other.success),
+            warning: mix(self.warning, other.warning),
+            error: mix(self.error, other.error),
+            info: mix(self.info, other.info),
+            muted: mix(self.muted, other.muted),
+            border: mix(self.border, other.border),
+            focus: mix(self.focus, other.focus),
+            colors: self
+                .colors
+                .keys()
+                .chain(other.colors.keys())
+                .filter_map(|name| {
+                    let color = match (self.colors.get(name), other.colors.get(name)) {
+                        (Some(a), Some(b)) => mix(*a, *b),
+                        (Some(a), None) => *a,
+                        (None, Some(b)) => *b,
+                        (None, None) => return None,
+                    };
+                    Some((name.clone(), color))
+                })
+                .collect(),
+        }
+    }
+
+    /// Add a named color to the theme, or replace one. Named colors let an
+    /// application keep its own palette, such as a brand color or the colors
+    /// of a chart's series, alongside the built-in roles, so that switching
+    /// themes switches them too.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let theme = Theme::dark().with_color("brand", Color::Magenta);
+    /// assert_eq!(theme.color("brand"), Some(Color::Magenta));
+    /// ```
+    pub fn with_color(mut self, name: &str, color: Color) -> Self {
+        self.set_color(name, color);
+        self
+    }
+
+    /// Add a named color to the theme, or replace one. See Theme::with_color.
+    pub fn set_color(&mut self, name: &str, color: Color) {
+        self.colors.insert(name.to_string(), color);
+    }
+
+    /// Look up a color by name. The built-in roles are named after their
+    /// fields, such as `accent` or `bg_primary`. Other names refer to colors
+    /// added with Theme::with_color, which may not replace the built-in
+    /// roles.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::prelude::*;
+    /// let theme = Theme::light();
+    /// assert_eq!(theme.color("error"), Some(theme.error));
+    /// assert_eq!(theme.color("brand"), None);
+    /// ```
+    pub fn color(&self, name: &str) -> Option<Color> {
+        let color = match name {
+            "bg_primary" => self.bg_primary,
+            "bg_secondary" => self.bg_secondary,
+            "bg_tertiary" => self.bg_tertiary,
+            "bg_selection" => self.bg_selection,
+            "fg_selection" => self.fg_selection,
+            "fg" => self.fg,
+            "accent" => self.accent,
+            "success" => self.success,
+            "warning" => self.warning,
+            "error" => self.error,
+            "info" => self.info,
+            "muted" => self.muted,
+            "border" => self.border,
+            "focus" => self.focus,
+            _ => return self.colors.get(name).copied(),
+        };
+        Some(color)
+    }
+
+    /// A theme with dark backgrounds and light text. This is the default
+    /// theme.
+    pub fn dark() -> Self {
         Self {
+            variant: ThemeVariant::Dark,
             bg_primary: Color::Rgb {
                 r: 36,
                 g: 39,
@@ -46,6 +237,143 @@ impl Default for Theme {
                 g: 0,
                 b: 150,
             },
+            success: Color::Rgb {
+                r: 166,
+                g: 218,
+                b: 149,
+            },
+            warning: Color::Rgb {
+                r: 238,
+                g: 212,
+                b: 159,
+            },
+            error: Color::Rgb {
+                r: 237,
+                g: 135,
+                b: 150,
+            },
+            info: Color::Rgb {
+                r: 138,
+                g: 173,
+                b: 244,
+            },
+            muted: Color::Rgb {
+                r: 110,
+                g: 115,
+                b: 141,
+            },
+            border: Color::Rgb {
+                r: 91,
+                g: 96,
+                b: 120,
+            },
+            focus: Color::Rgb {
+                r: 198,
+                g: 160,
+                b: 246,
+            },
+            colors: HashMap::new(),
+        }
+    }
+
+    /// A theme with light backgrounds and dark text.
+    pub fn light() -> Self {
+        Self {
+            variant: ThemeVariant::Light,
+            bg_primary: Color::Rgb {
+                r: 239,
+                g: 241,
+                b: 245,
+            },
+            bg_secondary: Color::Rgb {
+                r: 230,
+                g: 233,
+                b: 239,
+            },
+            bg_tertiary: Color::Rgb {
+                r: 204,
+                g: 208,
+                b: 218,
+            },
+            bg_selection: Color::Rgb {
+                r: 220,
+                g: 200,
+                b: 240,
+            },
+            fg_selection: Color::Black,
+            fg: Color::Rgb {
+                r: 76,
+                g: 79,
+                b: 105,
+            },
+            accent: Color::Rgb {
+                r: 136,
+                g: 57,
+                b: 239,
+            },
+            success: Color::Rgb {
+                r: 64,
+                g: 160,
+                b: 43,
+            },
+            warning: Color::Rgb {
+                r: 223,
+                g: 142,
+                b: 29,
+            },
+            error: Color::Rgb {
+                r: 210,
+                g: 15,
+                b: 57,
+            },
+            info: Color::Rgb {
+                r: 30,
+                g: 102,
+                b: 245,
+            },
+            muted: Color::Rgb {
+                r: 140,
+                g: 143,
+                b: 161,
+            },
+            border: Color::Rgb {
+                r: 172,
+                g: 176,
+                b: 190,
+            },
+            focus: Color::Rgb {
+                r: 136,
+                g: 57,
+                b: 239,
+            },
+            colors: HashMap::new(),
+        }
+    }
+
+    /// A theme using pure black and white with saturated roles, for maximum
+    /// legibility.
+    pub fn high_contrast() -> Self {
+        Self {
+            variant: ThemeVariant::HighContrast,
+            bg_primary: Color::Black,
+            bg_secondary: Color::Black,
+            bg_tertiary: Color::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            },
+            bg_selection: Color::White,
+            fg_selection: Color::Black,
+            fg: Color::White,
+            accent: Color::Yellow,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            info: Color::Cyan,
+            muted: Color::Grey,
+            border: Color::White,
+            focus: Color::Yellow,
+            colors: HashMap::new(),
         }
     }
 }
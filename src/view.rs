@@ -1,17 +1,42 @@
+use crossterm::style::Color;
+
 use crate::{
     geometry::{Pos, Rect, Size},
-    runes::{Rune, Runes},
+    runes::{wrap, RenderState, Rune, RuneSlice},
 };
 
 /// A renderable region. View stores the renderable state of an area of the
 /// screen. Views can be combined together to achieve a finalized view that
 /// repsresents the entire screens next render.
+///
+/// Views track which of their rows have been changed since the damage was
+/// last cleared, so that only those rows need to be compared with what the
+/// terminal displays. See View::damaged_rows.
+///
+/// The rows of a view are read and written through Deref and DerefMut. A
+/// view can also be built from rows with View::from.
 #[derive(Clone, Debug)]
-pub struct View(pub Vec<Vec<Rune>>);
+pub struct View {
+    rows: Vec<Vec<Rune>>,
+    /// Whether each row has been changed since the damage was cleared.
+    /// Rows without an entry are treated as changed.
+    damaged: Vec<bool>,
+}
 
 impl std::ops::DerefMut for View {
+    // Changes made through the rows directly cannot be tracked, so every row
+    // is treated as changed.
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        self.damaged.fill(true);
+        &mut self.rows
+    }
+}
+
+impl From<Vec<Vec<Rune>>> for View {
+    /// Construct a view from rows of runes. Every row is damaged.
+    fn from(rows: Vec<Vec<Rune>>) -> Self {
+        let damaged = vec![true; rows.len()];
+        Self { rows, damaged }
     }
 }
 
@@ -19,7 +44,7 @@ impl std::ops::Deref for View {
     type Target = Vec<Vec<Rune>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.rows
     }
 }
 
@@ -30,37 +55,230 @@ impl View {
         T: Into<Size>,
     {
         let size: Size = size.into();
-        Self(vec![vec![Rune::default(); size.width]; size.height])
+        Self::from(vec![vec![Rune::default(); size.width]; size.height])
+    }
+
+    /// The rows changed since the damage was last cleared. Every row of a
+    /// new view is damaged, and rows are damaged when runes are inserted,
+    /// filled, or applied from another view and change. Changing the rows
+    /// directly, through indexing or View::iter_mut, damages every row.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((4, 3));
+    /// view.clear_damage();
+    /// view.insert((0, 1), "ab");
+    /// assert_eq!(view.damaged_rows().collect::<Vec<_>>(), vec![1]);
+    /// ```
+    pub fn damaged_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.rows.len()).filter(|&row| self.is_damaged(row))
+    }
+
+    /// Returns true if a row has changed since the damage was last cleared.
+    pub fn is_damaged(&self, row: usize) -> bool {
+        self.damaged.get(row).copied().unwrap_or(true)
+    }
+
+    /// Mark every row as changed, so that the whole view is redrawn.
+    pub fn damage_all(&mut self) {
+        self.damaged = vec![true; self.rows.len()];
+    }
+
+    /// Mark every row as unchanged. This is done once the view has been
+    /// written to the terminal.
+    pub fn clear_damage(&mut self) {
+        self.damaged = vec![false; self.rows.len()];
+    }
+
+    /// Mark the rows of a region as changed.
+    fn damage<I: IntoIterator<Item = usize>>(&mut self, rows: I) {
+        for row in rows {
+            if let Some(damaged) = self.damaged.get_mut(row) {
+                *damaged = true;
+            }
+        }
     }
 
     /// Return an iterator for all runes in the view.
     pub fn iter(&self) -> impl Iterator<Item = &Vec<Rune>> {
-        self.0.iter()
+        self.rows.iter()
     }
 
     /// Apply another view onto this view at a given position.
     pub fn apply<P: Into<Pos>>(&mut self, pos: P, view: &View) {
         let pos = pos.into();
-        for (y, line) in view.0.iter().enumerate() {
-            if self.0.len() > y + pos.y {
-                for (x, rune) in line.iter().enumerate() {
-                    if self.0[y].len() > x + pos.x {
-                        let rune = (self.0[y + pos.y][x + pos.x]) + *rune;
-                        let _ = std::mem::replace(&mut self.0[y + pos.y][x + pos.x], rune);
-                    }
+        for (y, line) in view.rows.iter().enumerate() {
+            self.apply_row(Pos::new(pos.x, pos.y + y), line);
+        }
+    }
+
+    /// Apply a single row of runes onto this view at a given position. This
+    /// behaves like View::apply, but accepts borrowed runes so static or
+    /// shared styled text can be applied without first copying it into a
+    /// view.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    ///
+    /// let label = "hello".to_runes().fg(Color::Red);
+    /// let mut view = View::new((10, 1));
+    /// view.apply_runes((0, 0), &label);
+    /// ```
+    pub fn apply_runes<'a, P, S>(&mut self, pos: P, runes: S)
+    where
+        P: Into<Pos>,
+        S: Into<RuneSlice<'a>>,
+    {
+        let runes: RuneSlice = runes.into();
+        self.apply_row(pos.into(), &runes);
+    }
+
+    fn apply_row(&mut self, pos: Pos, line: &[Rune]) {
+        let Some(row) = self.rows.get_mut(pos.y) else {
+            return;
+        };
+        let mut changed = false;
+        for (cell, rune) in row.iter_mut().skip(pos.x).zip(line) {
+            let rune = *cell + *rune;
+            if *cell != rune {
+                *cell = rune;
+                changed = true;
+            }
+        }
+        if changed {
+            repair_wide(row, pos.x);
+            repair_wide(row, pos.x + line.len());
+            self.damage([pos.y]);
+        }
+    }
+
+    /// Apply another view onto this view at a given position, only changing
+    /// the cells of this view within a mask. The mask is given in this
+    /// view's coordinates, so a transition can reveal a growing region of a
+    /// view, or a split view can be drawn from two overlapping views.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((4, 1));
+    /// let mut other = View::new((4, 1));
+    /// other.fill_all(Rune::new().content('x'));
+    /// view.apply_masked((0, 0), &other, ((1, 0), (2, 1)));
+    /// let text: Vec<_> = view[0].iter().map(|r| r.content).collect();
+    /// assert_eq!(text, vec![None, Some('x'), Some('x'), None]);
+    /// ```
+    pub fn apply_masked<P, R>(&mut self, pos: P, view: &View, mask: R)
+    where
+        P: Into<Pos>,
+        R: Into<Rect>,
+    {
+        let pos = pos.into();
+        let mask = mask.into();
+        for (y, line) in view.rows.iter().enumerate() {
+            for (x, rune) in line.iter().enumerate() {
+                let target = Pos::new(x + pos.x, y + pos.y);
+                if !mask.contains(target) {
+                    continue;
+                }
+                if let Some(r) = self
+                    .rows
+                    .get_mut(target.y)
+                    .and_then(|l| l.get_mut(target.x))
+                {
+                    *r = *r + *rune;
                 }
             }
         }
+        for y in mask.rows() {
+            if let Some(row) = self.rows.get_mut(y) {
+                repair_wide(row, mask.pos.x);
+                repair_wide(row, mask.pos.x + mask.size.width);
+            }
+        }
+        self.damage(mask.rows());
+    }
+
+    /// A copy of the view with its contents moved by an offset, keeping the
+    /// same size. Runes moved outside of the view are dropped and the cells
+    /// left behind are empty.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((3, 1));
+    /// view.insert((0, 0), "abc");
+    /// assert_eq!(view.shifted(1, 0)[0][1].content, Some('a'));
+    /// assert_eq!(view.shifted(1, 0)[0][0].content, None);
+    /// assert_eq!(view.shifted(-2, 0)[0][0].content, Some('c'));
+    /// ```
+    pub fn shifted(&self, dx: i32, dy: i32) -> View {
+        let mut shifted = View::new(self.size());
+        for (y, line) in self.rows.iter().enumerate() {
+            let ty = y as i64 + dy as i64;
+            if ty < 0 || ty >= shifted.height() as i64 {
+                continue;
+            }
+            for (x, rune) in line.iter().enumerate() {
+                let tx = x as i64 + dx as i64;
+                if tx >= 0 && (tx as usize) < shifted.rows[ty as usize].len() {
+                    shifted.rows[ty as usize][tx as usize] = *rune;
+                }
+            }
+            let row = &mut shifted.rows[ty as usize];
+            repair_wide(row, 0);
+            let len = row.len();
+            repair_wide(row, len);
+        }
+        shifted
+    }
+
+    /// A copy of the view mirrored from left to right. Characters with a
+    /// mirrored counterpart, such as brackets, arrows, and box drawing
+    /// corners, are replaced with it so that borders remain intact.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((4, 1));
+    /// view.insert((0, 0), "(ab>");
+    /// let text: String = view.mirrored()[0].iter().filter_map(|r| r.content).collect();
+    /// assert_eq!(text, "<ba)");
+    /// ```
+    pub fn mirrored(&self) -> View {
+        View::from(
+            self.rows
+                .iter()
+                .map(|line| {
+                    let mut line: Vec<Rune> = line
+                        .iter()
+                        .rev()
+                        .map(|rune| Rune {
+                            content: rune.content.map(mirror),
+                            ..*rune
+                        })
+                        .collect();
+                    // Wide characters still come before their continuation.
+                    for x in 1..line.len() {
+                        if line[x - 1].continuation && line[x].width() == 2 {
+                            line.swap(x - 1, x);
+                        }
+                    }
+                    line
+                })
+                .collect::<Vec<_>>(),
+        )
     }
 
     // The width of the view.
     pub fn width(&self) -> usize {
-        self.0.first().map(|i| i.len()).unwrap_or_default()
+        self.rows.first().map(|i| i.len()).unwrap_or_default()
     }
 
     /// The height of the view.
     pub fn height(&self) -> usize {
-        self.0.len()
+        self.rows.len()
     }
 
     /// The region size of the view.
@@ -68,6 +286,16 @@ impl View {
         (self.width(), self.height()).into()
     }
 
+    /// The part of a region that lies within the view.
+    fn clip(&self, rect: Rect) -> Rect {
+        let size = self.size();
+        let x = rect.pos.x.min(size.width);
+        let y = rect.pos.y.min(size.height);
+        let right = rect.columns().end.min(size.width);
+        let bottom = rect.rows().end.min(size.height);
+        Rect::new((x, y), (right - x, bottom - y))
+    }
+
     /// Fill a region of the view with a single rune, repeating it in every
     /// position.
     pub fn fill<R, U>(&mut self, rect: R, rune: U)
@@ -75,11 +303,91 @@ impl View {
         R: Into<Rect>,
         U: Into<Rune>,
     {
-        let rect = rect.into();
+        let rect = self.clip(rect.into());
         let rune = rune.into();
-        for y in rect.pos.y..(rect.size.height + rect.pos.y).min(self.0.len()) {
-            for x in rect.pos.x..(rect.size.width + rect.pos.x).min(self.0[y].len()) {
-                let _ = std::mem::replace(&mut self.0[y][x], rune);
+        for Pos { x, y } in rect.cells() {
+            if let Some(r) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *r = rune;
+            }
+        }
+        for y in rect.rows() {
+            if let Some(row) = self.rows.get_mut(y) {
+                repair_wide(row, rect.pos.x);
+                repair_wide(row, rect.pos.x + rect.size.width);
+            }
+        }
+        self.damage(rect.rows());
+    }
+
+    /// Darken the colors of every rune within a region, blending them toward
+    /// black by the given amount between 0.0 and 1.0. Runes without a
+    /// background color are given a black background.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((2, 1));
+    /// view.fill_all(Color::White);
+    /// view.darken(((0, 0), (1, 1)), 0.5);
+    /// assert_eq!(view[0][0].bg, Some(Color::Rgb { r: 128, g: 128, b: 128 }));
+    /// assert_eq!(view[0][1].bg, Some(Color::White));
+    /// ```
+    pub fn darken<R>(&mut self, rect: R, amount: f32)
+    where
+        R: Into<Rect>,
+    {
+        let rect = self.clip(rect.into());
+        self.damage(rect.rows());
+        for Pos { x, y } in rect.cells() {
+            if let Some(r) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+                r.fg = r.fg.map(|c| crate::color::lerp(c, Color::Black, amount));
+                r.bg = Some(
+                    r.bg.map(|c| crate::color::lerp(c, Color::Black, amount))
+                        .unwrap_or(Color::Black),
+                );
+            }
+        }
+    }
+
+    /// Dim the colors of every rune within a region, draining them toward
+    /// grey and darkening them, for content that is disabled or inactive.
+    /// Runes without a color keep the terminal default. ViewContext::dim
+    /// also dims those, using the theme.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((2, 1));
+    /// view.fill_all(Rune::new().fg(Color::Red).bg(Color::White));
+    /// view.dim(((0, 0), (1, 1)));
+    /// assert_ne!(view[0][0].fg, Some(Color::Red));
+    /// assert_eq!(view[0][1].fg, Some(Color::Red));
+    /// ```
+    pub fn dim<R>(&mut self, rect: R)
+    where
+        R: Into<Rect>,
+    {
+        let rect = self.clip(rect.into());
+        self.damage(rect.rows());
+        for Pos { x, y } in rect.cells() {
+            if let Some(r) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+                r.fg = r.fg.map(|c| crate::color::dim(c, crate::color::DIM));
+                r.bg = r.bg.map(|c| crate::color::dim(c, crate::color::DIM));
+            }
+        }
+    }
+
+    /// Attach an identifier to every rune within a region without changing
+    /// its content or styling. See Rune::id and the Frame resource.
+    pub fn set_id<R>(&mut self, rect: R, id: u32)
+    where
+        R: Into<Rect>,
+    {
+        let rect = self.clip(rect.into());
+        self.damage(rect.rows());
+        for Pos { x, y } in rect.cells() {
+            if let Some(r) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+                r.id = Some(id);
             }
         }
     }
@@ -89,13 +397,8 @@ impl View {
     where
         R: Into<Rune>,
     {
-        let rune = rune.into();
-        let rect = Rect::new((0, 0), self.size());
-        for y in rect.pos.y..(rect.size.height + rect.pos.y).min(self.0.len()) {
-            for x in rect.pos.x..(rect.size.width + rect.pos.x).min(self.0[y].len()) {
-                let _ = std::mem::replace(&mut self.0[y][x], rune);
-            }
-        }
+        let size = self.size();
+        self.fill(size, rune);
     }
 
     /// Insert a string at the specific position in the view. Each chacter is
@@ -103,29 +406,143 @@ impl View {
     /// continueing to the right
     ///
     /// This function performs no wrapping of any kind.
-    pub fn insert<P: Into<Pos>, S: Into<Runes>>(&mut self, pos: P, value: S) {
+    ///
+    /// Wide characters, such as CJK characters and emoji, occupy two cells.
+    /// Runes converted from strings already include the continuation cell
+    /// that follows them, and one is added after any wide rune without it.
+    pub fn insert<'a, P: Into<Pos>, S: Into<RuneSlice<'a>>>(&mut self, pos: P, value: S) {
+        self.insert_with(pos, value, |rune, _| rune);
+    }
+
+    /// Insert runes like View::insert, passing each rune through `resolve`
+    /// along with the cell it is about to be merged onto.
+    pub(crate) fn insert_with<'a, P, S, F>(&mut self, pos: P, value: S, mut resolve: F)
+    where
+        P: Into<Pos>,
+        S: Into<RuneSlice<'a>>,
+        F: FnMut(Rune, &Rune) -> Rune,
+    {
         let Pos { x, y } = pos.into();
-        let runes: Runes = value.into();
-        if let Some(line) = self.0.get_mut(y) {
-            let line_len = line.len() as i32;
-            for (i, c) in runes
-                .iter()
-                .take((line_len - x as i32).max(0) as usize)
-                .enumerate()
-            {
-                let rune = line[x + i] + *c;
-                let _ = std::mem::replace(&mut line[x + i], rune);
+        let runes: RuneSlice = value.into();
+        crate::layout_checks::check_insert(Pos { x, y }, runes.len(), self.size());
+        if let Some(line) = self.rows.get_mut(y) {
+            let mut col = x;
+            for (i, c) in runes.iter().enumerate() {
+                if col >= line.len() {
+                    break;
+                }
+                let c = &resolve(*c, &line[col]);
+                line[col] = line[col] + *c;
+                col += 1;
+                let continued = runes.get(i + 1).is_some_and(|next| next.continuation);
+                if c.width() == 2 && !continued && col < line.len() {
+                    line[col] = line[col] + c.continued();
+                    col += 1;
+                }
             }
+            repair_wide(line, x);
+            repair_wide(line, col);
+            self.damage([y]);
+        }
+    }
+
+    /// Insert a string wrapped within a region of the view. Lines are broken
+    /// between words where possible, and at newlines. Any text that does not
+    /// fit within the height of the region is discarded.
+    ///
+    /// Returns the number of lines that were used.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((10, 5));
+    /// let lines = view.insert_wrapped(((0, 0), (10, 5)), "Some text to be wrapped");
+    /// assert_eq!(lines, 3);
+    /// ```
+    pub fn insert_wrapped<'a, R: Into<Rect>, S: Into<RuneSlice<'a>>>(
+        &mut self,
+        rect: R,
+        value: S,
+    ) -> usize {
+        let rect = rect.into();
+        let runes: RuneSlice = value.into();
+        let lines = wrap(&runes, rect.size.width);
+        for (row, line) in lines.iter().take(rect.size.height).enumerate() {
+            self.insert((rect.pos.x, rect.pos.y + row), RuneSlice::new(line));
         }
+        lines.len().min(rect.size.height)
+    }
+
+    /// Export the view as text with ANSI escape sequences for its styling,
+    /// as it would be written to the terminal. Each row ends by resetting
+    /// the styling, so the output can be printed with `cat`.
+    ///
+    /// Example:
+    /// ```
+    /// use arkham::{internal::View, prelude::*};
+    /// let mut view = View::new((2, 1));
+    /// view.insert((0, 0), "ok".to_runes().fg(Color::Green));
+    /// assert!(view.to_ansi().contains("ok\x1b[0m\n"));
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let mut out = vec![];
+        for line in self.rows.iter() {
+            let mut state = RenderState::new(true);
+            for rune in line.iter() {
+                // Writing into a buffer cannot fail.
+                let _ = rune.render(&mut out, &mut state);
+            }
+            let _ = state.finish(&mut out);
+            out.extend_from_slice(b"\x1b[0m\n");
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
+    /// Export the view as a standalone HTML document. Runes sharing the same
+    /// styling are grouped into a single span and hyperlinks are preserved.
+    /// Colors without a fixed value, such as Color::Reset, use the page
+    /// defaults.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n\
+             <body style=\"background:#000;color:#fff\">\n\
+             <pre style=\"font-family:monospace;line-height:1.2\">\n",
+        );
+        for line in self.rows.iter() {
+            let mut run: Option<(String, Option<String>)> = None;
+            for rune in line.iter() {
+                let key = (html_style(rune), rune.url());
+                if run.as_ref() != Some(&key) {
+                    if let Some(previous) = run.take() {
+                        close_html_run(&mut html, &previous);
+                    }
+                    if let Some(url) = &key.1 {
+                        html.push_str(&format!("<a href=\"{}\">", escape_html(url)));
+                    }
+                    html.push_str(&format!("<span style=\"{}\">", key.0));
+                    run = Some(key);
+                }
+                if !rune.continuation {
+                    html.push_str(&escape_html(&rune.text().unwrap_or(' '.into())));
+                }
+            }
+            if let Some(previous) = run.take() {
+                close_html_run(&mut html, &previous);
+            }
+            html.push('\n');
+        }
+        html.push_str("</pre>\n</body>\n</html>\n");
+        html
     }
 
     #[cfg(test)]
     pub fn render_text(&self) -> String {
-        self.0.iter().fold(String::new(), |mut acc, line| {
+        self.rows.iter().fold(String::new(), |mut acc, line| {
             acc.push_str(
                 &line
-                    .into_iter()
-                    .map(|r| r.content.unwrap_or_default())
+                    .iter()
+                    .filter(|r| !r.continuation)
+                    .map(|r| r.text().unwrap_or('\0'.into()))
                     .collect::<String>(),
             );
             acc.push('\n');
@@ -134,11 +551,119 @@ impl View {
     }
 }
 
+/// Blank the halves of wide characters split at the boundary before a
+/// column, such as a wide character whose continuation was overwritten or
+/// a continuation whose wide character was. A wide character in the last
+/// column, without room for its continuation, is blanked as well.
+fn repair_wide(line: &mut [Rune], x: usize) {
+    if x == 0 || x > line.len() {
+        return;
+    }
+    let wide = line[x - 1].width() == 2;
+    let continued = line.get(x).is_some_and(|r| r.continuation);
+    if wide && !continued {
+        line[x - 1].content = Some(' ');
+    } else if continued && !wide {
+        line[x].content = Some(' ');
+        line[x].continuation = false;
+    }
+}
+
+/// The inline CSS for the styling of a rune.
+fn html_style(rune: &Rune) -> String {
+    let mut style = String::new();
+    let (fg, bg) = if rune.reverse {
+        (rune.bg, rune.fg)
+    } else {
+        (rune.fg, rune.bg)
+    };
+    if let Some((r, g, b)) = fg.and_then(crate::color::to_rgb) {
+        style.push_str(&format!("color:#{r:02x}{g:02x}{b:02x};"));
+    }
+    if let Some((r, g, b)) = bg.and_then(crate::color::to_rgb) {
+        style.push_str(&format!("background:#{r:02x}{g:02x}{b:02x};"));
+    }
+    if rune.bold {
+        style.push_str("font-weight:bold;");
+    }
+    if rune.italic {
+        style.push_str("font-style:italic;");
+    }
+    if rune.dim {
+        style.push_str("opacity:0.6;");
+    }
+    let line = if rune.underline {
+        "underline"
+    } else if rune.undercurl {
+        "underline wavy"
+    } else {
+        ""
+    };
+    match (line, rune.crossed_out) {
+        ("", false) => {}
+        ("", true) => style.push_str("text-decoration:line-through;"),
+        (line, false) => style.push_str(&format!("text-decoration:{line};")),
+        (line, true) => style.push_str(&format!("text-decoration:{line} line-through;")),
+    }
+    style
+}
+
+fn close_html_run(html: &mut String, (_, url): &(String, Option<String>)) {
+    html.push_str("</span>");
+    if url.is_some() {
+        html.push_str("</a>");
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The mirrored counterpart of a character, or the character itself if it
+/// is symmetrical or has no counterpart.
+fn mirror(c: char) -> char {
+    const PAIRS: [(char, char); 17] = [
+        ('(', ')'),
+        ('[', ']'),
+        ('{', '}'),
+        ('<', '>'),
+        ('/', '\\'),
+        ('←', '→'),
+        ('◀', '▶'),
+        ('▌', '▐'),
+        ('┌', '┐'),
+        ('└', '┘'),
+        ('├', '┤'),
+        ('╭', '╮'),
+        ('╰', '╯'),
+        ('╔', '╗'),
+        ('╚', '╝'),
+        ('┏', '┓'),
+        ('┗', '┛'),
+    ];
+    for (a, b) in PAIRS {
+        if c == a {
+            return b;
+        }
+        if c == b {
+            return a;
+        }
+    }
+    c
+}
+
 #[cfg(test)]
 mod tests {
     use crossterm::style::Color;
 
-    use crate::{geometry::Rect, runes::Rune};
+    use crate::{
+        geometry::Rect,
+        runes::{Rune, ToRuneExt},
+    };
 
     use super::View;
 
@@ -146,47 +671,72 @@ mod tests {
     pub fn test_insert_pos() {
         let mut view = View::new((5, 3));
         view.insert((1, 2), "test");
-        dbg!(&view.0);
-        assert_eq!(view.0[2][1].content, Some('t'));
-        assert_eq!(view.0[2][2].content, Some('e'));
-        assert_eq!(view.0[2][3].content, Some('s'));
-        assert_eq!(view.0[2][4].content, Some('t'));
+        dbg!(&view.rows);
+        assert_eq!(view.rows[2][1].content, Some('t'));
+        assert_eq!(view.rows[2][2].content, Some('e'));
+        assert_eq!(view.rows[2][3].content, Some('s'));
+        assert_eq!(view.rows[2][4].content, Some('t'));
+    }
+
+    #[test]
+    pub fn test_insert_borrowed() {
+        let runes = "ab".to_runes().fg(Color::Blue);
+        let mut view = View::new((2, 2));
+        view.insert((0, 0), &runes);
+        view.insert((0, 1), &runes);
+        assert_eq!(view.render_text(), "ab\nab\n");
+        assert_eq!(view.rows[1][1].fg, Some(Color::Blue));
+    }
+
+    #[test]
+    pub fn test_insert_wrapped() {
+        let mut view = View::new((6, 3));
+        let used = view.insert_wrapped(((1, 0), (5, 2)), "ab cd ef gh");
+        assert_eq!(used, 2);
+        assert_eq!(view.render_text(), "\0ab cd\n\0ef gh\n\0\0\0\0\0\0\n");
     }
 
     #[test]
     pub fn test_fill() {
         let mut view = View::new((3, 3));
         view.fill(Rect::new((1, 1), (2, 2)), Rune::new().content('X'));
-        dbg!(&view.0);
-        assert_eq!(view.0[0][0].content, None);
-        assert_eq!(view.0[0][1].content, None);
-        assert_eq!(view.0[0][2].content, None);
-
-        assert_eq!(view.0[1][0].content, None);
-        assert_eq!(view.0[1][1].content, Some('X'));
-        assert_eq!(view.0[1][2].content, Some('X'));
-
-        assert_eq!(view.0[2][0].content, None);
-        assert_eq!(view.0[2][1].content, Some('X'));
-        assert_eq!(view.0[2][2].content, Some('X'));
+        dbg!(&view.rows);
+        assert_eq!(view.rows[0][0].content, None);
+        assert_eq!(view.rows[0][1].content, None);
+        assert_eq!(view.rows[0][2].content, None);
+
+        assert_eq!(view.rows[1][0].content, None);
+        assert_eq!(view.rows[1][1].content, Some('X'));
+        assert_eq!(view.rows[1][2].content, Some('X'));
+
+        assert_eq!(view.rows[2][0].content, None);
+        assert_eq!(view.rows[2][1].content, Some('X'));
+        assert_eq!(view.rows[2][2].content, Some('X'));
     }
 
     #[test]
     pub fn test_fill_overflow() {
         let mut view = View::new((3, 3));
         view.fill(Rect::new((1, 1), (4, 4)), Rune::new().content('X'));
-        dbg!(&view.0);
-        assert_eq!(view.0[0][0].content, None);
-        assert_eq!(view.0[0][1].content, None);
-        assert_eq!(view.0[0][2].content, None);
-
-        assert_eq!(view.0[1][0].content, None);
-        assert_eq!(view.0[1][1].content, Some('X'));
-        assert_eq!(view.0[1][2].content, Some('X'));
-
-        assert_eq!(view.0[2][0].content, None);
-        assert_eq!(view.0[2][1].content, Some('X'));
-        assert_eq!(view.0[2][2].content, Some('X'));
+        dbg!(&view.rows);
+        assert_eq!(view.rows[0][0].content, None);
+        assert_eq!(view.rows[0][1].content, None);
+        assert_eq!(view.rows[0][2].content, None);
+
+        assert_eq!(view.rows[1][0].content, None);
+        assert_eq!(view.rows[1][1].content, Some('X'));
+        assert_eq!(view.rows[1][2].content, Some('X'));
+
+        assert_eq!(view.rows[2][0].content, None);
+        assert_eq!(view.rows[2][1].content, Some('X'));
+        assert_eq!(view.rows[2][2].content, Some('X'));
+
+        // Regions extending past the end of usize are clipped to the view.
+        view.fill(Rect::new((0, 0), (usize::MAX, usize::MAX)), 'Y');
+        assert!(view.iter().flatten().all(|r| r.content == Some('Y')));
+        view.fill(Rect::new((usize::MAX, 2), (5, 5)), 'Z');
+        view.dim(Rect::new((2, usize::MAX), (5, 5)));
+        assert!(view.iter().flatten().all(|r| r.content == Some('Y')));
     }
 
     #[test]
@@ -195,22 +745,78 @@ mod tests {
         view1.fill(Rect::new((1, 1), (2, 2)), Rune::new().content('X'));
         let mut view2 = View::new((3, 4));
         view2.apply((0, 1), &view1);
-        dbg!(&view2.0);
-        assert_eq!(view2.0[0][0].content, None);
-        assert_eq!(view2.0[0][1].content, None);
-        assert_eq!(view2.0[0][2].content, None);
+        dbg!(&view2.rows);
+        assert_eq!(view2.rows[0][0].content, None);
+        assert_eq!(view2.rows[0][1].content, None);
+        assert_eq!(view2.rows[0][2].content, None);
+
+        assert_eq!(view2.rows[1][0].content, None);
+        assert_eq!(view2.rows[1][1].content, None);
+        assert_eq!(view2.rows[1][2].content, None);
+
+        assert_eq!(view2.rows[2][0].content, None);
+        assert_eq!(view2.rows[2][1].content, Some('X'));
+        assert_eq!(view2.rows[2][2].content, Some('X'));
+
+        assert_eq!(view2.rows[3][0].content, None);
+        assert_eq!(view2.rows[3][1].content, Some('X'));
+        assert_eq!(view2.rows[3][2].content, Some('X'));
+    }
 
-        assert_eq!(view2.0[1][0].content, None);
-        assert_eq!(view2.0[1][1].content, None);
-        assert_eq!(view2.0[1][2].content, None);
+    #[test]
+    pub fn test_apply_runes() {
+        let mut view = View::new((4, 2));
+        view.fill(Rect::new((0, 0), (4, 2)), Rune::new().bg(Color::Blue));
+        view.clear_damage();
+        let label = "abcdef".to_runes().fg(Color::Red);
+        view.apply_runes((1, 1), &label);
+        view.apply_runes((0, 5), &label);
+        assert_eq!(view.render_text(), "\0\0\0\0\n\0abc\n");
+        assert_eq!(view[1][1].bg, Some(Color::Blue));
+        assert_eq!(view[1][1].fg, Some(Color::Red));
+        assert_eq!(view.damaged_rows().collect::<Vec<_>>(), vec![1]);
+    }
 
-        assert_eq!(view2.0[2][0].content, None);
-        assert_eq!(view2.0[2][1].content, Some('X'));
-        assert_eq!(view2.0[2][2].content, Some('X'));
+    #[test]
+    pub fn test_transforms() {
+        let mut view = View::new((3, 2));
+        view.insert((0, 0), "┌─x");
+        view.insert((0, 1), "└─y".to_runes().fg(Color::Red));
+        let mirrored = view.mirrored();
+        assert_eq!(mirrored.render_text(), "x─┐\ny─┘\n");
+        assert_eq!(mirrored[1][0].fg, Some(Color::Red));
+
+        let shifted = view.shifted(-1, 1);
+        assert_eq!(shifted.render_text(), "\0\0\0\n─x\0\n");
+
+        // Cells outside of the target view are ignored.
+        let mut target = View::new((2, 2));
+        target.apply_masked((1, 0), &view, ((0, 1), (5, 5)));
+        assert_eq!(target.render_text(), "\0\0\n\0└\n");
+    }
 
-        assert_eq!(view2.0[3][0].content, None);
-        assert_eq!(view2.0[3][1].content, Some('X'));
-        assert_eq!(view2.0[3][2].content, Some('X'));
+    #[test]
+    pub fn test_wide_characters() {
+        let mut view = View::new((6, 1));
+        view.insert((0, 0), "中文x");
+        assert_eq!(view.render_text(), "中文x\0\n");
+        assert!(view[0][1].continuation);
+
+        // Overwriting half of a wide character blanks the other half.
+        view.insert((1, 0), "a");
+        view.insert((2, 0), Rune::new().content('b'));
+        assert_eq!(view.render_text(), " ab x\0\n");
+        assert!(!view.rows[0].iter().any(|r| r.continuation));
+
+        // A wide character without room for its continuation is blanked.
+        view.insert((5, 0), "字");
+        assert_eq!(view[0][5].content, Some(' '));
+        view.insert((4, 0), Rune::new().content('字'));
+        assert!(view[0][5].continuation);
+
+        let mirrored = view.mirrored();
+        assert_eq!(mirrored[0][0].content, Some('字'));
+        assert!(mirrored[0][1].continuation);
     }
 
     #[test]
@@ -219,18 +825,41 @@ mod tests {
         view0.fill(Rect::new((1, 1), (4, 4)), Rune::new().content('X'));
         let mut view = View::new((3, 3));
         view.apply((0, 0), &view0);
-        dbg!(&view.0);
-        assert_eq!(view.0[0][0].content, None);
-        assert_eq!(view.0[0][1].content, None);
-        assert_eq!(view.0[0][2].content, None);
-
-        assert_eq!(view.0[1][0].content, None);
-        assert_eq!(view.0[1][1].content, Some('X'));
-        assert_eq!(view.0[1][2].content, Some('X'));
+        dbg!(&view.rows);
+        assert_eq!(view.rows[0][0].content, None);
+        assert_eq!(view.rows[0][1].content, None);
+        assert_eq!(view.rows[0][2].content, None);
+
+        assert_eq!(view.rows[1][0].content, None);
+        assert_eq!(view.rows[1][1].content, Some('X'));
+        assert_eq!(view.rows[1][2].content, Some('X'));
+
+        assert_eq!(view.rows[2][0].content, None);
+        assert_eq!(view.rows[2][1].content, Some('X'));
+        assert_eq!(view.rows[2][2].content, Some('X'));
+    }
 
-        assert_eq!(view.0[2][0].content, None);
-        assert_eq!(view.0[2][1].content, Some('X'));
-        assert_eq!(view.0[2][2].content, Some('X'));
+    #[test]
+    pub fn test_damage() {
+        let mut view = View::new((4, 3));
+        assert_eq!(view.damaged_rows().count(), 3);
+        view.clear_damage();
+
+        let mut frame = View::new((4, 3));
+        frame.insert((0, 2), "ab");
+        view.apply((0, 0), &frame);
+        assert_eq!(view.damaged_rows().collect::<Vec<_>>(), vec![2]);
+
+        // Applying the same frame again changes nothing.
+        view.clear_damage();
+        view.apply((0, 0), &frame);
+        assert_eq!(view.damaged_rows().count(), 0);
+
+        view.fill(((0, 0), (2, 2)), Color::Red);
+        assert_eq!(view.damaged_rows().collect::<Vec<_>>(), vec![0, 1]);
+        view.clear_damage();
+        view[1][0].content = Some('x');
+        assert_eq!(view.damaged_rows().count(), 3);
     }
 
     #[test]
@@ -241,4 +870,28 @@ mod tests {
             .iter()
             .all(|rs| rs.iter().all(|r| r.bg == Some(Color::Red))));
     }
+
+    #[test]
+    pub fn test_to_html() {
+        let mut view = View::new((6, 1));
+        view.insert((0, 0), "<a>".to_runes().fg(Color::Red).bold());
+        view.insert((3, 0), "go".to_runes().link("https://example.com"));
+        let html = view.to_html();
+        assert!(html.contains("<span style=\"color:#ff0000;font-weight:bold;\">&lt;a&gt;</span>"));
+        assert!(html.contains(
+            "<a href=\"https://example.com\"><span style=\"\">go</span></a><span style=\"\"> </span>\n"
+        ));
+        let mut view = View::new((1, 1));
+        view.insert(
+            (0, 0),
+            "x".to_runes()
+                .fg(Color::Red)
+                .reverse()
+                .underline()
+                .crossed_out(),
+        );
+        assert!(view.to_html().contains(
+            "<span style=\"background:#ff0000;text-decoration:underline line-through;\">x</span>"
+        ));
+    }
 }
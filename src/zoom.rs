@@ -0,0 +1,116 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{geometry::Size, view::View};
+
+#[derive(Debug, Default)]
+struct ZoomState {
+    zoomed: Option<String>,
+    focused: Option<String>,
+    first: Option<String>,
+    size: Size,
+    view: Option<View>,
+}
+
+/// Zoom can be used as an injectable resource to control which component
+/// drawn with ViewContext::zoomable is displayed fullscreen.
+///
+/// The zoom key configured with App::zoom_key toggles zoom on the focused
+/// zoomable component, or the first one drawn if none has been focused.
+///
+/// ```no_run
+/// use arkham::prelude::*;
+///
+/// fn main() {
+///     App::new(root).zoom_key(KeyCode::F(2)).run().unwrap();
+/// }
+///
+/// fn root(ctx: &mut ViewContext, zoom: Res<Zoom>, kb: Res<Keyboard>) {
+///     if kb.code() == Some(KeyCode::Char('2')) {
+///         zoom.focus("memory");
+///     }
+///     ctx.zoomable("cpu", ((0, 0), (20, 5)), cpu);
+///     ctx.zoomable("memory", ((20, 0), (20, 5)), memory);
+/// }
+///
+/// fn cpu(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "CPU");
+/// }
+///
+/// fn memory(ctx: &mut ViewContext) {
+///     ctx.insert((0, 0), "Memory");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Zoom {
+    state: Rc<RefCell<ZoomState>>,
+}
+
+impl Zoom {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the zoomable component the zoom key applies to.
+    pub fn focus<S: ToString>(&self, id: S) {
+        self.state.borrow_mut().focused = Some(id.to_string());
+    }
+
+    /// Display the zoomable component with an identifier fullscreen.
+    pub fn zoom<S: ToString>(&self, id: S) {
+        self.state.borrow_mut().zoomed = Some(id.to_string());
+    }
+
+    /// Return to the normal layout.
+    pub fn restore(&self) {
+        self.state.borrow_mut().zoomed = None;
+    }
+
+    /// Zoom the focused component, or restore the normal layout if a
+    /// component is already zoomed.
+    pub fn toggle(&self) {
+        let mut state = self.state.borrow_mut();
+        state.zoomed = match state.zoomed {
+            Some(_) => None,
+            None => state.focused.clone().or(state.first.clone()),
+        };
+    }
+
+    /// The identifier of the component currently displayed fullscreen.
+    pub fn zoomed(&self) -> Option<String> {
+        self.state.borrow().zoomed.clone()
+    }
+
+    /// Prepare for a new frame rendered at a size.
+    pub(crate) fn begin_frame(&self, size: Size) {
+        let mut state = self.state.borrow_mut();
+        state.first = None;
+        state.size = size;
+        state.view = None;
+    }
+
+    /// Record that a zoomable component was drawn. Returns true if it is the
+    /// zoomed component.
+    pub(crate) fn register(&self, id: &str) -> bool {
+        let mut state = self.state.borrow_mut();
+        if state.first.is_none() {
+            state.first = Some(id.to_string());
+        }
+        state.zoomed.as_deref() == Some(id)
+    }
+
+    /// The size of the screen a zoomed component is drawn at.
+    pub(crate) fn size(&self) -> Size {
+        self.state.borrow().size
+    }
+
+    /// Store the rendered view of the zoomed component.
+    pub(crate) fn set_view(&self, view: View) {
+        self.state.borrow_mut().view = Some(view);
+    }
+
+    /// Take the rendered view of the zoomed component, if one was drawn
+    /// during the frame.
+    pub(crate) fn take_view(&self) -> Option<View> {
+        self.state.borrow_mut().view.take()
+    }
+}